@@ -1,9 +1,11 @@
 use anyhow::Context as _;
 use database::bson::doc;
 use komodo_client::{
-  api::read::FindUser,
+  api::{read::FindUser, write::UpdateApiKeyScopes},
   entities::{
-    api_key::ApiKey, config::cli::args::create::CreateApiKey,
+    ResourceTarget,
+    api_key::{ApiKey, ApiKeyScopes},
+    config::cli::args::create::CreateApiKey,
     komodo_timestamp, random_string,
   },
 };
@@ -17,6 +19,10 @@ pub async fn create(
     for_user,
     expires,
     use_api,
+    requests,
+    resources,
+    tags,
+    ips,
   }: &CreateApiKey,
 ) -> anyhow::Result<()> {
   let expires = if let Some(expires_days) = expires {
@@ -26,6 +32,16 @@ pub async fn create(
     0
   };
 
+  let scopes = ApiKeyScopes {
+    requests: requests.clone(),
+    resources: resources
+      .iter()
+      .map(|resource| parse_resource_target(resource))
+      .collect::<anyhow::Result<_>>()?,
+    tags: tags.clone(),
+    ips: ips.clone(),
+  };
+
   if *use_api {
     // USE API
     let client = crate::command::komodo_client().await?;
@@ -43,17 +59,28 @@ pub async fn create(
             user_id: user.id,
             name: name.clone().unwrap_or_default(),
             expires,
+            scopes,
           },
         )
         .await?
     } else {
       // For self
-      client
+      let keys = client
         .auth_manage(komodo_client::api::auth::manage::CreateApiKey {
           name: name.clone().unwrap_or_default(),
           expires: expires as u64,
         })
-        .await?
+        .await?;
+      if !scopes.is_empty() {
+        client
+          .write(UpdateApiKeyScopes {
+            key: keys.key.clone(),
+            scopes,
+          })
+          .await
+          .context("Failed to set api key scopes")?;
+      }
+      keys
     };
 
     println!(
@@ -85,6 +112,7 @@ pub async fn create(
         secret: hashed_secret.clone(),
         created_at: komodo_timestamp(),
         expires,
+        scopes,
       })
       .await?;
 
@@ -99,3 +127,14 @@ pub async fn create(
 
   Ok(())
 }
+
+/// Parses `Type:id_or_name`, eg `Stack:my-stack`
+fn parse_resource_target(
+  resource: &str,
+) -> anyhow::Result<ResourceTarget> {
+  let (variant, id) = resource.split_once(':').with_context(|| {
+    format!("Resource must be given as 'Type:id_or_name', got '{resource}'")
+  })?;
+  serde_json::from_value(json!({ "type": variant, "id": id }))
+    .with_context(|| format!("Invalid resource type '{variant}'"))
+}
@@ -2,9 +2,15 @@ use std::path::Path;
 
 use anyhow::Context;
 use colored::Colorize;
-use database::mungos::mongodb::bson::{Document, doc};
+use database::{
+  mungos::mongodb::bson::{Document, doc},
+  utils::{BackupRemote, RestoreFilter},
+};
 use komodo_client::entities::{
-  config::cli::args::database::DatabaseCommand, optional_string,
+  config::{
+    BackupRemoteConfig, cli::args::database::DatabaseCommand,
+  },
+  optional_string,
 };
 
 use crate::{command::sanitize_uri, config::cli_config};
@@ -14,10 +20,27 @@ pub async fn handle(command: &DatabaseCommand) -> anyhow::Result<()> {
     DatabaseCommand::Backup { yes, .. } => backup(*yes).await,
     DatabaseCommand::Restore {
       restore_folder,
+      from,
+      collections,
+      resources,
+      dry_run,
       index,
       yes,
       ..
-    } => restore(restore_folder.as_deref(), *index, *yes).await,
+    } => {
+      restore(
+        restore_folder.as_deref(),
+        from.as_deref(),
+        RestoreFilter {
+          collections: collections.clone(),
+          resources: resources.clone(),
+          dry_run: *dry_run,
+        },
+        *index,
+        *yes,
+      )
+      .await
+    }
     DatabaseCommand::Prune { yes, .. } => prune(*yes).await,
     DatabaseCommand::Copy { yes, index, .. } => {
       copy(*index, *yes).await
@@ -67,12 +90,27 @@ async fn backup(yes: bool) -> anyhow::Result<()> {
   } else {
     println!("{}: {}", " - Max Backups".dimmed(), config.max_backups);
   }
+  print_backup_remote();
 
   crate::command::wait_for_enter("start backup", yes)?;
 
   let db = database::init(&config.database).await?;
 
-  database::utils::backup(&db, &config.backups_folder).await?;
+  let backup_folder =
+    database::utils::backup(&db, &config.backups_folder).await?;
+
+  if !config.backup_remote.url.is_empty() {
+    let remote = BackupRemote::connect(&config.backup_remote).await?;
+    database::utils::upload_backup(
+      &remote,
+      &config.backups_folder,
+      &backup_folder,
+    )
+    .await?;
+    if let Err(e) = remote.prune().await {
+      warn!("Failed to prune remote backups | {e:#}");
+    }
+  }
 
   // Early return if backup pruning disabled
   if config.max_backups == 0 {
@@ -87,6 +125,8 @@ async fn backup(yes: bool) -> anyhow::Result<()> {
 
 async fn restore(
   restore_folder: Option<&Path>,
+  from: Option<&str>,
+  filter: RestoreFilter,
   index: bool,
   yes: bool,
 ) -> anyhow::Result<()> {
@@ -127,14 +167,39 @@ async fn restore(
       "DISABLED".red(),
     );
   }
-  println!(
-    "\n{}: {:?}",
-    " - Backups Folder".dimmed(),
-    config.backups_folder
-  );
+  if let Some(from) = from {
+    println!(
+      "\n{}: {}",
+      " - Restore From".dimmed(),
+      sanitize_uri(from)
+    );
+  } else {
+    println!(
+      "\n{}: {:?}",
+      " - Backups Folder".dimmed(),
+      config.backups_folder
+    );
+  }
   if let Some(restore_folder) = restore_folder {
     println!("{}: {restore_folder:?}", " - Restore Folder".dimmed());
   }
+  if !filter.collections.is_empty() {
+    println!(
+      "{}: {}",
+      " - Collections".dimmed(),
+      filter.collections.join(", ")
+    );
+  }
+  if !filter.resources.is_empty() {
+    println!(
+      "{}: {}",
+      " - Resources".dimmed(),
+      filter.resources.join(", ")
+    );
+  }
+  if filter.dry_run {
+    println!("{}: {}", " - Dry Run".dimmed(), "ENABLED".yellow());
+  }
 
   crate::command::wait_for_enter("start restore", yes)?;
 
@@ -144,12 +209,45 @@ async fn restore(
     database::init(&config.database_target).await?
   };
 
-  database::utils::restore(
-    &db,
-    &config.backups_folder,
-    restore_folder,
-  )
-  .await
+  let Some(from) = from else {
+    return database::utils::restore(
+      &db,
+      &config.backups_folder,
+      restore_folder,
+      &filter,
+    )
+    .await
+    .map(|_| ());
+  };
+
+  let remote = BackupRemote::connect(&BackupRemoteConfig {
+    url: from.to_string(),
+    ..config.backup_remote.clone()
+  })
+  .await?;
+  let download_folder = std::env::temp_dir()
+    .join(format!("komodo-restore-{}", std::process::id()));
+  let res = async {
+    let restore_folder = database::utils::download_backup(
+      &remote,
+      restore_folder.and_then(|folder| folder.to_str()),
+      &download_folder,
+    )
+    .await?;
+    database::utils::restore(
+      &db,
+      &download_folder,
+      Some(restore_folder.as_path()),
+      &filter,
+    )
+    .await
+    .map(|_| ())
+  }
+  .await;
+  if let Err(e) = tokio::fs::remove_dir_all(&download_folder).await {
+    warn!("Failed to clean up downloaded backup | {e:#}");
+  }
+  res
 }
 
 async fn prune(yes: bool) -> anyhow::Result<()> {
@@ -179,9 +277,10 @@ async fn prune(yes: bool) -> anyhow::Result<()> {
   } else {
     println!("{}: {}", " - Max Backups".dimmed(), config.max_backups);
   }
+  print_backup_remote();
 
   // Early return if backup pruning disabled
-  if config.max_backups == 0 {
+  if config.max_backups == 0 && config.backup_remote.url.is_empty() {
     info!(
       "Backup pruning is disabled, enabled using 'max_backups' (KOMODO_CLI_MAX_BACKUPS)"
     );
@@ -190,9 +289,53 @@ async fn prune(yes: bool) -> anyhow::Result<()> {
 
   crate::command::wait_for_enter("start backup prune", yes)?;
 
+  if !config.backup_remote.url.is_empty() {
+    BackupRemote::connect(&config.backup_remote)
+      .await?
+      .prune()
+      .await?;
+  }
+
+  if config.max_backups == 0 {
+    return Ok(());
+  }
+
   prune_inner().await
 }
 
+fn print_backup_remote() {
+  let remote = &cli_config().backup_remote;
+  if remote.url.is_empty() {
+    return;
+  }
+  println!(
+    "{}: {}",
+    " - Backup Remote".dimmed(),
+    sanitize_uri(&remote.url)
+  );
+  if remote.max_backups > 0 {
+    println!(
+      "{}: {}",
+      " - Remote Max Backups".dimmed(),
+      remote.max_backups
+    );
+  }
+  if remote.max_backup_age_days > 0 {
+    println!(
+      "{}: {}",
+      " - Remote Max Backup Age (days)".dimmed(),
+      remote.max_backup_age_days
+    );
+  }
+  if !remote.encryption_key.is_empty() {
+    println!(
+      "{}: {}",
+      " - Remote Encryption".dimmed(),
+      "ENABLED".green()
+    );
+  }
+}
+
 async fn prune_inner() -> anyhow::Result<()> {
   let config = cli_config();
 
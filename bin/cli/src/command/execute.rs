@@ -92,6 +92,9 @@ pub async fn handle(
       UnpauseDeployment,
       StopDeployment,
       DestroyDeployment,
+      RollbackDeployment,
+      BackupDeploymentVolumes,
+      RestoreDeploymentVolumes,
       CloneRepo,
       PullRepo,
       BuildRepo,
@@ -127,7 +130,10 @@ pub async fn handle(
       UnpauseStack,
       StopStack,
       DestroyStack,
+      RollbackStack,
       RunStackService,
+      BackupStackVolumes,
+      RestoreStackVolumes,
       TestAlerter,
       SendAlert,
       RemoveSwarmNodes,
@@ -142,9 +148,11 @@ pub async fn handle(
       RemoveSwarmSecrets,
       ClearRepoCache,
       BackupCoreDatabase,
+      RestoreCoreDatabase,
       GlobalAutoUpdate,
       RotateAllServerKeys,
       RotateCoreKeys,
+      RotateEncryptionKey,
     ],
     batch: [
       BatchRunAction,
@@ -5,7 +5,7 @@ use clap::Parser;
 use colored::Colorize;
 use komodo_client::entities::{
   config::{
-    DatabaseConfig,
+    BackupRemoteConfig, DatabaseConfig,
     cli::{
       CliConfig, Env,
       args::{CliArgs, Command, Execute, database::DatabaseCommand},
@@ -208,6 +208,55 @@ pub fn cli_config() -> &'static CliConfig {
       max_backups: env
         .komodo_cli_max_backups
         .unwrap_or(config.max_backups),
+      backup_remote: BackupRemoteConfig {
+        url: env
+          .komodo_cli_backup_remote_url
+          .unwrap_or(config.backup_remote.url),
+        s3_endpoint: env
+          .komodo_cli_backup_remote_s3_endpoint
+          .unwrap_or(config.backup_remote.s3_endpoint),
+        s3_region: env
+          .komodo_cli_backup_remote_s3_region
+          .unwrap_or(config.backup_remote.s3_region),
+        s3_path_style: env
+          .komodo_cli_backup_remote_s3_path_style
+          .unwrap_or(config.backup_remote.s3_path_style),
+        s3_access_key_id: maybe_read_item_from_file(
+          env.komodo_cli_backup_remote_s3_access_key_id_file,
+          env.komodo_cli_backup_remote_s3_access_key_id,
+        )
+        .unwrap_or(config.backup_remote.s3_access_key_id),
+        s3_secret_access_key: maybe_read_item_from_file(
+          env.komodo_cli_backup_remote_s3_secret_access_key_file,
+          env.komodo_cli_backup_remote_s3_secret_access_key,
+        )
+        .unwrap_or(config.backup_remote.s3_secret_access_key),
+        username: env
+          .komodo_cli_backup_remote_username
+          .unwrap_or(config.backup_remote.username),
+        password: maybe_read_item_from_file(
+          env.komodo_cli_backup_remote_password_file,
+          env.komodo_cli_backup_remote_password,
+        )
+        .unwrap_or(config.backup_remote.password),
+        sftp_private_key: env
+          .komodo_cli_backup_remote_sftp_private_key
+          .unwrap_or(config.backup_remote.sftp_private_key),
+        sftp_known_hosts: env
+          .komodo_cli_backup_remote_sftp_known_hosts
+          .unwrap_or(config.backup_remote.sftp_known_hosts),
+        max_backups: env
+          .komodo_cli_backup_remote_max_backups
+          .unwrap_or(config.backup_remote.max_backups),
+        max_backup_age_days: env
+          .komodo_cli_backup_remote_max_backup_age_days
+          .unwrap_or(config.backup_remote.max_backup_age_days),
+        encryption_key: maybe_read_item_from_file(
+          env.komodo_cli_backup_remote_encryption_key_file,
+          env.komodo_cli_backup_remote_encryption_key,
+        )
+        .unwrap_or(config.backup_remote.encryption_key),
+      },
       database_target: DatabaseConfig {
         uri: uri
           .or(env.komodo_cli_database_target_uri)
@@ -3,10 +3,16 @@
 ## Core deps installer
 
 apt-get update
-apt-get install -y git curl ca-certificates iproute2
+apt-get install -y git curl ca-certificates iproute2 openssh-client git-lfs age
 
 rm -rf /var/lib/apt/lists/*
 
+# SOPS, for [[sops:...]] secrets
+SOPS_VERSION=3.10.2
+curl -fsSL -o /usr/local/bin/sops \
+  "https://github.com/getsops/sops/releases/download/v${SOPS_VERSION}/sops-v${SOPS_VERSION}.linux.$(dpkg --print-architecture)"
+chmod +x /usr/local/bin/sops
+
 # Starship prompt
 curl -sS https://starship.rs/install.sh | sh -s -- --yes --bin-dir /usr/local/bin
 echo 'export STARSHIP_CONFIG=/starship.toml' >> /root/.bashrc
@@ -0,0 +1,162 @@
+use std::{
+  collections::HashMap,
+  sync::{Mutex, OnceLock},
+};
+
+use komodo_client::entities::{ResourceTarget, server::ServerState};
+
+use crate::state::server_status_cache;
+
+use super::*;
+
+const MINUTE_MS: i64 = 60_000;
+
+/// Alerts collected for an alerter since the last digest was sent.
+struct AlertDigest {
+  /// The timestamp the first alert was collected
+  opened_ts: i64,
+  alerts: Vec<Alert>,
+}
+
+/// Alerter id -> collected alerts
+fn alert_digests() -> &'static Mutex<HashMap<String, AlertDigest>> {
+  static DIGESTS: OnceLock<Mutex<HashMap<String, AlertDigest>>> =
+    OnceLock::new();
+  DIGESTS.get_or_init(Default::default)
+}
+
+/// Collect the alert to be sent with the next digest for the alerter.
+pub fn push(alerter: &Alerter, alert: &Alert) {
+  let mut digests = alert_digests().lock().unwrap();
+  digests
+    .entry(alerter.id.clone())
+    .or_insert_with(|| AlertDigest {
+      opened_ts: komodo_timestamp(),
+      alerts: Vec::new(),
+    })
+    .alerts
+    .push(alert.clone());
+}
+
+/// Whether the alert is about a Deployment / Stack
+/// on a Server which is currently unreachable.
+pub async fn is_unreachable_child(alert: &Alert) -> bool {
+  let server_id = match &alert.data {
+    AlertData::ContainerStateChange { server_id, .. }
+    | AlertData::DeploymentImageUpdateAvailable {
+      server_id, ..
+    }
+    | AlertData::DeploymentAutoUpdated { server_id, .. }
+    | AlertData::StackStateChange { server_id, .. }
+    | AlertData::StackImageUpdateAvailable { server_id, .. }
+    | AlertData::StackAutoUpdated { server_id, .. } => server_id,
+    _ => return false,
+  };
+  let Some(server_id) = server_id else {
+    return false;
+  };
+  server_status_cache()
+    .get(server_id)
+    .await
+    .map(|status| status.state == ServerState::NotOk)
+    .unwrap_or_default()
+}
+
+/// Send the collected alerts for alerters
+/// whose grouping window has elapsed.
+/// Called at the end of every monitoring cycle.
+pub async fn flush_alert_digests(ts: i64) {
+  if alert_digests().lock().unwrap().is_empty() {
+    return;
+  }
+
+  let Ok(alerters) =
+    find_collect(&db_client().alerters, Document::new(), None)
+      .await
+      .inspect_err(|e| {
+        error!(
+          "ERROR sending alert digests | failed to get alerters from db | {e:#}"
+        )
+      })
+  else {
+    return;
+  };
+
+  let ready = {
+    let mut digests = alert_digests().lock().unwrap();
+    let mut ready = Vec::new();
+    for alerter in alerters {
+      let Some(digest) = digests.get(&alerter.id) else {
+        continue;
+      };
+      let window = alerter.config.grouping.window * MINUTE_MS;
+      // If grouping was disabled in the meantime,
+      // send whatever was collected right away.
+      if alerter.config.grouping.enabled
+        && ts - digest.opened_ts < window
+      {
+        continue;
+      }
+      if let Some(digest) = digests.remove(&alerter.id) {
+        ready.push((alerter, digest.alerts));
+      }
+    }
+    // Drop stale digests, eg for alerters which were deleted.
+    digests.retain(|_, digest| {
+      ts - digest.opened_ts < 24 * 60 * MINUTE_MS
+    });
+    ready
+  };
+
+  let handles = ready
+    .iter()
+    .map(|(alerter, alerts)| send_digest(alerter, alerts, ts));
+
+  join_all(handles)
+    .await
+    .into_iter()
+    .filter_map(|res| res.err())
+    .for_each(|e| error!("{e:#}"));
+}
+
+async fn send_digest(
+  alerter: &Alerter,
+  alerts: &[Alert],
+  ts: i64,
+) -> anyhow::Result<()> {
+  let alert = match alerts {
+    [] => return Ok(()),
+    // No need to combine a single alert
+    [alert] => alert.clone(),
+    alerts => combine_alerts(alerts, ts),
+  };
+  send_to_endpoint(alerter, &alert).await
+}
+
+/// Combine many alerts into a single `Custom` alert,
+/// using the highest severity level among them.
+/// The digest is resolved at the flush time `ts`.
+fn combine_alerts(alerts: &[Alert], ts: i64) -> Alert {
+  let level = alerts.iter().fold(SeverityLevel::Ok, |max, alert| {
+    if alert.level > max { alert.level } else { max }
+  });
+  let details = alerts
+    .iter()
+    .map(standard_alert_content)
+    .filter(|content| !content.is_empty())
+    .collect::<Vec<_>>()
+    .join("\n\n");
+  Alert {
+    id: Default::default(),
+    ts,
+    resolved: true,
+    resolved_ts: Some(ts),
+    level,
+    target: ResourceTarget::system(),
+    data: AlertData::Custom {
+      message: format!("{} alerts", alerts.len()),
+      details,
+    },
+    escalation: Default::default(),
+  }
+}
@@ -125,6 +125,69 @@ pub async fn send_alert(
         "{level} | **{name}**{region} disk usage at **{percentage:.1}%** 💿\nmount point: `{path:?}`\nusing **{used_gb:.1} GiB** / **{total_gb:.1} GiB**\n{link}"
       )
     }
+    AlertData::ServerLoadAverage {
+      id,
+      name,
+      region,
+      load_average,
+      per_core,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      format!(
+        "{level} | **{name}**{region} load average at **{load_average:.2}** (**{per_core:.2}** per core)⚖️\n{link}",
+      )
+    }
+    AlertData::ServerNetwork {
+      id,
+      name,
+      region,
+      ingress_mb,
+      egress_mb,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      format!(
+        "{level} | **{name}**{region} network throughput high🌐\ningress: **{ingress_mb:.1} MB/s** | egress: **{egress_mb:.1} MB/s**\n{link}",
+      )
+    }
+    AlertData::ContainerCpu {
+      resource_type,
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      container,
+      percentage,
+    } => {
+      let link = resource_link(*resource_type, id);
+      let server = server_name
+        .as_ref()
+        .map(|server| format!("\nserver: **{server}**"))
+        .unwrap_or_default();
+      format!(
+        "{level} | {resource_type} **{name}** container **{container}** cpu usage at **{percentage:.1}%**{server}\n{link}",
+      )
+    }
+    AlertData::ContainerMem {
+      resource_type,
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      container,
+      percentage,
+      usage,
+    } => {
+      let link = resource_link(*resource_type, id);
+      let server = server_name
+        .as_ref()
+        .map(|server| format!("\nserver: **{server}**"))
+        .unwrap_or_default();
+      format!(
+        "{level} | {resource_type} **{name}** container **{container}** memory usage at **{percentage:.1}%**💾\nusing **{usage}**{server}\n{link}",
+      )
+    }
     AlertData::ContainerStateChange {
       id,
       name,
@@ -1,5 +1,8 @@
 use anyhow::{Context, anyhow};
-use database::mungos::{find::find_collect, mongodb::bson::doc};
+use database::mungos::{
+  find::find_collect,
+  mongodb::bson::{Document, doc},
+};
 use futures_util::future::join_all;
 use interpolate::Interpolator;
 use komodo_client::entities::{
@@ -17,10 +20,15 @@ use crate::helpers::{
 };
 use crate::{config::core_config, state::db_client};
 
+pub use digest::flush_alert_digests;
+
+mod digest;
 mod discord;
 mod ntfy;
 mod pushover;
 mod slack;
+mod smtp;
+mod webhook;
 
 pub async fn send_alerts(alerts: &[Alert]) {
   if alerts.is_empty() {
@@ -53,9 +61,22 @@ async fn send_alert_to_alerters(alerters: &[Alerter], alert: &Alert) {
     return;
   }
 
-  let handles = alerters
-    .iter()
-    .map(|alerter| send_alert_to_alerter(alerter, alert));
+  let handles = alerters.iter().map(|alerter| async move {
+    if !alerter_accepts_alert(alerter, alert) {
+      return Ok(());
+    }
+    let grouping = &alerter.config.grouping;
+    if grouping.hide_unreachable_children
+      && digest::is_unreachable_child(alert).await
+    {
+      return Ok(());
+    }
+    if grouping.enabled {
+      digest::push(alerter, alert);
+      return Ok(());
+    }
+    send_to_endpoint(alerter, alert).await
+  });
 
   join_all(handles)
     .await
@@ -68,43 +89,17 @@ pub async fn send_alert_to_alerter(
   alerter: &Alerter,
   alert: &Alert,
 ) -> anyhow::Result<()> {
-  // Don't send if not enabled
-  if !alerter.config.enabled {
-    return Ok(());
-  }
-
-  if is_in_maintenance(
-    &alerter.config.maintenance_windows,
-    komodo_timestamp(),
-  ) {
+  if !alerter_accepts_alert(alerter, alert) {
     return Ok(());
   }
+  send_to_endpoint(alerter, alert).await
+}
 
-  let alert_variant: AlertDataVariant = (&alert.data).into();
-
-  // In the test case, we don't want the filters inside this
-  // block to stop the test from being sent to the alerting endpoint.
-  if alert_variant != AlertDataVariant::Test {
-    // Don't send if alert type not configured on the alerter
-    if !alerter.config.alert_types.is_empty()
-      && !alerter.config.alert_types.contains(&alert_variant)
-    {
-      return Ok(());
-    }
-
-    // Don't send if resource is in the blacklist
-    if alerter.config.except_resources.contains(&alert.target) {
-      return Ok(());
-    }
-
-    // Don't send if whitelist configured and target is not included
-    if !alerter.config.resources.is_empty()
-      && !alerter.config.resources.contains(&alert.target)
-    {
-      return Ok(());
-    }
-  }
-
+/// Send the alert to the alerter endpoint, without any filtering.
+async fn send_to_endpoint(
+  alerter: &Alerter,
+  alert: &Alert,
+) -> anyhow::Result<()> {
   match &alerter.config.endpoint {
     AlerterEndpoint::Custom(CustomAlerterEndpoint { url }) => {
       send_custom_alert(url, alert).await.with_context(|| {
@@ -148,7 +143,69 @@ pub async fn send_alert_to_alerter(
         )
       })
     }
+    AlerterEndpoint::Webhook(endpoint) => {
+      webhook::send_alert(endpoint, alert).await.with_context(|| {
+        format!(
+          "Failed to send alert to Webhook Alerter {}",
+          alerter.name
+        )
+      })
+    }
+    AlerterEndpoint::Smtp(endpoint) => {
+      smtp::send_alert(endpoint, alert).await.with_context(|| {
+        format!(
+          "Failed to send alert to Smtp Alerter {}",
+          alerter.name
+        )
+      })
+    }
+  }
+}
+
+/// Whether the alerter is enabled, not in maintenance,
+/// and configured to send the given alert.
+pub fn alerter_accepts_alert(
+  alerter: &Alerter,
+  alert: &Alert,
+) -> bool {
+  // Don't send if not enabled
+  if !alerter.config.enabled {
+    return false;
+  }
+
+  if is_in_maintenance(
+    &alerter.config.maintenance_windows,
+    komodo_timestamp(),
+  ) {
+    return false;
+  }
+
+  let alert_variant: AlertDataVariant = (&alert.data).into();
+
+  // In the test case, we don't want the filters inside this
+  // block to stop the test from being sent to the alerting endpoint.
+  if alert_variant != AlertDataVariant::Test {
+    // Don't send if alert type not configured on the alerter
+    if !alerter.config.alert_types.is_empty()
+      && !alerter.config.alert_types.contains(&alert_variant)
+    {
+      return false;
+    }
+
+    // Don't send if resource is in the blacklist
+    if alerter.config.except_resources.contains(&alert.target) {
+      return false;
+    }
+
+    // Don't send if whitelist configured and target is not included
+    if !alerter.config.resources.is_empty()
+      && !alerter.config.resources.contains(&alert.target)
+    {
+      return false;
+    }
   }
+
+  true
 }
 
 async fn send_custom_alert(
@@ -241,7 +298,7 @@ fn resource_link(
 }
 
 /// Standard message content format
-/// used by Ntfy, Pushover.
+/// used by Ntfy, Pushover, Webhook, Smtp.
 fn standard_alert_content(alert: &Alert) -> String {
   let level = fmt_level(alert.level);
   match &alert.data {
@@ -356,6 +413,69 @@ fn standard_alert_content(alert: &Alert) -> String {
         "{level} | {name}{region} disk usage at {percentage:.1}%💿\nmount point: {path:?}\nusing {used_gb:.1} GiB / {total_gb:.1} GiB\n{link}",
       )
     }
+    AlertData::ServerLoadAverage {
+      id,
+      name,
+      region,
+      load_average,
+      per_core,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      format!(
+        "{level} | {name}{region} load average at {load_average:.2} ({per_core:.2} per core)⚖️\n{link}",
+      )
+    }
+    AlertData::ServerNetwork {
+      id,
+      name,
+      region,
+      ingress_mb,
+      egress_mb,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      format!(
+        "{level} | {name}{region} network throughput high🌐\ningress: {ingress_mb:.1} MB/s | egress: {egress_mb:.1} MB/s\n{link}",
+      )
+    }
+    AlertData::ContainerCpu {
+      resource_type,
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      container,
+      percentage,
+    } => {
+      let link = resource_link(*resource_type, id);
+      let server = server_name
+        .as_ref()
+        .map(|server| format!("\nserver: {server}"))
+        .unwrap_or_default();
+      format!(
+        "{level} | {resource_type} {name} container {container} cpu usage at {percentage:.1}%{server}\n{link}",
+      )
+    }
+    AlertData::ContainerMem {
+      resource_type,
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      container,
+      percentage,
+      usage,
+    } => {
+      let link = resource_link(*resource_type, id);
+      let server = server_name
+        .as_ref()
+        .map(|server| format!("\nserver: {server}"))
+        .unwrap_or_default();
+      format!(
+        "{level} | {resource_type} {name} container {container} memory usage at {percentage:.1}%💾\nusing {usage}{server}\n{link}",
+      )
+    }
     AlertData::ContainerStateChange {
       id,
       name,
@@ -6,8 +6,45 @@ pub async fn send_alert(
   url: &str,
   alert: &Alert,
 ) -> anyhow::Result<()> {
+  let (text, blocks) = alert_content(alert);
+  if text.is_empty() {
+    return Ok(());
+  }
+  let VariablesAndSecrets { variables, secrets } =
+    get_variables_and_secrets().await?;
+  let mut url_interpolated = url.to_string();
+
+  let mut interpolator =
+    Interpolator::new(Some(&variables), &secrets);
+
+  interpolator.interpolate_string(&mut url_interpolated)?;
+
+  let slack = ::slack::Client::new(url_interpolated);
+  slack
+    .send_owned_message_single(&text, None, blocks.as_deref())
+    .await
+    .map_err(|e| {
+      let replacers = interpolator
+        .secret_replacers
+        .into_iter()
+        .collect::<Vec<_>>();
+      let sanitized_error =
+        svi::replace_in_string(&format!("{e:?}"), &replacers);
+      anyhow::Error::msg(format!(
+        "Error with request to Slack: {sanitized_error}"
+      ))
+    })?;
+  Ok(())
+}
+
+/// Slack message text and blocks for the alert.
+/// The text is also used as the summary line
+/// for other endpoints, eg the Smtp subject.
+pub(super) fn alert_content(
+  alert: &Alert,
+) -> (String, Option<Vec<Block>>) {
   let level = fmt_level(alert.level);
-  let (text, blocks): (_, Option<_>) = match &alert.data {
+  match &alert.data {
     AlertData::Test { id, name } => {
       let text = format!(
         "{level} | If you see this message, then Alerter *{name}* is *working*"
@@ -272,6 +309,105 @@ pub async fn send_alert(
         }
       }
     }
+    AlertData::ServerLoadAverage {
+      id,
+      name,
+      region,
+      load_average,
+      per_core,
+    } => {
+      let region = fmt_region(region);
+      let text = format!(
+        "{level} | *{name}*{region} load average at *{load_average:.2}* ⚖️"
+      );
+      let blocks = vec![
+        Block::header(level),
+        Block::section(format!(
+          "*{name}*{region} load average at *{load_average:.2}* (*{per_core:.2}* per core) ⚖️"
+        )),
+        Block::section(resource_link(
+          ResourceTargetVariant::Server,
+          id,
+        )),
+      ];
+      (text, blocks.into())
+    }
+    AlertData::ServerNetwork {
+      id,
+      name,
+      region,
+      ingress_mb,
+      egress_mb,
+    } => {
+      let region = fmt_region(region);
+      let text = format!(
+        "{level} | *{name}*{region} network throughput high 🌐"
+      );
+      let blocks = vec![
+        Block::header(level),
+        Block::section(format!(
+          "*{name}*{region} network throughput high 🌐"
+        )),
+        Block::section(format!(
+          "ingress: *{ingress_mb:.1} MB/s* | egress: *{egress_mb:.1} MB/s*"
+        )),
+        Block::section(resource_link(
+          ResourceTargetVariant::Server,
+          id,
+        )),
+      ];
+      (text, blocks.into())
+    }
+    AlertData::ContainerCpu {
+      resource_type,
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      container,
+      percentage,
+    } => {
+      let text = format!(
+        "{level} | {resource_type} *{name}* container *{container}* cpu usage at *{percentage:.1}%* 📈"
+      );
+      let mut blocks = vec![
+        Block::header(level),
+        Block::section(format!(
+          "{resource_type} *{name}* container *{container}* cpu usage at *{percentage:.1}%* 📈"
+        )),
+      ];
+      if let Some(server) = server_name {
+        blocks.push(Block::section(format!("server: *{server}*")));
+      }
+      blocks.push(Block::section(resource_link(*resource_type, id)));
+      (text, blocks.into())
+    }
+    AlertData::ContainerMem {
+      resource_type,
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      container,
+      percentage,
+      usage,
+    } => {
+      let text = format!(
+        "{level} | {resource_type} *{name}* container *{container}* memory usage at *{percentage:.1}%* 💾"
+      );
+      let mut blocks = vec![
+        Block::header(level),
+        Block::section(format!(
+          "{resource_type} *{name}* container *{container}* memory usage at *{percentage:.1}%* 💾"
+        )),
+        Block::section(format!("using *{usage}*")),
+      ];
+      if let Some(server) = server_name {
+        blocks.push(Block::section(format!("server: *{server}*")));
+      }
+      blocks.push(Block::section(resource_link(*resource_type, id)));
+      (text, blocks.into())
+    }
     AlertData::ContainerStateChange {
       name,
       swarm_id: _swarm_id,
@@ -547,33 +683,5 @@ pub async fn send_alert(
       (text, blocks.into())
     }
     AlertData::None {} => Default::default(),
-  };
-  if text.is_empty() {
-    return Ok(());
   }
-  let VariablesAndSecrets { variables, secrets } =
-    get_variables_and_secrets().await?;
-  let mut url_interpolated = url.to_string();
-
-  let mut interpolator =
-    Interpolator::new(Some(&variables), &secrets);
-
-  interpolator.interpolate_string(&mut url_interpolated)?;
-
-  let slack = ::slack::Client::new(url_interpolated);
-  slack
-    .send_owned_message_single(&text, None, blocks.as_deref())
-    .await
-    .map_err(|e| {
-      let replacers = interpolator
-        .secret_replacers
-        .into_iter()
-        .collect::<Vec<_>>();
-      let sanitized_error =
-        svi::replace_in_string(&format!("{e:?}"), &replacers);
-      anyhow::Error::msg(format!(
-        "Error with request to Slack: {sanitized_error}"
-      ))
-    })?;
-  Ok(())
 }
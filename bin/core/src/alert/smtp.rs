@@ -0,0 +1,117 @@
+use lettre::{
+  AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+  message::{Mailbox, header::ContentType},
+  transport::smtp::{
+    authentication::Credentials,
+    client::{Tls, TlsParameters},
+  },
+};
+
+use super::*;
+
+pub async fn send_alert(
+  endpoint: &SmtpAlerterEndpoint,
+  alert: &Alert,
+) -> anyhow::Result<()> {
+  let (text, _) = slack::alert_content(alert);
+  if text.is_empty() {
+    return Ok(());
+  }
+  // Strip the slack markdown bold markers for the subject line.
+  let summary = text.replace('*', "");
+  let subject = if endpoint.subject_prefix.is_empty() {
+    summary
+  } else {
+    format!("{} {summary}", endpoint.subject_prefix)
+  };
+  let body = standard_alert_content(alert);
+
+  let VariablesAndSecrets { variables, secrets } =
+    get_variables_and_secrets().await?;
+
+  let mut username = endpoint.username.clone();
+  let mut password = endpoint.password.clone();
+
+  let mut interpolator =
+    Interpolator::new(Some(&variables), &secrets);
+
+  interpolator
+    .interpolate_string(&mut username)?
+    .interpolate_string(&mut password)?;
+
+  send_email(endpoint, username, password, subject, body)
+    .await
+    .map_err(|e| {
+      let replacers = interpolator
+        .secret_replacers
+        .into_iter()
+        .collect::<Vec<_>>();
+      let sanitized_error =
+        svi::replace_in_string(&format!("{e:?}"), &replacers);
+      anyhow::Error::msg(format!(
+        "Error sending email over SMTP: {sanitized_error}"
+      ))
+    })
+}
+
+async fn send_email(
+  endpoint: &SmtpAlerterEndpoint,
+  username: String,
+  password: String,
+  subject: String,
+  body: String,
+) -> anyhow::Result<()> {
+  if endpoint.to.is_empty() {
+    return Err(anyhow!("No recipients configured"));
+  }
+
+  let from = endpoint.from.parse::<Mailbox>().with_context(|| {
+    format!("Invalid 'from' address: {}", endpoint.from)
+  })?;
+
+  let mut message = Message::builder().from(from).subject(subject);
+  for to in &endpoint.to {
+    let to = to
+      .parse::<Mailbox>()
+      .with_context(|| format!("Invalid 'to' address: {to}"))?;
+    message = message.to(to);
+  }
+  let message = message
+    .header(ContentType::TEXT_PLAIN)
+    .body(body)
+    .context("Failed to build email message")?;
+
+  let tls = match endpoint.security {
+    SmtpSecurity::None => Tls::None,
+    SmtpSecurity::StartTls => Tls::Required(
+      TlsParameters::new(endpoint.host.clone())
+        .context("Failed to build TLS parameters")?,
+    ),
+    SmtpSecurity::Tls => Tls::Wrapper(
+      TlsParameters::new(endpoint.host.clone())
+        .context("Failed to build TLS parameters")?,
+    ),
+  };
+
+  let mut transport =
+    AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(
+      endpoint.host.as_str(),
+    )
+    .port(endpoint.port)
+    .tls(tls);
+
+  if !username.is_empty() {
+    transport =
+      transport.credentials(Credentials::new(username, password));
+  }
+
+  let response = transport
+    .build()
+    .send(message)
+    .await
+    .context("Failed to send email")?;
+
+  debug!("smtp alert sent successfully: {}", response.code());
+
+  Ok(())
+}
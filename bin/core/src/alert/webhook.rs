@@ -0,0 +1,224 @@
+use std::sync::OnceLock;
+
+use hex::ToHex;
+use hmac::{Hmac, Mac};
+use reqwest::{
+  Method,
+  header::{CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue},
+};
+use serde_json::Value;
+use sha2::Sha256;
+
+use super::*;
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub async fn send_alert(
+  endpoint: &WebhookAlerterEndpoint,
+  alert: &Alert,
+) -> anyhow::Result<()> {
+  let VariablesAndSecrets { variables, secrets } =
+    get_variables_and_secrets().await?;
+
+  let mut url = endpoint.url.clone();
+  let mut headers = endpoint.headers.clone();
+  let mut signing_secret = endpoint.signing_secret.clone();
+
+  let mut interpolator =
+    Interpolator::new(Some(&variables), &secrets);
+
+  interpolator
+    .interpolate_string(&mut url)?
+    .interpolate_string(&mut headers)?
+    .interpolate_string(&mut signing_secret)?;
+
+  let body = render_body(
+    &endpoint.body,
+    endpoint.content_type.contains("json"),
+    alert,
+  )?;
+
+  send_request(
+    &url,
+    endpoint.method,
+    &headers,
+    &endpoint.content_type,
+    &signing_secret,
+    body,
+  )
+  .await
+  .map_err(|e| {
+    let replacers = interpolator
+      .secret_replacers
+      .into_iter()
+      .collect::<Vec<_>>();
+    let sanitized_error =
+      svi::replace_in_string(&format!("{e:?}"), &replacers);
+    anyhow::Error::msg(format!(
+      "Error with request to Webhook: {sanitized_error}"
+    ))
+  })
+}
+
+async fn send_request(
+  url: &str,
+  method: WebhookAlerterMethod,
+  headers: &str,
+  content_type: &str,
+  signing_secret: &str,
+  body: String,
+) -> anyhow::Result<()> {
+  let method = match method {
+    WebhookAlerterMethod::Post => Method::POST,
+    WebhookAlerterMethod::Put => Method::PUT,
+    WebhookAlerterMethod::Patch => Method::PATCH,
+  };
+
+  let mut header_map = parse_headers(headers)?;
+
+  if !content_type.is_empty() {
+    header_map.insert(
+      CONTENT_TYPE,
+      HeaderValue::from_str(content_type)
+        .context("Invalid Content-Type header value")?,
+    );
+  }
+
+  if !signing_secret.is_empty() {
+    let mut mac = HmacSha256::new_from_slice(
+      signing_secret.as_bytes(),
+    )
+    .context("Failed to create hmac sha256 from signing secret")?;
+    mac.update(body.as_bytes());
+    let signature =
+      mac.finalize().into_bytes().encode_hex::<String>();
+    header_map.insert(
+      "x-komodo-signature",
+      HeaderValue::from_str(&format!("sha256={signature}"))
+        .context("Invalid signature header value")?,
+    );
+  }
+
+  let res = http_client()
+    .request(method, url)
+    .headers(header_map)
+    .body(body)
+    .send()
+    .await
+    .context("Failed to send request")?;
+
+  let status = res.status();
+  if status.is_success() {
+    debug!("webhook alert sent successfully: {status}");
+    Ok(())
+  } else {
+    let text = res.text().await.with_context(|| {
+      format!(
+        "Failed to send message to webhook | {status} | failed to get response text"
+      )
+    })?;
+    Err(anyhow!(
+      "Failed to send message to webhook | {status} | {text}"
+    ))
+  }
+}
+
+/// Parses headers given one per line as `Name: value`.
+/// Empty lines and lines starting with `#` are ignored.
+fn parse_headers(headers: &str) -> anyhow::Result<HeaderMap> {
+  let mut map = HeaderMap::new();
+  for line in headers
+    .lines()
+    .map(str::trim)
+    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+  {
+    let (name, value) = line
+      .split_once(':')
+      .context("Header line must be in format 'Name: value'")?;
+    let name = HeaderName::from_bytes(name.trim().as_bytes())
+      .with_context(|| format!("Invalid header name: {name}"))?;
+    let value = HeaderValue::from_str(value.trim())
+      .with_context(|| format!("Invalid value for header {name}"))?;
+    map.append(name, value);
+  }
+  Ok(map)
+}
+
+/// Renders the body template, replacing `{{path}}` placeholders
+/// with values taken from the alert.
+/// If the template is empty, the alert is serialized to JSON.
+fn render_body(
+  template: &str,
+  escape_json: bool,
+  alert: &Alert,
+) -> anyhow::Result<String> {
+  if template.trim().is_empty() {
+    return serde_json::to_string(alert)
+      .context("Failed to serialize alert to JSON");
+  }
+
+  let mut context = serde_json::to_value(alert)
+    .context("Failed to serialize alert to JSON")?;
+  if let Value::Object(map) = &mut context {
+    map.insert(
+      String::from("message"),
+      Value::String(standard_alert_content(alert)),
+    );
+    // Flatten the tagged data so fields are accessed as `data.<field>`.
+    if let Some(Value::Object(data)) = map.remove("data") {
+      let variant = data.get("type").cloned().unwrap_or_default();
+      let fields = data
+        .get("data")
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+      map.insert(String::from("variant"), variant);
+      map.insert(String::from("data"), Value::Object(fields));
+    }
+  }
+
+  let mut rendered = String::with_capacity(template.len());
+  let mut rest = template;
+  while let Some(start) = rest.find("{{") {
+    rendered.push_str(&rest[..start]);
+    let after = &rest[start + 2..];
+    let Some(end) = after.find("}}") else {
+      rendered.push_str(&rest[start..]);
+      rest = "";
+      break;
+    };
+    let path = after[..end].trim();
+    let value = lookup(&context, path);
+    rendered.push_str(&fmt_value(value, escape_json));
+    rest = &after[end + 2..];
+  }
+  rendered.push_str(rest);
+
+  Ok(rendered)
+}
+
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+  path.split('.').try_fold(value, |value, key| match value {
+    Value::Object(map) => map.get(key),
+    Value::Array(arr) => arr.get(key.parse::<usize>().ok()?),
+    _ => None,
+  })
+}
+
+fn fmt_value(value: Option<&Value>, escape_json: bool) -> String {
+  match value {
+    None | Some(Value::Null) => String::new(),
+    Some(Value::String(s)) if escape_json => {
+      // Serialize to get escaping, then strip the surrounding quotes.
+      let quoted = Value::String(s.clone()).to_string();
+      quoted[1..quoted.len() - 1].to_string()
+    }
+    Some(Value::String(s)) => s.clone(),
+    Some(value) => value.to_string(),
+  }
+}
+
+fn http_client() -> &'static reqwest::Client {
+  static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+  CLIENT.get_or_init(reqwest::Client::new)
+}
@@ -21,7 +21,7 @@ use komodo_client::{
     permission::PermissionLevel,
     random_string,
     update::Update,
-    user::action_user,
+    user::{User, action_user},
   },
   parsers::parse_key_value_list,
 };
@@ -41,6 +41,7 @@ use crate::{
   auth::KomodoAuthImpl,
   config::core_config,
   helpers::{
+    external_secrets::add_external_secrets,
     query::{VariablesAndSecrets, get_variables_and_secrets},
     update::update_update,
   },
@@ -169,6 +170,7 @@ impl Resolve<ExecuteArgs> for RunAction {
         &mut update,
         key.clone(),
         secret.clone(),
+        user,
       )
       .await?
       .into_iter()
@@ -258,6 +260,7 @@ impl Resolve<ExecuteArgs> for RunAction {
           target,
           ts: komodo_timestamp(),
           resolved_ts: Some(komodo_timestamp()),
+          escalation: Default::default(),
           resolved: true,
           level: SeverityLevel::Warning,
           data: AlertData::ActionFailed {
@@ -273,12 +276,13 @@ impl Resolve<ExecuteArgs> for RunAction {
   }
 }
 
-#[instrument("Interpolate", skip(contents, update, secret))]
+#[instrument("Interpolate", skip(contents, update, secret, user))]
 async fn interpolate(
   contents: &mut String,
   update: &mut Update,
   key: String,
   secret: String,
+  user: &User,
 ) -> mogh_error::Result<HashSet<(String, String)>> {
   let VariablesAndSecrets {
     variables,
@@ -287,6 +291,7 @@ async fn interpolate(
 
   secrets.insert(String::from("ACTION_API_KEY"), key);
   secrets.insert(String::from("ACTION_API_SECRET"), secret);
+  add_external_secrets(&mut secrets, contents, user).await?;
 
   let mut interpolator =
     Interpolator::new(Some(&variables), &secrets);
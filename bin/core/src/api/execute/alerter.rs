@@ -76,6 +76,7 @@ impl Resolve<ExecuteArgs> for TestAlerter {
         name: alerter.name.clone(),
       },
       resolved_ts: Some(ts),
+      escalation: Default::default(),
     };
 
     if let Err(e) = send_alert_to_alerter(&alerter, &alert).await {
@@ -173,6 +174,7 @@ impl Resolve<ExecuteArgs> for SendAlert {
         details: self.details,
       },
       resolved_ts: Some(ts),
+      escalation: Default::default(),
     };
 
     update.push_simple_log(
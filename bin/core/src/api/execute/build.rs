@@ -25,6 +25,7 @@ use komodo_client::{
     write::RefreshBuildCache,
   },
   entities::{
+    RepoExecutionArgs,
     alert::{Alert, AlertData, SeverityLevel},
     all_logs_success,
     build::{Build, BuildConfig},
@@ -49,6 +50,8 @@ use crate::{
     build_git_token,
     builder::{cleanup_builder_instance, connect_builder_periphery},
     channel::build_cancel_channel,
+    commit_status::{CommitState, CommitStatusReporter},
+    external_secrets::add_external_secrets,
     query::{
       VariablesAndSecrets, get_deployment_state,
       get_variables_and_secrets,
@@ -129,7 +132,7 @@ impl Resolve<ExecuteArgs> for RunBuild {
 
     let VariablesAndSecrets {
       mut variables,
-      secrets,
+      mut secrets,
     } = get_variables_and_secrets().await?;
 
     // Add the $VERSION to variables. Use with [[$VERSION]]
@@ -239,6 +242,13 @@ impl Resolve<ExecuteArgs> for RunBuild {
 
     // INTERPOLATE VARIABLES
     let secret_replacers = if !build.config.skip_secret_interp {
+      add_external_secrets(
+        &mut secrets,
+        &(&build.config, &repo),
+        user,
+      )
+      .await?;
+
       let mut interpolator =
         Interpolator::new(Some(&variables), &secrets);
 
@@ -255,6 +265,26 @@ impl Resolve<ExecuteArgs> for RunBuild {
       Default::default()
     };
 
+    let repo_args: RepoExecutionArgs =
+      repo.as_ref().map(Into::into).unwrap_or((&build).into());
+    let commit_status_context =
+      format!("komodo/build/{}", build.name);
+    let commit_status = (build.config.commit_status
+      && !build.config.files_on_host)
+      .then(|| {
+        CommitStatusReporter::new(
+          repo_args.clone(),
+          Some(commit_status_context),
+          None,
+        )
+      });
+    // Only reports once the cloned commit hash is known.
+    let report_status = |update: &Update, state: CommitState| {
+      if let Some(commit_status) = &commit_status {
+        commit_status.report(update, state);
+      }
+    };
+
     let commit_message = if !build.config.files_on_host
       && (!build.config.repo.is_empty()
         || !build.config.linked_repo.is_empty())
@@ -263,7 +293,7 @@ impl Resolve<ExecuteArgs> for RunBuild {
       let res = tokio::select! {
         res = periphery
           .request(api::git::PullOrCloneRepo {
-            args: repo.as_ref().map(Into::into).unwrap_or((&build).into()),
+            args: repo_args.clone(),
             git_token,
             environment: Default::default(),
             env_file_path: Default::default(),
@@ -288,6 +318,7 @@ impl Resolve<ExecuteArgs> for RunBuild {
           update.logs.extend(res.res.logs);
           update.commit_hash =
             res.res.commit_hash.unwrap_or_default().to_string();
+          report_status(&update, CommitState::Pending);
           res.res.commit_message.unwrap_or_default()
         }
         Err(e) => {
@@ -326,6 +357,7 @@ impl Resolve<ExecuteArgs> for RunBuild {
           update.push_error_log("Build cancelled", String::from("User cancelled build during docker build"));
           cleanup_builder_instance(periphery, cleanup_data, &mut update)
             .await;
+          report_status(&update, CommitState::Failure);
           return handle_early_return(update, build.id, build.name, true).await
         },
       };
@@ -347,6 +379,8 @@ impl Resolve<ExecuteArgs> for RunBuild {
 
     update.finalize();
 
+    report_status(&update, CommitState::from_success(update.success));
+
     let db = db_client();
 
     if update.success {
@@ -407,6 +441,7 @@ impl Resolve<ExecuteArgs> for RunBuild {
           target,
           ts: komodo_timestamp(),
           resolved_ts: Some(komodo_timestamp()),
+          escalation: Default::default(),
           resolved: true,
           level: SeverityLevel::Warning,
           data: AlertData::BuildFailed { id, name, version },
@@ -461,6 +496,7 @@ async fn handle_early_return(
         target,
         ts: komodo_timestamp(),
         resolved_ts: Some(komodo_timestamp()),
+        escalation: Default::default(),
         resolved: true,
         level: SeverityLevel::Warning,
         data: AlertData::BuildFailed {
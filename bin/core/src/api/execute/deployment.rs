@@ -6,15 +6,18 @@ use interpolate::Interpolator;
 use komodo_client::{
   api::execute::*,
   entities::{
-    SwarmOrServer, Version,
+    Operation, ResourceTarget, SwarmOrServer, TerminationSignal,
+    Version,
     build::{Build, ImageRegistryConfig},
     deployment::{
-      Deployment, DeploymentImage, DeploymentInfo,
+      DeployStrategy, Deployment, DeploymentConfig, DeploymentImage,
+      DeploymentInfo, DeploymentState, PartialDeploymentConfig,
       extract_registry_domain,
     },
     komodo_timestamp, optional_string,
     permission::PermissionLevel,
     server::Server,
+    to_path_compatible_name,
     update::{Log, Update},
   },
 };
@@ -26,18 +29,21 @@ use reqwest::StatusCode;
 
 use crate::{
   helpers::{
+    external_secrets::add_external_secrets,
+    health_check::wait_for_healthy,
     periphery_client,
     query::{VariablesAndSecrets, get_variables_and_secrets},
     registry_token,
     swarm::swarm_request,
-    update::update_update,
+    update::{get_rollback_update, update_update},
   },
   monitor::{refresh_server_cache, refresh_swarm_cache},
+  permission::get_check_permissions,
   resource::{self, setup_deployment_execution},
-  state::action_states,
+  state::{action_states, deployment_status_cache},
 };
 
-use super::{ExecuteArgs, ExecuteRequest};
+use super::{ExecuteArgs, ExecuteRequest, inner_handler};
 
 impl super::BatchExecute for BatchDeploy {
   type Resource = Deployment;
@@ -118,6 +124,10 @@ impl Resolve<ExecuteArgs> for Deploy {
     // Send update after setting action state, this way UI gets correct state.
     update_update(update.clone()).await?;
 
+    // The config recorded on the update for rollback,
+    // taken before any interpolation.
+    let mut deployed_config = deployment.config.clone();
+
     // This block resolves the attached Build to an actual versioned image
     let (version, registry_token) = match &deployment.config.image {
       DeploymentImage::Build { build_id, version } => {
@@ -132,6 +142,11 @@ impl Resolve<ExecuteArgs> for Deploy {
         } else {
           *version
         };
+        // Pin the deployed build version for rollback
+        deployed_config.image = DeploymentImage::Build {
+          build_id: build_id.clone(),
+          version,
+        };
         let version_str = version.to_string();
         // Potentially add the build image_tag postfix
         let version_str = if build.config.image_tag.is_empty() {
@@ -192,8 +207,12 @@ impl Resolve<ExecuteArgs> for Deploy {
     // interpolate variables / secrets, returning the sanitizing replacers to send to
     // periphery so it may sanitize the final command for safe logging (avoids exposing secret values)
     let secret_replacers = if !deployment.config.skip_secret_interp {
-      let VariablesAndSecrets { variables, secrets } =
-        get_variables_and_secrets().await?;
+      let VariablesAndSecrets {
+        variables,
+        mut secrets,
+      } = get_variables_and_secrets().await?;
+      add_external_secrets(&mut secrets, &deployment.config, user)
+        .await?;
 
       let mut interpolator =
         Interpolator::new(Some(&variables), &secrets);
@@ -208,13 +227,56 @@ impl Resolve<ExecuteArgs> for Deploy {
     };
 
     update.version = version;
+    update.deployed_config = serde_json::to_string(&deployed_config)
+      .context("Failed to serialize deployed config")?;
     update_update(update.clone()).await?;
 
     let deployment_id = deployment.id.clone();
+    let deployment_name = deployment.name.clone();
+    let health_check = deployment.config.health_check.then(|| {
+      (
+        deployment.config.health_check_url.clone(),
+        deployment.config.health_check_timeout,
+      )
+    });
+    let rollback_on_health_failure =
+      deployment.config.rollback_on_health_failure;
+    let mut health_check_failed = false;
+
+    // Blue / green only makes sense with a running container to keep up,
+    // otherwise fall back to recreating the container.
+    let blue_green = if deployment.config.deploy_strategy
+      == DeployStrategy::BlueGreen
+      && matches!(swarm_or_server, SwarmOrServer::Server(_))
+    {
+      let state = deployment_status_cache()
+        .get(&deployment_id)
+        .await
+        .unwrap_or_default()
+        .curr
+        .state;
+      if state != DeploymentState::Running {
+        update.push_simple_log(
+          "Blue Green Deploy",
+          format!(
+            "Deployment is {state}, there is no running container to keep up. Recreating the container."
+          ),
+        );
+      }
+      state == DeploymentState::Running
+    } else {
+      false
+    };
 
     match swarm_or_server {
       SwarmOrServer::None => unreachable!(),
       SwarmOrServer::Swarm(swarm) => {
+        if health_check.is_some() {
+          update.push_simple_log(
+            "Health Check",
+            "Health check is not supported for Swarm Deployments, skipping.",
+          );
+        }
         match swarm_request(
           &swarm.config.server_ids,
           api::swarm::CreateSwarmService {
@@ -237,6 +299,25 @@ impl Resolve<ExecuteArgs> for Deploy {
           }
         };
       }
+      SwarmOrServer::Server(server) if blue_green => {
+        if let Err(e) = deploy_blue_green(
+          &server,
+          deployment,
+          self.stop_signal,
+          self.stop_time,
+          registry_token,
+          secret_replacers.into_iter().collect(),
+          &mut update,
+        )
+        .await
+        {
+          update.push_error_log(
+            "Blue Green Deploy",
+            format_serror(&e.into()),
+          );
+        }
+        refresh_server_cache(&server, true).await;
+      }
       SwarmOrServer::Server(server) => {
         match periphery_client(&server)
           .await?
@@ -250,8 +331,22 @@ impl Resolve<ExecuteArgs> for Deploy {
           .await
         {
           Ok(log) => {
+            let deployed = log.success;
+            update.logs.push(log);
+            if deployed && let Some((url, timeout)) = &health_check {
+              // Send the deploy log while waiting on the health check
+              update_update(update.clone()).await?;
+              let log = wait_for_healthy(
+                &server,
+                std::slice::from_ref(&deployment_name),
+                url,
+                *timeout,
+              )
+              .await;
+              health_check_failed = !log.success;
+              update.logs.push(log);
+            }
             refresh_server_cache(&server, true).await;
-            update.logs.push(log)
           }
           Err(e) => {
             update.push_error_log(
@@ -277,13 +372,140 @@ impl Resolve<ExecuteArgs> for Deploy {
       );
     }
 
+    let trigger_rollback = health_check_failed
+      && rollback_on_health_failure
+      // Don't roll back a failed rollback
+      && update.operation != Operation::RollbackDeployment;
+
+    if trigger_rollback {
+      update.push_simple_log(
+        "Rollback",
+        "Health check failed, triggering RollbackDeployment",
+      );
+    }
+
     update.finalize();
     update_update(update.clone()).await?;
 
+    if trigger_rollback {
+      // Release the action state so the rollback can proceed.
+      drop(_action_guard);
+      if let Err(e) = inner_handler(
+        ExecuteRequest::RollbackDeployment(RollbackDeployment {
+          deployment: deployment_id.clone(),
+          stop_signal: None,
+          stop_time: None,
+        }),
+        user.clone(),
+      )
+      .await
+      {
+        warn!(
+          "Failed to trigger rollback for deployment {deployment_id} | {e:#}"
+        );
+      }
+    }
+
     Ok(update)
   }
 }
 
+/// Starts the new container next to the currently running one,
+/// and only replaces it after the new container is healthy.
+/// If anything fails before the old container is removed,
+/// the new container is removed and the old one keeps running.
+async fn deploy_blue_green(
+  server: &Server,
+  mut deployment: Deployment,
+  stop_signal: Option<TerminationSignal>,
+  stop_time: Option<i32>,
+  registry_token: Option<String>,
+  replacers: Vec<(String, String)>,
+  update: &mut Update,
+) -> anyhow::Result<()> {
+  let periphery = periphery_client(server).await?;
+
+  let name = deployment.name.clone();
+  let next_name = format!("{name}-komodo-next");
+  let url = deployment.config.health_check_url.clone();
+  let timeout = deployment.config.health_check_timeout;
+  deployment.name = next_name.clone();
+
+  let log = periphery
+    .request(api::container::RunContainer {
+      deployment,
+      stop_signal,
+      stop_time,
+      registry_token,
+      replacers,
+    })
+    .await
+    .context("Failed to start new container")?;
+  let started = log.success;
+  update.logs.push(log);
+
+  if started {
+    // Send the run log while waiting on the health check
+    update_update(update.clone()).await?;
+    let log = wait_for_healthy(
+      server,
+      std::slice::from_ref(&next_name),
+      &url,
+      timeout,
+    )
+    .await;
+    let healthy = log.success;
+    update.logs.push(log);
+    if healthy {
+      let log = periphery
+        .request(api::container::RemoveContainer {
+          name: name.clone(),
+          signal: stop_signal,
+          time: stop_time,
+        })
+        .await
+        .context("Failed to remove old container")?;
+      let removed = log.success;
+      update.logs.push(log);
+      if !removed {
+        update.push_error_log(
+          "Blue Green Deploy",
+          format!(
+            "Failed to remove the old container. The new container is left running as {next_name}."
+          ),
+        );
+        return Ok(());
+      }
+      let log = periphery
+        .request(api::container::RenameContainer {
+          curr_name: next_name,
+          new_name: name,
+        })
+        .await
+        .context("Failed to rename new container")?;
+      update.logs.push(log);
+      return Ok(());
+    }
+  }
+
+  // The old container is untouched, clean up the new one.
+  let log = periphery
+    .request(api::container::RemoveContainer {
+      name: next_name,
+      signal: None,
+      time: None,
+    })
+    .await
+    .context("Failed to remove new container")?;
+  update.logs.push(log);
+  update.push_error_log(
+    "Blue Green Deploy",
+    "The new container failed to become healthy. The old container was kept running.",
+  );
+
+  Ok(())
+}
+
 /// Wait this long after a pull to allow another pull through
 const PULL_TIMEOUT: i64 = 5_000;
 type ServerId = String;
@@ -991,3 +1213,231 @@ impl Resolve<ExecuteArgs> for DestroyDeployment {
     Ok(update)
   }
 }
+
+impl Resolve<ExecuteArgs> for RollbackDeployment {
+  #[instrument(
+    "RollbackDeployment",
+    skip_all,
+    fields(
+      task_id = task_id.to_string(),
+      operator = user.id,
+      update_id = update.id,
+      deployment = self.deployment,
+      stop_signal = format!("{:?}", self.stop_signal),
+      stop_time = self.stop_time,
+    )
+  )]
+  async fn resolve(
+    self,
+    ExecuteArgs {
+      user,
+      update,
+      task_id,
+    }: &ExecuteArgs,
+  ) -> mogh_error::Result<Update> {
+    let deployment = get_check_permissions::<Deployment>(
+      &self.deployment,
+      user,
+      PermissionLevel::Execute.into(),
+    )
+    .await?;
+
+    let rollback = get_rollback_update(
+      &ResourceTarget::Deployment(deployment.id.clone()),
+      &[Operation::Deploy, Operation::RollbackDeployment],
+    )
+    .await?;
+
+    let config: DeploymentConfig =
+      serde_json::from_str(&rollback.deployed_config)
+        .context("Failed to parse deployed config for rollback")?;
+    let config: PartialDeploymentConfig = config.into();
+
+    let deployment_id = deployment.id.clone();
+
+    // Restore the config, which also records an UpdateDeployment
+    // with the config diff. Only requires Execute permission.
+    resource::restore_config::<Deployment>(deployment, config, user)
+      .await
+      .context("Failed to restore deployment config")?;
+
+    let mut update = update.clone();
+    update.push_simple_log(
+      "Rollback",
+      format!(
+        "Restored config from previous deploy (update id: {}){}",
+        rollback.id,
+        if rollback.version.is_none() {
+          String::new()
+        } else {
+          format!(" | version: {}", rollback.version)
+        }
+      ),
+    );
+
+    Deploy {
+      deployment: deployment_id,
+      stop_signal: self.stop_signal,
+      stop_time: self.stop_time,
+    }
+    .resolve(&ExecuteArgs {
+      user: user.clone(),
+      update,
+      task_id: *task_id,
+    })
+    .await
+  }
+}
+
+impl Resolve<ExecuteArgs> for BackupDeploymentVolumes {
+  #[instrument(
+    "BackupDeploymentVolumes",
+    skip_all,
+    fields(
+      task_id = task_id.to_string(),
+      operator = user.id,
+      update_id = update.id,
+      deployment = self.deployment,
+      mode = self.mode.to_string(),
+      skip_bind_mounts = self.skip_bind_mounts,
+    )
+  )]
+  async fn resolve(
+    self,
+    ExecuteArgs {
+      user,
+      update,
+      task_id,
+    }: &ExecuteArgs,
+  ) -> mogh_error::Result<Update> {
+    let (deployment, swarm_or_server) = setup_deployment_execution(
+      &self.deployment,
+      user,
+      PermissionLevel::Execute.into(),
+    )
+    .await?;
+
+    let SwarmOrServer::Server(server) = swarm_or_server else {
+      return Err(
+        anyhow!("BackupDeploymentVolumes should not be called for Deployment in Swarm Mode")
+          .status_code(StatusCode::BAD_REQUEST),
+      );
+    };
+
+    let action_state = action_states()
+      .deployment
+      .get_or_insert_default(&deployment.id)
+      .await;
+
+    let _action_guard =
+      action_state.update(|state| state.backing_up = true)?;
+
+    let mut update = update.clone();
+
+    update_update(update.clone()).await?;
+
+    let logs = match periphery_client(&server)
+      .await?
+      .request(api::docker::BackupContainerVolumes {
+        name: deployment_volume_backup_name(&deployment),
+        containers: vec![deployment.name],
+        mode: self.mode,
+        skip_bind_mounts: self.skip_bind_mounts,
+      })
+      .await
+    {
+      Ok(logs) => logs,
+      Err(e) => vec![Log::error(
+        "Backup Volumes",
+        format_serror(
+          &e.context("Failed to back up deployment volumes").into(),
+        ),
+      )],
+    };
+
+    update.logs.extend(logs);
+    refresh_server_cache(&server, true).await;
+    update.finalize();
+    update_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
+impl Resolve<ExecuteArgs> for RestoreDeploymentVolumes {
+  #[instrument(
+    "RestoreDeploymentVolumes",
+    skip_all,
+    fields(
+      task_id = task_id.to_string(),
+      operator = user.id,
+      update_id = update.id,
+      deployment = self.deployment,
+      backup = self.backup,
+    )
+  )]
+  async fn resolve(
+    self,
+    ExecuteArgs {
+      user,
+      update,
+      task_id,
+    }: &ExecuteArgs,
+  ) -> mogh_error::Result<Update> {
+    let (deployment, swarm_or_server) = setup_deployment_execution(
+      &self.deployment,
+      user,
+      PermissionLevel::Execute.into(),
+    )
+    .await?;
+
+    let SwarmOrServer::Server(server) = swarm_or_server else {
+      return Err(
+        anyhow!("RestoreDeploymentVolumes should not be called for Deployment in Swarm Mode")
+          .status_code(StatusCode::BAD_REQUEST),
+      );
+    };
+
+    let action_state = action_states()
+      .deployment
+      .get_or_insert_default(&deployment.id)
+      .await;
+
+    let _action_guard =
+      action_state.update(|state| state.restoring = true)?;
+
+    let mut update = update.clone();
+
+    update_update(update.clone()).await?;
+
+    let logs = match periphery_client(&server)
+      .await?
+      .request(api::docker::RestoreContainerVolumes {
+        name: deployment_volume_backup_name(&deployment),
+        backup: optional_string(self.backup),
+        containers: vec![deployment.name],
+      })
+      .await
+    {
+      Ok(logs) => logs,
+      Err(e) => vec![Log::error(
+        "Restore Volumes",
+        format_serror(
+          &e.context("Failed to restore deployment volumes").into(),
+        ),
+      )],
+    };
+
+    update.logs.extend(logs);
+    refresh_server_cache(&server, true).await;
+    update.finalize();
+    update_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
+/// The folder the deployment volume backups are kept in on the server.
+fn deployment_volume_backup_name(deployment: &Deployment) -> String {
+  format!("deployments/{}", to_path_compatible_name(&deployment.name))
+}
@@ -1,21 +1,28 @@
-use std::{fmt::Write as _, sync::OnceLock};
+use std::{
+  fmt::Write as _,
+  path::{Component, Path},
+  str::FromStr,
+  sync::OnceLock,
+};
 
 use anyhow::{Context, anyhow};
 use command::run_komodo_standard_command;
 use database::{
-  bson::{Document, doc},
+  bson::{Document, doc, oid::ObjectId},
   mungos::find::find_collect,
+  utils::RestoreFilter,
 };
 use formatting::{bold, format_serror};
 use futures_util::{StreamExt, stream::FuturesOrdered};
 use komodo_client::{
   api::execute::{
     BackupCoreDatabase, ClearRepoCache, GlobalAutoUpdate,
-    RotateAllServerKeys, RotateCoreKeys,
+    RestoreCoreDatabase, RotateAllServerKeys, RotateCoreKeys,
+    RotateEncryptionKey,
   },
   entities::{
-    SwarmOrServer, deployment::DeploymentState, server::ServerState,
-    stack::StackState, swarm::SwarmState,
+    SwarmOrServer, deployment::DeploymentState, optional_string,
+    server::ServerState, stack::StackState, swarm::SwarmState,
   },
 };
 use mogh_error::AddStatusCodeError;
@@ -33,7 +40,11 @@ use crate::{
   },
   config::{core_config, core_keys},
   helpers::{
-    periphery_client, query::find_swarm_or_server,
+    encryption::{
+      decrypt_secret, encryption_keys, is_encrypted, rotate_secret,
+    },
+    periphery_client,
+    query::find_swarm_or_server,
     update::update_update,
   },
   resource::rotate_server_keys,
@@ -181,6 +192,115 @@ impl Resolve<ExecuteArgs> for BackupCoreDatabase {
   }
 }
 
+impl Resolve<ExecuteArgs> for RestoreCoreDatabase {
+  #[instrument(
+    "RestoreCoreDatabase",
+    skip_all,
+    fields(
+      task_id = task_id.to_string(),
+      operator = user.id,
+      update_id = update.id,
+      restore_folder = self.restore_folder,
+      collections = format!("{:?}", self.collections),
+      resources = format!("{:?}", self.resources),
+      dry_run = self.dry_run,
+    )
+  )]
+  async fn resolve(
+    self,
+    ExecuteArgs {
+      user,
+      update,
+      task_id,
+    }: &ExecuteArgs,
+  ) -> Result<Self::Response, Self::Error> {
+    if !user.admin {
+      return Err(
+        anyhow!("This method is admin only.")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+
+    let RestoreCoreDatabase {
+      restore_folder,
+      collections,
+      resources,
+      dry_run,
+    } = self;
+
+    // Only allow restoring the dated folders inside 'backups_folder'
+    let restore_folder = optional_string(restore_folder);
+    if let Some(restore_folder) = &restore_folder
+      && !matches!(
+        Path::new(restore_folder).components().collect::<Vec<_>>()[..],
+        [Component::Normal(_)]
+      )
+    {
+      return Err(
+        anyhow!("Restore folder must be a backup folder name, eg '2025-08-01_05-04-53'")
+          .status_code(StatusCode::BAD_REQUEST),
+      );
+    }
+
+    // Don't restore while backing up
+    let _lock = backup_database_lock()
+      .try_lock()
+      .context("Backup or restore already in progress...")?;
+
+    let mut update = update.clone();
+
+    update_update(update.clone()).await?;
+
+    let reports = database::utils::restore(
+      &db_client().db,
+      &core_config().backups_folder,
+      restore_folder.as_deref().map(Path::new),
+      &RestoreFilter {
+        collections,
+        resources,
+        dry_run,
+      },
+    )
+    .await;
+
+    let stage = if dry_run {
+      "Restore Core Database (Dry Run)"
+    } else {
+      "Restore Core Database"
+    };
+
+    match reports {
+      Ok(reports) => {
+        let mut log = String::new();
+        for report in reports {
+          if let Some(e) = &report.error {
+            update.push_error_log(
+              stage,
+              format!("[{}]: {e}", report.collection),
+            );
+          } else if report.inserted + report.overwritten > 0 {
+            let _ = writeln!(&mut log, "{}", report.summary(dry_run));
+          }
+        }
+        if log.is_empty() {
+          log =
+            String::from("No documents matched the restore filter");
+        }
+        update.push_simple_log(stage, log);
+      }
+      Err(e) => {
+        update.push_error_log(stage, format_serror(&e.into()))
+      }
+    }
+
+    update.finalize();
+
+    update_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
 //
 
 /// Makes sure the method can only be called once at a time
@@ -669,3 +789,221 @@ impl Resolve<ExecuteArgs> for RotateCoreKeys {
     Ok(update)
   }
 }
+
+/// Makes sure the method can only be called once at a time
+fn rotate_encryption_key_lock() -> &'static Mutex<()> {
+  static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+  LOCK.get_or_init(Default::default)
+}
+
+impl Resolve<ExecuteArgs> for RotateEncryptionKey {
+  #[instrument(
+    "RotateEncryptionKey",
+    skip_all,
+    fields(
+      task_id = task_id.to_string(),
+      operator = user.id,
+      update_id = update.id,
+    )
+  )]
+  async fn resolve(
+    self,
+    ExecuteArgs {
+      user,
+      update,
+      task_id,
+    }: &ExecuteArgs,
+  ) -> Result<Self::Response, Self::Error> {
+    if !user.admin {
+      return Err(
+        anyhow!("This method is admin only.")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+
+    let _lock = rotate_encryption_key_lock()
+      .try_lock()
+      .context("Encryption key rotation already in progress...")?;
+
+    let mut update = update.clone();
+
+    update_update(update.clone()).await?;
+
+    let mut log = match encryption_keys().current_id() {
+      Some(id) => format!("Encrypting with key: {}\n", bold(id)),
+      None => String::from(
+        "No 'encryption_key' configured, decrypting stored values\n",
+      ),
+    };
+
+    let db = db_client();
+
+    let variables =
+      find_collect(&db.variables, Document::new(), None)
+        .await
+        .context("Failed to query variables from database")?;
+    let mut count = 0;
+    for variable in variables {
+      // Only secret variables are stored encrypted.
+      let value = if variable.is_secret {
+        rotate_secret(&variable.value)
+      } else if is_encrypted(&variable.value) {
+        decrypt_secret(variable.value).map(Some)
+      } else {
+        Ok(None)
+      };
+      let res = match value {
+        // Only matches the value which was read,
+        // so concurrent changes aren't overwritten.
+        Ok(Some(value)) => db
+          .variables
+          .update_one(
+            doc! { "name": &variable.name, "value": &variable.value },
+            doc! { "$set": { "value": value } },
+          )
+          .await
+          .context("Failed to update value on database")
+          .and_then(|res| check_rotation_matched(res.matched_count)),
+        Ok(None) => continue,
+        Err(e) => Err(e),
+      };
+      match res {
+        Ok(_) => count += 1,
+        Err(e) => update.push_error_log(
+          "Rotation Failure",
+          format_serror(
+            &e.context(format!(
+              "Failed to rotate Variable {}",
+              bold(&variable.name)
+            ))
+            .into(),
+          ),
+        ),
+      }
+    }
+    let _ = write!(&mut log, "\nVariables updated: {count}");
+
+    let accounts =
+      find_collect(&db.git_accounts, Document::new(), None)
+        .await
+        .context("Failed to query git accounts from database")?;
+    let mut count = 0;
+    for account in accounts {
+      let res = async {
+        let Some((filter, set)) = rotate_fields(
+          &account.id,
+          &[
+            ("token", &account.token),
+            ("ssh_private_key", &account.ssh_private_key),
+          ],
+        )?
+        else {
+          return anyhow::Ok(false);
+        };
+        let res = db
+          .git_accounts
+          .update_one(filter, doc! { "$set": set })
+          .await
+          .context("Failed to update account on database")?;
+        check_rotation_matched(res.matched_count)?;
+        anyhow::Ok(true)
+      }
+      .await;
+      match res {
+        Ok(true) => count += 1,
+        Ok(false) => {}
+        Err(e) => update.push_error_log(
+          "Rotation Failure",
+          format_serror(
+            &e.context(format!(
+              "Failed to rotate git provider account {} on {}",
+              bold(&account.username),
+              account.domain
+            ))
+            .into(),
+          ),
+        ),
+      }
+    }
+    let _ =
+      write!(&mut log, "\nGit provider accounts updated: {count}");
+
+    let accounts =
+      find_collect(&db.registry_accounts, Document::new(), None)
+        .await
+        .context("Failed to query registry accounts from database")?;
+    let mut count = 0;
+    for account in accounts {
+      let res = async {
+        let Some((filter, set)) =
+          rotate_fields(&account.id, &[("token", &account.token)])?
+        else {
+          return anyhow::Ok(false);
+        };
+        let res = db
+          .registry_accounts
+          .update_one(filter, doc! { "$set": set })
+          .await
+          .context("Failed to update account on database")?;
+        check_rotation_matched(res.matched_count)?;
+        anyhow::Ok(true)
+      }
+      .await;
+      match res {
+        Ok(true) => count += 1,
+        Ok(false) => {}
+        Err(e) => update.push_error_log(
+          "Rotation Failure",
+          format_serror(
+            &e.context(format!(
+              "Failed to rotate docker registry account {} on {}",
+              bold(&account.username),
+              account.domain
+            ))
+            .into(),
+          ),
+        ),
+      }
+    }
+    let _ =
+      write!(&mut log, "\nDocker registry accounts updated: {count}");
+
+    update.push_simple_log("Rotate Encryption Key", log);
+    update.finalize();
+    update_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
+/// The fields which need to be updated to match the current key,
+/// along with a filter matching the values they were rotated from.
+/// Returns None if no fields need to be updated.
+fn rotate_fields(
+  id: &str,
+  fields: &[(&str, &String)],
+) -> anyhow::Result<Option<(Document, Document)>> {
+  let mut filter = doc! { "_id": ObjectId::from_str(id)? };
+  let mut set = Document::new();
+  for (field, value) in fields {
+    if let Some(rotated) = rotate_secret(value)? {
+      filter.insert(*field, value.as_str());
+      set.insert(*field, rotated);
+    }
+  }
+  if set.is_empty() {
+    return Ok(None);
+  }
+  Ok(Some((filter, set)))
+}
+
+/// The update filters match on the values which were rotated,
+/// so no match means the value was changed during the rotation.
+fn check_rotation_matched(matched_count: u64) -> anyhow::Result<()> {
+  if matched_count == 0 {
+    return Err(anyhow!(
+      "Value was changed during the rotation and was not updated. Run the rotation again to rotate it."
+    ));
+  }
+  Ok(())
+}
@@ -28,7 +28,7 @@ use typeshare::typeshare;
 use uuid::Uuid;
 
 use crate::{
-  auth::KomodoAuthImpl,
+  auth::{KomodoAuthImpl, api_key::check_api_key_request_scope},
   helpers::update::{init_execution_update, update_update},
   resource::{KomodoResource, list_full_for_user_using_pattern},
   state::db_client,
@@ -80,7 +80,10 @@ pub enum ExecuteRequest {
   UnpauseStack(UnpauseStack),
   DestroyStack(DestroyStack),
   BatchDestroyStack(BatchDestroyStack),
+  RollbackStack(RollbackStack),
   RunStackService(RunStackService),
+  BackupStackVolumes(BackupStackVolumes),
+  RestoreStackVolumes(RestoreStackVolumes),
 
   // ==== DEPLOYMENT ====
   Deploy(Deploy),
@@ -93,6 +96,9 @@ pub enum ExecuteRequest {
   StopDeployment(StopDeployment),
   DestroyDeployment(DestroyDeployment),
   BatchDestroyDeployment(BatchDestroyDeployment),
+  RollbackDeployment(RollbackDeployment),
+  BackupDeploymentVolumes(BackupDeploymentVolumes),
+  RestoreDeploymentVolumes(RestoreDeploymentVolumes),
 
   // ==== BUILD ====
   RunBuild(RunBuild),
@@ -161,9 +167,11 @@ pub enum ExecuteRequest {
   // ==== MAINTENANCE ====
   ClearRepoCache(ClearRepoCache),
   BackupCoreDatabase(BackupCoreDatabase),
+  RestoreCoreDatabase(RestoreCoreDatabase),
   GlobalAutoUpdate(GlobalAutoUpdate),
   RotateAllServerKeys(RotateAllServerKeys),
   RotateCoreKeys(RotateCoreKeys),
+  RotateEncryptionKey(RotateEncryptionKey),
 }
 
 pub fn router() -> Router {
@@ -187,10 +195,121 @@ async fn variant_handler(
   handler(user, Json(req)).await
 }
 
+/// Whether the request resolves its targets through
+/// `get_check_permissions` or `list_resources_for_user`,
+/// which apply the api key resource scope.
+/// Resource scoped api keys can only call other requests
+/// when they are named explicitly in the scope requests.
+/// There is no wildcard, so new requests must be sorted here.
+fn targets_resources(method: ExecuteRequestMethod) -> bool {
+  use ExecuteRequestMethod as M;
+  match method {
+    // ==== STACK ====
+    M::DeployStack
+    | M::BatchDeployStack
+    | M::DeployStackIfChanged
+    | M::BatchDeployStackIfChanged
+    | M::PullStack
+    | M::BatchPullStack
+    | M::StartStack
+    | M::RestartStack
+    | M::StopStack
+    | M::PauseStack
+    | M::UnpauseStack
+    | M::DestroyStack
+    | M::BatchDestroyStack
+    | M::RollbackStack
+    | M::RunStackService
+    | M::BackupStackVolumes
+    | M::RestoreStackVolumes => true,
+    // ==== DEPLOYMENT ====
+    M::Deploy
+    | M::BatchDeploy
+    | M::PullDeployment
+    | M::StartDeployment
+    | M::RestartDeployment
+    | M::PauseDeployment
+    | M::UnpauseDeployment
+    | M::StopDeployment
+    | M::DestroyDeployment
+    | M::BatchDestroyDeployment
+    | M::RollbackDeployment
+    | M::BackupDeploymentVolumes
+    | M::RestoreDeploymentVolumes => true,
+    // ==== BUILD ====
+    M::RunBuild | M::BatchRunBuild | M::CancelBuild => true,
+    // ==== REPO ====
+    M::CloneRepo
+    | M::BatchCloneRepo
+    | M::PullRepo
+    | M::BatchPullRepo
+    | M::BuildRepo
+    | M::BatchBuildRepo
+    | M::CancelRepoBuild => true,
+    // ==== PROCEDURE ====
+    M::RunProcedure | M::BatchRunProcedure => true,
+    // ==== ACTION ====
+    M::RunAction | M::BatchRunAction => true,
+    // ==== SYNC ====
+    M::RunSync => true,
+    // ==== ALERTER ====
+    M::TestAlerter | M::SendAlert => true,
+    // ==== SERVER ====
+    M::StartContainer
+    | M::RestartContainer
+    | M::PauseContainer
+    | M::UnpauseContainer
+    | M::StopContainer
+    | M::DestroyContainer
+    | M::StartAllContainers
+    | M::RestartAllContainers
+    | M::PauseAllContainers
+    | M::UnpauseAllContainers
+    | M::StopAllContainers
+    | M::PruneContainers
+    | M::DeleteNetwork
+    | M::PruneNetworks
+    | M::DeleteImage
+    | M::PruneImages
+    | M::DeleteVolume
+    | M::PruneVolumes
+    | M::PruneDockerBuilders
+    | M::PruneBuildx
+    | M::PruneSystem => true,
+    // ==== SWARM ====
+    M::RemoveSwarmNodes
+    | M::UpdateSwarmNode
+    | M::RemoveSwarmStacks
+    | M::RemoveSwarmServices
+    | M::CreateSwarmConfig
+    | M::RotateSwarmConfig
+    | M::RemoveSwarmConfigs
+    | M::CreateSwarmSecret
+    | M::RotateSwarmSecret
+    | M::RemoveSwarmSecrets => true,
+    // ==== MAINTENANCE ====
+    M::ClearRepoCache
+    | M::BackupCoreDatabase
+    | M::RestoreCoreDatabase
+    | M::GlobalAutoUpdate
+    | M::RotateAllServerKeys
+    | M::RotateCoreKeys
+    | M::RotateEncryptionKey => false,
+  }
+}
+
 async fn handler(
   Extension(user): Extension<User>,
   Json(request): Json<ExecuteRequest>,
 ) -> mogh_error::Result<(TypedHeader<ContentType>, String)> {
+  let method: ExecuteRequestMethod = (&request).into();
+  check_api_key_request_scope(
+    &user,
+    "execute",
+    &method.to_string(),
+    targets_resources(method),
+  )?;
+
   let res = match inner_handler(request, user).await? {
     ExecutionResult::Single(update) => serde_json::to_string(&update)
       .context("Failed to serialize Update")?,
@@ -170,6 +170,7 @@ fn resolve_inner(
           target,
           ts: komodo_timestamp(),
           resolved_ts: Some(komodo_timestamp()),
+          escalation: Default::default(),
           resolved: true,
           level: SeverityLevel::Warning,
           data: AlertData::ProcedureFailed {
@@ -13,6 +13,7 @@ use interpolate::Interpolator;
 use komodo_client::{
   api::{execute::*, write::RefreshRepoCache},
   entities::{
+    RepoExecutionArgs,
     alert::{Alert, AlertData, SeverityLevel},
     builder::{Builder, BuilderConfig},
     komodo_timestamp,
@@ -20,6 +21,7 @@ use komodo_client::{
     repo::Repo,
     server::Server,
     update::{Log, Update},
+    user::User,
   },
 };
 use mogh_resolver::Resolve;
@@ -32,6 +34,8 @@ use crate::{
   helpers::{
     builder::{cleanup_builder_instance, connect_builder_periphery},
     channel::repo_cancel_channel,
+    commit_status::{CommitState, CommitStatusReporter},
+    external_secrets::add_external_secrets,
     git_token, periphery_client,
     query::{VariablesAndSecrets, get_variables_and_secrets},
     update::update_update,
@@ -116,6 +120,7 @@ impl Resolve<ExecuteArgs> for CloneRepo {
     let git_token = git_token(
       &repo.config.git_provider,
       &repo.config.git_account,
+      repo.config.git_ssh,
       |https| repo.config.git_https = https,
     )
     .await
@@ -131,7 +136,7 @@ impl Resolve<ExecuteArgs> for CloneRepo {
     // interpolate variables / secrets, returning the sanitizing replacers to send to
     // periphery so it may sanitize the final command for safe logging (avoids exposing secret values)
     let secret_replacers =
-      interpolate(&mut repo, &mut update).await?;
+      interpolate(&mut repo, &mut update, user).await?;
 
     let logs = match periphery
       .request(api::git::CloneRepo {
@@ -252,6 +257,7 @@ impl Resolve<ExecuteArgs> for PullRepo {
     let git_token = git_token(
       &repo.config.git_provider,
       &repo.config.git_account,
+      repo.config.git_ssh,
       |https| repo.config.git_https = https,
     )
     .await
@@ -267,7 +273,7 @@ impl Resolve<ExecuteArgs> for PullRepo {
     // interpolate variables / secrets, returning the sanitizing replacers to send to
     // periphery so it may sanitize the final command for safe logging (avoids exposing secret values)
     let secret_replacers =
-      interpolate(&mut repo, &mut update).await?;
+      interpolate(&mut repo, &mut update, user).await?;
 
     let logs = match periphery
       .request(api::git::PullRepo {
@@ -432,6 +438,7 @@ impl Resolve<ExecuteArgs> for BuildRepo {
     let git_token = git_token(
       &repo.config.git_provider,
       &repo.config.git_account,
+      repo.config.git_ssh,
       |https| repo.config.git_https = https,
     )
     .await
@@ -513,12 +520,25 @@ impl Resolve<ExecuteArgs> for BuildRepo {
     // interpolate variables / secrets, returning the sanitizing replacers to send to
     // periphery so it may sanitize the final command for safe logging (avoids exposing secret values)
     let secret_replacers =
-      interpolate(&mut repo, &mut update).await?;
+      interpolate(&mut repo, &mut update, user).await?;
+
+    let repo_args: RepoExecutionArgs = (&repo).into();
+
+    let commit_status = repo.config.commit_status.then(|| {
+      CommitStatusReporter::new(
+        repo_args.clone(),
+        Some(format!("komodo/repo/{}", repo.name)),
+        None,
+      )
+    });
+    if let Some(commit_status) = &commit_status {
+      commit_status.report_pending(&update);
+    }
 
     let res = tokio::select! {
       res = periphery
         .request(api::git::CloneRepo {
-          args: (&repo).into(),
+          args: repo_args.clone(),
           git_token,
           environment: repo.config.env_vars()?,
           env_file_path: repo.config.env_file_path,
@@ -556,6 +576,11 @@ impl Resolve<ExecuteArgs> for BuildRepo {
 
     update.finalize();
 
+    if let Some(commit_status) = &commit_status {
+      commit_status
+        .report(&update, CommitState::from_success(update.success));
+    }
+
     let db = db_client();
 
     if update.success {
@@ -605,6 +630,7 @@ impl Resolve<ExecuteArgs> for BuildRepo {
           target,
           ts: komodo_timestamp(),
           resolved_ts: Some(komodo_timestamp()),
+          escalation: Default::default(),
           resolved: true,
           level: SeverityLevel::Warning,
           data: AlertData::RepoBuildFailed {
@@ -651,6 +677,7 @@ async fn handle_builder_early_return(
         target,
         ts: komodo_timestamp(),
         resolved_ts: Some(komodo_timestamp()),
+        escalation: Default::default(),
         resolved: true,
         level: SeverityLevel::Warning,
         data: AlertData::RepoBuildFailed {
@@ -796,10 +823,14 @@ impl Resolve<ExecuteArgs> for CancelRepoBuild {
 async fn interpolate(
   repo: &mut Repo,
   update: &mut Update,
+  user: &User,
 ) -> anyhow::Result<HashSet<(String, String)>> {
   if !repo.config.skip_secret_interp {
-    let VariablesAndSecrets { variables, secrets } =
-      get_variables_and_secrets().await?;
+    let VariablesAndSecrets {
+      variables,
+      mut secrets,
+    } = get_variables_and_secrets().await?;
+    add_external_secrets(&mut secrets, &repo.config, user).await?;
 
     let mut interpolator =
       Interpolator::new(Some(&variables), &secrets);
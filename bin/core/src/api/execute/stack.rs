@@ -9,13 +9,16 @@ use interpolate::Interpolator;
 use komodo_client::{
   api::{execute::*, write::RefreshStackCache},
   entities::{
-    FileContents, SwarmOrServer,
+    FileContents, Operation, ResourceTarget, SwarmOrServer,
+    optional_string,
     permission::PermissionLevel,
     repo::Repo,
     server::Server,
     stack::{
-      Stack, StackFileRequires, StackInfo, StackRemoteFileContents,
+      PartialStackConfig, Stack, StackConfig, StackFileRequires,
+      StackInfo, StackRemoteFileContents,
     },
+    to_path_compatible_name,
     update::{Log, Update},
     user::User,
   },
@@ -23,7 +26,10 @@ use komodo_client::{
 use mogh_error::AddStatusCodeError as _;
 use mogh_resolver::Resolve;
 use periphery_client::api::{
-  DeployStackResponse, compose::*, swarm::DeploySwarmStack,
+  DeployStackResponse,
+  compose::*,
+  docker::{BackupContainerVolumes, RestoreContainerVolumes},
+  swarm::DeploySwarmStack,
 };
 use reqwest::StatusCode;
 use uuid::Uuid;
@@ -31,12 +37,16 @@ use uuid::Uuid;
 use crate::{
   api::write::WriteArgs,
   helpers::{
+    commit_status::{CommitState, CommitStatusReporter},
+    external_secrets::add_external_secrets,
+    health_check::wait_for_healthy,
     periphery_client,
     query::{VariablesAndSecrets, get_variables_and_secrets},
     stack_git_token,
     swarm::swarm_request,
     update::{
-      add_update_without_send, init_execution_update, update_update,
+      add_update_without_send, get_rollback_update,
+      init_execution_update, update_update,
     },
   },
   monitor::{refresh_server_cache, refresh_swarm_cache},
@@ -48,10 +58,10 @@ use crate::{
     },
     setup_stack_execution,
   },
-  state::{action_states, db_client},
+  state::{action_states, db_client, stack_status_cache},
 };
 
-use super::{ExecuteArgs, ExecuteRequest};
+use super::{ExecuteArgs, ExecuteRequest, inner_handler};
 
 impl super::BatchExecute for BatchDeployStack {
   type Resource = Stack;
@@ -105,6 +115,19 @@ impl Resolve<ExecuteArgs> for DeployStack {
       update,
       task_id,
     }: &ExecuteArgs,
+  ) -> mogh_error::Result<Update> {
+    self.deploy(user, update, None).await
+  }
+}
+
+impl DeployStack {
+  /// Deploys the Stack, optionally checking out the given commit
+  /// of the linked Repo instead of its configured one.
+  async fn deploy(
+    self,
+    user: &User,
+    update: &Update,
+    linked_repo_commit: Option<String>,
   ) -> mogh_error::Result<Update> {
     let (mut stack, swarm_or_server) = setup_stack_execution(
       &self.stack,
@@ -115,12 +138,24 @@ impl Resolve<ExecuteArgs> for DeployStack {
 
     swarm_or_server.verify_has_target()?;
 
+    let full_deploy = self.services.is_empty();
+
+    // The config recorded on the update for rollback,
+    // taken before any interpolation.
+    // Only recorded for full stack deploys.
+    let mut deployed_config =
+      full_deploy.then(|| stack.config.clone());
+
     let mut repo = if !stack.config.files_on_host
       && !stack.config.linked_repo.is_empty()
     {
-      crate::resource::get::<Repo>(&stack.config.linked_repo)
-        .await?
-        .into()
+      let mut repo =
+        crate::resource::get::<Repo>(&stack.config.linked_repo)
+          .await?;
+      if let Some(commit) = linked_repo_commit {
+        repo.config.commit = commit;
+      }
+      Some(repo)
     } else {
       None
     };
@@ -161,8 +196,16 @@ impl Resolve<ExecuteArgs> for DeployStack {
     // interpolate variables / secrets, returning the sanitizing replacers to send to
     // periphery so it may sanitize the final command for safe logging (avoids exposing secret values)
     let secret_replacers = if !stack.config.skip_secret_interp {
-      let VariablesAndSecrets { variables, secrets } =
-        get_variables_and_secrets().await?;
+      let VariablesAndSecrets {
+        variables,
+        mut secrets,
+      } = get_variables_and_secrets().await?;
+      add_external_secrets(
+        &mut secrets,
+        &(&stack.config, &repo),
+        user,
+      )
+      .await?;
 
       let mut interpolator =
         Interpolator::new(Some(&variables), &secrets);
@@ -180,6 +223,35 @@ impl Resolve<ExecuteArgs> for DeployStack {
       Default::default()
     };
 
+    let stack_id = stack.id.clone();
+    let health_check = (full_deploy && stack.config.health_check)
+      .then(|| {
+        (
+          stack.config.ignore_services.clone(),
+          stack.config.health_check_url.clone(),
+          stack.config.health_check_timeout,
+        )
+      });
+    let rollback_on_health_failure =
+      stack.config.rollback_on_health_failure;
+    let mut health_check_failed = false;
+    let commit_status = ((stack.config.commit_status
+      || stack.config.deployment_status)
+      && !stack.config.files_on_host)
+      .then(|| {
+        CommitStatusReporter::new(
+          repo.as_ref().map(Into::into).unwrap_or((&stack).into()),
+          stack
+            .config
+            .commit_status
+            .then(|| format!("komodo/deploy/{}", stack.name)),
+          stack.config.deployment_status.then(|| stack.name.clone()),
+        )
+      });
+    if let Some(commit_status) = &commit_status {
+      commit_status.report_pending(&update);
+    }
+
     let DeployStackResponse {
       logs,
       deployed,
@@ -221,6 +293,27 @@ impl Resolve<ExecuteArgs> for DeployStack {
     };
 
     update.logs.extend(logs);
+    if let Some(hash) = &commit_hash {
+      update.commit_hash = hash.clone();
+    }
+
+    if deployed && let Some(mut config) = deployed_config.take() {
+      // Pin the deployed commit for rollback.
+      // For linked Repos, the commit is checked out on the Repo.
+      if !config.files_on_host
+        && config.file_contents.is_empty()
+        && let Some(hash) = &commit_hash
+      {
+        config.commit = hash.clone();
+      }
+      match serde_json::to_string(&config) {
+        Ok(config) => update.deployed_config = config,
+        Err(e) => warn!(
+          "Failed to serialize deployed config for Stack {} | {e:?}",
+          stack.name
+        ),
+      }
+    }
 
     let update_info = async {
       let latest_services = if services.is_empty() {
@@ -287,6 +380,7 @@ impl Resolve<ExecuteArgs> for DeployStack {
           .then_some(remote_errors),
         latest_hash: commit_hash,
         latest_message: commit_message,
+        preview_template: stack.info.preview_template,
       };
 
       let info = to_document(&info)
@@ -318,19 +412,117 @@ impl Resolve<ExecuteArgs> for DeployStack {
       SwarmOrServer::None => unreachable!(),
       SwarmOrServer::Swarm(swarm) => {
         refresh_swarm_cache(&swarm, true).await;
+        if health_check.is_some() {
+          update.push_simple_log(
+            "Health Check",
+            "Health check is not supported for Swarm Stacks, skipping.",
+          );
+        }
       }
       SwarmOrServer::Server(server) => {
         refresh_server_cache(&server, true).await;
+        if deployed
+          && let Some((ignore_services, url, timeout)) = health_check
+        {
+          // Send the deploy logs while waiting on the health check
+          update_update(update.clone()).await?;
+          let log = stack_health_check(
+            &stack_id,
+            &server,
+            &ignore_services,
+            &url,
+            timeout,
+          )
+          .await;
+          health_check_failed = !log.success;
+          update.logs.push(log);
+          refresh_server_cache(&server, true).await;
+        }
       }
     }
 
+    let trigger_rollback = health_check_failed
+      && rollback_on_health_failure
+      // Don't roll back a failed rollback
+      && update.operation != Operation::RollbackStack;
+
+    if trigger_rollback {
+      update.push_simple_log(
+        "Rollback",
+        "Health check failed, triggering RollbackStack",
+      );
+    }
+
     update.finalize();
     update_update(update.clone()).await?;
 
+    if let Some(commit_status) = &commit_status {
+      commit_status
+        .report(&update, CommitState::from_success(update.success));
+    }
+
+    if trigger_rollback {
+      // Release the action state so the rollback can proceed.
+      drop(_action_guard);
+      if let Err(e) = inner_handler(
+        ExecuteRequest::RollbackStack(RollbackStack {
+          stack: stack_id.clone(),
+          stop_time: None,
+        }),
+        user.clone(),
+      )
+      .await
+      {
+        warn!(
+          "Failed to trigger rollback for stack {stack_id} | {e:#}"
+        );
+      }
+    }
+
     Ok(update)
   }
 }
 
+/// Health checks the stack service containers,
+/// after the server cache has been refreshed following deploy.
+async fn stack_health_check(
+  stack_id: &str,
+  server: &Server,
+  ignore_services: &[String],
+  url: &str,
+  timeout: i32,
+) -> Log {
+  let Some(status) = stack_status_cache().get(stack_id).await else {
+    return Log::error(
+      "Health Check",
+      String::from("No Stack status found to health check"),
+    );
+  };
+  let mut containers = Vec::new();
+  let mut missing = Vec::new();
+  for service in status
+    .curr
+    .services
+    .iter()
+    .filter(|service| !ignore_services.contains(&service.service))
+  {
+    match &service.container {
+      Some(container) => containers.push(container.name.clone()),
+      None => missing.push(service.service.as_str()),
+    }
+  }
+  if !missing.is_empty() {
+    return Log::error(
+      "Health Check",
+      format!(
+        "No container found for service/s: {}",
+        missing.join(", ")
+      ),
+    );
+  }
+  wait_for_healthy(server, &containers, url, timeout).await
+}
+
 impl super::BatchExecute for BatchDeployStackIfChanged {
   type Resource = Stack;
   fn single_request(stack: String) -> ExecuteRequest {
@@ -449,7 +641,7 @@ impl Resolve<ExecuteArgs> for DeployStackIfChanged {
         // For git repo based stacks, need to do a
         // PullStack in order to ensure latest repo contents on the
         // host before restart.
-        maybe_pull_stack(&stack, Some(&mut update)).await?;
+        maybe_pull_stack(&stack, Some(&mut update), user).await?;
 
         let mut update =
           restart_services(stack.name, Vec::new(), user).await?;
@@ -485,7 +677,7 @@ impl Resolve<ExecuteArgs> for DeployStackIfChanged {
             // For git repo based stacks, need to do a
             // PullStack in order to ensure latest repo contents on the
             // host before restart. Only necessary if no "deploys" (deploy already pulls stack).
-            maybe_pull_stack(&stack, Some(&mut update)).await?;
+            maybe_pull_stack(&stack, Some(&mut update), user).await?;
 
             let mut update =
               restart_services(stack.name, restart, user).await?;
@@ -793,6 +985,7 @@ impl Resolve<ExecuteArgs> for BatchPullStack {
 async fn maybe_pull_stack(
   stack: &Stack,
   update: Option<&mut Update>,
+  user: &User,
 ) -> anyhow::Result<()> {
   if stack.config.files_on_host
     || (stack.config.repo.is_empty()
@@ -810,8 +1003,15 @@ async fn maybe_pull_stack(
   } else {
     None
   };
-  pull_stack_inner(stack.clone(), Vec::new(), &server, repo, update)
-    .await?;
+  pull_stack_inner(
+    stack.clone(),
+    Vec::new(),
+    &server,
+    repo,
+    update,
+    user,
+  )
+  .await?;
   Ok(())
 }
 
@@ -829,6 +1029,7 @@ pub async fn pull_stack_inner(
   server: &Server,
   mut repo: Option<Repo>,
   mut update: Option<&mut Update>,
+  user: &User,
 ) -> anyhow::Result<ComposePullResponse> {
   if let Some(update) = update.as_mut()
     && !services.is_empty()
@@ -853,8 +1054,12 @@ pub async fn pull_stack_inner(
 
   // interpolate variables / secrets
   let secret_replacers = if !stack.config.skip_secret_interp {
-    let VariablesAndSecrets { variables, secrets } =
-      get_variables_and_secrets().await?;
+    let VariablesAndSecrets {
+      variables,
+      mut secrets,
+    } = get_variables_and_secrets().await?;
+    add_external_secrets(&mut secrets, &(&stack.config, &repo), user)
+      .await?;
 
     let mut interpolator =
       Interpolator::new(Some(&variables), &secrets);
@@ -955,6 +1160,7 @@ impl Resolve<ExecuteArgs> for PullStack {
       &server,
       repo,
       Some(&mut update),
+      user,
     )
     .await?;
 
@@ -1264,6 +1470,86 @@ impl Resolve<ExecuteArgs> for DestroyStack {
   }
 }
 
+impl Resolve<ExecuteArgs> for RollbackStack {
+  #[instrument(
+    "RollbackStack",
+    skip_all,
+    fields(
+      task_id = task_id.to_string(),
+      operator = user.id,
+      update_id = update.id,
+      stack = self.stack,
+      stop_time = self.stop_time,
+    )
+  )]
+  async fn resolve(
+    self,
+    ExecuteArgs {
+      user,
+      update,
+      task_id,
+    }: &ExecuteArgs,
+  ) -> mogh_error::Result<Update> {
+    let stack = get_check_permissions::<Stack>(
+      &self.stack,
+      user,
+      PermissionLevel::Execute.into(),
+    )
+    .await?;
+
+    let rollback = get_rollback_update(
+      &ResourceTarget::Stack(stack.id.clone()),
+      &[Operation::DeployStack, Operation::RollbackStack],
+    )
+    .await?;
+
+    let mut config: StackConfig =
+      serde_json::from_str(&rollback.deployed_config)
+        .context("Failed to parse deployed config for rollback")?;
+    let commit = config.commit.clone();
+    // The pinned commit belongs to the linked Repo, so keep the
+    // Stack's own commit config and check it out on the Repo instead.
+    let linked_repo_commit = (!config.files_on_host
+      && !config.linked_repo.is_empty()
+      && !commit.is_empty())
+    .then(|| {
+      config.commit = stack.config.commit.clone();
+      commit.clone()
+    });
+    let config: PartialStackConfig = config.into();
+
+    let stack_id = stack.id.clone();
+
+    // Restore the config, which also records an UpdateStack
+    // with the config diff. Only requires Execute permission.
+    resource::restore_config::<Stack>(stack, config, user)
+      .await
+      .context("Failed to restore stack config")?;
+
+    let mut update = update.clone();
+    update.push_simple_log(
+      "Rollback",
+      format!(
+        "Restored config from previous deploy (update id: {}){}",
+        rollback.id,
+        if commit.is_empty() {
+          String::new()
+        } else {
+          format!(" | commit: {commit}")
+        }
+      ),
+    );
+
+    DeployStack {
+      stack: stack_id,
+      services: Vec::new(),
+      stop_time: self.stop_time,
+    }
+    .deploy(user, &update, linked_repo_commit)
+    .await
+  }
+}
+
 impl Resolve<ExecuteArgs> for RunStackService {
   #[instrument(
     "RunStackService",
@@ -1331,8 +1617,16 @@ impl Resolve<ExecuteArgs> for RunStackService {
     )?;
 
     let secret_replacers = if !stack.config.skip_secret_interp {
-      let VariablesAndSecrets { variables, secrets } =
-        get_variables_and_secrets().await?;
+      let VariablesAndSecrets {
+        variables,
+        mut secrets,
+      } = get_variables_and_secrets().await?;
+      add_external_secrets(
+        &mut secrets,
+        &(&stack.config, &repo),
+        user,
+      )
+      .await?;
 
       let mut interpolator =
         Interpolator::new(Some(&variables), &secrets);
@@ -1379,3 +1673,197 @@ impl Resolve<ExecuteArgs> for RunStackService {
     Ok(update)
   }
 }
+
+impl Resolve<ExecuteArgs> for BackupStackVolumes {
+  #[instrument(
+    "BackupStackVolumes",
+    skip_all,
+    fields(
+      task_id = task_id.to_string(),
+      operator = user.id,
+      update_id = update.id,
+      stack = self.stack,
+      services = format!("{:?}", self.services),
+      mode = self.mode.to_string(),
+      skip_bind_mounts = self.skip_bind_mounts,
+    )
+  )]
+  async fn resolve(
+    self,
+    ExecuteArgs {
+      user,
+      update,
+      task_id,
+    }: &ExecuteArgs,
+  ) -> mogh_error::Result<Update> {
+    let (stack, swarm_or_server) = setup_stack_execution(
+      &self.stack,
+      user,
+      PermissionLevel::Execute.into(),
+    )
+    .await?;
+
+    let SwarmOrServer::Server(server) = swarm_or_server else {
+      return Err(
+        anyhow!(
+          "BackupStackVolumes should not be called for Stack in Swarm Mode"
+        )
+        .status_code(StatusCode::BAD_REQUEST),
+      );
+    };
+
+    let action_state =
+      action_states().stack.get_or_insert_default(&stack.id).await;
+
+    let _action_guard =
+      action_state.update(|state| state.backing_up = true)?;
+
+    let mut update = update.clone();
+    update_update(update.clone()).await?;
+
+    let containers =
+      stack_service_containers(&stack, &server, &self.services)
+        .await?;
+
+    let logs = match periphery_client(&server)
+      .await?
+      .request(BackupContainerVolumes {
+        name: stack_volume_backup_name(&stack),
+        containers,
+        mode: self.mode,
+        skip_bind_mounts: self.skip_bind_mounts,
+      })
+      .await
+    {
+      Ok(logs) => logs,
+      Err(e) => vec![Log::error(
+        "Backup Volumes",
+        format_serror(
+          &e.context("Failed to back up stack volumes").into(),
+        ),
+      )],
+    };
+
+    update.logs.extend(logs);
+    refresh_server_cache(&server, true).await;
+    update.finalize();
+    update_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
+impl Resolve<ExecuteArgs> for RestoreStackVolumes {
+  #[instrument(
+    "RestoreStackVolumes",
+    skip_all,
+    fields(
+      task_id = task_id.to_string(),
+      operator = user.id,
+      update_id = update.id,
+      stack = self.stack,
+      backup = self.backup,
+      services = format!("{:?}", self.services),
+    )
+  )]
+  async fn resolve(
+    self,
+    ExecuteArgs {
+      user,
+      update,
+      task_id,
+    }: &ExecuteArgs,
+  ) -> mogh_error::Result<Update> {
+    let (stack, swarm_or_server) = setup_stack_execution(
+      &self.stack,
+      user,
+      PermissionLevel::Execute.into(),
+    )
+    .await?;
+
+    let SwarmOrServer::Server(server) = swarm_or_server else {
+      return Err(
+        anyhow!(
+          "RestoreStackVolumes should not be called for Stack in Swarm Mode"
+        )
+        .status_code(StatusCode::BAD_REQUEST),
+      );
+    };
+
+    let action_state =
+      action_states().stack.get_or_insert_default(&stack.id).await;
+
+    let _action_guard =
+      action_state.update(|state| state.restoring = true)?;
+
+    let mut update = update.clone();
+    update_update(update.clone()).await?;
+
+    let containers =
+      stack_service_containers(&stack, &server, &self.services)
+        .await?;
+
+    let logs = match periphery_client(&server)
+      .await?
+      .request(RestoreContainerVolumes {
+        name: stack_volume_backup_name(&stack),
+        backup: optional_string(self.backup),
+        containers,
+      })
+      .await
+    {
+      Ok(logs) => logs,
+      Err(e) => vec![Log::error(
+        "Restore Volumes",
+        format_serror(
+          &e.context("Failed to restore stack volumes").into(),
+        ),
+      )],
+    };
+
+    update.logs.extend(logs);
+    refresh_server_cache(&server, true).await;
+    update.finalize();
+    update_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
+/// The folder the stack volume backups are kept in on the server.
+fn stack_volume_backup_name(stack: &Stack) -> String {
+  format!("stacks/{}", to_path_compatible_name(&stack.name))
+}
+
+/// The names of the stack service containers,
+/// optionally filtered to specific services.
+async fn stack_service_containers(
+  stack: &Stack,
+  server: &Server,
+  services: &[String],
+) -> anyhow::Result<Vec<String>> {
+  // Make sure the containers are up to date
+  refresh_server_cache(server, true).await;
+  let status =
+    stack_status_cache().get(&stack.id).await.with_context(|| {
+      format!("No status found for Stack {}", stack.name)
+    })?;
+  let containers = status
+    .curr
+    .services
+    .iter()
+    .filter(|service| {
+      services.is_empty() || services.contains(&service.service)
+    })
+    .filter_map(|service| {
+      service.container.as_ref().map(|c| c.name.clone())
+    })
+    .collect::<Vec<_>>();
+  if containers.is_empty() {
+    return Err(anyhow!(
+      "No containers found for Stack {}. It must be deployed to back up / restore volumes.",
+      stack.name
+    ));
+  }
+  Ok(containers)
+}
@@ -0,0 +1,137 @@
+use anyhow::{Context, anyhow};
+use axum::http::HeaderMap;
+use hmac::Hmac;
+use serde::Deserialize;
+use sha2::Sha256;
+
+use super::{
+  ExtractBranch, ExtractChangedFiles, VerifySecret, verify_hmac,
+};
+
+/// Listener implementation for Bitbucket Cloud and Bitbucket Server / Data Center
+pub struct Bitbucket;
+
+impl VerifySecret for Bitbucket {
+  #[instrument("VerifyBitbucketSecret", skip_all)]
+  fn verify_secret(
+    headers: &HeaderMap,
+    body: &str,
+    custom_secret: &str,
+  ) -> anyhow::Result<()> {
+    let signature = headers
+      .get("x-hub-signature")
+      .context("No bitbucket signature in headers")?;
+    let signature = signature
+      .to_str()
+      .context("Failed to get signature as string")?;
+    let signature =
+      signature.strip_prefix("sha256=").unwrap_or(signature);
+    verify_hmac::<Hmac<Sha256>>(signature, body, custom_secret)
+  }
+}
+
+/// Bitbucket pushes can include multiple branches.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BitbucketWebhookBody {
+  /// `repo:push` event
+  Cloud { push: BitbucketCloudPush },
+  /// `repo:refs_changed` event
+  Server { changes: Vec<BitbucketServerChange> },
+}
+
+#[derive(Deserialize)]
+struct BitbucketCloudPush {
+  changes: Vec<BitbucketCloudChange>,
+}
+
+#[derive(Deserialize)]
+struct BitbucketCloudChange {
+  /// Null when the branch is deleted
+  new: Option<BitbucketCloudRef>,
+}
+
+#[derive(Deserialize)]
+struct BitbucketCloudRef {
+  #[serde(rename = "type")]
+  ty: String,
+  name: String,
+}
+
+#[derive(Deserialize)]
+struct BitbucketServerChange {
+  #[serde(rename = "ref")]
+  reference: BitbucketServerRef,
+  #[serde(rename = "type")]
+  ty: String,
+}
+
+#[derive(Deserialize)]
+struct BitbucketServerRef {
+  #[serde(rename = "displayId")]
+  display_id: String,
+  #[serde(rename = "type")]
+  ty: String,
+}
+
+impl BitbucketWebhookBody {
+  /// The pushed (not deleted) branches
+  fn branches(self) -> Vec<String> {
+    match self {
+      BitbucketWebhookBody::Cloud { push } => push
+        .changes
+        .into_iter()
+        .filter_map(|change| change.new)
+        .filter(|new| new.ty == "branch")
+        .map(|new| new.name)
+        .collect(),
+      BitbucketWebhookBody::Server { changes } => changes
+        .into_iter()
+        .filter(|change| {
+          change.reference.ty == "BRANCH" && change.ty != "DELETE"
+        })
+        .map(|change| change.reference.display_id)
+        .collect(),
+    }
+  }
+}
+
+fn extract_branches(body: &str) -> anyhow::Result<Vec<String>> {
+  let branches = serde_json::from_str::<BitbucketWebhookBody>(body)
+    .context("Failed to parse bitbucket request body")?
+    .branches();
+  if branches.is_empty() {
+    Err(anyhow!("No pushed branches in bitbucket request body"))
+  } else {
+    Ok(branches)
+  }
+}
+
+impl ExtractBranch for Bitbucket {
+  fn extract_branch(body: &str) -> anyhow::Result<String> {
+    extract_branches(body)?
+      .into_iter()
+      .next()
+      .context("No pushed branches in bitbucket request body")
+  }
+
+  fn verify_branch(body: &str, expected: &str) -> anyhow::Result<()> {
+    if extract_branches(body)?
+      .iter()
+      .any(|branch| branch == expected)
+    {
+      Ok(())
+    } else {
+      Err(anyhow!("request branch does not match expected"))
+    }
+  }
+}
+
+impl ExtractChangedFiles for Bitbucket {
+  /// Bitbucket push bodies don't include the changed files.
+  fn extract_changed_files(
+    _body: &str,
+  ) -> anyhow::Result<Option<Vec<String>>> {
+    Ok(None)
+  }
+}
@@ -0,0 +1,100 @@
+use anyhow::Context;
+use axum::http::HeaderMap;
+use hmac::Hmac;
+use komodo_client::entities::config::core::WebhookHmacAlgorithm;
+use serde_json::Value;
+use sha1::Sha1;
+use sha2::{Sha256, Sha512};
+
+use crate::config::core_config;
+
+use super::{
+  ExtractBranch, ExtractChangedFiles, VerifySecret,
+  changed_files_from_commits, verify_hmac,
+};
+
+/// Listener implementation for any provider which signs the
+/// request body with an hmac. Configured in the core config with
+/// the `webhook_generic_*` fields.
+pub struct Generic;
+
+impl VerifySecret for Generic {
+  #[instrument("VerifyGenericSecret", skip_all)]
+  fn verify_secret(
+    headers: &HeaderMap,
+    body: &str,
+    custom_secret: &str,
+  ) -> anyhow::Result<()> {
+    let config = core_config();
+    let header = config.webhook_generic_signature_header.as_str();
+    let signature = headers
+      .get(header)
+      .with_context(|| format!("No {header} signature in headers"))?;
+    let signature = signature
+      .to_str()
+      .context("Failed to get signature as string")?;
+    let signature = signature
+      .strip_prefix(config.webhook_generic_signature_prefix.as_str())
+      .unwrap_or(signature);
+    match config.webhook_generic_algorithm {
+      WebhookHmacAlgorithm::Sha1 => {
+        verify_hmac::<Hmac<Sha1>>(signature, body, custom_secret)
+      }
+      WebhookHmacAlgorithm::Sha256 => {
+        verify_hmac::<Hmac<Sha256>>(signature, body, custom_secret)
+      }
+      WebhookHmacAlgorithm::Sha512 => {
+        verify_hmac::<Hmac<Sha512>>(signature, body, custom_secret)
+      }
+    }
+  }
+}
+
+impl ExtractBranch for Generic {
+  fn extract_branch(body: &str) -> anyhow::Result<String> {
+    let path = core_config().webhook_generic_branch_path.as_str();
+    let body = serde_json::from_str::<Value>(body)
+      .context("Failed to parse generic request body")?;
+    let branch = body
+      .pointer(&json_path_to_pointer(path))
+      .and_then(Value::as_str)
+      .with_context(|| {
+        format!("No branch at {path} in generic request body")
+      })?
+      .replace("refs/heads/", "");
+    Ok(branch)
+  }
+}
+
+impl ExtractChangedFiles for Generic {
+  /// Uses the Github style `commits` if the body includes them.
+  fn extract_changed_files(
+    body: &str,
+  ) -> anyhow::Result<Option<Vec<String>>> {
+    Ok(
+      changed_files_from_commits(body)
+        .ok()
+        .filter(|files| !files.is_empty()),
+    )
+  }
+}
+
+/// Converts a simple JSONPath, eg `$.push.changes[0].new.name`,
+/// into a JSON Pointer, eg `/push/changes/0/new/name`.
+fn json_path_to_pointer(path: &str) -> String {
+  let path = path.trim();
+  let path = path.strip_prefix('$').unwrap_or(path);
+  path
+    .replace('[', ".")
+    .split('.')
+    .map(|segment| {
+      segment.trim_end_matches(']').trim_matches(['\'', '"'])
+    })
+    .filter(|segment| !segment.is_empty())
+    .fold(String::new(), |mut pointer, segment| {
+      pointer.push('/');
+      pointer
+        .push_str(&segment.replace('~', "~0").replace('/', "~1"));
+      pointer
+    })
+}
@@ -0,0 +1,61 @@
+use anyhow::Context;
+use axum::http::HeaderMap;
+use hmac::Hmac;
+use serde::Deserialize;
+use sha2::Sha256;
+
+use super::{
+  ExtractBranch, ExtractChangedFiles, PullRequestEvent, VerifySecret,
+  changed_files_from_commits, pull_request_from_body, verify_hmac,
+};
+
+/// Listener implementation for Gitea type API, including Forgejo
+pub struct Gitea;
+
+impl VerifySecret for Gitea {
+  #[instrument("VerifyGiteaSecret", skip_all)]
+  fn verify_secret(
+    headers: &HeaderMap,
+    body: &str,
+    custom_secret: &str,
+  ) -> anyhow::Result<()> {
+    let signature = headers
+      .get("x-gitea-signature")
+      .or_else(|| headers.get("x-forgejo-signature"))
+      .context("No gitea / forgejo signature in headers")?;
+    let signature = signature
+      .to_str()
+      .context("Failed to get signature as string")?;
+    verify_hmac::<Hmac<Sha256>>(signature, body, custom_secret)
+  }
+}
+
+#[derive(Deserialize)]
+struct GiteaWebhookBody {
+  #[serde(rename = "ref")]
+  branch: String,
+}
+
+impl ExtractBranch for Gitea {
+  fn extract_branch(body: &str) -> anyhow::Result<String> {
+    let branch = serde_json::from_str::<GiteaWebhookBody>(body)
+      .context("Failed to parse gitea request body")?
+      .branch
+      .replace("refs/heads/", "");
+    Ok(branch)
+  }
+
+  fn extract_pull_request(
+    body: &str,
+  ) -> anyhow::Result<PullRequestEvent> {
+    pull_request_from_body(body)
+  }
+}
+
+impl ExtractChangedFiles for Gitea {
+  fn extract_changed_files(
+    body: &str,
+  ) -> anyhow::Result<Option<Vec<String>>> {
+    changed_files_from_commits(body).map(Some)
+  }
+}
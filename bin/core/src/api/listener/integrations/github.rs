@@ -7,7 +7,10 @@ use sha2::Sha256;
 
 use crate::config::core_config;
 
-use super::{ExtractBranch, VerifySecret};
+use super::{
+  ExtractBranch, ExtractChangedFiles, PullRequestEvent, VerifySecret,
+  changed_files_from_commits, pull_request_from_body,
+};
 
 type HmacSha256 = Hmac<Sha256>;
 
@@ -60,4 +63,18 @@ impl ExtractBranch for Github {
       .replace("refs/heads/", "");
     Ok(branch)
   }
+
+  fn extract_pull_request(
+    body: &str,
+  ) -> anyhow::Result<PullRequestEvent> {
+    pull_request_from_body(body)
+  }
+}
+
+impl ExtractChangedFiles for Github {
+  fn extract_changed_files(
+    body: &str,
+  ) -> anyhow::Result<Option<Vec<String>>> {
+    changed_files_from_commits(body).map(Some)
+  }
 }
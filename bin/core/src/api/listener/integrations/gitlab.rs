@@ -4,7 +4,10 @@ use serde::Deserialize;
 
 use crate::config::core_config;
 
-use super::{ExtractBranch, VerifySecret};
+use super::{
+  ExtractBranch, ExtractChangedFiles, PullRequestAction,
+  PullRequestEvent, VerifySecret, changed_files_from_commits,
+};
 
 /// Listener implementation for Gitlab type API
 pub struct Gitlab;
@@ -48,4 +51,62 @@ impl ExtractBranch for Gitlab {
       .replace("refs/heads/", "");
     Ok(branch)
   }
+
+  fn extract_pull_request(
+    body: &str,
+  ) -> anyhow::Result<PullRequestEvent> {
+    let body = serde_json::from_str::<GitlabMergeRequestBody>(body)
+      .context("Failed to parse gitlab merge request body")?
+      .object_attributes;
+    let action = match body.action.as_deref() {
+      Some("open" | "reopen" | "update") => PullRequestAction::Update,
+      Some("close" | "merge") => PullRequestAction::Close,
+      _ => PullRequestAction::Ignore,
+    };
+    Ok(PullRequestEvent {
+      action,
+      number: body.iid,
+      branch: body.source_branch,
+      base_branch: body.target_branch,
+      from_fork: body.source_project_id != body.target_project_id,
+    })
+  }
+}
+
+#[derive(Deserialize)]
+struct GitlabMergeRequestBody {
+  object_attributes: GitlabMergeRequestAttributes,
+}
+
+#[derive(Deserialize)]
+struct GitlabMergeRequestAttributes {
+  iid: u64,
+  action: Option<String>,
+  source_branch: String,
+  target_branch: String,
+  source_project_id: u64,
+  target_project_id: u64,
+}
+
+#[derive(Deserialize)]
+struct GitlabWebhookCommitCount {
+  #[serde(default)]
+  total_commits_count: usize,
+  #[serde(default)]
+  commits: Vec<serde_json::Value>,
+}
+
+impl ExtractChangedFiles for Gitlab {
+  fn extract_changed_files(
+    body: &str,
+  ) -> anyhow::Result<Option<Vec<String>>> {
+    let count =
+      serde_json::from_str::<GitlabWebhookCommitCount>(body)
+        .context("Failed to parse gitlab request body")?;
+    // Gitlab only includes the first 20 commits of large pushes.
+    if count.total_commits_count > count.commits.len() {
+      return Ok(None);
+    }
+    changed_files_from_commits(body).map(Some)
+  }
 }
@@ -1,4 +1,130 @@
+use anyhow::Context;
+use hmac::{Mac, digest::KeyInit};
+use serde::Deserialize;
+
+use crate::config::core_config;
+
+pub mod bitbucket;
+pub mod generic;
+pub mod gitea;
 pub mod github;
 pub mod gitlab;
 
-use super::{ExtractBranch, VerifySecret};
+use super::{
+  ExtractBranch, ExtractChangedFiles, PullRequestAction,
+  PullRequestEvent, VerifySecret,
+};
+
+/// Verifies the hex encoded hmac `signature` of the request body,
+/// using the custom secret if provided, or the core webhook secret.
+fn verify_hmac<M: Mac + KeyInit>(
+  signature: &str,
+  body: &str,
+  custom_secret: &str,
+) -> anyhow::Result<()> {
+  let secret_bytes = if custom_secret.is_empty() {
+    core_config().webhook_secret.as_bytes()
+  } else {
+    custom_secret.as_bytes()
+  };
+  let signature =
+    hex::decode(signature).context("Signature is not valid hex")?;
+  let mut mac = <M as Mac>::new_from_slice(secret_bytes)
+    .context("Failed to create hmac from secret")?;
+  mac.update(body.as_bytes());
+  mac
+    .verify_slice(&signature)
+    .context("Signature does not equal expected")
+}
+
+/// The `commits` in Github / Gitlab / Gitea style push bodies.
+#[derive(Deserialize)]
+struct PushCommits {
+  #[serde(default)]
+  commits: Vec<PushCommit>,
+}
+
+#[derive(Deserialize)]
+struct PushCommit {
+  #[serde(default)]
+  added: Vec<String>,
+  #[serde(default)]
+  modified: Vec<String>,
+  #[serde(default)]
+  removed: Vec<String>,
+}
+
+/// Collects the files changed across all the pushed commits.
+fn changed_files_from_commits(
+  body: &str,
+) -> anyhow::Result<Vec<String>> {
+  let mut files = serde_json::from_str::<PushCommits>(body)
+    .context("Failed to parse commits from request body")?
+    .commits
+    .into_iter()
+    .flat_map(|commit| {
+      [commit.added, commit.modified, commit.removed].concat()
+    })
+    .collect::<Vec<_>>();
+  files.sort();
+  files.dedup();
+  Ok(files)
+}
+
+/// Github / Gitea style pull request bodies.
+#[derive(Deserialize)]
+struct PullRequestBody {
+  action: String,
+  number: u64,
+  pull_request: PullRequestBodyInner,
+}
+
+#[derive(Deserialize)]
+struct PullRequestBodyInner {
+  head: PullRequestBodyRef,
+  base: PullRequestBodyRef,
+}
+
+#[derive(Deserialize)]
+struct PullRequestBodyRef {
+  #[serde(rename = "ref")]
+  branch: String,
+  /// Null if the head repo (fork) was deleted.
+  repo: Option<PullRequestBodyRepo>,
+}
+
+#[derive(Deserialize)]
+struct PullRequestBodyRepo {
+  full_name: String,
+}
+
+fn pull_request_from_body(
+  body: &str,
+) -> anyhow::Result<PullRequestEvent> {
+  let body = serde_json::from_str::<PullRequestBody>(body)
+    .context("Failed to parse pull request from request body")?;
+  let action = match body.action.as_str() {
+    // Gitea uses 'synchronized'
+    "opened" | "reopened" | "synchronize" | "synchronized" => {
+      PullRequestAction::Update
+    }
+    "closed" => PullRequestAction::Close,
+    _ => PullRequestAction::Ignore,
+  };
+  let PullRequestBodyInner { head, base } = body.pull_request;
+  // The head branch only exists in the base repo
+  // if the pull request isn't from a fork.
+  let from_fork = match (&head.repo, &base.repo) {
+    (Some(head), Some(base)) => {
+      !head.full_name.eq_ignore_ascii_case(&base.full_name)
+    }
+    _ => true,
+  };
+  Ok(PullRequestEvent {
+    action,
+    number: body.number,
+    branch: head.branch,
+    base_branch: base.branch,
+    from_fork,
+  })
+}
@@ -6,7 +6,7 @@ use komodo_client::entities::resource::Resource;
 use mogh_cache::CloneCache;
 use tokio::sync::Mutex;
 
-use crate::resource::KomodoResource;
+use crate::{helpers::matcher::Matcher, resource::KomodoResource};
 
 mod integrations;
 mod resources;
@@ -18,6 +18,9 @@ pub fn router() -> Router {
   Router::new()
     .nest("/github", router::router::<github::Github>())
     .nest("/gitlab", router::router::<gitlab::Gitlab>())
+    .nest("/gitea", router::router::<gitea::Gitea>())
+    .nest("/bitbucket", router::router::<bitbucket::Bitbucket>())
+    .nest("/generic", router::router::<generic::Generic>())
 }
 
 type ListenerLockCache = CloneCache<String, Arc<Mutex<()>>>;
@@ -49,6 +52,84 @@ trait ExtractBranch {
       Err(anyhow!("request branch does not match expected"))
     }
   }
+  /// Parses pull request events, used for preview Stacks.
+  fn extract_pull_request(
+    _body: &str,
+  ) -> anyhow::Result<PullRequestEvent> {
+    Err(anyhow!(
+      "Pull request events are not supported for this provider"
+    ))
+  }
+}
+
+/// A pull request webhook event, used for preview Stacks.
+struct PullRequestEvent {
+  action: PullRequestAction,
+  /// The pull request number
+  number: u64,
+  /// The branch with the changes
+  branch: String,
+  /// The branch the pull request targets
+  base_branch: String,
+  /// Whether the branch with the changes is in a fork,
+  /// rather than the repo the pull request targets.
+  from_fork: bool,
+}
+
+enum PullRequestAction {
+  /// Opened, reopened, or pushed to
+  Update,
+  /// Closed or merged
+  Close,
+  /// Any other action, eg labeled
+  Ignore,
+}
+
+/// Implemented on the integration struct, eg [integrations::github::Github]
+trait ExtractChangedFiles {
+  /// The files changed by the push, or `None`
+  /// if the provider doesn't include them in the payload.
+  fn extract_changed_files(
+    body: &str,
+  ) -> anyhow::Result<Option<Vec<String>>>;
+}
+
+/// Checks the files changed by the push against the resource
+/// `webhook_path_filters`. Returns the reason to skip the webhook
+/// if none of the changed files match.
+fn path_filters_skip_reason<P: ExtractChangedFiles>(
+  body: &str,
+  path_filters: &[String],
+) -> anyhow::Result<Option<String>> {
+  if path_filters.is_empty() {
+    return Ok(None);
+  }
+  let Some(changed_files) = P::extract_changed_files(body)? else {
+    info!(
+      "Webhook body does not include the changed files, ignoring path filters"
+    );
+    return Ok(None);
+  };
+  let mut include = Vec::new();
+  let mut exclude = Vec::new();
+  for filter in path_filters {
+    match filter.strip_prefix('!') {
+      Some(filter) => exclude.push(Matcher::new(filter)?),
+      None => include.push(Matcher::new(filter)?),
+    }
+  }
+  let matched = changed_files.iter().any(|path| {
+    (include.is_empty() || include.iter().any(|m| m.is_match(path)))
+      && !exclude.iter().any(|m| m.is_match(path))
+  });
+  if matched {
+    Ok(None)
+  } else {
+    Ok(Some(format!(
+      "None of the {} changed file/s match the path filters",
+      changed_files.len()
+    )))
+  }
 }
 
 /// For Procedures and Actions, incoming webhook
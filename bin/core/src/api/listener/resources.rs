@@ -23,9 +23,13 @@ use crate::{
   },
   helpers::update::init_execution_update,
   resource,
+  stack::preview::{delete_preview_stack, deploy_preview_stack},
 };
 
-use super::{ANY_BRANCH, ListenerLockCache};
+use super::{
+  ANY_BRANCH, ExtractBranch, ExtractChangedFiles, ListenerLockCache,
+  PullRequestAction, PullRequestEvent, path_filters_skip_reason,
+};
 
 // =======
 //  BUILD
@@ -42,7 +46,9 @@ fn build_locks() -> &'static ListenerLockCache {
   BUILD_LOCKS.get_or_init(Default::default)
 }
 
-pub async fn handle_build_webhook<B: super::ExtractBranch>(
+pub async fn handle_build_webhook<
+  B: ExtractBranch + ExtractChangedFiles,
+>(
   build: Build,
   body: String,
 ) -> anyhow::Result<()> {
@@ -69,6 +75,14 @@ pub async fn handle_build_webhook<B: super::ExtractBranch>(
 
   B::verify_branch(&body, &branch)?;
 
+  if let Some(reason) = path_filters_skip_reason::<B>(
+    &body,
+    &build.config.webhook_path_filters,
+  )? {
+    info!("Skipping webhook for build {} | {reason}", build.name);
+    return Ok(());
+  }
+
   let user = git_webhook_user().to_owned();
   let req = ExecuteRequest::RunBuild(RunBuild { build: build.id });
   let update = init_execution_update(&req, &user).await?;
@@ -185,7 +199,9 @@ pub enum RepoWebhookOption {
   Build,
 }
 
-pub async fn handle_repo_webhook<B: super::ExtractBranch>(
+pub async fn handle_repo_webhook<
+  B: ExtractBranch + ExtractChangedFiles,
+>(
   option: RepoWebhookOption,
   repo: Repo,
   body: String,
@@ -204,7 +220,7 @@ pub async fn handle_repo_webhook<B: super::ExtractBranch>(
 }
 
 async fn handle_repo_webhook_inner<
-  B: super::ExtractBranch,
+  B: ExtractBranch + ExtractChangedFiles,
   E: RepoExecution,
 >(
   repo: Repo,
@@ -222,6 +238,14 @@ async fn handle_repo_webhook_inner<
 
   B::verify_branch(&body, &repo.config.branch)?;
 
+  if let Some(reason) = path_filters_skip_reason::<B>(
+    &body,
+    &repo.config.webhook_path_filters,
+  )? {
+    info!("Skipping webhook for repo {} | {reason}", repo.name);
+    return Ok(());
+  }
+
   E::resolve(repo).await
 }
 
@@ -305,9 +329,13 @@ impl StackExecution for DeployStack {
 pub enum StackWebhookOption {
   Refresh,
   Deploy,
+  /// Pull request events, for template Stacks.
+  Preview,
 }
 
-pub async fn handle_stack_webhook<B: super::ExtractBranch>(
+pub async fn handle_stack_webhook<
+  B: ExtractBranch + ExtractChangedFiles,
+>(
   option: StackWebhookOption,
   stack: Stack,
   body: String,
@@ -320,11 +348,78 @@ pub async fn handle_stack_webhook<B: super::ExtractBranch>(
     StackWebhookOption::Deploy => {
       handle_stack_webhook_inner::<B, DeployStack>(stack, body).await
     }
+    StackWebhookOption::Preview => {
+      handle_stack_preview_webhook::<B>(stack, body).await
+    }
+  }
+}
+
+/// Creates / deploys a preview copy of the template Stack when a
+/// pull request is opened or updated, and deletes it when closed.
+async fn handle_stack_preview_webhook<B: ExtractBranch>(
+  template: Stack,
+  body: String,
+) -> anyhow::Result<()> {
+  if !template.config.webhook_enabled {
+    return Ok(());
+  }
+
+  if !template.template {
+    return Err(anyhow!(
+      "Preview webhooks require the Stack to be a template"
+    ));
+  }
+
+  if !template.config.linked_repo.is_empty()
+    || template.config.repo.is_empty()
+  {
+    return Err(anyhow!(
+      "Preview webhooks require the Stack to configure a repo directly, not with 'linked_repo'"
+    ));
+  }
+
+  // Acquire and hold lock to make a task queue for
+  // subsequent listener calls on same resource.
+  let lock = stack_locks().get_or_insert_default(&template.id).await;
+  let _lock = lock.lock().await;
+
+  let PullRequestEvent {
+    action,
+    number,
+    branch,
+    base_branch,
+    from_fork,
+  } = B::extract_pull_request(&body)?;
+
+  if base_branch != template.config.branch {
+    return Err(anyhow!(
+      "pull request base branch does not match expected"
+    ));
+  }
+
+  // Previews clone the template repo at the pull request branch,
+  // which doesn't exist there (or is unrelated) for forks.
+  if from_fork {
+    info!(
+      "Skipping preview for pull request #{number} on template stack {} | pull request is from a fork",
+      template.name
+    );
+    return Ok(());
+  }
+
+  match action {
+    PullRequestAction::Update => {
+      deploy_preview_stack(&template, number, &branch).await
+    }
+    PullRequestAction::Close => {
+      delete_preview_stack(&template, number).await
+    }
+    PullRequestAction::Ignore => Ok(()),
   }
 }
 
 pub async fn handle_stack_webhook_inner<
-  B: super::ExtractBranch,
+  B: ExtractBranch + ExtractChangedFiles,
   E: StackExecution,
 >(
   stack: Stack,
@@ -353,6 +448,14 @@ pub async fn handle_stack_webhook_inner<
 
   B::verify_branch(&body, &branch)?;
 
+  if let Some(reason) = path_filters_skip_reason::<B>(
+    &body,
+    &stack.config.webhook_path_filters,
+  )? {
+    info!("Skipping webhook for stack {} | {reason}", stack.name);
+    return Ok(());
+  }
+
   E::resolve(stack).await.map_err(|e| e.error)
 }
 
@@ -418,7 +521,9 @@ pub enum SyncWebhookOption {
   Sync,
 }
 
-pub async fn handle_sync_webhook<B: super::ExtractBranch>(
+pub async fn handle_sync_webhook<
+  B: ExtractBranch + ExtractChangedFiles,
+>(
   option: SyncWebhookOption,
   sync: ResourceSync,
   body: String,
@@ -437,7 +542,7 @@ pub async fn handle_sync_webhook<B: super::ExtractBranch>(
 }
 
 async fn handle_sync_webhook_inner<
-  B: super::ExtractBranch,
+  B: ExtractBranch + ExtractChangedFiles,
   E: SyncExecution,
 >(
   sync: ResourceSync,
@@ -466,6 +571,14 @@ async fn handle_sync_webhook_inner<
 
   B::verify_branch(&body, &branch)?;
 
+  if let Some(reason) = path_filters_skip_reason::<B>(
+    &body,
+    &sync.config.webhook_path_filters,
+  )? {
+    info!("Skipping webhook for sync {} | {reason}", sync.name);
+    return Ok(());
+  }
+
   E::resolve(sync).await
 }
 
@@ -485,7 +598,7 @@ fn procedure_locks() -> &'static ListenerLockCache {
   PROCEDURE_LOCKS.get_or_init(Default::default)
 }
 
-pub async fn handle_procedure_webhook<B: super::ExtractBranch>(
+pub async fn handle_procedure_webhook<B: ExtractBranch>(
   procedure: Procedure,
   target_branch: &str,
   body: String,
@@ -539,7 +652,7 @@ fn action_locks() -> &'static ListenerLockCache {
   ACTION_LOCKS.get_or_init(Default::default)
 }
 
-pub async fn handle_action_webhook<B: super::ExtractBranch>(
+pub async fn handle_action_webhook<B: ExtractBranch>(
   action: Action,
   target_branch: &str,
   body: String,
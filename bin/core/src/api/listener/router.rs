@@ -15,7 +15,7 @@ use tracing::Instrument;
 use crate::{auth::GENERAL_RATE_LIMITER, resource::KomodoResource};
 
 use super::{
-  CustomSecret, ExtractBranch, VerifySecret,
+  CustomSecret, ExtractBranch, ExtractChangedFiles, VerifySecret,
   resources::{
     RepoWebhookOption, StackWebhookOption, SyncWebhookOption,
     handle_action_webhook, handle_build_webhook,
@@ -46,7 +46,9 @@ fn default_branch() -> String {
   String::from("main")
 }
 
-pub fn router<P: VerifySecret + ExtractBranch>() -> Router {
+pub fn router<
+  P: VerifySecret + ExtractBranch + ExtractChangedFiles,
+>() -> Router {
   Router::new()
   .route(
     "/build/{id}",
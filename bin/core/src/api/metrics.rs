@@ -0,0 +1,646 @@
+use std::{
+  collections::HashMap,
+  fmt::Write as _,
+  sync::{Mutex, OnceLock},
+};
+
+use anyhow::{Context, anyhow};
+use axum::{
+  Router,
+  http::{HeaderMap, header::CONTENT_TYPE},
+  routing::get,
+};
+use database::mungos::{
+  find::find_collect,
+  mongodb::bson::{Bson, Document, doc},
+};
+use futures_util::TryStreamExt;
+use hmac::{Hmac, Mac};
+use indexmap::IndexMap;
+use komodo_client::entities::{
+  Operation,
+  server::ServerState,
+  update::{Update, UpdateStatus},
+};
+use mogh_auth_server::request_ip::RequestIp;
+use mogh_error::AddStatusCode;
+use mogh_rate_limit::WithFailureRateLimit;
+use reqwest::StatusCode;
+use sha2::Sha256;
+
+use crate::{
+  auth::{
+    GENERAL_RATE_LIMITER, middleware::auth_api_key_check_enabled,
+  },
+  config::core_config,
+  monitor::network_mb_per_sec,
+  state::{
+    all_resources_cache, db_client, deployment_status_cache,
+    server_status_cache, stack_status_cache,
+  },
+};
+
+const BYTES_PER_GB: f64 = 1073741824.0;
+const BYTES_PER_MB: f64 = 1048576.0;
+
+pub fn router() -> Router {
+  Router::new().route(
+    "/",
+    get(|RequestIp(ip), headers: HeaderMap| async move {
+      if !core_config().metrics_enabled {
+        return Err(
+          anyhow!("Metrics are not enabled")
+            .status_code(StatusCode::NOT_FOUND),
+        );
+      }
+      async {
+        auth_metrics_request(&headers)
+          .await
+          .status_code(StatusCode::UNAUTHORIZED)
+      }
+      .with_failure_rate_limit_using_ip(&GENERAL_RATE_LIMITER, &ip)
+      .await
+      .inspect_err(|e| {
+        warn!(
+          source_ip = ip.to_string(),
+          "Metrics request failed to authenticate | ERROR: {:#}",
+          e.error
+        )
+      })?;
+      let metrics = collect_metrics()
+        .await
+        .status_code(StatusCode::INTERNAL_SERVER_ERROR)?;
+      mogh_error::Result::Ok((
+        [(CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")],
+        metrics,
+      ))
+    }),
+  )
+}
+
+/// Compares the tokens in constant time, so the endpoint
+/// doesn't leak how much of the token matched.
+fn metrics_token_matches(token: &str, metrics_token: &str) -> bool {
+  let mac = || {
+    Hmac::<Sha256>::new_from_slice(metrics_token.as_bytes())
+      .expect("HMAC accepts keys of any size")
+  };
+  let mut expected = mac();
+  expected.update(metrics_token.as_bytes());
+  let expected = expected.finalize().into_bytes();
+  let mut provided = mac();
+  provided.update(token.as_bytes());
+  // verify_slice compares in constant time
+  provided.verify_slice(&expected).is_ok()
+}
+
+/// Accepts either the configured `metrics_token` as a bearer token,
+/// or the api key and secret of an admin user.
+async fn auth_metrics_request(
+  headers: &HeaderMap,
+) -> anyhow::Result<()> {
+  let metrics_token = core_config().metrics_token.as_str();
+  if let Some(auth) = headers.get("authorization") {
+    let auth = auth
+      .to_str()
+      .context("Failed to get authorization header as string")?;
+    let token = auth
+      .strip_prefix("Bearer ")
+      .context("Authorization header must use Bearer scheme")?;
+    if !metrics_token.is_empty()
+      && metrics_token_matches(token, metrics_token)
+    {
+      return Ok(());
+    }
+    return Err(anyhow!("Invalid metrics token"));
+  }
+  let (Some(key), Some(secret)) =
+    (headers.get("x-api-key"), headers.get("x-api-secret"))
+  else {
+    return Err(anyhow!(
+      "Must provide either 'Authorization: Bearer <metrics_token>' or X-Api-Key and X-Api-Secret headers"
+    ));
+  };
+  let user = auth_api_key_check_enabled(
+    key.to_str().context("Invalid X-Api-Key header")?,
+    secret.to_str().context("Invalid X-Api-Secret header")?,
+  )
+  .await?;
+  if user.admin {
+    Ok(())
+  } else {
+    Err(anyhow!("Only admins can access metrics"))
+  }
+}
+
+async fn collect_metrics() -> anyhow::Result<String> {
+  let mut metrics = Metrics::default();
+  server_metrics(&mut metrics).await;
+  deployment_metrics(&mut metrics).await;
+  stack_metrics(&mut metrics).await;
+  alert_metrics(&mut metrics).await?;
+  run_metrics(&mut metrics);
+  Ok(metrics.render())
+}
+
+async fn server_metrics(metrics: &mut Metrics) {
+  metrics.family(
+    "komodo_server_up",
+    "gauge",
+    "Whether Core can reach the server Periphery (1) or not (0).",
+  );
+  metrics.family(
+    "komodo_server_cpu_usage_percent",
+    "gauge",
+    "Server cpu usage percentage.",
+  );
+  metrics.family(
+    "komodo_server_load_average",
+    "gauge",
+    "Server load average over the given period.",
+  );
+  metrics.family(
+    "komodo_server_memory_used_bytes",
+    "gauge",
+    "Server used memory in bytes.",
+  );
+  metrics.family(
+    "komodo_server_memory_total_bytes",
+    "gauge",
+    "Server total memory in bytes.",
+  );
+  metrics.family(
+    "komodo_server_network_receive_bytes_per_second",
+    "gauge",
+    "Server network ingress over the last poll, in bytes per second.",
+  );
+  metrics.family(
+    "komodo_server_network_transmit_bytes_per_second",
+    "gauge",
+    "Server network egress over the last poll, in bytes per second.",
+  );
+  metrics.family(
+    "komodo_server_disk_used_bytes",
+    "gauge",
+    "Server disk used space in bytes, by mount point.",
+  );
+  metrics.family(
+    "komodo_server_disk_total_bytes",
+    "gauge",
+    "Server disk total size in bytes, by mount point.",
+  );
+
+  let resources = all_resources_cache().load();
+  for server in resources.servers.values() {
+    let labels = [
+      ("server_id", server.id.as_str()),
+      ("server", server.name.as_str()),
+    ];
+    let Some(status) = server_status_cache().get(&server.id).await
+    else {
+      metrics.push("komodo_server_up", &labels, 0.0);
+      continue;
+    };
+    metrics.push(
+      "komodo_server_up",
+      &labels,
+      (status.state == ServerState::Ok) as u8 as f64,
+    );
+    let Some(stats) = &status.system_stats else {
+      continue;
+    };
+    metrics.push(
+      "komodo_server_cpu_usage_percent",
+      &labels,
+      stats.cpu_perc as f64,
+    );
+    for (period, load) in [
+      ("1m", stats.load_average.one),
+      ("5m", stats.load_average.five),
+      ("15m", stats.load_average.fifteen),
+    ] {
+      metrics.push(
+        "komodo_server_load_average",
+        &[labels[0], labels[1], ("period", period)],
+        load,
+      );
+    }
+    metrics.push(
+      "komodo_server_memory_used_bytes",
+      &labels,
+      stats.mem_used_gb * BYTES_PER_GB,
+    );
+    metrics.push(
+      "komodo_server_memory_total_bytes",
+      &labels,
+      stats.mem_total_gb * BYTES_PER_GB,
+    );
+    let (ingress_mb, egress_mb) = network_mb_per_sec(
+      stats.network_ingress_bytes,
+      stats.network_egress_bytes,
+      stats.polling_rate,
+    );
+    metrics.push(
+      "komodo_server_network_receive_bytes_per_second",
+      &labels,
+      ingress_mb * BYTES_PER_MB,
+    );
+    metrics.push(
+      "komodo_server_network_transmit_bytes_per_second",
+      &labels,
+      egress_mb * BYTES_PER_MB,
+    );
+    for disk in &stats.disks {
+      let mount = disk.mount.to_string_lossy();
+      let labels = [labels[0], labels[1], ("mount", mount.as_ref())];
+      metrics.push(
+        "komodo_server_disk_used_bytes",
+        &labels,
+        disk.used_gb * BYTES_PER_GB,
+      );
+      metrics.push(
+        "komodo_server_disk_total_bytes",
+        &labels,
+        disk.total_gb * BYTES_PER_GB,
+      );
+    }
+  }
+}
+
+async fn deployment_metrics(metrics: &mut Metrics) {
+  metrics.family(
+    "komodo_deployment_state",
+    "gauge",
+    "The current deployment container state. Always 1, the state is given as a label.",
+  );
+  let resources = all_resources_cache().load();
+  for deployment in resources.deployments.values() {
+    let state = deployment_status_cache()
+      .get(&deployment.id)
+      .await
+      .map(|status| status.curr.state)
+      .unwrap_or_default()
+      .to_string();
+    let server = resources
+      .servers
+      .get(&deployment.config.server_id)
+      .map(|server| server.name.as_str())
+      .unwrap_or_default();
+    metrics.push(
+      "komodo_deployment_state",
+      &[
+        ("deployment_id", deployment.id.as_str()),
+        ("deployment", deployment.name.as_str()),
+        ("server", server),
+        ("state", state.as_str()),
+      ],
+      1.0,
+    );
+  }
+}
+
+async fn stack_metrics(metrics: &mut Metrics) {
+  metrics.family(
+    "komodo_stack_state",
+    "gauge",
+    "The current stack state. Always 1, the state is given as a label.",
+  );
+  metrics.family(
+    "komodo_stack_services",
+    "gauge",
+    "The number of services in the stack with a running container.",
+  );
+  let resources = all_resources_cache().load();
+  for stack in resources.stacks.values() {
+    let status = stack_status_cache().get(&stack.id).await;
+    let state = status
+      .as_ref()
+      .map(|status| status.curr.state)
+      .unwrap_or_default()
+      .to_string();
+    let server = resources
+      .servers
+      .get(&stack.config.server_id)
+      .map(|server| server.name.as_str())
+      .unwrap_or_default();
+    let labels = [
+      ("stack_id", stack.id.as_str()),
+      ("stack", stack.name.as_str()),
+      ("server", server),
+    ];
+    metrics.push(
+      "komodo_stack_state",
+      &[labels[0], labels[1], labels[2], ("state", state.as_str())],
+      1.0,
+    );
+    let running = status
+      .map(|status| {
+        status
+          .curr
+          .services
+          .iter()
+          .filter(|service| service.container.is_some())
+          .count()
+      })
+      .unwrap_or_default();
+    metrics.push("komodo_stack_services", &labels, running as f64);
+  }
+}
+
+async fn alert_metrics(metrics: &mut Metrics) -> anyhow::Result<()> {
+  metrics.family(
+    "komodo_alerts_open",
+    "gauge",
+    "The number of open alerts, by severity level and alert type.",
+  );
+  let alerts = find_collect(
+    &db_client().alerts,
+    doc! { "resolved": false },
+    None,
+  )
+  .await
+  .context("Failed to get open alerts from db")?;
+  let mut counts = HashMap::<(String, String), usize>::new();
+  for alert in alerts {
+    let variant: komodo_client::entities::alert::AlertDataVariant =
+      (&alert.data).into();
+    *counts
+      .entry((alert.level.to_string(), format!("{variant:?}")))
+      .or_default() += 1;
+  }
+  let mut counts = counts.into_iter().collect::<Vec<_>>();
+  counts.sort();
+  for ((level, variant), count) in counts {
+    metrics.push(
+      "komodo_alerts_open",
+      &[("level", level.as_str()), ("type", variant.as_str())],
+      count as f64,
+    );
+  }
+  Ok(())
+}
+
+/// Build and Procedure run totals,
+/// keyed by (operation, target id, success).
+type RunTotalsMap = HashMap<(Operation, String, bool), RunTotals>;
+
+#[derive(Default, Clone, Copy)]
+struct RunTotals {
+  count: u64,
+  duration_ms: i64,
+}
+
+fn run_totals() -> &'static Mutex<RunTotalsMap> {
+  static RUN_TOTALS: OnceLock<Mutex<RunTotalsMap>> = OnceLock::new();
+  RUN_TOTALS.get_or_init(Default::default)
+}
+
+/// Seeds the run totals from the completed Updates once on startup.
+/// Afterwards they are only incremented as runs complete,
+/// so scrapes don't need to aggregate over all Updates,
+/// and the counters don't go down when old Updates are pruned.
+pub async fn init_run_totals() {
+  if !core_config().metrics_enabled {
+    return;
+  }
+  if let Err(e) = init_run_totals_inner().await {
+    error!("Failed to initialize run metrics | {e:#}");
+  }
+}
+
+async fn init_run_totals_inner() -> anyhow::Result<()> {
+  let pipeline = [
+    doc! {
+      "$match": {
+        "operation": {
+          "$in": [
+            Operation::RunBuild.as_ref(),
+            Operation::RunProcedure.as_ref(),
+          ]
+        },
+        "status": UpdateStatus::Complete.to_string(),
+      }
+    },
+    doc! {
+      "$group": {
+        "_id": {
+          "operation": "$operation",
+          "target": "$target.id",
+          "success": "$success",
+        },
+        "count": { "$sum": 1 },
+        "duration": {
+          "$sum": { "$subtract": ["$end_ts", "$start_ts"] }
+        },
+      }
+    },
+  ];
+
+  let groups = db_client()
+    .updates
+    .aggregate(pipeline)
+    .await
+    .context("Failed to aggregate updates")?
+    .try_collect::<Vec<Document>>()
+    .await
+    .context("Failed to collect update aggregation")?;
+
+  let mut totals = run_totals().lock().unwrap();
+  for group in groups {
+    let Ok(id) = group.get_document("_id") else {
+      continue;
+    };
+    let (Ok(operation), Ok(target), Ok(success)) = (
+      id.get_str("operation"),
+      id.get_str("target"),
+      id.get_bool("success"),
+    ) else {
+      continue;
+    };
+    let Ok(operation) = operation.parse::<Operation>() else {
+      continue;
+    };
+    let entry = totals
+      .entry((operation, target.to_string(), success))
+      .or_default();
+    entry.count +=
+      group.get("count").map(bson_number).unwrap_or_default() as u64;
+    entry.duration_ms +=
+      group.get("duration").map(bson_number).unwrap_or_default()
+        as i64;
+  }
+
+  Ok(())
+}
+
+/// Called when an Update is finalized,
+/// counts it if it completes a Build or Procedure run.
+pub fn record_completed_run(update: &Update) {
+  if !core_config().metrics_enabled
+    || update.status != UpdateStatus::Complete
+    || !matches!(
+      update.operation,
+      Operation::RunBuild | Operation::RunProcedure
+    )
+  {
+    return;
+  }
+  let mut totals = run_totals().lock().unwrap();
+  let entry = totals
+    .entry((
+      update.operation,
+      update.target.extract_variant_id().1.clone(),
+      update.success,
+    ))
+    .or_default();
+  entry.count += 1;
+  entry.duration_ms +=
+    update.end_ts.unwrap_or(update.start_ts) - update.start_ts;
+}
+
+/// Build and Procedure run counts and durations.
+fn run_metrics(metrics: &mut Metrics) {
+  metrics.family(
+    "komodo_build_runs_total",
+    "counter",
+    "The number of completed build runs, by result.",
+  );
+  metrics.family(
+    "komodo_build_duration_seconds_total",
+    "counter",
+    "The total time spent on completed build runs, by result.",
+  );
+  metrics.family(
+    "komodo_procedure_runs_total",
+    "counter",
+    "The number of completed procedure runs, by result.",
+  );
+  metrics.family(
+    "komodo_procedure_duration_seconds_total",
+    "counter",
+    "The total time spent on completed procedure runs, by result.",
+  );
+
+  let mut totals = run_totals()
+    .lock()
+    .unwrap()
+    .iter()
+    .map(|(key, totals)| (key.clone(), *totals))
+    .collect::<Vec<_>>();
+  totals.sort_by(|(a, _), (b, _)| {
+    (a.0.as_ref(), &a.1).cmp(&(b.0.as_ref(), &b.1))
+  });
+
+  let resources = all_resources_cache().load();
+
+  for ((operation, target, success), totals) in totals {
+    let (prefix, id_label, name_label, name) = match operation {
+      Operation::RunBuild => (
+        "komodo_build",
+        "build_id",
+        "build",
+        resources.builds.get(&target).map(|b| b.name.as_str()),
+      ),
+      _ => (
+        "komodo_procedure",
+        "procedure_id",
+        "procedure",
+        resources.procedures.get(&target).map(|p| p.name.as_str()),
+      ),
+    };
+    let labels = [
+      (id_label, target.as_str()),
+      (name_label, name.unwrap_or_default()),
+      ("result", if success { "success" } else { "failure" }),
+    ];
+    metrics.push(
+      &format!("{prefix}_runs_total"),
+      &labels,
+      totals.count as f64,
+    );
+    metrics.push(
+      &format!("{prefix}_duration_seconds_total"),
+      &labels,
+      totals.duration_ms as f64 / 1000.0,
+    );
+  }
+}
+
+fn bson_number(value: &Bson) -> f64 {
+  match value {
+    Bson::Int32(n) => *n as f64,
+    Bson::Int64(n) => *n as f64,
+    Bson::Double(n) => *n,
+    _ => 0.0,
+  }
+}
+
+/// Collects samples grouped by metric family,
+/// as required by the Prometheus text format.
+#[derive(Default)]
+struct Metrics {
+  families: IndexMap<String, MetricFamily>,
+}
+
+struct MetricFamily {
+  kind: &'static str,
+  help: &'static str,
+  samples: Vec<String>,
+}
+
+impl Metrics {
+  fn family(
+    &mut self,
+    name: &str,
+    kind: &'static str,
+    help: &'static str,
+  ) {
+    self.families.insert(
+      name.to_string(),
+      MetricFamily {
+        kind,
+        help,
+        samples: Vec::new(),
+      },
+    );
+  }
+
+  fn push(
+    &mut self,
+    name: &str,
+    labels: &[(&str, &str)],
+    value: f64,
+  ) {
+    let Some(family) = self.families.get_mut(name) else {
+      return;
+    };
+    let labels = labels
+      .iter()
+      .map(|(key, value)| {
+        format!("{key}=\"{}\"", escape_label(value))
+      })
+      .collect::<Vec<_>>()
+      .join(",");
+    family.samples.push(format!("{name}{{{labels}}} {value}"));
+  }
+
+  fn render(self) -> String {
+    let mut out = String::new();
+    for (name, family) in self.families {
+      let _ = writeln!(out, "# HELP {name} {}", family.help);
+      let _ = writeln!(out, "# TYPE {name} {}", family.kind);
+      for sample in family.samples {
+        out.push_str(&sample);
+        out.push('\n');
+      }
+    }
+    out
+  }
+}
+
+fn escape_label(value: &str) -> String {
+  value
+    .replace('\\', "\\\\")
+    .replace('"', "\\\"")
+    .replace('\n', "\\n")
+}
@@ -7,9 +7,15 @@ use mogh_server::{
   ui::serve_static_ui,
 };
 
-use crate::{auth::KomodoAuthImpl, config::core_config, ts_client};
+use crate::{
+  auth::{self, KomodoAuthImpl},
+  config::core_config,
+  helpers::audit::audit_auth_request,
+  ts_client,
+};
 
 pub mod execute;
+pub mod metrics;
 pub mod read;
 pub mod write;
 
@@ -28,13 +34,23 @@ pub fn app() -> Router {
   Router::new()
     .merge(openapi::serve_docs())
     .route("/version", get(|| async { env!("CARGO_PKG_VERSION") }))
-    .nest("/auth", mogh_auth_server::api::router::<KomodoAuthImpl>())
+    .nest(
+      "/auth",
+      mogh_auth_server::api::router::<KomodoAuthImpl>()
+        .layer(axum::middleware::from_fn(
+          auth::oidc::guard_auth_oidc_login,
+        ))
+        .layer(axum::middleware::from_fn(audit_auth_request)),
+    )
+    .nest("/ldap", auth::ldap::router())
+    .nest("/oidc", auth::oidc::router())
     .nest("/user", user_router())
     .nest("/read", read::router())
     .nest("/write", write::router())
     .nest("/execute", execute::router())
     .nest("/terminal", terminal::router())
     .nest("/listener", listener::router())
+    .nest("/metrics", metrics::router())
     .nest("/ws", ws::router())
     .nest("/client", ts_client::router())
     .layer(memory_session_layer(config))
@@ -15,7 +15,8 @@ use mogh_resolver::Resolve;
 use crate::{
   config::core_config,
   permission::{
-    check_user_target_access, user_resource_target_query,
+    check_user_target_access, has_resource_scoped_api_key,
+    user_resource_target_query,
   },
   state::db_client,
 };
@@ -67,7 +68,9 @@ impl Resolve<ReadArgs> for GetAlert {
       .await
       .context("failed to query db for alert")?
       .context("no alert found with given id")?;
-    if user.admin || core_config().transparent_mode {
+    if (user.admin || core_config().transparent_mode)
+      && !has_resource_scoped_api_key(user)
+    {
       return Ok(alert);
     }
     check_user_target_access(
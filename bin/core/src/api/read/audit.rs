@@ -0,0 +1,104 @@
+use anyhow::{Context, anyhow};
+use database::mungos::{
+  find::find_collect,
+  mongodb::{
+    bson::{Document, doc},
+    options::FindOptions,
+  },
+};
+use komodo_client::api::read::{
+  ListAuditEvents, ListAuditEventsResponse,
+};
+use mogh_error::AddStatusCodeError as _;
+use mogh_resolver::Resolve;
+use reqwest::StatusCode;
+
+use crate::state::db_client;
+
+use super::ReadArgs;
+
+const NUM_EVENTS_PER_PAGE: u64 = 500;
+
+impl Resolve<ReadArgs> for ListAuditEvents {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> mogh_error::Result<ListAuditEventsResponse> {
+    if !user.admin {
+      return Err(
+        anyhow!("Only admins can list audit events")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+
+    let ListAuditEvents {
+      user,
+      ip,
+      operation,
+      target,
+      success,
+      start_ts,
+      end_ts,
+      page,
+    } = self;
+
+    let mut query = Document::new();
+    if let Some(user) = user {
+      query.insert(
+        "$or",
+        vec![doc! { "user_id": &user }, doc! { "username": &user }],
+      );
+    }
+    if let Some(ip) = ip {
+      query.insert("ip", ip);
+    }
+    if let Some(operation) = operation {
+      query.insert("operation", operation.as_ref());
+    }
+    if let Some(target) = target {
+      let (variant, id_or_name) = target.extract_variant_id();
+      query.insert("target.type", variant.as_ref());
+      // Events store the id and name at the time of the event.
+      query.insert(
+        "$and",
+        vec![doc! { "$or": [
+          { "target.id": id_or_name.as_str() },
+          { "target_name": id_or_name.as_str() },
+        ] }],
+      );
+    }
+    if let Some(success) = success {
+      query.insert("success", success);
+    }
+    let mut ts = Document::new();
+    if let Some(start_ts) = start_ts {
+      ts.insert("$gte", start_ts);
+    }
+    if let Some(end_ts) = end_ts {
+      ts.insert("$lte", end_ts);
+    }
+    if !ts.is_empty() {
+      query.insert("ts", ts);
+    }
+
+    let events = find_collect(
+      &db_client().audit_events,
+      query,
+      FindOptions::builder()
+        .sort(doc! { "ts": -1 })
+        .limit(NUM_EVENTS_PER_PAGE as i64)
+        .skip(page * NUM_EVENTS_PER_PAGE)
+        .build(),
+    )
+    .await
+    .context("Failed to get audit events from db")?;
+
+    let next_page = if events.len() < NUM_EVENTS_PER_PAGE as usize {
+      None
+    } else {
+      Some((page + 1) as i64)
+    };
+
+    Ok(ListAuditEventsResponse { events, next_page })
+  }
+}
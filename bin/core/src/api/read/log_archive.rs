@@ -0,0 +1,121 @@
+use anyhow::{Context, anyhow};
+use database::mungos::{
+  find::find_collect,
+  mongodb::{
+    bson::{Document, doc},
+    options::FindOptions,
+  },
+};
+use komodo_client::{
+  api::read::{SearchArchivedLogs, SearchArchivedLogsResponse},
+  entities::{
+    ResourceTarget, deployment::Deployment,
+    permission::PermissionLevel, stack::Stack,
+  },
+};
+use mogh_error::{AddStatusCode as _, AddStatusCodeError as _};
+use mogh_resolver::Resolve;
+use reqwest::StatusCode;
+
+use crate::{permission::get_check_permissions, state::db_client};
+
+use super::ReadArgs;
+
+const NUM_LOGS_PER_PAGE: u64 = 500;
+
+impl Resolve<ReadArgs> for SearchArchivedLogs {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> mogh_error::Result<SearchArchivedLogsResponse> {
+    let SearchArchivedLogs {
+      target,
+      container,
+      start_ts,
+      end_ts,
+      regex,
+      case_insensitive,
+      page,
+    } = self;
+
+    // Resolves target names to ids.
+    let target = match target {
+      ResourceTarget::Deployment(deployment) => {
+        ResourceTarget::Deployment(
+          get_check_permissions::<Deployment>(
+            &deployment,
+            user,
+            PermissionLevel::Read.logs(),
+          )
+          .await?
+          .id,
+        )
+      }
+      ResourceTarget::Stack(stack) => ResourceTarget::Stack(
+        get_check_permissions::<Stack>(
+          &stack,
+          user,
+          PermissionLevel::Read.logs(),
+        )
+        .await?
+        .id,
+      ),
+      _ => {
+        return Err(
+          anyhow!("Target must be a Deployment or Stack")
+            .status_code(StatusCode::BAD_REQUEST),
+        );
+      }
+    };
+
+    let (variant, id) = target.extract_variant_id();
+    let mut query = doc! {
+      "target.type": variant.as_ref(),
+      "target.id": id.as_str(),
+    };
+    if let Some(container) = container {
+      query.insert("container", container);
+    }
+    let mut ts = Document::new();
+    if let Some(start_ts) = start_ts {
+      ts.insert("$gte", start_ts);
+    }
+    if let Some(end_ts) = end_ts {
+      ts.insert("$lte", end_ts);
+    }
+    if !ts.is_empty() {
+      query.insert("ts", ts);
+    }
+    if let Some(regex) = regex.filter(|regex| !regex.is_empty()) {
+      // Validate before sending to the db for a better error.
+      regex::Regex::new(&regex)
+        .context("Invalid regex")
+        .status_code(StatusCode::BAD_REQUEST)?;
+      let options = if case_insensitive { "i" } else { "" };
+      query.insert(
+        "line",
+        doc! { "$regex": regex, "$options": options },
+      );
+    }
+
+    let logs = find_collect(
+      &db_client().archived_logs,
+      query,
+      FindOptions::builder()
+        .sort(doc! { "ts": -1 })
+        .limit(NUM_LOGS_PER_PAGE as i64)
+        .skip(page * NUM_LOGS_PER_PAGE)
+        .build(),
+    )
+    .await
+    .context("Failed to get archived logs from db")?;
+
+    let next_page = if logs.len() < NUM_LOGS_PER_PAGE as usize {
+      None
+    } else {
+      Some((page + 1) as i64)
+    };
+
+    Ok(SearchArchivedLogsResponse { logs, next_page })
+  }
+}
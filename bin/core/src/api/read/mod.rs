@@ -1,13 +1,18 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, net::IpAddr};
 
 use anyhow::{Context, anyhow};
 use axum::{
   Extension, Router, extract::Path, middleware, routing::post,
 };
+use database::{
+  bson::{Document, doc},
+  mungos::find::find_collect,
+};
 use komodo_client::{
   api::read::*,
   entities::{
     ResourceTarget,
+    audit::{AuditEvent, AuditOperation},
     build::Build,
     builder::{Builder, BuilderConfig},
     config::{DockerRegistry, GitProvider},
@@ -18,7 +23,9 @@ use komodo_client::{
     user::User,
   },
 };
-use mogh_auth_server::middleware::authenticate_request;
+use mogh_auth_server::{
+  middleware::authenticate_request, request_ip::RequestIp,
+};
 use mogh_error::Response;
 use mogh_error::{AddStatusCodeError, Json};
 use mogh_resolver::Resolve;
@@ -30,10 +37,14 @@ use typeshare::typeshare;
 use uuid::Uuid;
 
 use crate::{
-  auth::KomodoAuthImpl,
+  auth::{KomodoAuthImpl, api_key::check_api_key_request_scope},
   config::{core_config, core_keys},
-  helpers::periphery_client,
+  helpers::{
+    audit::{audit_event, record_audit_event},
+    periphery_client,
+  },
   resource,
+  state::db_client,
 };
 
 use super::Variant;
@@ -41,9 +52,11 @@ use super::Variant;
 mod action;
 mod alert;
 mod alerter;
+mod audit;
 mod build;
 mod builder;
 mod deployment;
+mod log_archive;
 mod onboarding_key;
 mod permission;
 mod procedure;
@@ -251,6 +264,12 @@ enum ReadRequest {
   ListAlerts(ListAlerts),
   GetAlert(GetAlert),
 
+  // ==== LOG ARCHIVE ====
+  SearchArchivedLogs(SearchArchivedLogs),
+
+  // ==== AUDIT ====
+  ListAuditEvents(ListAuditEvents),
+
   // ==== VARIABLE ====
   GetVariable(GetVariable),
   ListVariables(ListVariables),
@@ -276,6 +295,7 @@ pub fn router() -> Router {
 
 async fn variant_handler(
   user: Extension<User>,
+  ip: RequestIp,
   Path(Variant { variant }): Path<Variant>,
   Json(params): Json<serde_json::Value>,
 ) -> mogh_error::Result<axum::response::Response> {
@@ -283,16 +303,197 @@ async fn variant_handler(
     "type": variant,
     "params": params,
   }))?;
-  handler(user, Json(req)).await
+  handler(user, ip, Json(req)).await
+}
+
+/// Whether the request resolves its targets through
+/// `get_check_permissions` or `list_resources_for_user`,
+/// which apply the api key resource scope.
+/// Resource scoped api keys can only call other requests
+/// when they are named explicitly in the scope requests.
+/// There is no wildcard, so new requests must be sorted here.
+fn targets_resources(method: ReadRequestMethod) -> bool {
+  use ReadRequestMethod as M;
+  match method {
+    M::GetVersion
+    | M::GetCoreInfo
+    | M::ListSecrets
+    | M::ListGitProvidersFromConfig
+    | M::ListDockerRegistriesFromConfig => false,
+    // ==== SWARM ====
+    M::GetSwarmsSummary
+    | M::GetSwarm
+    | M::GetSwarmActionState
+    | M::ListSwarms
+    | M::InspectSwarm
+    | M::ListFullSwarms
+    | M::ListSwarmNodes
+    | M::InspectSwarmNode
+    | M::ListSwarmConfigs
+    | M::InspectSwarmConfig
+    | M::ListSwarmSecrets
+    | M::InspectSwarmSecret
+    | M::ListSwarmStacks
+    | M::InspectSwarmStack
+    | M::ListSwarmTasks
+    | M::InspectSwarmTask
+    | M::ListSwarmServices
+    | M::InspectSwarmService
+    | M::GetSwarmServiceLog
+    | M::SearchSwarmServiceLog
+    | M::ListSwarmNetworks => true,
+    // ==== SERVER ====
+    M::GetServersSummary
+    | M::GetServer
+    | M::GetServerState
+    | M::GetPeripheryInformation
+    | M::GetServerActionState
+    | M::ListServers
+    | M::ListFullServers => true,
+    // ==== TERMINAL ====
+    M::ListTerminals => true,
+    // ==== DOCKER ====
+    M::GetDockerContainersSummary
+    | M::ListAllDockerContainers
+    | M::ListDockerContainers
+    | M::InspectDockerContainer
+    | M::GetResourceMatchingContainer
+    | M::GetContainerLog
+    | M::SearchContainerLog
+    | M::ListComposeProjects
+    | M::ListDockerNetworks
+    | M::InspectDockerNetwork
+    | M::ListDockerImages
+    | M::InspectDockerImage
+    | M::ListDockerImageHistory
+    | M::ListDockerVolumes
+    | M::InspectDockerVolume => true,
+    // ==== SERVER STATS ====
+    M::GetSystemInformation
+    | M::GetSystemStats
+    | M::GetHistoricalServerStats
+    | M::ListSystemProcesses => true,
+    // ==== STACK ====
+    M::GetStacksSummary
+    | M::GetStack
+    | M::GetStackActionState
+    | M::GetStackLog
+    | M::SearchStackLog
+    | M::InspectStackContainer
+    | M::InspectStackSwarmService
+    | M::ListStacks
+    | M::ListFullStacks
+    | M::ListStackServices
+    | M::ListCommonStackExtraArgs
+    | M::ListCommonStackBuildExtraArgs => true,
+    // ==== DEPLOYMENT ====
+    M::GetDeploymentsSummary
+    | M::GetDeployment
+    | M::GetDeploymentContainer
+    | M::GetDeploymentActionState
+    | M::GetDeploymentStats
+    | M::GetDeploymentLog
+    | M::SearchDeploymentLog
+    | M::InspectDeploymentContainer
+    | M::InspectDeploymentSwarmService
+    | M::ListDeployments
+    | M::ListFullDeployments
+    | M::ListCommonDeploymentExtraArgs => true,
+    // ==== BUILD ====
+    M::GetBuildsSummary
+    | M::GetBuild
+    | M::GetBuildActionState
+    | M::ListBuildVersions
+    | M::ListBuilds
+    | M::ListFullBuilds
+    | M::ListCommonBuildExtraArgs => true,
+    M::GetBuildMonthlyStats => false,
+    // ==== REPO ====
+    M::GetReposSummary
+    | M::GetRepo
+    | M::GetRepoActionState
+    | M::ListRepos
+    | M::ListFullRepos => true,
+    // ==== PROCEDURE ====
+    M::GetProceduresSummary
+    | M::GetProcedure
+    | M::GetProcedureActionState
+    | M::ListProcedures
+    | M::ListFullProcedures => true,
+    // ==== ACTION ====
+    M::GetActionsSummary
+    | M::GetAction
+    | M::GetActionActionState
+    | M::ListActions
+    | M::ListFullActions => true,
+    // ==== SCHEDULE ====
+    M::ListSchedules => true,
+    // ==== SYNC ====
+    M::GetResourceSyncsSummary
+    | M::GetResourceSync
+    | M::GetResourceSyncActionState
+    | M::ListResourceSyncs
+    | M::ListFullResourceSyncs => true,
+    // ==== BUILDER ====
+    M::GetBuildersSummary
+    | M::GetBuilder
+    | M::ListBuilders
+    | M::ListFullBuilders => true,
+    // ==== ALERTER ====
+    M::GetAlertersSummary
+    | M::GetAlerter
+    | M::ListAlerters
+    | M::ListFullAlerters => true,
+    // ==== TOML ====
+    M::ExportAllResourcesToToml | M::ExportResourcesToToml => false,
+    // ==== TAG ====
+    M::GetTag | M::ListTags => false,
+    // ==== USER ====
+    M::GetUsername
+    | M::GetPermission
+    | M::FindUser
+    | M::ListUsers
+    | M::ListApiKeys
+    | M::ListApiKeysForServiceUser
+    | M::ListPermissions
+    | M::ListUserTargetPermissions => false,
+    // ==== USER GROUP ====
+    M::GetUserGroup | M::ListUserGroups => false,
+    // ==== UPDATE ====
+    M::GetUpdate | M::ListUpdates => true,
+    // ==== ALERT ====
+    M::ListAlerts | M::GetAlert => true,
+    // ==== LOG ARCHIVE ====
+    M::SearchArchivedLogs => true,
+    // ==== AUDIT ====
+    M::ListAuditEvents => false,
+    // ==== VARIABLE ====
+    M::GetVariable | M::ListVariables => false,
+    // ==== PROVIDER ====
+    M::GetGitProviderAccount
+    | M::ListGitProviderAccounts
+    | M::GetDockerRegistryAccount
+    | M::ListDockerRegistryAccounts => false,
+    // ==== ONBOARDING KEY ====
+    M::ListOnboardingKeys => false,
+  }
 }
 
 async fn handler(
   Extension(user): Extension<User>,
+  RequestIp(ip): RequestIp,
   Json(request): Json<ReadRequest>,
 ) -> mogh_error::Result<axum::response::Response> {
   let req_id = Uuid::new_v4();
   let method: ReadRequestMethod = (&request).into();
 
+  check_api_key_request_scope(
+    &user,
+    "read",
+    &method.to_string(),
+    targets_resources(method),
+  )?;
+
   let user_id = user.id.clone();
   let username = user.username.clone();
 
@@ -304,6 +505,8 @@ async fn handler(
     "READ REQUEST",
   );
 
+  let secret_read = secret_read_audit_event(&request, &user, ip);
+
   let res = request.resolve(&ReadArgs { user }).await;
 
   if let Err(e) = &res {
@@ -317,9 +520,69 @@ async fn handler(
     );
   }
 
+  if res.is_ok()
+    && let Some((event, filter)) = secret_read
+  {
+    record_secret_read(event, filter);
+  }
+
   res.map(|res| res.0)
 }
 
+/// Only admins receive the values of secret variables.
+/// Returns the audit event and the filter for the secret
+/// variables included in the response.
+fn secret_read_audit_event(
+  request: &ReadRequest,
+  user: &User,
+  ip: IpAddr,
+) -> Option<(AuditEvent, Document)> {
+  if !user.admin {
+    return None;
+  }
+  let (request, filter) = match request {
+    ReadRequest::GetVariable(req) => {
+      ("GetVariable", doc! { "name": &req.name, "is_secret": true })
+    }
+    ReadRequest::ListVariables(_) => {
+      ("ListVariables", doc! { "is_secret": true })
+    }
+    _ => return None,
+  };
+  let event =
+    audit_event(user, ip, AuditOperation::ReadSecret, request);
+  Some((event, filter))
+}
+
+/// Records the secret read if any secret variables were included.
+fn record_secret_read(mut event: AuditEvent, filter: Document) {
+  tokio::spawn(async move {
+    let secrets = match find_collect(
+      &db_client().variables,
+      filter,
+      None,
+    )
+    .await
+    {
+      Ok(secrets) => secrets,
+      Err(e) => {
+        warn!("Failed to query db for secret variables | {e:#}");
+        return;
+      }
+    };
+    if secrets.is_empty() {
+      return;
+    }
+    event.success = true;
+    event.details = secrets
+      .into_iter()
+      .map(|variable| variable.name)
+      .collect::<Vec<_>>()
+      .join(", ");
+    record_audit_event(event);
+  });
+}
+
 impl Resolve<ReadArgs> for GetVersion {
   async fn resolve(
     self,
@@ -7,7 +7,12 @@ use database::mungos::{
 use komodo_client::api::read::*;
 use mogh_resolver::Resolve;
 
-use crate::state::db_client;
+use crate::{
+  helpers::encryption::{
+    decrypt_git_account, decrypt_registry_account,
+  },
+  state::db_client,
+};
 
 use super::ReadArgs;
 
@@ -27,7 +32,7 @@ impl Resolve<ReadArgs> for GetGitProviderAccount {
       .context(
         "did not find git provider account with the given id",
       )?;
-    Ok(res)
+    Ok(decrypt_git_account(res)?)
   }
 }
 
@@ -56,7 +61,10 @@ impl Resolve<ReadArgs> for ListGitProviderAccounts {
         .build(),
     )
     .await
-    .context("failed to query db for git provider accounts")?;
+    .context("failed to query db for git provider accounts")?
+    .into_iter()
+    .map(decrypt_git_account)
+    .collect::<anyhow::Result<_>>()?;
     Ok(res)
   }
 }
@@ -79,7 +87,7 @@ impl Resolve<ReadArgs> for GetDockerRegistryAccount {
         .context(
           "did not find docker registry account with the given id",
         )?;
-    Ok(res)
+    Ok(decrypt_registry_account(res)?)
   }
 }
 
@@ -109,7 +117,10 @@ impl Resolve<ReadArgs> for ListDockerRegistryAccounts {
         .build(),
     )
     .await
-    .context("failed to query db for docker registry accounts")?;
+    .context("failed to query db for docker registry accounts")?
+    .into_iter()
+    .map(decrypt_registry_account)
+    .collect::<anyhow::Result<_>>()?;
     Ok(res)
   }
 }
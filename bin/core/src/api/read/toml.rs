@@ -21,8 +21,9 @@ use komodo_client::{
 use mogh_resolver::Resolve;
 
 use crate::{
-  helpers::query::{
-    get_all_tags, get_id_to_tags, get_user_user_group_ids,
+  helpers::{
+    encryption::decrypt_variable,
+    query::{get_all_tags, get_id_to_tags, get_user_user_group_ids},
   },
   permission::get_check_permissions,
   resource,
@@ -226,6 +227,9 @@ impl Resolve<ReadArgs> for ExportResourcesToToml {
       .await
       .context("failed to get variables from db")?
       .into_iter()
+      .map(decrypt_variable)
+      .collect::<anyhow::Result<Vec<_>>>()?
+      .into_iter()
       .map(|mut variable| {
         if !user.admin && variable.is_secret {
           variable.value = "#".repeat(variable.value.len())
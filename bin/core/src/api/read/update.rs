@@ -19,7 +19,8 @@ use mogh_resolver::Resolve;
 use crate::{
   config::core_config,
   permission::{
-    check_user_target_access, user_resource_target_query,
+    check_user_target_access, has_resource_scoped_api_key,
+    user_resource_target_query,
   },
   state::db_client,
 };
@@ -97,7 +98,9 @@ impl Resolve<ReadArgs> for GetUpdate {
       .await
       .context("failed to query to db")?
       .context("no update exists with given id")?;
-    if user.admin || core_config().transparent_mode {
+    if (user.admin || core_config().transparent_mode)
+      && !has_resource_scoped_api_key(user)
+    {
       return Ok(update);
     }
     check_user_target_access(
@@ -6,7 +6,10 @@ use database::mungos::{
 use komodo_client::api::read::*;
 use mogh_resolver::Resolve;
 
-use crate::{helpers::query::get_variable, state::db_client};
+use crate::{
+  helpers::{encryption::decrypt_variables, query::get_variable},
+  state::db_client,
+};
 
 use super::ReadArgs;
 
@@ -36,6 +39,7 @@ impl Resolve<ReadArgs> for ListVariables {
     )
     .await
     .context("failed to query db for variables")?;
+    let variables = decrypt_variables(variables)?;
     if user.admin {
       return Ok(variables);
     }
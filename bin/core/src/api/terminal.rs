@@ -1,11 +1,20 @@
 use anyhow::Context;
 use axum::{Extension, Router, middleware, routing::post};
-use komodo_client::{api::terminal::*, entities::user::User};
-use mogh_auth_server::middleware::authenticate_request;
+use komodo_client::{
+  api::terminal::*,
+  entities::{audit::AuditOperation, user::User},
+};
+use mogh_auth_server::{
+  middleware::authenticate_request, request_ip::RequestIp,
+};
 use mogh_error::Json;
 
 use crate::{
-  auth::KomodoAuthImpl, helpers::terminal::setup_target_for_user,
+  auth::{KomodoAuthImpl, api_key::check_api_key_request_scope},
+  helpers::{
+    audit::{audit_event, record_audit_event, terminal_audit_target},
+    terminal::setup_target_for_user,
+  },
 };
 
 pub fn router() -> Router {
@@ -32,6 +41,7 @@ pub fn router() -> Router {
 )]
 async fn execute_terminal(
   Extension(user): Extension<User>,
+  RequestIp(ip): RequestIp,
   Json(ExecuteTerminalBody {
     target,
     terminal,
@@ -44,8 +54,33 @@ async fn execute_terminal(
     user.username, user.id
   );
 
-  let (target, terminal, periphery) =
-    setup_target_for_user(target, terminal, init, &user).await?;
+  check_api_key_request_scope(
+    &user,
+    "execute",
+    "ExecuteTerminal",
+    true,
+  )?;
+
+  let mut event = audit_event(
+    &user,
+    ip,
+    AuditOperation::Terminal,
+    "ExecuteTerminal",
+  );
+  event.target = terminal_audit_target(&target);
+  event.details = format!(
+    "Terminal: {} | Command: {command}",
+    terminal.as_deref().unwrap_or_default()
+  );
+
+  let res =
+    setup_target_for_user(target, terminal, init, &user).await;
+  event.success = res.is_ok();
+  if let Err(e) = &res {
+    event.details.push_str(&format!(" | Error: {e:#}"));
+  }
+  record_audit_event(event);
+  let (target, terminal, periphery) = res?;
 
   let stream = periphery
     .execute_terminal(target, terminal, command)
@@ -1,13 +1,23 @@
 use std::str::FromStr;
 
 use anyhow::{Context, anyhow};
+use database::mungos::by_id::find_one_by_id;
 use database::mungos::mongodb::bson::{doc, oid::ObjectId};
-use komodo_client::{api::write::CloseAlert, entities::NoData};
+use komodo_client::{
+  api::write::{AcknowledgeAlert, CloseAlert},
+  entities::{NoData, komodo_timestamp, permission::PermissionLevel},
+};
 use mogh_error::AddStatusCodeError;
 use mogh_resolver::Resolve;
 use reqwest::StatusCode;
 
-use crate::{api::write::WriteArgs, state::db_client};
+use crate::{
+  api::write::WriteArgs,
+  permission::{
+    check_user_target_access, has_resource_scoped_api_key,
+  },
+  state::db_client,
+};
 
 impl Resolve<WriteArgs> for CloseAlert {
   #[instrument(
@@ -39,3 +49,44 @@ impl Resolve<WriteArgs> for CloseAlert {
     Ok(NoData {})
   }
 }
+
+impl Resolve<WriteArgs> for AcknowledgeAlert {
+  #[instrument(
+    "AcknowledgeAlert",
+    skip_all,
+    fields(
+      operator = user.id,
+      alert_id = self.id,
+    )
+  )]
+  async fn resolve(
+    self,
+    WriteArgs { user }: &WriteArgs,
+  ) -> Result<Self::Response, Self::Error> {
+    let alert = find_one_by_id(&db_client().alerts, &self.id)
+      .await
+      .context("Failed to query db for Alert")?
+      .context("No Alert found with given id")?;
+    if !user.admin || has_resource_scoped_api_key(user) {
+      check_user_target_access(
+        &alert.target,
+        user,
+        PermissionLevel::Execute.into(),
+      )
+      .await?;
+    }
+    db_client()
+      .alerts
+      .update_one(
+        doc! { "_id": ObjectId::from_str(&self.id)? },
+        doc! { "$set": {
+          "escalation.acknowledged": true,
+          "escalation.acknowledged_by": &user.id,
+          "escalation.acknowledged_ts": komodo_timestamp(),
+        } },
+      )
+      .await
+      .context("Failed to acknowledge Alert on database")?;
+    Ok(NoData {})
+  }
+}
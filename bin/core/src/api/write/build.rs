@@ -244,6 +244,10 @@ async fn write_dockerfile_contents_git(
   };
   let root = repo_args.unique_path(&core_config().repo_directory)?;
   repo_args.destination = Some(root.display().to_string());
+  repo_args.ssh_known_hosts =
+    core_config().git_ssh_known_hosts.clone();
+  repo_args.ssh_accept_new_host_keys =
+    core_config().git_ssh_accept_new_host_keys;
 
   let build_path = build
     .config
@@ -267,7 +271,7 @@ async fn write_dockerfile_contents_git(
   }
 
   let access_token = if let Some(account) = &repo_args.account {
-    git_token(&repo_args.provider, account, |https| repo_args.https = https)
+    git_token(&repo_args.provider, account, repo_args.ssh, |https| repo_args.https = https)
     .await
     .with_context(
       || format!("Failed to get git token in call to db. Stopping run. | {} | {account}", repo_args.provider),
@@ -546,9 +550,13 @@ async fn get_git_remote(
   let config = core_config();
   let repo_path = clone_args.unique_path(&config.repo_directory)?;
   clone_args.destination = Some(repo_path.display().to_string());
+  clone_args.ssh_known_hosts =
+    core_config().git_ssh_known_hosts.clone();
+  clone_args.ssh_accept_new_host_keys =
+    core_config().git_ssh_accept_new_host_keys;
 
   let access_token = if let Some(username) = &clone_args.account {
-    git_token(&clone_args.provider, username, |https| {
+    git_token(&clone_args.provider, username, clone_args.ssh, |https| {
           clone_args.https = https
         })
         .await
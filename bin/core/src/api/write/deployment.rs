@@ -536,6 +536,7 @@ pub async fn check_deployment_for_update_inner(
               ts,
               resolved: true,
               resolved_ts: ts.into(),
+              escalation: Default::default(),
               level: SeverityLevel::Ok,
               target: ResourceTarget::Deployment(id.clone()),
               data: AlertData::DeploymentAutoUpdated {
@@ -583,6 +584,7 @@ pub async fn check_deployment_for_update_inner(
       ts,
       resolved: true,
       resolved_ts: ts.into(),
+      escalation: Default::default(),
       level: SeverityLevel::Ok,
       target: ResourceTarget::Deployment(deployment.id.clone()),
       data: AlertData::DeploymentImageUpdateAvailable {
@@ -1,9 +1,20 @@
+use std::net::IpAddr;
+
 use anyhow::Context;
 use axum::{
   Extension, Router, extract::Path, middleware, routing::post,
 };
-use komodo_client::{api::write::*, entities::user::User};
-use mogh_auth_server::middleware::authenticate_request;
+use komodo_client::{
+  api::write::*,
+  entities::{
+    ResourceTarget,
+    audit::{AuditEvent, AuditOperation},
+    user::User,
+  },
+};
+use mogh_auth_server::{
+  middleware::authenticate_request, request_ip::RequestIp,
+};
 use mogh_error::Json;
 use mogh_error::Response;
 use mogh_resolver::Resolve;
@@ -14,7 +25,12 @@ use strum::EnumDiscriminants;
 use typeshare::typeshare;
 use uuid::Uuid;
 
-use crate::auth::KomodoAuthImpl;
+use crate::{
+  auth::{KomodoAuthImpl, api_key::check_api_key_request_scope},
+  helpers::audit::{
+    audit_event, record_audit_event, terminal_audit_target,
+  },
+};
 
 use super::Variant;
 
@@ -178,6 +194,7 @@ pub enum WriteRequest {
   UpdateServiceUserDescription(UpdateServiceUserDescription),
   CreateApiKeyForServiceUser(CreateApiKeyForServiceUser),
   DeleteApiKeyForServiceUser(DeleteApiKeyForServiceUser),
+  UpdateApiKeyScopes(UpdateApiKeyScopes),
 
   // ==== USER GROUP ====
   CreateUserGroup(CreateUserGroup),
@@ -211,12 +228,14 @@ pub enum WriteRequest {
   CreateGitProviderAccount(CreateGitProviderAccount),
   UpdateGitProviderAccount(UpdateGitProviderAccount),
   DeleteGitProviderAccount(DeleteGitProviderAccount),
+  GenerateGitProviderAccountSshKey(GenerateGitProviderAccountSshKey),
   CreateDockerRegistryAccount(CreateDockerRegistryAccount),
   UpdateDockerRegistryAccount(UpdateDockerRegistryAccount),
   DeleteDockerRegistryAccount(DeleteDockerRegistryAccount),
 
   // ==== ALERT ====
   CloseAlert(CloseAlert),
+  AcknowledgeAlert(AcknowledgeAlert),
 }
 
 pub fn router() -> Router {
@@ -230,6 +249,7 @@ pub fn router() -> Router {
 
 async fn variant_handler(
   user: Extension<User>,
+  ip: RequestIp,
   Path(Variant { variant }): Path<Variant>,
   Json(params): Json<serde_json::Value>,
 ) -> mogh_error::Result<axum::response::Response> {
@@ -237,14 +257,174 @@ async fn variant_handler(
     "type": variant,
     "params": params,
   }))?;
-  handler(user, Json(req)).await
+  handler(user, ip, Json(req)).await
+}
+
+/// Whether the request resolves its targets through
+/// `get_check_permissions` or `list_resources_for_user`,
+/// which apply the api key resource scope.
+/// Resource scoped api keys can only call other requests
+/// when they are named explicitly in the scope requests.
+/// There is no wildcard, so new requests must be sorted here.
+fn targets_resources(method: WriteRequestMethod) -> bool {
+  use WriteRequestMethod as M;
+  match method {
+    // ==== RESOURCE ====
+    M::UpdateResourceMeta => true,
+    // ==== SWARM ====
+    M::CopySwarm
+    | M::DeleteSwarm
+    | M::UpdateSwarm
+    | M::RenameSwarm => true,
+    M::CreateSwarm => false,
+    // ==== SERVER ====
+    M::CopyServer
+    | M::DeleteServer
+    | M::UpdateServer
+    | M::RenameServer
+    | M::CreateNetwork
+    | M::UpdateServerPublicKey
+    | M::RotateServerKeys => true,
+    M::CreateServer => false,
+    // ==== TERMINAL ====
+    M::CreateTerminal
+    | M::DeleteTerminal
+    | M::DeleteAllTerminals
+    | M::BatchDeleteAllTerminals => true,
+    // ==== STACK ====
+    M::CopyStack
+    | M::DeleteStack
+    | M::UpdateStack
+    | M::RenameStack
+    | M::WriteStackFileContents
+    | M::RefreshStackCache
+    | M::CheckStackForUpdate
+    | M::BatchCheckStackForUpdate => true,
+    M::CreateStack => false,
+    // ==== DEPLOYMENT ====
+    M::CopyDeployment
+    | M::CreateDeploymentFromContainer
+    | M::DeleteDeployment
+    | M::UpdateDeployment
+    | M::RenameDeployment
+    | M::CheckDeploymentForUpdate
+    | M::BatchCheckDeploymentForUpdate => true,
+    M::CreateDeployment => false,
+    // ==== BUILD ====
+    M::CopyBuild
+    | M::DeleteBuild
+    | M::UpdateBuild
+    | M::RenameBuild
+    | M::WriteBuildFileContents
+    | M::RefreshBuildCache => true,
+    M::CreateBuild => false,
+    // ==== REPO ====
+    M::CopyRepo
+    | M::DeleteRepo
+    | M::UpdateRepo
+    | M::RenameRepo
+    | M::RefreshRepoCache => true,
+    M::CreateRepo => false,
+    // ==== PROCEDURE ====
+    M::CopyProcedure
+    | M::DeleteProcedure
+    | M::UpdateProcedure
+    | M::RenameProcedure => true,
+    M::CreateProcedure => false,
+    // ==== ACTION ====
+    M::CopyAction
+    | M::DeleteAction
+    | M::UpdateAction
+    | M::RenameAction => true,
+    M::CreateAction => false,
+    // ==== SYNC ====
+    M::CopyResourceSync
+    | M::DeleteResourceSync
+    | M::UpdateResourceSync
+    | M::RenameResourceSync
+    | M::WriteSyncFileContents
+    | M::CommitSync
+    | M::RefreshResourceSyncPending => true,
+    M::CreateResourceSync => false,
+    // ==== BUILDER ====
+    M::CopyBuilder
+    | M::DeleteBuilder
+    | M::UpdateBuilder
+    | M::RenameBuilder => true,
+    M::CreateBuilder => false,
+    // ==== ALERTER ====
+    M::CopyAlerter
+    | M::DeleteAlerter
+    | M::UpdateAlerter
+    | M::RenameAlerter => true,
+    M::CreateAlerter => false,
+    // ==== ONBOARDING KEY ====
+    M::CreateOnboardingKey
+    | M::UpdateOnboardingKey
+    | M::DeleteOnboardingKey => false,
+    // ==== USER ====
+    M::PushRecentlyViewed
+    | M::SetLastSeenUpdate
+    | M::CreateLocalUser
+    | M::DeleteUser => false,
+    // ==== SERVICE USER ====
+    M::CreateServiceUser
+    | M::UpdateServiceUserDescription
+    | M::CreateApiKeyForServiceUser
+    | M::DeleteApiKeyForServiceUser
+    | M::UpdateApiKeyScopes => false,
+    // ==== USER GROUP ====
+    M::CreateUserGroup
+    | M::RenameUserGroup
+    | M::DeleteUserGroup
+    | M::AddUserToUserGroup
+    | M::RemoveUserFromUserGroup
+    | M::SetUsersInUserGroup
+    | M::SetEveryoneUserGroup => false,
+    // ==== PERMISSIONS ====
+    M::UpdateUserAdmin
+    | M::UpdateUserBasePermissions
+    | M::UpdatePermissionOnResourceType
+    | M::UpdatePermissionOnTarget => false,
+    // ==== TAG ====
+    M::CreateTag
+    | M::DeleteTag
+    | M::RenameTag
+    | M::UpdateTagColor => false,
+    // ==== VARIABLE ====
+    M::CreateVariable
+    | M::UpdateVariableValue
+    | M::UpdateVariableDescription
+    | M::UpdateVariableIsSecret
+    | M::DeleteVariable => false,
+    // ==== PROVIDER ====
+    M::CreateGitProviderAccount
+    | M::UpdateGitProviderAccount
+    | M::DeleteGitProviderAccount
+    | M::GenerateGitProviderAccountSshKey
+    | M::CreateDockerRegistryAccount
+    | M::UpdateDockerRegistryAccount
+    | M::DeleteDockerRegistryAccount => false,
+    // ==== ALERT ====
+    M::AcknowledgeAlert => true,
+    M::CloseAlert => false,
+  }
 }
 
 async fn handler(
   Extension(user): Extension<User>,
+  RequestIp(ip): RequestIp,
   Json(request): Json<WriteRequest>,
 ) -> mogh_error::Result<axum::response::Response> {
-  let res = tokio::spawn(task(request, user))
+  let method: WriteRequestMethod = (&request).into();
+  check_api_key_request_scope(
+    &user,
+    "write",
+    &method.to_string(),
+    targets_resources(method),
+  )?;
+
+  let res = tokio::spawn(task(request, user, ip))
     .await
     .context("failure in spawned task");
 
@@ -254,6 +434,7 @@ async fn handler(
 async fn task(
   request: WriteRequest,
   user: User,
+  ip: IpAddr,
 ) -> mogh_error::Result<axum::response::Response> {
   let task_id = Uuid::new_v4();
   let method: WriteRequestMethod = (&request).into();
@@ -275,6 +456,8 @@ async fn task(
     );
   }
 
+  let audit = write_audit_event(&request, &user, ip);
+
   let res = request.resolve(&WriteArgs { user }).await;
 
   if let Err(e) = &res {
@@ -288,5 +471,79 @@ async fn task(
     );
   }
 
+  if let Some(mut event) = audit {
+    event.success = res.is_ok();
+    if let Err(e) = &res {
+      event.details.push_str(&format!(" | Error: {:#}", e.error));
+    }
+    record_audit_event(event);
+  }
+
   res.map(|res| res.0)
 }
+
+/// Starts the audit event for the security relevant write requests.
+fn write_audit_event(
+  request: &WriteRequest,
+  user: &User,
+  ip: IpAddr,
+) -> Option<AuditEvent> {
+  let (operation, target) = match request {
+    WriteRequest::CreateApiKeyForServiceUser(_) => {
+      (AuditOperation::CreateApiKey, None)
+    }
+    WriteRequest::DeleteApiKeyForServiceUser(_) => {
+      (AuditOperation::DeleteApiKey, None)
+    }
+    WriteRequest::UpdateApiKeyScopes(_) => {
+      (AuditOperation::UpdateApiKey, None)
+    }
+    WriteRequest::CreateLocalUser(_)
+    | WriteRequest::CreateServiceUser(_)
+    | WriteRequest::DeleteUser(_) => {
+      (AuditOperation::ManageUser, None)
+    }
+    WriteRequest::UpdatePermissionOnTarget(req) => (
+      AuditOperation::UpdatePermission,
+      Some(req.resource_target.clone()),
+    ),
+    WriteRequest::UpdateUserAdmin(_)
+    | WriteRequest::UpdateUserBasePermissions(_)
+    | WriteRequest::UpdatePermissionOnResourceType(_)
+    | WriteRequest::DeleteUserGroup(_)
+    | WriteRequest::AddUserToUserGroup(_)
+    | WriteRequest::RemoveUserFromUserGroup(_)
+    | WriteRequest::SetUsersInUserGroup(_)
+    | WriteRequest::SetEveryoneUserGroup(_) => {
+      (AuditOperation::UpdatePermission, None)
+    }
+    WriteRequest::CreateTerminal(req) => {
+      (AuditOperation::Terminal, terminal_audit_target(&req.target))
+    }
+    WriteRequest::DeleteTerminal(req) => {
+      (AuditOperation::Terminal, terminal_audit_target(&req.target))
+    }
+    WriteRequest::DeleteAllTerminals(req) => (
+      AuditOperation::Terminal,
+      Some(ResourceTarget::Server(req.server.clone())),
+    ),
+    WriteRequest::BatchDeleteAllTerminals(_) => {
+      (AuditOperation::Terminal, None)
+    }
+    _ => return None,
+  };
+  let method: WriteRequestMethod = request.into();
+  let mut event =
+    audit_event(user, ip, operation, method.to_string());
+  event.target = target;
+  // Record the request params, without any password.
+  if let Ok(serde_json::Value::Object(mut request)) =
+    serde_json::to_value(request)
+    && let Some(serde_json::Value::Object(mut params)) =
+      request.remove("params")
+  {
+    params.remove("password");
+    event.details = serde_json::Value::Object(params).to_string();
+  }
+  Some(event)
+}
@@ -1,4 +1,5 @@
 use anyhow::{Context, anyhow};
+use command::run_komodo_standard_command;
 use database::mungos::{
   by_id::{delete_one_by_id, find_one_by_id, update_one_by_id},
   mongodb::bson::{doc, to_document},
@@ -15,7 +16,15 @@ use mogh_resolver::Resolve;
 use reqwest::StatusCode;
 
 use crate::{
-  helpers::update::{add_update, make_update},
+  helpers::{
+    encryption::{
+      decrypt_git_account, decrypt_registry_account,
+      encrypt_git_account, encrypt_partial_git_account,
+      encrypt_partial_registry_account, encrypt_registry_account,
+      encrypt_secret,
+    },
+    update::{add_update, make_update},
+  },
   state::db_client,
 };
 
@@ -67,7 +76,7 @@ impl Resolve<WriteArgs> for CreateGitProviderAccount {
 
     account.id = db_client()
       .git_accounts
-      .insert_one(&account)
+      .insert_one(&encrypt_git_account(account.clone())?)
       .await
       .context("Failed to create git provider account on db")?
       .inserted_id
@@ -146,6 +155,8 @@ impl Resolve<WriteArgs> for UpdateGitProviderAccount {
       user,
     );
 
+    encrypt_partial_git_account(&mut self.account)?;
+
     let account = to_document(&self.account).context(
       "Failed to serialize partial git provider account to bson",
     )?;
@@ -165,6 +176,7 @@ impl Resolve<WriteArgs> for UpdateGitProviderAccount {
     else {
       return Err(anyhow!("No account found with given id").into());
     };
+    let account = decrypt_git_account(account)?;
 
     update.push_simple_log(
       "Update git provider account",
@@ -223,6 +235,9 @@ impl Resolve<WriteArgs> for DeleteGitProviderAccount {
           .status_code(StatusCode::BAD_REQUEST),
       );
     };
+    // Still allow deleting accounts encrypted with an unavailable key.
+    let account =
+      decrypt_git_account(account.clone()).unwrap_or(account);
     delete_one_by_id(&db.git_accounts, &self.id, None)
       .await
       .context("failed to delete git account on db")?;
@@ -248,6 +263,132 @@ impl Resolve<WriteArgs> for DeleteGitProviderAccount {
   }
 }
 
+impl Resolve<WriteArgs> for GenerateGitProviderAccountSshKey {
+  #[instrument(
+    "GenerateGitProviderAccountSshKey",
+    skip_all,
+    fields(
+      operator = user.id,
+      id = self.id,
+    )
+  )]
+  async fn resolve(
+    self,
+    WriteArgs { user }: &WriteArgs,
+  ) -> mogh_error::Result<GenerateGitProviderAccountSshKeyResponse>
+  {
+    if !user.admin {
+      return Err(
+        anyhow!("Only admins can update git provider accounts")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+
+    let db = db_client();
+    let Some(account) = find_one_by_id(&db.git_accounts, &self.id)
+      .await
+      .context("Failed to query db for git accounts")?
+    else {
+      return Err(
+        anyhow!("No account found with given id")
+          .status_code(StatusCode::BAD_REQUEST),
+      );
+    };
+
+    let (private_key, public_key) = generate_ssh_key(&format!(
+      "komodo-{}@{}",
+      account.username, account.domain
+    ))
+    .await?;
+
+    let mut update = make_update(
+      ResourceTarget::system(),
+      Operation::UpdateGitProviderAccount,
+      user,
+    );
+
+    update_one_by_id(
+      &db.git_accounts,
+      &self.id,
+      doc! { "$set": {
+        "ssh_private_key": encrypt_secret(private_key)?,
+        "ssh_public_key": &public_key,
+      } },
+      None,
+    )
+    .await
+    .context("Failed to update git provider account on db")?;
+
+    let Some(account) = find_one_by_id(&db.git_accounts, &self.id)
+      .await
+      .context("Failed to query db for git accounts")?
+    else {
+      return Err(anyhow!("No account found with given id").into());
+    };
+    let account = decrypt_git_account(account)?;
+
+    update.push_simple_log(
+      "Generate SSH key",
+      format!(
+        "Generated SSH key for git provider account for {} with username {}\n\n{public_key}",
+        account.domain, account.username
+      ),
+    );
+
+    update.finalize();
+
+    add_update(update)
+      .await
+      .inspect_err(|e| {
+        error!("Failed to add update for generate git provider account ssh key | {e:#}")
+      })
+      .ok();
+
+    Ok(account)
+  }
+}
+
+/// Generates an ed25519 key pair using `ssh-keygen`.
+/// Returns (private key, public key).
+async fn generate_ssh_key(
+  comment: &str,
+) -> anyhow::Result<(String, String)> {
+  let dir = std::env::temp_dir()
+    .join(format!("komodo-ssh-keygen-{}", uuid::Uuid::new_v4()));
+  tokio::fs::create_dir_all(&dir)
+    .await
+    .context("Failed to create ssh-keygen directory")?;
+  let key_file = dir.join("id_ed25519");
+  let res = async {
+    let log = run_komodo_standard_command(
+      "Generate SSH Key",
+      None,
+      format!(
+        "ssh-keygen -q -t ed25519 -N '' -C '{comment}' -f '{}'",
+        key_file.display()
+      ),
+    )
+    .await;
+    if !log.success {
+      return Err(anyhow!(
+        "Failed to generate ssh key | {}",
+        log.stderr
+      ));
+    }
+    let private_key = tokio::fs::read_to_string(&key_file)
+      .await
+      .context("Failed to read generated private key")?;
+    let public_key =
+      tokio::fs::read_to_string(key_file.with_extension("pub"))
+        .await
+        .context("Failed to read generated public key")?;
+    anyhow::Ok((private_key, public_key.trim().to_string()))
+  }
+  .await;
+  let _ = tokio::fs::remove_dir_all(&dir).await;
+  res
+}
+
 impl Resolve<WriteArgs> for CreateDockerRegistryAccount {
   #[instrument(
     "CreateDockerRegistryAccount",
@@ -295,7 +436,7 @@ impl Resolve<WriteArgs> for CreateDockerRegistryAccount {
 
     account.id = db_client()
       .registry_accounts
-      .insert_one(&account)
+      .insert_one(&encrypt_registry_account(account.clone())?)
       .await
       .context(
         "Failed to create docker registry account account on db",
@@ -378,6 +519,8 @@ impl Resolve<WriteArgs> for UpdateDockerRegistryAccount {
       user,
     );
 
+    encrypt_partial_registry_account(&mut self.account)?;
+
     let account = to_document(&self.account).context(
       "Failed to serialize partial docker registry account account to bson",
     )?;
@@ -401,6 +544,7 @@ impl Resolve<WriteArgs> for UpdateDockerRegistryAccount {
     else {
       return Err(anyhow!("No account found with given id").into());
     };
+    let account = decrypt_registry_account(account)?;
 
     update.push_simple_log(
       "Update docker registry account",
@@ -460,6 +604,9 @@ impl Resolve<WriteArgs> for DeleteDockerRegistryAccount {
           .status_code(StatusCode::BAD_REQUEST),
       );
     };
+    // Still allow deleting accounts encrypted with an unavailable key.
+    let account =
+      decrypt_registry_account(account.clone()).unwrap_or(account);
     delete_one_by_id(&db.registry_accounts, &self.id, None)
       .await
       .context("Failed to delete registry account on db")?;
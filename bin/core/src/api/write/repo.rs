@@ -220,9 +220,13 @@ impl Resolve<WriteArgs> for RefreshRepoCache {
     let repo_path =
       clone_args.unique_path(&core_config().repo_directory)?;
     clone_args.destination = Some(repo_path.display().to_string());
+    clone_args.ssh_known_hosts =
+      core_config().git_ssh_known_hosts.clone();
+    clone_args.ssh_accept_new_host_keys =
+      core_config().git_ssh_accept_new_host_keys;
 
     let access_token = if let Some(username) = &clone_args.account {
-      git_token(&clone_args.provider, username, |https| {
+      git_token(&clone_args.provider, username, clone_args.ssh, |https| {
           clone_args.https = https
         })
         .await
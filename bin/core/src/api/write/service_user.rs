@@ -14,7 +14,11 @@ use mogh_resolver::Resolve;
 use reqwest::StatusCode;
 
 use crate::{
-  auth::KomodoAuthImpl, helpers::validations::validate_username,
+  auth::{
+    KomodoAuthImpl,
+    api_key::{update_api_key_scopes, validate_api_key_scopes},
+  },
+  helpers::validations::validate_username,
   state::db_client,
 };
 
@@ -136,6 +140,7 @@ impl Resolve<WriteArgs> for CreateApiKeyForServiceUser {
       service_user = self.user_id,
       name = self.name,
       expires = self.expires,
+      scopes = format!("{:?}", self.scopes),
     )
   )]
   async fn resolve(
@@ -162,7 +167,17 @@ impl Resolve<WriteArgs> for CreateApiKeyForServiceUser {
       );
     };
 
-    create_api_key(
+    if user.api_key_scopes.is_some() {
+      return Err(
+        anyhow!("Cannot create api keys using a scoped api key")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+
+    validate_api_key_scopes(&self.scopes)
+      .status_code(StatusCode::BAD_REQUEST)?;
+
+    let res = create_api_key(
       &KomodoAuthImpl,
       service_user.id,
       CreateApiKey {
@@ -170,7 +185,13 @@ impl Resolve<WriteArgs> for CreateApiKeyForServiceUser {
         expires: self.expires as u64,
       },
     )
-    .await
+    .await?;
+
+    if !self.scopes.is_empty() {
+      update_api_key_scopes(&res.key, &self.scopes).await?;
+    }
+
+    Ok(res)
   }
 }
 
@@ -223,3 +244,65 @@ impl Resolve<WriteArgs> for DeleteApiKeyForServiceUser {
     Ok(DeleteApiKeyForServiceUserResponse {})
   }
 }
+
+impl Resolve<WriteArgs> for UpdateApiKeyScopes {
+  #[instrument(
+    "UpdateApiKeyScopes",
+    skip_all,
+    fields(
+      operator = user.id,
+      key = self.key,
+      scopes = format!("{:?}", self.scopes),
+    )
+  )]
+  async fn resolve(
+    self,
+    WriteArgs { user }: &WriteArgs,
+  ) -> mogh_error::Result<UpdateApiKeyScopesResponse> {
+    // Otherwise a scoped api key could lift its own restrictions.
+    if user.api_key_scopes.is_some() {
+      return Err(
+        anyhow!(
+          "Cannot manage api key scopes using a scoped api key"
+        )
+        .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+
+    validate_api_key_scopes(&self.scopes)
+      .status_code(StatusCode::BAD_REQUEST)?;
+
+    let api_key = db_client()
+      .api_keys
+      .find_one(doc! { "key": &self.key })
+      .await
+      .context("Failed to query db for api key")?
+      .context("Did not find matching api key")
+      .status_code(StatusCode::NOT_FOUND)?;
+
+    // Admins can also manage Service User api keys
+    if api_key.user_id != user.id {
+      if !user.admin {
+        return Err(
+          anyhow!("Api key does not belong to user")
+            .status_code(StatusCode::FORBIDDEN),
+        );
+      }
+      let key_user =
+        find_one_by_id(&db_client().users, &api_key.user_id)
+          .await
+          .context("Failed to query db for user")?
+          .context("No user found with id")?;
+      let UserConfig::Service { .. } = &key_user.config else {
+        return Err(
+          anyhow!("Target user is not Service User")
+            .status_code(StatusCode::FORBIDDEN),
+        );
+      };
+    }
+
+    update_api_key_scopes(&self.key, &self.scopes).await?;
+
+    Ok(UpdateApiKeyScopesResponse {})
+  }
+}
@@ -339,6 +339,10 @@ async fn write_stack_file_contents_git(
   };
   let root = repo_args.unique_path(&core_config().repo_directory)?;
   repo_args.destination = Some(root.display().to_string());
+  repo_args.ssh_known_hosts =
+    core_config().git_ssh_known_hosts.clone();
+  repo_args.ssh_accept_new_host_keys =
+    core_config().git_ssh_accept_new_host_keys;
 
   let file_path = stack
     .config
@@ -655,6 +659,7 @@ impl Resolve<WriteArgs> for RefreshStackCache {
       remote_errors,
       latest_hash,
       latest_message,
+      preview_template: stack.info.preview_template.clone(),
     };
 
     let info = to_document(&info)
@@ -889,6 +894,7 @@ pub async fn check_stack_for_update_inner(
         ts,
         resolved: true,
         resolved_ts: ts.into(),
+        escalation: Default::default(),
         level: SeverityLevel::Ok,
         target: ResourceTarget::Stack(stack.id.clone()),
         data: AlertData::StackImageUpdateAvailable {
@@ -985,6 +991,7 @@ pub async fn check_stack_for_update_inner(
             ts,
             resolved: true,
             resolved_ts: ts.into(),
+            escalation: Default::default(),
             level: SeverityLevel::Ok,
             target: ResourceTarget::Stack(stack.id.clone()),
             data: AlertData::StackAutoUpdated {
@@ -314,9 +314,13 @@ async fn write_sync_file_contents_git(
   };
   let root = repo_args.unique_path(&core_config().repo_directory)?;
   repo_args.destination = Some(root.display().to_string());
+  repo_args.ssh_known_hosts =
+    core_config().git_ssh_known_hosts.clone();
+  repo_args.ssh_accept_new_host_keys =
+    core_config().git_ssh_accept_new_host_keys;
 
   let git_token = if let Some(account) = &repo_args.account {
-    git_token(&repo_args.provider, account, |https| repo_args.https = https)
+    git_token(&repo_args.provider, account, repo_args.ssh, |https| repo_args.https = https)
     .await
     .with_context(
       || format!("Failed to get git token in call to db. Stopping run. | {} | {account}", repo_args.provider),
@@ -655,9 +659,12 @@ async fn commit_git_sync(
 ) -> anyhow::Result<()> {
   let root = args.unique_path(&core_config().repo_directory)?;
   args.destination = Some(root.display().to_string());
+  args.ssh_known_hosts = core_config().git_ssh_known_hosts.clone();
+  args.ssh_accept_new_host_keys =
+    core_config().git_ssh_accept_new_host_keys;
 
   let access_token = if let Some(account) = &args.account {
-    git_token(&args.provider, account, |https| args.https = https)
+    git_token(&args.provider, account, args.ssh, |https| args.https = https)
       .await
       .with_context(
         || format!("Failed to get git token in call to db. Stopping run. | {} | {account}", args.provider),
@@ -929,6 +936,7 @@ impl Resolve<WriteArgs> for RefreshResourceSyncPending {
             target: ResourceTarget::ResourceSync(id.clone()),
             data: AlertData::ResourceSyncPendingUpdates { id, name },
             resolved_ts: None,
+            escalation: Default::default(),
           };
           db.alerts
             .insert_one(&alert)
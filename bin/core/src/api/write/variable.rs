@@ -10,6 +10,7 @@ use reqwest::StatusCode;
 
 use crate::{
   helpers::{
+    encryption::{decrypt_secret, encrypt_secret, encrypt_variable},
     query::get_variable,
     update::{add_update, make_update},
     validations::{validate_variable_name, validate_variable_value},
@@ -62,7 +63,7 @@ impl Resolve<WriteArgs> for CreateVariable {
 
     db_client()
       .variables
-      .insert_one(&variable)
+      .insert_one(&encrypt_variable(variable.clone())?)
       .await
       .context("Failed to create Variable on db")?;
 
@@ -116,11 +117,17 @@ impl Resolve<WriteArgs> for UpdateVariableValue {
       return Ok(variable);
     }
 
+    let stored_value = if variable.is_secret {
+      encrypt_secret(value.clone())?
+    } else {
+      value.clone()
+    };
+
     db_client()
       .variables
       .update_one(
         doc! { "name": &name },
-        doc! { "$set": { "value": &value } },
+        doc! { "$set": { "value": stored_value } },
       )
       .await
       .context("Failed to update variable value on db")?;
@@ -207,11 +214,20 @@ impl Resolve<WriteArgs> for UpdateVariableIsSecret {
       );
     }
 
+    let variable = get_variable(&self.name).await?;
+
+    // Secret values are stored encrypted, others in plain text.
+    let value = if self.is_secret {
+      encrypt_secret(variable.value)?
+    } else {
+      decrypt_secret(variable.value)?
+    };
+
     db_client()
       .variables
       .update_one(
         doc! { "name": &self.name },
-        doc! { "$set": { "is_secret": self.is_secret } },
+        doc! { "$set": { "is_secret": self.is_secret, "value": value } },
       )
       .await
       .context("Failed to update Variable 'is_secret' on db")?;
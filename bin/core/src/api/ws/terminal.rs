@@ -8,7 +8,8 @@ use bytes::Bytes;
 use colored::Colorize;
 use futures_util::{SinkExt, StreamExt as _};
 use komodo_client::{
-  api::terminal::ConnectTerminalQuery, entities::user::User,
+  api::terminal::ConnectTerminalQuery,
+  entities::{audit::AuditOperation, user::User},
 };
 use mogh_auth_server::request_ip::RequestIp;
 use periphery_client::api::terminal::DisconnectTerminal;
@@ -16,7 +17,10 @@ use serde::de::DeserializeOwned;
 use tokio_util::sync::CancellationToken;
 
 use crate::{
-  helpers::terminal::setup_target_for_user,
+  helpers::{
+    audit::{audit_event, record_audit_event, terminal_audit_target},
+    terminal::setup_target_for_user,
+  },
   periphery::{PeripheryClient, terminal::ConnectTerminalResponse},
   state::periphery_connections,
 };
@@ -34,17 +38,35 @@ pub async fn handler(
       return;
     };
 
-    let (periphery, response) =
-      match setup_forwarding(query, &user).await {
-        Ok(response) => response,
-        Err(e) => {
-          let _ = client_socket
-            .send(ws::Message::text(format!("ERROR: {e:#}")))
-            .await;
-          let _ = client_socket.close().await;
-          return;
-        }
-      };
+    let mut event = audit_event(
+      &user,
+      ip,
+      AuditOperation::Terminal,
+      "ConnectTerminal",
+    );
+    event.target = terminal_audit_target(&query.target);
+    event.details = format!(
+      "Terminal: {}",
+      query.terminal.as_deref().unwrap_or_default()
+    );
+
+    let res = setup_forwarding(query, &user).await;
+    event.success = res.is_ok();
+    if let Err(e) = &res {
+      event.details.push_str(&format!(" | Error: {e:#}"));
+    }
+    record_audit_event(event);
+
+    let (periphery, response) = match res {
+      Ok(response) => response,
+      Err(e) => {
+        let _ = client_socket
+          .send(ws::Message::text(format!("ERROR: {e:#}")))
+          .await;
+        let _ = client_socket.close().await;
+        return;
+      }
+    };
 
     forward_ws_channel(periphery, client_socket, response).await
   })
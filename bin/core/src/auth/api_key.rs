@@ -1,9 +1,17 @@
-use anyhow::Context as _;
-use database::bson::doc;
-use komodo_client::entities::{api_key::ApiKey, komodo_timestamp};
+use std::{net::IpAddr, str::FromStr};
+
+use anyhow::{Context as _, anyhow};
+use database::bson::{doc, to_bson};
+use ipnetwork::IpNetwork;
+use komodo_client::entities::{
+  api_key::{ApiKey, ApiKeyScopes},
+  komodo_timestamp,
+  user::User,
+};
 use mogh_auth_client::api::manage::CreateApiKey;
+use mogh_error::{AddStatusCodeError, StatusCode};
 
-use crate::state::db_client;
+use crate::{helpers::query::get_tag, state::db_client};
 
 pub async fn create_api_key(
   user_id: String,
@@ -18,6 +26,7 @@ pub async fn create_api_key(
     user_id,
     created_at: komodo_timestamp(),
     expires: expires as i64,
+    scopes: Default::default(),
   };
 
   db_client()
@@ -37,3 +46,91 @@ pub async fn delete_api_key(key: &str) -> anyhow::Result<()> {
     .context("Failed to delete api key from database")?;
   Ok(())
 }
+
+pub async fn update_api_key_scopes(
+  key: &str,
+  scopes: &ApiKeyScopes,
+) -> anyhow::Result<()> {
+  let scopes = to_bson(scopes)
+    .context("Failed to serialize api key scopes to bson")?;
+  db_client()
+    .api_keys
+    .update_one(
+      doc! { "key": key },
+      doc! { "$set": { "scopes": scopes } },
+    )
+    .await
+    .context("Failed to update api key scopes on database")?;
+  Ok(())
+}
+
+pub fn validate_api_key_scopes(
+  scopes: &ApiKeyScopes,
+) -> anyhow::Result<()> {
+  for ip in &scopes.ips {
+    IpNetwork::from_str(ip)
+      .with_context(|| format!("Invalid api key scope IP: {ip}"))?;
+  }
+  Ok(())
+}
+
+/// Resolves the scope tag names to ids,
+/// so they can be matched against resource tags.
+/// Tags which don't exist are kept as is, and match nothing.
+pub async fn resolve_api_key_scope_tags(scopes: &mut ApiKeyScopes) {
+  for tag in &mut scopes.tags {
+    if let Ok(resolved) = get_tag(tag).await {
+      *tag = resolved.id;
+    }
+  }
+}
+
+/// Scoped api keys can only be used from the allowed IPs.
+pub fn check_api_key_ip_scope(
+  scopes: &ApiKeyScopes,
+  ip: IpAddr,
+) -> anyhow::Result<()> {
+  if scopes.ips.is_empty()
+    || scopes.ips.iter().any(|allowed| {
+      IpNetwork::from_str(allowed)
+        .is_ok_and(|network| network.contains(ip))
+    })
+  {
+    Ok(())
+  } else {
+    Err(anyhow!("Api key is not scoped to be used from {ip}"))
+  }
+}
+
+/// Scoped api keys can only call the allowed requests.
+/// `kind` is `read`, `write` or `execute`.
+/// Keys scoped to resources / tags can only call requests
+/// which don't target resources when they are named explicitly.
+pub fn check_api_key_request_scope(
+  user: &User,
+  kind: &str,
+  request: &str,
+  targets_resources: bool,
+) -> mogh_error::Result<()> {
+  let Some(scopes) = &user.api_key_scopes else {
+    return Ok(());
+  };
+  if !scopes.allows_request(kind, request) {
+    return Err(
+      anyhow!("Api key is not scoped to call {request}")
+        .status_code(StatusCode::FORBIDDEN),
+    );
+  }
+  if scopes.restricts_resources()
+    && !targets_resources
+    && !scopes.requests.iter().any(|allowed| allowed == request)
+  {
+    return Err(
+      anyhow!(
+        "Api key is scoped to resources, {request} must be named in the scope requests"
+      )
+      .status_code(StatusCode::FORBIDDEN),
+    );
+  }
+  Ok(())
+}
@@ -0,0 +1,394 @@
+use std::{
+  sync::{Arc, LazyLock},
+  time::Duration,
+};
+
+use anyhow::{Context as _, anyhow};
+use axum::{
+  Router,
+  routing::{get, post},
+};
+use database::{
+  bson::{Document, doc},
+  mungos::by_id::update_one_by_id,
+};
+use komodo_client::{
+  api::ldap::{LdapLogin, LdapLoginOptions, LdapLoginResponse},
+  entities::{
+    audit::{AuditEvent, AuditOperation},
+    komodo_timestamp,
+    user::{NewUserParams, User, UserConfig},
+  },
+};
+use ldap3::{
+  Ldap, LdapConnAsync, LdapConnSettings, Scope, SearchEntry,
+  ldap_escape,
+};
+use mogh_auth_server::request_ip::RequestIp;
+use mogh_error::{
+  AddStatusCode, AddStatusCodeError, Json, StatusCode,
+};
+use mogh_rate_limit::{RateLimiter, WithFailureRateLimit};
+
+use crate::{
+  config::core_config,
+  helpers::{
+    audit::record_audit_event, query::find_ldap_user,
+    validations::validate_username,
+  },
+  state::db_client,
+};
+
+use super::{JWT_PROVIDER, sync_user_groups};
+
+const LDAP_TIMEOUT: Duration = Duration::from_secs(10);
+
+static LDAP_LOGIN_RATE_LIMITER: LazyLock<Arc<RateLimiter>> =
+  LazyLock::new(|| {
+    let config = core_config();
+    RateLimiter::new(
+      config.auth_rate_limit_disabled,
+      config.auth_rate_limit_max_attempts as usize,
+      config.auth_rate_limit_window_seconds,
+    )
+  });
+
+pub fn router() -> Router {
+  Router::new()
+    .route(
+      "/options",
+      get(|| async {
+        Json(LdapLoginOptions {
+          enabled: core_config().ldap_enabled(),
+        })
+      }),
+    )
+    .route("/login", post(login_handler))
+}
+
+async fn login_handler(
+  RequestIp(ip): RequestIp,
+  axum::Json(body): axum::Json<LdapLogin>,
+) -> mogh_error::Result<Json<LdapLoginResponse>> {
+  let username = body.username.clone();
+  let res = login(body)
+    .with_failure_rate_limit_using_ip(&LDAP_LOGIN_RATE_LIMITER, &ip)
+    .await
+    .inspect_err(|e| {
+      warn!(
+        username,
+        source_ip = ip.to_string(),
+        "LDAP login failed | ERROR: {:#}",
+        e.error
+      )
+    });
+  record_audit_event(AuditEvent {
+    operation: AuditOperation::Login,
+    request: String::from("LdapLogin"),
+    success: res.is_ok(),
+    user_id: res
+      .as_ref()
+      .ok()
+      .and_then(|res| JWT_PROVIDER.decode_sub(&res.jwt).ok())
+      .unwrap_or_default(),
+    username,
+    ip: ip.to_string(),
+    details: res
+      .as_ref()
+      .err()
+      .map(|e| format!("{:#}", e.error))
+      .unwrap_or_default(),
+    ..Default::default()
+  });
+  res.map(Json)
+}
+
+/// The user entry found in the directory.
+struct LdapUser {
+  dn: String,
+  username: String,
+  email: String,
+  /// Only queried when `ldap_group_sync` is enabled.
+  groups: Vec<String>,
+}
+
+async fn login(
+  LdapLogin { username, password }: LdapLogin,
+) -> mogh_error::Result<LdapLoginResponse> {
+  let config = core_config();
+
+  if !config.ldap_enabled() {
+    return Err(
+      anyhow!("LDAP login is not enabled")
+        .status_code(StatusCode::UNAUTHORIZED),
+    );
+  }
+
+  // An empty password performs an unauthenticated bind,
+  // which many servers accept as a successful bind.
+  if username.is_empty() || password.is_empty() {
+    return Err(
+      anyhow!("Username and password are required")
+        .status_code(StatusCode::BAD_REQUEST),
+    );
+  }
+
+  let ldap_user = authenticate(&username, &password)
+    .await
+    .status_code(StatusCode::UNAUTHORIZED)?;
+
+  let user = find_or_create_user(&ldap_user).await?;
+
+  // Rejected before the sync, so a refused login
+  // doesn't change the user's UserGroups.
+  if !user.external_skip_2fa
+    && (user.totp.enrolled() || user.passkey.passkey.is_some())
+  {
+    return Err(
+      anyhow!(
+        "User has 2FA enabled, which is not supported for LDAP login"
+      )
+      .status_code(StatusCode::UNAUTHORIZED),
+    );
+  }
+
+  if config.ldap_group_sync {
+    sync_user_groups(
+      &user.id,
+      &config.ldap_group_prefix,
+      &ldap_user.groups,
+    )
+    .await?;
+  }
+
+  let jwt = JWT_PROVIDER
+    .encode_sub(&user.id)
+    .context("Failed to generate JWT")?
+    .jwt;
+
+  Ok(LdapLoginResponse { jwt })
+}
+
+/// Finds the user in the directory and verifies the password
+/// by binding as the user.
+async fn authenticate(
+  username: &str,
+  password: &str,
+) -> anyhow::Result<LdapUser> {
+  let config = core_config();
+  let settings = LdapConnSettings::new()
+    .set_conn_timeout(LDAP_TIMEOUT)
+    .set_starttls(config.ldap_start_tls)
+    .set_no_tls_verify(config.ldap_tls_insecure);
+  let (conn, mut ldap) =
+    LdapConnAsync::with_settings(settings, &config.ldap_url)
+      .await
+      .context("Failed to connect to LDAP server")?;
+  ldap3::drive!(conn);
+  let res = authenticate_inner(&mut ldap, username, password).await;
+  let _ = ldap.unbind().await;
+  res
+}
+
+async fn authenticate_inner(
+  ldap: &mut Ldap,
+  username: &str,
+  password: &str,
+) -> anyhow::Result<LdapUser> {
+  let config = core_config();
+
+  service_bind(ldap).await?;
+
+  let filter = config
+    .ldap_user_filter
+    .replace("{username}", &ldap_escape(username));
+  let (entries, _) = ldap
+    .with_timeout(LDAP_TIMEOUT)
+    .search(
+      &config.ldap_user_base_dn,
+      Scope::Subtree,
+      &filter,
+      vec![
+        config.ldap_username_attribute.as_str(),
+        config.ldap_email_attribute.as_str(),
+      ],
+    )
+    .await
+    .context("Failed to search for LDAP user")?
+    .success()
+    .context("Failed to search for LDAP user")?;
+
+  let mut entries = entries.into_iter().map(SearchEntry::construct);
+  let entry = entries
+    .next()
+    .with_context(|| format!("No LDAP user found for {username}"))?;
+  if entries.next().is_some() {
+    return Err(anyhow!(
+      "Multiple LDAP users found for {username}, check the 'ldap_user_filter'"
+    ));
+  }
+
+  ldap
+    .with_timeout(LDAP_TIMEOUT)
+    .simple_bind(&entry.dn, password)
+    .await
+    .context("Failed to bind as LDAP user")?
+    .success()
+    .context("Invalid LDAP credentials")?;
+
+  let groups = if config.ldap_group_sync {
+    // Search groups with the service account again,
+    // users may not be able to read group membership.
+    service_bind(ldap).await?;
+    user_groups(ldap, &entry.dn, username).await?
+  } else {
+    Vec::new()
+  };
+
+  let username =
+    first_attribute(&entry, &config.ldap_username_attribute)
+      .unwrap_or_else(|| username.to_string());
+  let email = first_attribute(&entry, &config.ldap_email_attribute)
+    .unwrap_or_default();
+
+  Ok(LdapUser {
+    dn: entry.dn,
+    username,
+    email,
+    groups,
+  })
+}
+
+/// Binds with the configured service account,
+/// or stays anonymous if none is configured.
+async fn service_bind(ldap: &mut Ldap) -> anyhow::Result<()> {
+  let config = core_config();
+  if config.ldap_bind_dn.is_empty() {
+    return Ok(());
+  }
+  ldap
+    .with_timeout(LDAP_TIMEOUT)
+    .simple_bind(&config.ldap_bind_dn, &config.ldap_bind_password)
+    .await
+    .context("Failed to bind with LDAP service account")?
+    .success()
+    .context("Failed to bind with LDAP service account")?;
+  Ok(())
+}
+
+/// The names of the groups the user is a member of.
+async fn user_groups(
+  ldap: &mut Ldap,
+  user_dn: &str,
+  username: &str,
+) -> anyhow::Result<Vec<String>> {
+  let config = core_config();
+  if config.ldap_group_base_dn.is_empty() {
+    return Err(anyhow!(
+      "Must configure 'ldap_group_base_dn' to use 'ldap_group_sync'"
+    ));
+  }
+  let filter = config
+    .ldap_group_filter
+    .replace("{user_dn}", &ldap_escape(user_dn))
+    .replace("{username}", &ldap_escape(username));
+  let (entries, _) = ldap
+    .with_timeout(LDAP_TIMEOUT)
+    .search(
+      &config.ldap_group_base_dn,
+      Scope::Subtree,
+      &filter,
+      vec![config.ldap_group_name_attribute.as_str()],
+    )
+    .await
+    .context("Failed to search for LDAP groups")?
+    .success()
+    .context("Failed to search for LDAP groups")?;
+  let groups = entries
+    .into_iter()
+    .map(SearchEntry::construct)
+    .filter_map(|entry| {
+      first_attribute(&entry, &config.ldap_group_name_attribute)
+    })
+    .collect();
+  Ok(groups)
+}
+
+/// Attribute names are case insensitive,
+/// and the server may return them with different case than configured.
+fn first_attribute(
+  entry: &SearchEntry,
+  name: &str,
+) -> Option<String> {
+  entry
+    .attrs
+    .iter()
+    .find(|(attr, _)| attr.eq_ignore_ascii_case(name))
+    .and_then(|(_, values)| values.first())
+    .filter(|value| !value.is_empty())
+    .cloned()
+}
+
+async fn find_or_create_user(
+  ldap_user: &LdapUser,
+) -> mogh_error::Result<User> {
+  let config = core_config();
+
+  if let Some(user) = find_ldap_user(&ldap_user.dn).await? {
+    // Keep the email up to date with the directory
+    if let UserConfig::Ldap { email, .. } = &user.config
+      && email != &ldap_user.email
+    {
+      update_one_by_id(
+        &db_client().users,
+        &user.id,
+        doc! { "$set": { "config.data.email": &ldap_user.email } },
+        None,
+      )
+      .await
+      .context("Failed to update LDAP user email on database")?;
+    }
+    return Ok(user);
+  }
+
+  let no_users_exist = db_client()
+    .users
+    .find_one(Document::new())
+    .await
+    .context("Failed to query database for users")?
+    .is_none();
+
+  if !no_users_exist && config.disable_user_registration {
+    return Err(
+      anyhow!("User registration is disabled")
+        .status_code(StatusCode::UNAUTHORIZED),
+    );
+  }
+
+  validate_username(&ldap_user.username)
+    .status_code(StatusCode::BAD_REQUEST)?;
+
+  let mut user = User::new(NewUserParams {
+    username: ldap_user.username.clone(),
+    enabled: no_users_exist || config.enable_new_users,
+    admin: no_users_exist,
+    super_admin: no_users_exist,
+    config: UserConfig::Ldap {
+      dn: ldap_user.dn.clone(),
+      email: ldap_user.email.clone(),
+    },
+    updated_at: komodo_timestamp(),
+  });
+
+  user.id = db_client()
+    .users
+    .insert_one(&user)
+    .await
+    .context("Failed to create user on database")?
+    .inserted_id
+    .as_object_id()
+    .context("inserted_id is not ObjectId")?
+    .to_string();
+
+  Ok(user)
+}
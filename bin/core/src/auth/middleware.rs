@@ -1,28 +1,39 @@
 use anyhow::{Context, anyhow};
 use database::mungos::mongodb::bson::doc;
-use komodo_client::entities::{komodo_timestamp, user::User};
+use komodo_client::entities::{
+  api_key::ApiKey, komodo_timestamp, user::User,
+};
 use mogh_auth_server::RequestAuthentication;
 
 use crate::{
-  auth::JWT_PROVIDER, helpers::query::get_user, state::db_client,
+  auth::{JWT_PROVIDER, api_key::resolve_api_key_scope_tags},
+  helpers::query::get_user,
+  state::db_client,
 };
 
 pub async fn extract_user_from_auth(
   auth: RequestAuthentication,
   require_user_enabled: bool,
 ) -> anyhow::Result<User> {
-  let user_id = match auth {
-    RequestAuthentication::UserId(user_id) => user_id,
+  let (user_id, api_key_scopes) = match auth {
+    RequestAuthentication::UserId(user_id) => (user_id, None),
     RequestAuthentication::KeyAndSecret { key, secret } => {
-      auth_api_key_get_user_id(&key, &secret).await?
+      let key = auth_api_key(&key, &secret).await?;
+      let scopes = (!key.scopes.is_empty()).then_some(key.scopes);
+      (key.user_id, scopes)
     }
     RequestAuthentication::PublicKey(_) => todo!(),
   };
-  if require_user_enabled {
-    check_enabled(&user_id).await
+  let mut user = if require_user_enabled {
+    check_enabled(&user_id).await?
   } else {
-    get_user(&user_id).await
+    get_user(&user_id).await?
+  };
+  if let Some(mut scopes) = api_key_scopes {
+    resolve_api_key_scope_tags(&mut scopes).await;
+    user.api_key_scopes = Some(scopes);
   }
+  Ok(user)
 }
 
 pub async fn auth_jwt_check_enabled(
@@ -36,17 +47,23 @@ pub async fn auth_api_key_check_enabled(
   key: &str,
   secret: &str,
 ) -> anyhow::Result<User> {
-  let user_id = auth_api_key_get_user_id(key, secret).await?;
-  check_enabled(&user_id).await
+  let key = auth_api_key(key, secret).await?;
+  // These connections don't go through the api key scope checks.
+  if !key.scopes.is_empty() {
+    return Err(anyhow!(
+      "Scoped api keys can only be used with the read, write and execute apis"
+    ));
+  }
+  check_enabled(&key.user_id).await
 }
 
 /// Api Key Clock skew tolerance in milliseconds (5 minutes for Api Keys)
 const API_KEY_CLOCK_SKEW_TOLERANCE_MS: i64 = 5 * 60 * 1000;
 
-pub async fn auth_api_key_get_user_id(
+pub async fn auth_api_key(
   key: &str,
   secret: &str,
-) -> anyhow::Result<String> {
+) -> anyhow::Result<ApiKey> {
   let key = db_client()
     .api_keys
     .find_one(doc! { "key": key })
@@ -66,7 +83,7 @@ pub async fn auth_api_key_get_user_id(
     .map_err(|_| anyhow!("Invalid user credentials"))?
   {
     // secret matches
-    Ok(key.user_id)
+    Ok(key)
   } else {
     // secret mismatch
     Err(anyhow!("Invalid user credentials"))
@@ -4,11 +4,13 @@ use anyhow::{Context as _, anyhow};
 use async_timing_util::{
   Timelength, get_timelength_in_ms, unix_timestamp_ms,
 };
+use axum::extract::{FromRequestParts, OriginalUri};
 use database::{
   bson::{Document, doc, to_bson},
   mungos::by_id::update_one_by_id,
 };
 use komodo_client::entities::{
+  api_key::ApiKeyScopes,
   komodo_timestamp, optional_str,
   user::{NewUserParams, User, UserConfig, UserConfigVariant},
 };
@@ -24,6 +26,7 @@ use mogh_auth_server::{
     passkey::PasskeyProvider,
   },
   rand::random_string,
+  request_ip::RequestIp,
   user::{AuthUserImpl, BoxAuthUser},
 };
 use mogh_error::{AddStatusCode, AddStatusCodeError, StatusCode};
@@ -43,9 +46,10 @@ use crate::{
   state::db_client,
 };
 
-mod api_key;
-
+pub mod api_key;
+pub mod ldap;
 pub mod middleware;
+pub mod oidc;
 
 pub static JWT_PROVIDER: LazyLock<JwtProvider> =
   LazyLock::new(|| {
@@ -131,6 +135,103 @@ impl AuthUserImpl for AuthUser {
   }
 }
 
+/// The apis which check the request and resource scopes.
+/// Scoped api keys can't be used with any other api,
+/// such as to manage api keys.
+const API_KEY_SCOPED_APIS: &[&str] =
+  &["/read", "/write", "/execute", "/terminal", "/user"];
+
+async fn check_api_key_scopes(
+  scopes: &ApiKeyScopes,
+  req: axum::extract::Request,
+) -> anyhow::Result<axum::extract::Request> {
+  let (mut parts, body) = req.into_parts();
+
+  if !scopes.requests.is_empty() || scopes.restricts_resources() {
+    // The nested routers only see the path relative to the nest.
+    let path = parts
+      .extensions
+      .get::<OriginalUri>()
+      .map(|uri| uri.path())
+      .unwrap_or_else(|| parts.uri.path());
+    if !API_KEY_SCOPED_APIS.iter().any(|api| path.starts_with(api)) {
+      return Err(anyhow!(
+        "Scoped api keys can only be used with the read, write and execute apis"
+      ));
+    }
+  }
+
+  if !scopes.ips.is_empty() {
+    let RequestIp(ip) =
+      RequestIp::from_request_parts(&mut parts, &())
+        .await
+        .map_err(|_| anyhow!("Failed to get request IP"))?;
+    api_key::check_api_key_ip_scope(scopes, ip)?;
+  }
+
+  Ok(axum::extract::Request::from_parts(parts, body))
+}
+
+/// Only the UserGroups named with `prefix` are managed by the sync.
+/// Adds the user to the UserGroups named `{prefix}{group}`,
+/// and removes them from the other managed UserGroups.
+/// UserGroups without the prefix are left alone.
+async fn sync_user_groups(
+  user_id: &str,
+  prefix: &str,
+  groups: &[String],
+) -> anyhow::Result<()> {
+  if prefix.is_empty() {
+    return Err(anyhow!(
+      "Group sync requires a non-empty UserGroup prefix"
+    ));
+  }
+  let names = groups
+    .iter()
+    .map(|group| format!("{prefix}{group}"))
+    .collect::<Vec<_>>();
+  let managed =
+    doc! { "$regex": format!("^{}", regex::escape(prefix)) };
+  let user_groups = &db_client().user_groups;
+  user_groups
+    .update_many(
+      doc! { "name": { "$in": &names } },
+      doc! { "$addToSet": { "users": user_id } },
+    )
+    .await
+    .context("Failed to add user to UserGroups")?;
+  user_groups
+    .update_many(
+      doc! {
+        "$and": [
+          { "name": managed },
+          { "name": { "$nin": &names } },
+        ],
+        "users": user_id,
+      },
+      doc! { "$pull": { "users": user_id } },
+    )
+    .await
+    .context("Failed to remove user from UserGroups")?;
+  Ok(())
+}
+
+fn oidc_config() -> &'static OidcConfig {
+  static OIDC_CONFIG: LazyLock<OidcConfig> = LazyLock::new(|| {
+    let config = core_config();
+    OidcConfig {
+      enabled: config.oidc_enabled,
+      provider: config.oidc_provider.clone(),
+      redirect_host: config.oidc_redirect_host.clone(),
+      client_id: config.oidc_client_id.clone(),
+      client_secret: config.oidc_client_secret.clone(),
+      use_full_email: config.oidc_use_full_email,
+      additional_audiences: config.oidc_additional_audiences.clone(),
+    }
+  });
+  &OIDC_CONFIG
+}
+
 pub struct KomodoAuthImpl;
 
 impl AuthImpl for KomodoAuthImpl {
@@ -181,6 +282,11 @@ impl AuthImpl for KomodoAuthImpl {
       )
       .await
       .status_code(StatusCode::UNAUTHORIZED)?;
+      if let Some(scopes) = &user.api_key_scopes {
+        req = check_api_key_scopes(scopes, req)
+          .await
+          .status_code(StatusCode::FORBIDDEN)?;
+      }
       // Sanitize the user for safety before
       // attaching to the request handlers.
       user.sanitize();
@@ -336,21 +442,7 @@ impl AuthImpl for KomodoAuthImpl {
   // =============
 
   fn oidc_config(&self) -> Option<&OidcConfig> {
-    static OIDC_CONFIG: LazyLock<OidcConfig> = LazyLock::new(|| {
-      let config = core_config();
-      OidcConfig {
-        enabled: config.oidc_enabled,
-        provider: config.oidc_provider.clone(),
-        redirect_host: config.oidc_redirect_host.clone(),
-        client_id: config.oidc_client_id.clone(),
-        client_secret: config.oidc_client_secret.clone(),
-        use_full_email: config.oidc_use_full_email,
-        additional_audiences: config
-          .oidc_additional_audiences
-          .clone(),
-      }
-    });
-    Some(&OIDC_CONFIG)
+    Some(oidc_config())
   }
 
   fn find_user_with_oidc_subject(
@@ -0,0 +1,465 @@
+//! OIDC login owned by Komodo Core, used when `oidc_group_sync`
+//! is enabled. The `/auth` OIDC login doesn't expose the ID token
+//! claims, which are needed to sync the user's UserGroups.
+
+use std::sync::Arc;
+
+use anyhow::{Context as _, anyhow};
+use arc_swap::ArcSwapOption;
+use async_timing_util::unix_timestamp_ms;
+use axum::{
+  Extension, Router,
+  extract::{Query, Request},
+  middleware::Next,
+  response::{IntoResponse as _, Redirect, Response},
+  routing::{get, post},
+};
+use data_encoding::BASE64URL_NOPAD;
+use database::{
+  bson::{Document, doc},
+  mungos::by_id::update_one_by_id,
+};
+use komodo_client::{
+  api::oidc::{OidcLoginOptions, OidcLoginResponse},
+  entities::{
+    audit::{AuditEvent, AuditOperation},
+    komodo_timestamp,
+    user::{NewUserParams, User, UserConfig},
+  },
+};
+use mogh_auth_server::{
+  api::StandardCallbackQuery,
+  provider::oidc::{
+    OidcProvider, SessionOidcLogin, SubjectIdentifier, TokenResponse,
+  },
+  rand::random_string,
+  request_ip::RequestIp,
+};
+use mogh_error::{
+  AddStatusCode, AddStatusCodeError, Json, StatusCode,
+};
+use mogh_rate_limit::WithFailureRateLimit;
+use openidconnect::{
+  CsrfToken, Nonce, PkceCodeChallenge, TokenResponse as _,
+};
+use serde_json::Value;
+use tower_sessions::Session;
+
+use crate::{
+  config::core_config,
+  helpers::{audit::record_audit_event, query::find_oidc_user},
+  state::db_client,
+};
+
+use super::{
+  GENERAL_RATE_LIMITER, JWT_PROVIDER, oidc_config, sync_user_groups,
+};
+
+const SESSION_OIDC_LOGIN: &str = "komodo-oidc-login";
+const SESSION_OIDC_USER_ID: &str = "komodo-oidc-user-id";
+
+/// Cache discovery data for 1min, same as the `/auth` OIDC login.
+const PROVIDER_VALID_FOR_MS: u128 = 60_000;
+
+struct CachedOidcProvider {
+  provider: OidcProvider,
+  valid_until: u128,
+}
+
+static OIDC_PROVIDER: ArcSwapOption<CachedOidcProvider> =
+  ArcSwapOption::const_empty();
+
+pub fn router() -> Router {
+  Router::new()
+    .route(
+      "/options",
+      get(|| async {
+        Json(OidcLoginOptions {
+          enabled: core_config().oidc_group_sync_enabled(),
+        })
+      }),
+    )
+    .route("/login", get(login_handler))
+    .route("/callback", get(callback_handler))
+    .route("/redeem", post(redeem_handler))
+}
+
+/// Stops the `/auth` OIDC login from skipping the group sync.
+/// Linking an OIDC login to an existing user still uses `/auth`.
+pub async fn guard_auth_oidc_login(
+  req: Request,
+  next: Next,
+) -> Response {
+  if core_config().oidc_group_sync_enabled()
+    && req.uri().path() == "/oidc/login"
+  {
+    return anyhow!(
+      "OIDC group sync is enabled, login with /oidc/login instead"
+    )
+    .status_code(StatusCode::BAD_REQUEST)
+    .into_response();
+  }
+  next.run(req).await
+}
+
+/// The redirect uri is `{host}/oidc/callback`,
+/// separate from the `/auth` OIDC login.
+async fn oidc_provider() -> anyhow::Result<Arc<CachedOidcProvider>> {
+  let now = unix_timestamp_ms();
+  if let Some(cached) = OIDC_PROVIDER.load_full()
+    && cached.valid_until > now
+  {
+    return Ok(cached);
+  }
+  let valid_until = now + PROVIDER_VALID_FOR_MS;
+  let provider = OidcProvider::new(
+    "Komodo",
+    &core_config().host,
+    "",
+    oidc_config(),
+    valid_until,
+  )
+  .await
+  .context("Failed to initialize OIDC provider")?;
+  let cached = Arc::new(CachedOidcProvider {
+    provider,
+    valid_until,
+  });
+  OIDC_PROVIDER.store(Some(cached.clone()));
+  Ok(cached)
+}
+
+async fn login_handler(
+  RequestIp(ip): RequestIp,
+  Extension(session): Extension<Session>,
+) -> mogh_error::Result<Redirect> {
+  async {
+    if !core_config().oidc_group_sync_enabled() {
+      return Err(
+        anyhow!("OIDC group sync is not enabled")
+          .status_code(StatusCode::BAD_REQUEST),
+      );
+    }
+
+    let cached = oidc_provider().await?;
+
+    let (pkce_challenge, pkce_verifier) =
+      PkceCodeChallenge::new_random_sha256();
+    let (auth_url, csrf_token, nonce) =
+      cached.provider.authorize_url(pkce_challenge);
+
+    // Matched on callback for csrf protection.
+    session
+      .insert(
+        SESSION_OIDC_LOGIN,
+        SessionOidcLogin {
+          csrf_token: csrf_token.secret().clone(),
+          pkce_verifier,
+          nonce,
+          redirect: None,
+        },
+      )
+      .await
+      .context("Failed to serialize session data")?;
+
+    auth_redirect(auth_url.as_str()).map_err(Into::into)
+  }
+  .with_failure_rate_limit_using_ip(&GENERAL_RATE_LIMITER, &ip)
+  .await
+}
+
+/// Applies `oidc_redirect_host`
+fn auth_redirect(auth_url: &str) -> anyhow::Result<Redirect> {
+  let redirect_host = &core_config().oidc_redirect_host;
+  if redirect_host.is_empty() {
+    return Ok(Redirect::to(auth_url));
+  }
+  let (protocol, rest) = auth_url
+    .split_once("://")
+    .context("Invalid URL: Missing protocol (eg 'https://')")?;
+  let host = rest
+    .split_once(['/', '?'])
+    .map(|(host, _)| host)
+    .unwrap_or(rest);
+  Ok(Redirect::to(
+    &auth_url.replace(&format!("{protocol}://{host}"), redirect_host),
+  ))
+}
+
+async fn callback_handler(
+  RequestIp(ip): RequestIp,
+  Extension(session): Extension<Session>,
+  Query(query): Query<StandardCallbackQuery>,
+) -> mogh_error::Result<Redirect> {
+  let res = callback(&session, query)
+    .with_failure_rate_limit_using_ip(&GENERAL_RATE_LIMITER, &ip)
+    .await
+    .inspect_err(|e| {
+      warn!(
+        source_ip = ip.to_string(),
+        "OIDC login failed | ERROR: {:#}", e.error
+      )
+    });
+  record_audit_event(AuditEvent {
+    operation: AuditOperation::Login,
+    request: String::from("OidcLogin"),
+    success: res.is_ok(),
+    user_id: res
+      .as_ref()
+      .map(|user| user.id.clone())
+      .unwrap_or_default(),
+    username: res
+      .as_ref()
+      .map(|user| user.username.clone())
+      .unwrap_or_default(),
+    ip: ip.to_string(),
+    details: res
+      .as_ref()
+      .err()
+      .map(|e| format!("{:#}", e.error))
+      .unwrap_or_default(),
+    ..Default::default()
+  });
+  res?;
+  Ok(Redirect::to(&format!(
+    "{}/?oidc_redeem_ready=true",
+    core_config().host
+  )))
+}
+
+async fn callback(
+  session: &Session,
+  query: StandardCallbackQuery,
+) -> mogh_error::Result<User> {
+  let config = core_config();
+
+  if !config.oidc_group_sync_enabled() {
+    return Err(
+      anyhow!("OIDC group sync is not enabled")
+        .status_code(StatusCode::UNAUTHORIZED),
+    );
+  }
+
+  let (state, code) = query.open()?;
+
+  let SessionOidcLogin {
+    csrf_token,
+    pkce_verifier,
+    nonce,
+    ..
+  } = session
+    .remove(SESSION_OIDC_LOGIN)
+    .await
+    .context("Internal session type error")?
+    .context("OIDC login has not been initiated for this session")
+    .status_code(StatusCode::UNAUTHORIZED)?;
+
+  let cached = oidc_provider().await?;
+
+  let (subject, token) = cached
+    .provider
+    .validate_extract_subject_and_token(
+      oidc_config(),
+      (CsrfToken::new(state), csrf_token),
+      code,
+      pkce_verifier,
+      &nonce,
+    )
+    .await
+    .status_code(StatusCode::UNAUTHORIZED)?;
+
+  let groups = groups_claim(&token)?;
+
+  let user = match find_oidc_user(&subject).await? {
+    Some(user) => user,
+    None => {
+      create_user(&cached.provider, &subject, &token, &nonce).await?
+    }
+  };
+
+  // Rejected before the sync, so a refused login
+  // doesn't change the user's permissions.
+  if !user.external_skip_2fa
+    && (user.totp.enrolled() || user.passkey.passkey.is_some())
+  {
+    return Err(
+      anyhow!(
+        "User has 2FA enabled, which is not supported with OIDC group sync"
+      )
+      .status_code(StatusCode::UNAUTHORIZED),
+    );
+  }
+
+  sync_user_groups(&user.id, &config.oidc_group_prefix, &groups)
+    .await?;
+  sync_admin(&user, &groups).await?;
+
+  session
+    .insert(SESSION_OIDC_USER_ID, &user.id)
+    .await
+    .context("Failed to serialize session data")?;
+
+  Ok(user)
+}
+
+/// Reads `oidc_groups_claim` from the ID token.
+/// The token is already verified by `validate_extract_subject_and_token`.
+fn groups_claim(
+  token: &TokenResponse,
+) -> anyhow::Result<Vec<String>> {
+  let claim = &core_config().oidc_groups_claim;
+  let id_token = token
+    .id_token()
+    .context("OIDC Server did not return an ID token")?
+    .to_string();
+  let payload = id_token
+    .split('.')
+    .nth(1)
+    .context("ID token is not a valid JWT")?;
+  let payload = BASE64URL_NOPAD
+    .decode(payload.as_bytes())
+    .context("ID token payload is not valid base64url")?;
+  let mut claims: serde_json::Map<String, Value> =
+    serde_json::from_slice(&payload)
+      .context("ID token payload is not a JSON object")?;
+  let groups = match claims.remove(claim) {
+    None | Some(Value::Null) => Vec::new(),
+    Some(Value::String(group)) => vec![group],
+    Some(Value::Array(groups)) => groups
+      .into_iter()
+      .map(|group| match group {
+        Value::String(group) => Ok(group),
+        _ => {
+          Err(anyhow!("The '{claim}' claim contains a non string"))
+        }
+      })
+      .collect::<anyhow::Result<_>>()?,
+    Some(_) => {
+      return Err(anyhow!("The '{claim}' claim is not a list"));
+    }
+  };
+  Ok(groups)
+}
+
+async fn create_user(
+  provider: &OidcProvider,
+  subject: &SubjectIdentifier,
+  token: &TokenResponse,
+  nonce: &Nonce,
+) -> mogh_error::Result<User> {
+  let config = core_config();
+
+  let no_users_exist = db_client()
+    .users
+    .find_one(Document::new())
+    .await
+    .context("Failed to query database for users")?
+    .is_none();
+
+  if !no_users_exist && config.disable_user_registration {
+    return Err(
+      anyhow!("User registration is disabled")
+        .status_code(StatusCode::UNAUTHORIZED),
+    );
+  }
+
+  let mut username =
+    provider.get_username(subject, token, nonce).await;
+
+  // Modify username if it already exists
+  if db_client()
+    .users
+    .find_one(doc! { "username": &username })
+    .await
+    .context("Failed to query database for users")?
+    .is_some()
+  {
+    username += "-";
+    username += &random_string(5);
+  }
+
+  let mut user = User::new(NewUserParams {
+    username,
+    enabled: no_users_exist || config.enable_new_users,
+    admin: no_users_exist,
+    super_admin: no_users_exist,
+    config: UserConfig::Oidc {
+      provider: config.oidc_provider.clone(),
+      user_id: subject.to_string(),
+    },
+    updated_at: komodo_timestamp(),
+  });
+
+  user.id = db_client()
+    .users
+    .insert_one(&user)
+    .await
+    .context("Failed to create user on database")?
+    .inserted_id
+    .as_object_id()
+    .context("inserted_id is not ObjectId")?
+    .to_string();
+
+  info!(
+    user_id = user.id,
+    username = user.username,
+    "New user registration (OIDC)"
+  );
+
+  Ok(user)
+}
+
+/// Makes the user admin if they are in `oidc_admin_group`,
+/// and removes admin otherwise. Super admins are left alone.
+async fn sync_admin(
+  user: &User,
+  groups: &[String],
+) -> anyhow::Result<()> {
+  let admin_group = &core_config().oidc_admin_group;
+  if admin_group.is_empty() || user.super_admin {
+    return Ok(());
+  }
+  let admin = groups.contains(admin_group);
+  if admin == user.admin {
+    return Ok(());
+  }
+  update_one_by_id(
+    &db_client().users,
+    &user.id,
+    doc! { "$set": { "admin": admin } },
+    None,
+  )
+  .await
+  .context("Failed to update user admin on database")?;
+  info!(
+    user_id = user.id,
+    username = user.username,
+    admin,
+    "Updated admin from OIDC '{admin_group}' group"
+  );
+  Ok(())
+}
+
+async fn redeem_handler(
+  RequestIp(ip): RequestIp,
+  Extension(session): Extension<Session>,
+) -> mogh_error::Result<Json<OidcLoginResponse>> {
+  async {
+    let user_id: String = session
+      .remove(SESSION_OIDC_USER_ID)
+      .await
+      .context("Internal session type error")?
+      .context(
+        "OIDC login must be completed before JWT can be retrieved",
+      )
+      .status_code(StatusCode::UNAUTHORIZED)?;
+    let jwt = JWT_PROVIDER
+      .encode_sub(&user_id)
+      .context("Failed to generate JWT")?
+      .jwt;
+    Ok(OidcLoginResponse { jwt })
+  }
+  .with_failure_rate_limit_using_ip(&GENERAL_RATE_LIMITER, &ip)
+  .await
+  .map(Json)
+}
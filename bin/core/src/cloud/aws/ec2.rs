@@ -192,6 +192,7 @@ pub async fn terminate_ec2_instance_with_retry(
               message: format!("{e:#}"),
             },
             resolved_ts: None,
+            escalation: Default::default(),
           };
           send_alerts(&[alert]).await;
           return Err(e);
@@ -172,6 +172,11 @@ pub fn core_config() -> &'static CoreConfig {
         env.komodo_webhook_secret,
       )
       .unwrap_or(config.webhook_secret),
+      metrics_token: maybe_read_item_from_file(
+        env.komodo_metrics_token_file,
+        env.komodo_metrics_token,
+      )
+      .unwrap_or(config.metrics_token),
       database: DatabaseConfig {
         uri: maybe_read_item_from_file(
           env.komodo_database_uri_file,
@@ -235,6 +240,63 @@ pub fn core_config() -> &'static CoreConfig {
         env.komodo_oidc_additional_audiences,
       )
       .unwrap_or(config.oidc_additional_audiences),
+      oidc_group_sync: env
+        .komodo_oidc_group_sync
+        .unwrap_or(config.oidc_group_sync),
+      oidc_groups_claim: env
+        .komodo_oidc_groups_claim
+        .unwrap_or(config.oidc_groups_claim),
+      oidc_group_prefix: env
+        .komodo_oidc_group_prefix
+        .unwrap_or(config.oidc_group_prefix),
+      oidc_admin_group: env
+        .komodo_oidc_admin_group
+        .unwrap_or(config.oidc_admin_group),
+      ldap_enabled: env
+        .komodo_ldap_enabled
+        .unwrap_or(config.ldap_enabled),
+      ldap_url: env.komodo_ldap_url.unwrap_or(config.ldap_url),
+      ldap_start_tls: env
+        .komodo_ldap_start_tls
+        .unwrap_or(config.ldap_start_tls),
+      ldap_tls_insecure: env
+        .komodo_ldap_tls_insecure
+        .unwrap_or(config.ldap_tls_insecure),
+      ldap_bind_dn: env
+        .komodo_ldap_bind_dn
+        .unwrap_or(config.ldap_bind_dn),
+      ldap_bind_password: maybe_read_item_from_file(
+        env.komodo_ldap_bind_password_file,
+        env.komodo_ldap_bind_password,
+      )
+      .unwrap_or(config.ldap_bind_password),
+      ldap_user_base_dn: env
+        .komodo_ldap_user_base_dn
+        .unwrap_or(config.ldap_user_base_dn),
+      ldap_user_filter: env
+        .komodo_ldap_user_filter
+        .unwrap_or(config.ldap_user_filter),
+      ldap_username_attribute: env
+        .komodo_ldap_username_attribute
+        .unwrap_or(config.ldap_username_attribute),
+      ldap_email_attribute: env
+        .komodo_ldap_email_attribute
+        .unwrap_or(config.ldap_email_attribute),
+      ldap_group_sync: env
+        .komodo_ldap_group_sync
+        .unwrap_or(config.ldap_group_sync),
+      ldap_group_base_dn: env
+        .komodo_ldap_group_base_dn
+        .unwrap_or(config.ldap_group_base_dn),
+      ldap_group_filter: env
+        .komodo_ldap_group_filter
+        .unwrap_or(config.ldap_group_filter),
+      ldap_group_name_attribute: env
+        .komodo_ldap_group_name_attribute
+        .unwrap_or(config.ldap_group_name_attribute),
+      ldap_group_prefix: env
+        .komodo_ldap_group_prefix
+        .unwrap_or(config.ldap_group_prefix),
       google_oauth: NamedOauthConfig {
         enabled: env
           .komodo_google_oauth_enabled
@@ -324,9 +386,39 @@ pub fn core_config() -> &'static CoreConfig {
       keep_alerts_for_days: env
         .komodo_keep_alerts_for_days
         .unwrap_or(config.keep_alerts_for_days),
+      keep_archived_logs_for_days: env
+        .komodo_keep_archived_logs_for_days
+        .unwrap_or(config.keep_archived_logs_for_days),
+      keep_audit_events_for_days: env
+        .komodo_keep_audit_events_for_days
+        .unwrap_or(config.keep_audit_events_for_days),
       webhook_base_url: env
         .komodo_webhook_base_url
         .unwrap_or(config.webhook_base_url),
+      webhook_generic_signature_header: env
+        .komodo_webhook_generic_signature_header
+        .unwrap_or(config.webhook_generic_signature_header),
+      webhook_generic_signature_prefix: env
+        .komodo_webhook_generic_signature_prefix
+        .unwrap_or(config.webhook_generic_signature_prefix),
+      webhook_generic_algorithm: env
+        .komodo_webhook_generic_algorithm
+        .unwrap_or(config.webhook_generic_algorithm),
+      webhook_generic_branch_path: env
+        .komodo_webhook_generic_branch_path
+        .unwrap_or(config.webhook_generic_branch_path),
+      metrics_enabled: env
+        .komodo_metrics_enabled
+        .unwrap_or(config.metrics_enabled),
+      audit_export_file: env
+        .komodo_audit_export_file
+        .unwrap_or(config.audit_export_file),
+      audit_export_syslog: env
+        .komodo_audit_export_syslog
+        .unwrap_or(config.audit_export_syslog),
+      audit_export_url: env
+        .komodo_audit_export_url
+        .unwrap_or(config.audit_export_url),
       transparent_mode: env
         .komodo_transparent_mode
         .unwrap_or(config.transparent_mode),
@@ -419,10 +511,47 @@ pub fn core_config() -> &'static CoreConfig {
       action_directory: env
         .komodo_action_directory
         .unwrap_or(config.action_directory),
+      backups_folder: env
+        .komodo_cli_backups_folder
+        .unwrap_or(config.backups_folder),
+
+      encryption_key: maybe_read_item_from_file(
+        env.komodo_encryption_key_file,
+        env.komodo_encryption_key,
+      )
+      .unwrap_or(config.encryption_key),
+      previous_encryption_keys: maybe_read_list_from_file(
+        env.komodo_previous_encryption_keys_file,
+        env.komodo_previous_encryption_keys,
+      )
+      .unwrap_or(config.previous_encryption_keys),
+      vault_address: env
+        .komodo_vault_address
+        .unwrap_or(config.vault_address),
+      vault_token: maybe_read_item_from_file(
+        env.komodo_vault_token_file,
+        env.komodo_vault_token,
+      )
+      .unwrap_or(config.vault_token),
+      vault_namespace: env
+        .komodo_vault_namespace
+        .unwrap_or(config.vault_namespace),
+      secret_files_directory: env
+        .komodo_secret_files_directory
+        .unwrap_or(config.secret_files_directory),
+      sops_directory: env
+        .komodo_sops_directory
+        .unwrap_or(config.sops_directory),
 
       // These can't be overridden on env
       secrets: config.secrets,
       git_providers: config.git_providers,
+      git_ssh_known_hosts: env
+        .komodo_git_ssh_known_hosts
+        .or(config.git_ssh_known_hosts),
+      git_ssh_accept_new_host_keys: env
+        .komodo_git_ssh_accept_new_host_keys
+        .unwrap_or(config.git_ssh_accept_new_host_keys),
       docker_registries: config.docker_registries,
     }
   })
@@ -0,0 +1,410 @@
+use std::{net::IpAddr, sync::OnceLock};
+
+use anyhow::{Context, anyhow};
+use axum::{
+  body::{Body, to_bytes},
+  extract::Request,
+  http::{Method, header::AUTHORIZATION},
+  middleware::Next,
+  response::Response,
+};
+use database::bson::doc;
+use komodo_client::entities::{
+  ResourceTarget,
+  action::Action,
+  alerter::Alerter,
+  audit::{AuditEvent, AuditOperation},
+  build::Build,
+  builder::Builder,
+  deployment::Deployment,
+  komodo_timestamp,
+  procedure::Procedure,
+  repo::Repo,
+  server::Server,
+  stack::Stack,
+  swarm::Swarm,
+  sync::ResourceSync,
+  terminal::TerminalTarget,
+  user::User,
+};
+use mogh_auth_server::request_ip::RequestIp;
+use tokio::{io::AsyncWriteExt, net::UdpSocket, sync::Mutex};
+
+use crate::{
+  auth::JWT_PROVIDER,
+  config::core_config,
+  helpers::query::get_user,
+  resource::{self, KomodoResource},
+  state::db_client,
+};
+
+/// Auth requests and responses are small,
+/// larger bodies are passed through without auditing.
+const MAX_AUTH_BODY_SIZE: usize = 1024 * 1024;
+
+/// Starts an audit event for a request made by a user.
+/// It is recorded as failed until `success` is set.
+pub fn audit_event(
+  user: &User,
+  ip: IpAddr,
+  operation: AuditOperation,
+  request: impl Into<String>,
+) -> AuditEvent {
+  AuditEvent {
+    operation,
+    request: request.into(),
+    user_id: user.id.clone(),
+    username: user.username.clone(),
+    ip: ip.to_string(),
+    ..Default::default()
+  }
+}
+
+/// Stores the event on the database and sends it
+/// to the configured export sinks.
+///
+/// Recording happens in the background and failures are only logged,
+/// they never fail the request.
+pub fn record_audit_event(mut event: AuditEvent) {
+  if event.ts == 0 {
+    event.ts = komodo_timestamp();
+  }
+  tokio::spawn(async move {
+    if let Some(target) = &mut event.target
+      && event.target_name.is_empty()
+    {
+      // Deleted resources keep the target as given in the request.
+      if let Ok(name) = resolve_target(target).await {
+        event.target_name = name;
+      }
+    }
+    if let Err(e) = db_client().audit_events.insert_one(&event).await
+    {
+      warn!(
+        "Failed to store {} audit event for {} | {e:#}",
+        event.operation, event.username
+      );
+    }
+    export_audit_event(&event).await;
+  });
+}
+
+/// Maps a terminal target to the resource it belongs to.
+pub fn terminal_audit_target(
+  target: &TerminalTarget,
+) -> Option<ResourceTarget> {
+  match target {
+    TerminalTarget::Server { server } => {
+      server.clone().map(ResourceTarget::Server)
+    }
+    TerminalTarget::Container { server, .. } => {
+      Some(ResourceTarget::Server(server.clone()))
+    }
+    TerminalTarget::Stack { stack, .. } => {
+      Some(ResourceTarget::Stack(stack.clone()))
+    }
+    TerminalTarget::Deployment { deployment } => {
+      Some(ResourceTarget::Deployment(deployment.clone()))
+    }
+  }
+}
+
+/// Replaces the target id or name with the resource id,
+/// and returns the resource name.
+async fn resolve_target(
+  target: &mut ResourceTarget,
+) -> anyhow::Result<String> {
+  match target {
+    ResourceTarget::System(_) => Ok(String::new()),
+    ResourceTarget::Swarm(id) => resolve::<Swarm>(id).await,
+    ResourceTarget::Server(id) => resolve::<Server>(id).await,
+    ResourceTarget::Stack(id) => resolve::<Stack>(id).await,
+    ResourceTarget::Deployment(id) => resolve::<Deployment>(id).await,
+    ResourceTarget::Build(id) => resolve::<Build>(id).await,
+    ResourceTarget::Repo(id) => resolve::<Repo>(id).await,
+    ResourceTarget::Procedure(id) => resolve::<Procedure>(id).await,
+    ResourceTarget::Action(id) => resolve::<Action>(id).await,
+    ResourceTarget::Builder(id) => resolve::<Builder>(id).await,
+    ResourceTarget::Alerter(id) => resolve::<Alerter>(id).await,
+    ResourceTarget::ResourceSync(id) => {
+      resolve::<ResourceSync>(id).await
+    }
+  }
+}
+
+async fn resolve<T: KomodoResource>(
+  id_or_name: &mut String,
+) -> anyhow::Result<String> {
+  let resource = resource::get::<T>(id_or_name).await?;
+  *id_or_name = resource.id;
+  Ok(resource.name)
+}
+
+// ==========
+//   EXPORT
+// ==========
+
+async fn export_audit_event(event: &AuditEvent) {
+  let config = core_config();
+  if config.audit_export_file.is_empty()
+    && config.audit_export_syslog.is_empty()
+    && config.audit_export_url.is_empty()
+  {
+    return;
+  }
+  let json = match serde_json::to_string(event) {
+    Ok(json) => json,
+    Err(e) => {
+      warn!("Failed to serialize audit event for export | {e:#}");
+      return;
+    }
+  };
+  if !config.audit_export_file.is_empty()
+    && let Err(e) =
+      export_to_file(&config.audit_export_file, &json).await
+  {
+    warn!(
+      "Failed to export audit event to file {} | {e:#}",
+      config.audit_export_file
+    );
+  }
+  if !config.audit_export_syslog.is_empty()
+    && let Err(e) =
+      export_to_syslog(&config.audit_export_syslog, event, &json)
+        .await
+  {
+    warn!(
+      "Failed to export audit event to syslog {} | {e:#}",
+      config.audit_export_syslog
+    );
+  }
+  if !config.audit_export_url.is_empty()
+    && let Err(e) =
+      export_to_url(&config.audit_export_url, &json).await
+  {
+    warn!("Failed to export audit event to url | {e:#}");
+  }
+}
+
+/// Appends the event as a single JSON line.
+async fn export_to_file(
+  path: &str,
+  json: &str,
+) -> anyhow::Result<()> {
+  // Serialize writes so concurrent events never interleave lines.
+  static LOCK: Mutex<()> = Mutex::const_new(());
+  let _lock = LOCK.lock().await;
+  let mut file = tokio::fs::OpenOptions::new()
+    .create(true)
+    .append(true)
+    .open(path)
+    .await
+    .context("Failed to open audit export file")?;
+  file
+    .write_all(format!("{json}\n").as_bytes())
+    .await
+    .context("Failed to write to audit export file")
+}
+
+/// Sends the event as an RFC 5424 syslog message over UDP,
+/// with the JSON event as the message body.
+async fn export_to_syslog(
+  address: &str,
+  event: &AuditEvent,
+  json: &str,
+) -> anyhow::Result<()> {
+  // Facility 'authpriv' (10), severity 'info' (6) or 'warning' (4).
+  let severity = if event.success { 6 } else { 4 };
+  let pri = 10 * 8 + severity;
+  let ts = chrono::DateTime::from_timestamp_millis(event.ts)
+    .unwrap_or_else(chrono::Utc::now)
+    .to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+  let message = format!("<{pri}>1 {ts} - komodo - - - {json}");
+  let address = tokio::net::lookup_host(address)
+    .await
+    .context("Failed to resolve syslog address")?
+    .next()
+    .context("Syslog address did not resolve")?;
+  let bind = if address.is_ipv6() {
+    "[::]:0"
+  } else {
+    "0.0.0.0:0"
+  };
+  let socket = UdpSocket::bind(bind)
+    .await
+    .context("Failed to bind udp socket")?;
+  socket
+    .send_to(message.as_bytes(), address)
+    .await
+    .context("Failed to send syslog message")?;
+  Ok(())
+}
+
+async fn export_to_url(url: &str, json: &str) -> anyhow::Result<()> {
+  let res = http_client()
+    .post(url)
+    .header("Content-Type", "application/json")
+    .body(json.to_string())
+    .send()
+    .await
+    .context("Failed to send audit event")?;
+  let status = res.status();
+  if status.is_success() {
+    Ok(())
+  } else {
+    let text = res.text().await.unwrap_or_default();
+    Err(anyhow!("{status} | {text}"))
+  }
+}
+
+fn http_client() -> &'static reqwest::Client {
+  static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+  CLIENT.get_or_init(reqwest::Client::new)
+}
+
+// ================
+//   AUTH REQUESTS
+// ================
+
+/// Records logins and login management requests to the `/auth` api.
+pub async fn audit_auth_request(
+  RequestIp(ip): RequestIp,
+  req: Request,
+  next: Next,
+) -> Response {
+  let path = req.uri().path().to_string();
+  let Some((operation, request)) =
+    auth_request_operation(req.method(), &path)
+  else {
+    return next.run(req).await;
+  };
+
+  let mut event = AuditEvent {
+    operation,
+    request,
+    ip: ip.to_string(),
+    ..Default::default()
+  };
+
+  // The user making a login management request.
+  let headers = req.headers();
+  if let Some(jwt) =
+    headers.get(AUTHORIZATION).and_then(|jwt| jwt.to_str().ok())
+  {
+    let jwt = jwt.trim_start_matches("Bearer ").trim();
+    if let Ok(user_id) = JWT_PROVIDER.decode_sub(jwt) {
+      event.user_id = user_id;
+    }
+  } else if let Some(key) =
+    headers.get("x-api-key").and_then(|key| key.to_str().ok())
+    && let Ok(Some(api_key)) =
+      db_client().api_keys.find_one(doc! { "key": key }).await
+  {
+    event.user_id = api_key.user_id;
+  }
+
+  // The username attempted on login.
+  let (parts, body) = req.into_parts();
+  let Ok(body) = to_bytes(body, MAX_AUTH_BODY_SIZE).await else {
+    return Response::builder()
+      .status(axum::http::StatusCode::PAYLOAD_TOO_LARGE)
+      .body(Body::empty())
+      .unwrap_or_default();
+  };
+  if let Ok(serde_json::Value::Object(params)) =
+    serde_json::from_slice::<serde_json::Value>(&body)
+    && let Some(serde_json::Value::String(username)) =
+      params.get("username")
+  {
+    event.username = username.clone();
+  }
+  let req = Request::from_parts(parts, Body::from(body));
+
+  let res = next.run(req).await;
+  let status = res.status();
+  event.success = status.is_success() || status.is_redirection();
+
+  // The user logged in with the returned jwt.
+  let res = if event.operation == AuditOperation::Login
+    && event.success
+    && event.user_id.is_empty()
+  {
+    let (parts, body) = res.into_parts();
+    let body =
+      to_bytes(body, MAX_AUTH_BODY_SIZE).await.unwrap_or_default();
+    if let Ok(serde_json::Value::Object(res)) =
+      serde_json::from_slice::<serde_json::Value>(&body)
+      && let Some(serde_json::Value::String(jwt)) = res.get("jwt")
+      && let Ok(user_id) = JWT_PROVIDER.decode_sub(jwt)
+    {
+      event.user_id = user_id;
+    }
+    Response::from_parts(parts, Body::from(body))
+  } else {
+    res
+  };
+
+  if !event.success {
+    event.details = format!("Status: {status}");
+  }
+
+  tokio::spawn(async move {
+    fill_audit_user(&mut event).await;
+    record_audit_event(event);
+  });
+
+  res
+}
+
+/// Maps the `/auth` api path to the audit operation.
+/// Returns None for requests which are not audited,
+/// such as the login options.
+fn auth_request_operation(
+  method: &Method,
+  path: &str,
+) -> Option<(AuditOperation, String)> {
+  let mut segments = path.trim_matches('/').rsplit('/');
+  let request = segments.next()?;
+  // OAuth providers redirect back to '/{provider}/callback'
+  if request == "callback" {
+    let provider = segments.next().unwrap_or_default();
+    return Some((
+      AuditOperation::Login,
+      format!("{provider}/callback"),
+    ));
+  }
+  if method == Method::GET {
+    return None;
+  }
+  let operation = match request {
+    "CreateApiKey" => AuditOperation::CreateApiKey,
+    "DeleteApiKey" => AuditOperation::DeleteApiKey,
+    request if request.starts_with("SignUp") => {
+      AuditOperation::ManageUser
+    }
+    request
+      if request.contains("Login") || request.contains("Jwt") =>
+    {
+      AuditOperation::Login
+    }
+    _ if path.contains("/manage/") => AuditOperation::ManageLogin,
+    _ => return None,
+  };
+  Some((operation, request.to_string()))
+}
+
+/// Fills whichever of user id / username is missing.
+async fn fill_audit_user(event: &mut AuditEvent) {
+  let user = if event.user_id.is_empty() {
+    &event.username
+  } else {
+    &event.user_id
+  };
+  if user.is_empty() {
+    return;
+  }
+  if let Ok(user) = get_user(user).await {
+    event.user_id = user.id;
+    event.username = user.username;
+  }
+}
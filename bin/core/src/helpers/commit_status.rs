@@ -0,0 +1,597 @@
+use std::sync::OnceLock;
+
+use anyhow::{Context, anyhow};
+use komodo_client::entities::{RepoExecutionArgs, update::Update};
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::mpsc;
+
+use crate::config::core_config;
+
+use super::git_token;
+
+#[derive(Debug, Clone, Copy)]
+pub enum CommitState {
+  Pending,
+  Success,
+  Failure,
+}
+
+impl CommitState {
+  pub fn from_success(success: bool) -> CommitState {
+    if success {
+      CommitState::Success
+    } else {
+      CommitState::Failure
+    }
+  }
+}
+
+/// The commit status api flavor, inferred from the provider domain.
+enum ProviderApi {
+  Github,
+  Gitlab,
+  /// Gitea / Forgejo
+  Gitea,
+}
+
+impl ProviderApi {
+  fn from_domain(domain: &str) -> ProviderApi {
+    if domain.contains("gitlab") {
+      ProviderApi::Gitlab
+    } else if domain.contains("github") {
+      ProviderApi::Github
+    } else {
+      ProviderApi::Gitea
+    }
+  }
+
+  fn base_url(&self, args: &RepoExecutionArgs) -> String {
+    // Over ssh, the api is still reached over https.
+    let protocol = if args.https || args.ssh {
+      "https"
+    } else {
+      "http"
+    };
+    let domain = &args.provider;
+    match self {
+      ProviderApi::Github if domain == "github.com" => {
+        String::from("https://api.github.com")
+      }
+      ProviderApi::Github => format!("{protocol}://{domain}/api/v3"),
+      ProviderApi::Gitlab => format!("{protocol}://{domain}/api/v4"),
+      ProviderApi::Gitea => format!("{protocol}://{domain}/api/v1"),
+    }
+  }
+
+  /// Gitea / Forgejo have no deployments api.
+  fn supports_deployments(&self) -> bool {
+    !matches!(self, ProviderApi::Gitea)
+  }
+
+  fn auth(
+    &self,
+    req: reqwest::RequestBuilder,
+    token: &str,
+  ) -> reqwest::RequestBuilder {
+    match self {
+      ProviderApi::Github => req.bearer_auth(token),
+      ProviderApi::Gitlab => req.header("PRIVATE-TOKEN", token),
+      ProviderApi::Gitea => {
+        req.header("Authorization", format!("token {token}"))
+      }
+    }
+  }
+}
+
+/// Reports the commit states of a single run to the git provider
+/// commit status api under `context`, linking to the Update.
+/// Supports Github, Gitlab and Gitea / Forgejo.
+///
+/// If given an `environment`, also reports the run to the provider
+/// deployments api (Github Deployments / Gitlab environments).
+///
+/// States are sent in order from a single background task,
+/// so a final state can never be overwritten by an earlier pending one.
+/// Reporting failures are only logged, they never fail the run.
+pub struct CommitStatusReporter {
+  tx: mpsc::UnboundedSender<Report>,
+}
+
+struct Report {
+  /// The commit hash of the run,
+  /// or None to use the configured commit / branch.
+  commit_hash: Option<String>,
+  update_id: String,
+  state: CommitState,
+}
+
+impl CommitStatusReporter {
+  pub fn new(
+    args: RepoExecutionArgs,
+    context: Option<String>,
+    environment: Option<String>,
+  ) -> CommitStatusReporter {
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(report_commit_states(
+      args,
+      context,
+      environment,
+      rx,
+    ));
+    CommitStatusReporter { tx }
+  }
+
+  /// Queues the pending state before the commit hash of the run
+  /// is known, resolving the configured commit / branch instead.
+  pub fn report_pending(&self, update: &Update) {
+    let _ = self.tx.send(Report {
+      commit_hash: None,
+      update_id: update.id.clone(),
+      state: CommitState::Pending,
+    });
+  }
+
+  /// Queues the commit `state` for the Update commit hash.
+  /// If the commit hash isn't known, the state is only reported
+  /// when a pending state was, using the same commit.
+  pub fn report(&self, update: &Update, state: CommitState) {
+    let _ = self.tx.send(Report {
+      commit_hash: (!update.commit_hash.is_empty())
+        .then(|| update.commit_hash.clone()),
+      update_id: update.id.clone(),
+      state,
+    });
+  }
+}
+
+async fn report_commit_states(
+  args: RepoExecutionArgs,
+  context: Option<String>,
+  environment: Option<String>,
+  mut rx: mpsc::UnboundedReceiver<Report>,
+) {
+  let mut reporter = ReportState {
+    args,
+    context,
+    environment,
+    provider: None,
+    resolved: None,
+    pending: None,
+    deployments: Vec::new(),
+  };
+  while let Some(report) = rx.recv().await {
+    reporter.report(report).await;
+  }
+  // The run ended without reporting a final state,
+  // eg. it returned early with an error.
+  if let Some((_, update_id)) = reporter.pending.clone() {
+    reporter
+      .report(Report {
+        commit_hash: None,
+        update_id,
+        state: CommitState::Failure,
+      })
+      .await;
+  }
+}
+
+struct ReportState {
+  args: RepoExecutionArgs,
+  context: Option<String>,
+  environment: Option<String>,
+  provider: Option<ProviderContext>,
+  /// (git ref, full sha)
+  resolved: Option<(String, String)>,
+  /// (sha, update id) of the unfinished pending state.
+  /// If the run ends up on another commit,
+  /// the final state is reported on both.
+  pending: Option<(String, String)>,
+  /// (sha, deployment id)
+  deployments: Vec<(String, u64)>,
+}
+
+impl ReportState {
+  async fn report(&mut self, report: Report) {
+    let state = report.state;
+    if let Err(e) = self.report_inner(report).await {
+      warn!(
+        "Failed to report {state:?} commit status for {} to {} | {e:#}",
+        self.args.name, self.args.provider
+      );
+    }
+  }
+
+  async fn report_inner(
+    &mut self,
+    Report {
+      commit_hash,
+      update_id,
+      state,
+    }: Report,
+  ) -> anyhow::Result<()> {
+    let is_pending = matches!(state, CommitState::Pending);
+    if commit_hash.is_none() && !is_pending && self.pending.is_none()
+    {
+      return Ok(());
+    }
+
+    let provider = match self.provider.take() {
+      Some(provider) => provider,
+      None => ProviderContext::new(&self.args).await?,
+    };
+    let provider = self.provider.insert(provider);
+
+    let git_ref = match commit_hash {
+      Some(commit_hash) => commit_hash,
+      None => match (&self.pending, &self.args.commit) {
+        (Some((sha, _)), _) => sha.clone(),
+        (None, Some(commit)) => commit.clone(),
+        (None, None) => self.args.branch.clone(),
+      },
+    };
+    let sha = match self.resolved.take() {
+      Some((git_ref_, sha)) if git_ref_ == git_ref => sha,
+      _ => provider.full_commit_sha(&git_ref).await?,
+    };
+    self.resolved = Some((git_ref, sha.clone()));
+
+    let mut shas = vec![sha.clone()];
+    if is_pending {
+      self.pending = Some((sha.clone(), update_id.clone()));
+    } else if let Some((pending_sha, _)) = self.pending.take()
+      && pending_sha != sha
+    {
+      shas.push(pending_sha);
+    }
+
+    if let Some(context) = &self.context {
+      for sha in &shas {
+        provider
+          .post_commit_status(sha, context, state, &update_id)
+          .await?;
+      }
+    }
+
+    let Some(environment) = &self.environment else {
+      return Ok(());
+    };
+    if !provider.api.supports_deployments() {
+      return Ok(());
+    }
+    if !self.deployments.iter().any(|(sha_, _)| sha_ == &sha) {
+      let id = provider
+        .create_deployment(&sha, &self.args.branch, environment)
+        .await?;
+      self.deployments.push((sha.clone(), id));
+    }
+    for (sha_, id) in &self.deployments {
+      // Pending is only reported on the deployment it created.
+      if is_pending && sha_ != &sha {
+        continue;
+      }
+      provider
+        .post_deployment_state(*id, environment, state, &update_id)
+        .await?;
+    }
+    Ok(())
+  }
+}
+
+/// The api, repo and token used to report to the git provider.
+struct ProviderContext {
+  api: ProviderApi,
+  base_url: String,
+  repo: String,
+  token: String,
+}
+
+impl ProviderContext {
+  async fn new(
+    args: &RepoExecutionArgs,
+  ) -> anyhow::Result<ProviderContext> {
+    let repo = args.repo.clone().context("No repo configured")?;
+    let account = args
+      .account
+      .as_deref()
+      .context("No git account configured to report commit status")?;
+    let token = git_token(&args.provider, account, false, |_| {})
+      .await?
+      .with_context(|| {
+        format!("No token for git account {account}")
+      })?;
+    // Tokens may be given as 'username:token'
+    let token = token
+      .split_once(':')
+      .map(|(_, token)| token)
+      .unwrap_or(&token)
+      .trim()
+      .to_string();
+    let api = ProviderApi::from_domain(&args.provider);
+    let base_url = api.base_url(args);
+    Ok(ProviderContext {
+      api,
+      base_url,
+      repo,
+      token,
+    })
+  }
+
+  async fn post_commit_status(
+    &self,
+    sha: &str,
+    context: &str,
+    state: CommitState,
+    update_id: &str,
+  ) -> anyhow::Result<()> {
+    let ProviderContext {
+      api,
+      base_url,
+      repo,
+      token,
+    } = self;
+
+    let target_url =
+      format!("{}/updates/{update_id}", core_config().host);
+    let description = match state {
+      CommitState::Pending => "Komodo run in progress",
+      CommitState::Success => "Komodo run succeeded",
+      CommitState::Failure => "Komodo run failed",
+    };
+
+    let req = match api {
+      ProviderApi::Github | ProviderApi::Gitea => {
+        let state = match state {
+          CommitState::Pending => "pending",
+          CommitState::Success => "success",
+          CommitState::Failure => "failure",
+        };
+        http_client()
+          .post(format!("{base_url}/repos/{repo}/statuses/{sha}"))
+          .json(&json!({
+            "state": state,
+            "target_url": target_url,
+            "description": description,
+            "context": context,
+          }))
+      }
+      ProviderApi::Gitlab => {
+        let state = match state {
+          CommitState::Pending => "running",
+          CommitState::Success => "success",
+          CommitState::Failure => "failed",
+        };
+        http_client()
+          .post(format!(
+            "{base_url}/projects/{}/statuses/{sha}",
+            urlencoding::encode(repo)
+          ))
+          .json(&json!({
+            "state": state,
+            "target_url": target_url,
+            "description": description,
+            "name": context,
+          }))
+      }
+    };
+
+    let res = api
+      .auth(req, token)
+      .send()
+      .await
+      .context("Failed to send commit status request")?;
+
+    let status = res.status();
+    if status.is_success() {
+      Ok(())
+    } else {
+      let text = res.text().await.unwrap_or_default();
+      Err(anyhow!("{status} | {text}"))
+    }
+  }
+
+  /// Komodo records short commit hashes,
+  /// but the commit status apis require the full sha.
+  /// Branch names are resolved to their latest commit.
+  async fn full_commit_sha(
+    &self,
+    commit_hash: &str,
+  ) -> anyhow::Result<String> {
+    if commit_hash.len() == 40 {
+      return Ok(commit_hash.to_string());
+    }
+    let ProviderContext {
+      api,
+      base_url,
+      repo,
+      token,
+    } = self;
+    let req = match api {
+      ProviderApi::Github => http_client()
+        .get(format!("{base_url}/repos/{repo}/commits/{commit_hash}"))
+        .header("Accept", "application/vnd.github.sha"),
+      ProviderApi::Gitlab => http_client().get(format!(
+        "{base_url}/projects/{}/repository/commits/{}",
+        urlencoding::encode(repo),
+        urlencoding::encode(commit_hash)
+      )),
+      // The single commit endpoint only accepts hashes,
+      // listing commits accepts branch names too.
+      ProviderApi::Gitea => http_client()
+        .get(format!("{base_url}/repos/{repo}/commits"))
+        .query(&[("sha", commit_hash), ("limit", "1")]),
+    };
+    let res = api
+      .auth(req, token)
+      .send()
+      .await
+      .context("Failed to get full commit sha")?;
+    let status = res.status();
+    if !status.is_success() {
+      let text = res.text().await.unwrap_or_default();
+      return Err(anyhow!(
+        "Failed to get full commit sha | {status} | {text}"
+      ));
+    }
+    let sha = match api {
+      ProviderApi::Github => res.text().await?.trim().to_string(),
+      ProviderApi::Gitlab => res.json::<GitlabCommit>().await?.id,
+      ProviderApi::Gitea => {
+        res
+          .json::<Vec<GiteaCommit>>()
+          .await?
+          .pop()
+          .context("No commit found")?
+          .sha
+      }
+    };
+    Ok(sha)
+  }
+
+  /// Creates a deployment of the commit to the environment,
+  /// returning the deployment id.
+  async fn create_deployment(
+    &self,
+    sha: &str,
+    branch: &str,
+    environment: &str,
+  ) -> anyhow::Result<u64> {
+    let ProviderContext {
+      api,
+      base_url,
+      repo,
+      token,
+    } = self;
+    let req = match api {
+      ProviderApi::Github => http_client()
+        .post(format!("{base_url}/repos/{repo}/deployments"))
+        .json(&json!({
+          "ref": sha,
+          "environment": environment,
+          "description": "Komodo deploy",
+          "auto_merge": false,
+          // Don't wait on the commit statuses Komodo is reporting.
+          "required_contexts": [],
+        })),
+      ProviderApi::Gitlab => http_client()
+        .post(format!(
+          "{base_url}/projects/{}/deployments",
+          urlencoding::encode(repo)
+        ))
+        .json(&json!({
+          "environment": environment,
+          "sha": sha,
+          "ref": branch,
+          "tag": false,
+          "status": "running",
+        })),
+      ProviderApi::Gitea => {
+        return Err(anyhow!(
+          "Deployments are not supported for Gitea / Forgejo"
+        ));
+      }
+    };
+    let res = api
+      .auth(req, token)
+      .send()
+      .await
+      .context("Failed to send create deployment request")?;
+    let status = res.status();
+    if !status.is_success() {
+      let text = res.text().await.unwrap_or_default();
+      return Err(anyhow!(
+        "Failed to create deployment | {status} | {text}"
+      ));
+    }
+    Ok(res.json::<ProviderDeployment>().await?.id)
+  }
+
+  async fn post_deployment_state(
+    &self,
+    id: u64,
+    environment: &str,
+    state: CommitState,
+    update_id: &str,
+  ) -> anyhow::Result<()> {
+    let ProviderContext {
+      api,
+      base_url,
+      repo,
+      token,
+    } = self;
+    let log_url =
+      format!("{}/updates/{update_id}", core_config().host);
+    let req = match api {
+      ProviderApi::Github => {
+        let state = match state {
+          CommitState::Pending => "in_progress",
+          CommitState::Success => "success",
+          CommitState::Failure => "failure",
+        };
+        http_client()
+          .post(format!(
+            "{base_url}/repos/{repo}/deployments/{id}/statuses"
+          ))
+          .json(&json!({
+            "state": state,
+            "log_url": log_url,
+            "environment": environment,
+          }))
+      }
+      ProviderApi::Gitlab => {
+        let state = match state {
+          // Gitlab deployments are created running.
+          CommitState::Pending => return Ok(()),
+          CommitState::Success => "success",
+          CommitState::Failure => "failed",
+        };
+        http_client()
+          .put(format!(
+            "{base_url}/projects/{}/deployments/{id}",
+            urlencoding::encode(repo)
+          ))
+          .json(&json!({ "status": state }))
+      }
+      ProviderApi::Gitea => return Ok(()),
+    };
+    let res = api
+      .auth(req, token)
+      .send()
+      .await
+      .context("Failed to send deployment status request")?;
+    let status = res.status();
+    if status.is_success() {
+      Ok(())
+    } else {
+      let text = res.text().await.unwrap_or_default();
+      Err(anyhow!("{status} | {text}"))
+    }
+  }
+}
+
+/// Github / Gitlab deployment response
+#[derive(Deserialize)]
+struct ProviderDeployment {
+  id: u64,
+}
+
+#[derive(Deserialize)]
+struct GitlabCommit {
+  id: String,
+}
+
+#[derive(Deserialize)]
+struct GiteaCommit {
+  sha: String,
+}
+
+fn http_client() -> &'static reqwest::Client {
+  static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+  CLIENT.get_or_init(|| {
+    reqwest::Client::builder()
+      // Github rejects requests without a user agent
+      .user_agent("komodo")
+      .build()
+      .expect("Failed to build commit status http client")
+  })
+}
@@ -0,0 +1,408 @@
+//! Envelope encryption for secrets stored on the database.
+//!
+//! Each value is encrypted with its own random data key,
+//! and the data key is encrypted (wrapped) with the Core master key.
+//! Stored values have the form:
+//!
+//! `enc:v1:<master key id>:<wrapped data key>:<ciphertext>`
+//!
+//! Rotating the master key only needs to re-wrap the data keys.
+
+use std::{path::Path, sync::OnceLock};
+
+use aes_gcm::{
+  Aes256Gcm, Nonce,
+  aead::{Aead, KeyInit},
+};
+use anyhow::{Context, anyhow};
+use data_encoding::BASE64;
+use database::mungos::mongodb::bson::doc;
+use komodo_client::entities::{
+  provider::{
+    DockerRegistryAccount, GitProviderAccount,
+    PartialDockerRegistryAccount, PartialGitProviderAccount,
+  },
+  variable::Variable,
+};
+use rand::Rng as _;
+use sha2::{Digest, Sha256};
+
+use crate::{config::core_config, state::db_client};
+
+const PREFIX: &str = "enc:v1:";
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+struct MasterKey {
+  /// Identifies the key used to encrypt a value,
+  /// without revealing anything about the key.
+  id: String,
+  cipher: Aes256Gcm,
+}
+
+pub struct EncryptionKeys {
+  current: Option<MasterKey>,
+  previous: Vec<MasterKey>,
+}
+
+impl EncryptionKeys {
+  /// The id of the current master key, if configured.
+  pub fn current_id(&self) -> Option<&str> {
+    self.current.as_ref().map(|key| key.id.as_str())
+  }
+
+  fn get(&self, id: &str) -> anyhow::Result<&MasterKey> {
+    self
+      .current
+      .iter()
+      .chain(&self.previous)
+      .find(|key| key.id == id)
+      .with_context(|| {
+        format!(
+          "No encryption key configured with id {id}. Add the key used to encrypt the value to 'previous_encryption_keys'."
+        )
+      })
+  }
+}
+
+/// Should call in startup to ensure Core errors without valid encryption keys.
+pub fn encryption_keys() -> &'static EncryptionKeys {
+  static ENCRYPTION_KEYS: OnceLock<EncryptionKeys> = OnceLock::new();
+  ENCRYPTION_KEYS.get_or_init(|| {
+    let config = core_config();
+    let current = if config.encryption_key.is_empty() {
+      None
+    } else {
+      Some(
+        load_master_key(&config.encryption_key)
+          .context("Invalid 'encryption_key'")
+          .unwrap(),
+      )
+    };
+    let previous = config
+      .previous_encryption_keys
+      .iter()
+      .map(|spec| {
+        load_master_key(spec)
+          .context("Invalid 'previous_encryption_keys'")
+          .unwrap()
+      })
+      .collect();
+    EncryptionKeys { current, previous }
+  })
+}
+
+/// Generates the `file:` encryption key if the file does not exist yet.
+/// Refuses if the database already has encrypted values,
+/// as they could never be decrypted with a new key.
+///
+/// Must be called after the db client is initialized,
+/// and before `encryption_keys`.
+pub async fn generate_missing_encryption_key() -> anyhow::Result<()> {
+  let Some(path) = core_config().encryption_key.strip_prefix("file:")
+  else {
+    return Ok(());
+  };
+  let path = Path::new(path);
+  if path.exists() {
+    return Ok(());
+  }
+  if encrypted_values_exist().await? {
+    return Err(anyhow!(
+      "Encryption key file {path:?} does not exist, but the database has encrypted values. Restore the key file, the values cannot be decrypted with a new key."
+    ));
+  }
+  let encoded = BASE64.encode(&random_bytes::<KEY_LEN>());
+  if let Some(parent) = path.parent() {
+    std::fs::create_dir_all(parent).with_context(|| {
+      format!("Failed to create directory {parent:?}")
+    })?;
+  }
+  std::fs::write(path, &encoded).with_context(|| {
+    format!("Failed to write encryption key to {path:?}")
+  })?;
+  warn!(
+    "Generated new encryption key at {path:?}. Back up this key, encrypted values cannot be recovered without it."
+  );
+  Ok(())
+}
+
+async fn encrypted_values_exist() -> anyhow::Result<bool> {
+  let db = db_client();
+  let encrypted = doc! { "$regex": format!("^{PREFIX}") };
+  let (variable, git_account, registry_account) = tokio::try_join!(
+    db.variables.find_one(doc! { "value": encrypted.clone() }),
+    db.git_accounts.find_one(doc! { "$or": [
+      { "token": encrypted.clone() },
+      { "ssh_private_key": encrypted.clone() },
+    ] }),
+    db.registry_accounts.find_one(doc! { "token": encrypted }),
+  )
+  .context("Failed to query database for encrypted values")?;
+  Ok(
+    variable.is_some()
+      || git_account.is_some()
+      || registry_account.is_some(),
+  )
+}
+
+/// Loads a base64 encoded key, or `file:/path/to/key`.
+fn load_master_key(spec: &str) -> anyhow::Result<MasterKey> {
+  let encoded = if let Some(path) = spec.strip_prefix("file:") {
+    std::fs::read_to_string(path).with_context(|| {
+      format!("Failed to read encryption key from {path:?}")
+    })?
+  } else {
+    spec.to_string()
+  };
+  let key = BASE64
+    .decode(encoded.trim().as_bytes())
+    .context("Encryption key is not valid base64")?;
+  if key.len() != KEY_LEN {
+    return Err(anyhow!(
+      "Encryption key must be {KEY_LEN} bytes, got {}",
+      key.len()
+    ));
+  }
+  let id = hex::encode(&Sha256::digest(&key)[..4]);
+  let cipher = Aes256Gcm::new_from_slice(&key)
+    .context("Failed to initialize encryption key")?;
+  Ok(MasterKey { id, cipher })
+}
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+  let mut bytes = [0u8; N];
+  rand::rng().fill_bytes(&mut bytes);
+  bytes
+}
+
+/// `nonce || ciphertext`
+fn seal(cipher: &Aes256Gcm, data: &[u8]) -> anyhow::Result<Vec<u8>> {
+  let nonce = random_bytes::<NONCE_LEN>();
+  let ciphertext = cipher
+    .encrypt(Nonce::from_slice(&nonce), data)
+    .map_err(|_| anyhow!("Failed to encrypt value"))?;
+  Ok([nonce.as_slice(), &ciphertext].concat())
+}
+
+fn open(
+  cipher: &Aes256Gcm,
+  sealed: &[u8],
+) -> anyhow::Result<Vec<u8>> {
+  if sealed.len() < NONCE_LEN {
+    return Err(anyhow!("Encrypted value is too short"));
+  }
+  let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+  cipher
+    .decrypt(Nonce::from_slice(nonce), ciphertext)
+    .map_err(|_| {
+      anyhow!("Failed to decrypt value, the key may be incorrect")
+    })
+}
+
+pub fn is_encrypted(value: &str) -> bool {
+  value.starts_with(PREFIX)
+}
+
+/// Encrypts the value with the current master key.
+/// Returns the value unchanged if no `encryption_key` is configured,
+/// or the value is empty or already encrypted.
+pub fn encrypt_secret(value: String) -> anyhow::Result<String> {
+  let Some(key) = &encryption_keys().current else {
+    return Ok(value);
+  };
+  if value.is_empty() || is_encrypted(&value) {
+    return Ok(value);
+  }
+  let data_key = random_bytes::<KEY_LEN>();
+  let cipher = Aes256Gcm::new_from_slice(&data_key)
+    .context("Failed to initialize data key")?;
+  let ciphertext = seal(&cipher, value.as_bytes())?;
+  let wrapped_key = seal(&key.cipher, &data_key)?;
+  Ok(format!(
+    "{PREFIX}{}:{}:{}",
+    key.id,
+    BASE64.encode(&wrapped_key),
+    BASE64.encode(&ciphertext)
+  ))
+}
+
+/// Decrypts the value if it is encrypted,
+/// otherwise returns it unchanged.
+pub fn decrypt_secret(value: String) -> anyhow::Result<String> {
+  let Some(encrypted) = value.strip_prefix(PREFIX) else {
+    return Ok(value);
+  };
+  let EncryptedValue {
+    key_id,
+    wrapped_key,
+    ciphertext,
+  } = EncryptedValue::parse(encrypted)?;
+  let key = encryption_keys().get(key_id)?;
+  let data_key = open(&key.cipher, &wrapped_key)?;
+  let cipher = Aes256Gcm::new_from_slice(&data_key)
+    .context("Invalid data key")?;
+  let value = open(&cipher, &ciphertext)?;
+  String::from_utf8(value)
+    .context("Decrypted value is not valid utf8")
+}
+
+/// Brings a stored value up to date with the configured keys:
+///  - Encrypted with a previous key -> data key re-wrapped with the current key.
+///  - Unencrypted -> encrypted with the current key.
+///  - No current key -> decrypted.
+///
+/// Returns None if the value is already up to date.
+pub fn rotate_secret(value: &str) -> anyhow::Result<Option<String>> {
+  let keys = encryption_keys();
+  let Some(current) = &keys.current else {
+    if is_encrypted(value) {
+      return decrypt_secret(value.to_string()).map(Some);
+    }
+    return Ok(None);
+  };
+  let Some(encrypted) = value.strip_prefix(PREFIX) else {
+    if value.is_empty() {
+      return Ok(None);
+    }
+    return encrypt_secret(value.to_string()).map(Some);
+  };
+  let EncryptedValue {
+    key_id,
+    wrapped_key,
+    ciphertext,
+  } = EncryptedValue::parse(encrypted)?;
+  if key_id == current.id {
+    return Ok(None);
+  }
+  let data_key = open(&keys.get(key_id)?.cipher, &wrapped_key)?;
+  let wrapped_key = seal(&current.cipher, &data_key)?;
+  Ok(Some(format!(
+    "{PREFIX}{}:{}:{}",
+    current.id,
+    BASE64.encode(&wrapped_key),
+    BASE64.encode(&ciphertext)
+  )))
+}
+
+struct EncryptedValue<'a> {
+  key_id: &'a str,
+  wrapped_key: Vec<u8>,
+  ciphertext: Vec<u8>,
+}
+
+impl<'a> EncryptedValue<'a> {
+  /// Parses the value after the prefix.
+  fn parse(encrypted: &'a str) -> anyhow::Result<Self> {
+    let mut parts = encrypted.splitn(3, ':');
+    let (Some(key_id), Some(wrapped_key), Some(ciphertext)) =
+      (parts.next(), parts.next(), parts.next())
+    else {
+      return Err(anyhow!("Encrypted value is malformed"));
+    };
+    Ok(EncryptedValue {
+      key_id,
+      wrapped_key: BASE64
+        .decode(wrapped_key.as_bytes())
+        .context("Encrypted data key is not valid base64")?,
+      ciphertext: BASE64
+        .decode(ciphertext.as_bytes())
+        .context("Encrypted value is not valid base64")?,
+    })
+  }
+}
+
+// ============
+//   ENTITIES
+// ============
+
+/// Only secret variables are encrypted.
+pub fn encrypt_variable(
+  mut variable: Variable,
+) -> anyhow::Result<Variable> {
+  if variable.is_secret {
+    variable.value = encrypt_secret(variable.value)?;
+  }
+  Ok(variable)
+}
+
+pub fn decrypt_variable(
+  mut variable: Variable,
+) -> anyhow::Result<Variable> {
+  variable.value =
+    decrypt_secret(variable.value).with_context(|| {
+      format!("Failed to decrypt variable {}", variable.name)
+    })?;
+  Ok(variable)
+}
+
+pub fn decrypt_variables(
+  variables: Vec<Variable>,
+) -> anyhow::Result<Vec<Variable>> {
+  variables.into_iter().map(decrypt_variable).collect()
+}
+
+pub fn encrypt_git_account(
+  mut account: GitProviderAccount,
+) -> anyhow::Result<GitProviderAccount> {
+  account.token = encrypt_secret(account.token)?;
+  account.ssh_private_key = encrypt_secret(account.ssh_private_key)?;
+  Ok(account)
+}
+
+pub fn encrypt_partial_git_account(
+  account: &mut PartialGitProviderAccount,
+) -> anyhow::Result<()> {
+  if let Some(token) = account.token.take() {
+    account.token = Some(encrypt_secret(token)?);
+  }
+  if let Some(key) = account.ssh_private_key.take() {
+    account.ssh_private_key = Some(encrypt_secret(key)?);
+  }
+  Ok(())
+}
+
+pub fn decrypt_git_account(
+  mut account: GitProviderAccount,
+) -> anyhow::Result<GitProviderAccount> {
+  let context = || {
+    format!(
+      "Failed to decrypt git provider account {} on {}",
+      account.username, account.domain
+    )
+  };
+  account.token =
+    decrypt_secret(account.token).with_context(context)?;
+  account.ssh_private_key =
+    decrypt_secret(account.ssh_private_key).with_context(context)?;
+  Ok(account)
+}
+
+pub fn encrypt_registry_account(
+  mut account: DockerRegistryAccount,
+) -> anyhow::Result<DockerRegistryAccount> {
+  account.token = encrypt_secret(account.token)?;
+  Ok(account)
+}
+
+pub fn encrypt_partial_registry_account(
+  account: &mut PartialDockerRegistryAccount,
+) -> anyhow::Result<()> {
+  if let Some(token) = account.token.take() {
+    account.token = Some(encrypt_secret(token)?);
+  }
+  Ok(())
+}
+
+pub fn decrypt_registry_account(
+  mut account: DockerRegistryAccount,
+) -> anyhow::Result<DockerRegistryAccount> {
+  account.token =
+    decrypt_secret(account.token).with_context(|| {
+      format!(
+        "Failed to decrypt docker registry account {} on {}",
+        account.username, account.domain
+      )
+    })?;
+  Ok(account)
+}
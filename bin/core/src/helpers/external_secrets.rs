@@ -0,0 +1,248 @@
+use std::{
+  collections::HashMap,
+  path::{Component, Path, PathBuf},
+  sync::OnceLock,
+  time::Duration,
+};
+
+use anyhow::{Context, anyhow};
+use komodo_client::entities::{
+  permission::PermissionLevel, repo::Repo, sync::ResourceSync,
+  user::User,
+};
+use serde::Serialize;
+use tokio::process::Command;
+
+use crate::{
+  config::core_config, permission::get_check_permissions,
+  state::action_states,
+};
+
+/// Resolves the external secrets referenced in `target`,
+/// eg `[[vault:kv/data/app#password]]`, and adds them to `secrets`.
+/// They are then interpolated and sanitized like any other secret.
+///
+/// Secrets are read from the providers on every call,
+/// and are never stored. `user` must have Execute permission
+/// on any Resource Sync referenced by a SOPS secret.
+pub async fn add_external_secrets(
+  secrets: &mut HashMap<String, String>,
+  target: &impl Serialize,
+  user: &User,
+) -> anyhow::Result<()> {
+  let target = serde_json::to_string(target)
+    .context("Failed to serialize interpolation target")?;
+  for reference in interpolate::external_secret_references(&target) {
+    if secrets.contains_key(&reference) {
+      continue;
+    }
+    let Some((provider, path)) = reference.split_once(':') else {
+      continue;
+    };
+    let value = match provider {
+      "vault" => vault_secret(path).await,
+      "file" => file_secret(path).await,
+      "sops" => sops_secret(path, user).await,
+      // Not a secret provider, may be a regular variable.
+      _ => continue,
+    }
+    .with_context(|| {
+      format!("Failed to resolve external secret '{reference}'")
+    })?;
+    secrets.insert(reference, value);
+  }
+  Ok(())
+}
+
+/// `[[vault:<path>#<key>]]`
+async fn vault_secret(reference: &str) -> anyhow::Result<String> {
+  let config = core_config();
+  if config.vault_address.is_empty() {
+    return Err(anyhow!(
+      "Vault secrets require 'vault_address' to be configured"
+    ));
+  }
+  let (path, key) = reference
+    .split_once('#')
+    .context("Vault reference must be in form '<path>#<key>'")?;
+  let mut req = http_client()
+    .get(format!(
+      "{}/v1/{}",
+      config.vault_address.trim_end_matches('/'),
+      path.trim_start_matches('/')
+    ))
+    .header("X-Vault-Token", &config.vault_token);
+  if !config.vault_namespace.is_empty() {
+    req = req.header("X-Vault-Namespace", &config.vault_namespace);
+  }
+  let res = req
+    .send()
+    .await
+    .context("Failed to send request to Vault")?;
+  let status = res.status();
+  if !status.is_success() {
+    let text = res.text().await.unwrap_or_default();
+    return Err(anyhow!("{status} | {text}"));
+  }
+  let body = res
+    .json::<serde_json::Value>()
+    .await
+    .context("Failed to parse Vault response")?;
+  let data = &body["data"];
+  // KV v2 nests the secret data under 'data.data'
+  let data = if data["data"].is_object() {
+    &data["data"]
+  } else {
+    data
+  };
+  match data.get(key) {
+    Some(serde_json::Value::String(value)) => Ok(value.clone()),
+    Some(serde_json::Value::Null) | None => {
+      Err(anyhow!("No key '{key}' at Vault path '{path}'"))
+    }
+    Some(value) => Ok(value.to_string()),
+  }
+}
+
+/// `[[file:<file name>]]`
+async fn file_secret(reference: &str) -> anyhow::Result<String> {
+  let directory = &core_config().secret_files_directory;
+  if directory.as_os_str().is_empty() {
+    return Err(anyhow!(
+      "File secrets require 'secret_files_directory' to be configured"
+    ));
+  }
+  let path = contained_path(directory, reference)?;
+  let contents = tokio::fs::read_to_string(&path)
+    .await
+    .with_context(|| format!("Failed to read {path:?}"))?;
+  Ok(contents.trim_end_matches(['\n', '\r']).to_string())
+}
+
+/// `[[sops:<sync>:<file path>#<key>]]` for files in a Resource Sync repo,
+/// or `[[sops:<file path>#<key>]]` for files in `sops_directory`.
+/// Omit `#<key>` for the whole decrypted file.
+async fn sops_secret(
+  reference: &str,
+  user: &User,
+) -> anyhow::Result<String> {
+  let (path, key) = match reference.split_once('#') {
+    Some((path, key)) => (path, Some(key)),
+    None => (reference, None),
+  };
+  // Holds the Resource Sync action state until decrypted,
+  // so the files aren't changed by a concurrent pull.
+  let sync_action_state;
+  let (path, _sync_guard) = match path.split_once(':') {
+    Some((sync, path)) => {
+      let sync = get_check_permissions::<ResourceSync>(
+        sync,
+        user,
+        PermissionLevel::Execute.into(),
+      )
+      .await?;
+      sync_action_state =
+        action_states().sync.get_or_insert_default(&sync.id).await;
+      let guard =
+        sync_action_state.update(|state| state.syncing = true)?;
+      let directory = sync_sops_directory(&sync).await?;
+      (contained_path(&directory, path)?, Some(guard))
+    }
+    None => {
+      let directory = &core_config().sops_directory;
+      if directory.as_os_str().is_empty() {
+        return Err(anyhow!(
+          "SOPS secrets require 'sops_directory' to be configured, or to reference a Resource Sync as '<sync>:<file path>'"
+        ));
+      }
+      (contained_path(directory, path)?, None)
+    }
+  };
+  let mut command = Command::new("sops");
+  command.arg("--decrypt");
+  if let Some(key) = key {
+    command.arg("--extract").arg(sops_extract_path(key));
+  }
+  let output = match command.arg(&path).output().await {
+    Ok(output) => output,
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+      return Err(anyhow!(
+        "The 'sops' binary was not found on Komodo Core. It must be installed to use SOPS secrets."
+      ));
+    }
+    Err(e) => {
+      return Err(
+        anyhow::Error::from(e).context("Failed to run sops"),
+      );
+    }
+  };
+  if !output.status.success() {
+    return Err(anyhow!(
+      "sops failed to decrypt {path:?} | {}",
+      String::from_utf8_lossy(&output.stderr).trim()
+    ));
+  }
+  let value = String::from_utf8(output.stdout)
+    .context("Decrypted secret is not valid utf8")?;
+  Ok(value.trim_end_matches(['\n', '\r']).to_string())
+}
+
+/// The Resource Sync files directory,
+/// pulling the latest commit for repo based Syncs.
+async fn sync_sops_directory(
+  sync: &ResourceSync,
+) -> anyhow::Result<PathBuf> {
+  let repo = if !sync.config.files_on_host
+    && !sync.config.linked_repo.is_empty()
+  {
+    crate::resource::get::<Repo>(&sync.config.linked_repo)
+      .await?
+      .into()
+  } else {
+    None
+  };
+  crate::sync::remote::sync_files_directory(sync, repo.as_ref()).await
+}
+
+/// Converts `database.password` or `users.0.token`
+/// to the sops extract syntax `["database"]["password"]`.
+fn sops_extract_path(key: &str) -> String {
+  key
+    .split('.')
+    .map(|segment| {
+      if segment.parse::<usize>().is_ok() {
+        format!("[{segment}]")
+      } else {
+        format!("[\"{}\"]", segment.replace('"', "\\\""))
+      }
+    })
+    .collect()
+}
+
+/// Only allows paths inside the configured directory.
+fn contained_path(
+  directory: &Path,
+  path: &str,
+) -> anyhow::Result<PathBuf> {
+  let path = Path::new(path);
+  if path
+    .components()
+    .any(|component| !matches!(component, Component::Normal(_)))
+  {
+    return Err(anyhow!(
+      "Secret path must be relative, without '..' components"
+    ));
+  }
+  Ok(directory.join(path))
+}
+
+fn http_client() -> &'static reqwest::Client {
+  static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+  CLIENT.get_or_init(|| {
+    reqwest::Client::builder()
+      .connect_timeout(Duration::from_secs(10))
+      .timeout(Duration::from_secs(30))
+      .build()
+      .expect("Failed to build external secrets http client")
+  })
+}
@@ -0,0 +1,147 @@
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::{Context, anyhow};
+use formatting::format_serror;
+use komodo_client::entities::{
+  docker::container::{ContainerStateStatusEnum, HealthStatusEnum},
+  komodo_timestamp,
+  server::Server,
+  update::Log,
+};
+use periphery_client::api::container::{InspectContainer, ProbeHttp};
+
+use super::periphery_client;
+
+const HEALTH_CHECK_STAGE: &str = "Health Check";
+const HEALTH_CHECK_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Waits up to `timeout_secs` for all the containers to be running
+/// and `healthy` (if they have a Docker healthcheck),
+/// and for the optional `url` to return a 2xx response.
+///
+/// If there is nothing to signal readiness (no Docker healthchecks
+/// and no url), the containers must stay running for the whole timeout.
+///
+/// Fails early if any container exits, restarts, or becomes `unhealthy`.
+pub async fn wait_for_healthy(
+  server: &Server,
+  containers: &[String],
+  url: &str,
+  timeout_secs: i32,
+) -> Log {
+  match wait_for_healthy_inner(server, containers, url, timeout_secs)
+    .await
+  {
+    Ok(msg) => Log::simple(HEALTH_CHECK_STAGE, msg),
+    Err(e) => {
+      Log::error(HEALTH_CHECK_STAGE, format_serror(&e.into()))
+    }
+  }
+}
+
+async fn wait_for_healthy_inner(
+  server: &Server,
+  containers: &[String],
+  url: &str,
+  timeout_secs: i32,
+) -> anyhow::Result<String> {
+  if containers.is_empty() {
+    return Err(anyhow!("No containers found to health check"));
+  }
+
+  let periphery = periphery_client(server).await?;
+  let start = komodo_timestamp();
+  let deadline = start + timeout_secs.max(0) as i64 * 1_000;
+
+  // container name -> restart count at first inspect
+  let mut restart_counts = HashMap::<&str, i64>::new();
+
+  loop {
+    let mut pending = Vec::new();
+    let mut has_healthcheck = false;
+
+    for name in containers {
+      let container = periphery
+        .request(InspectContainer { name: name.clone() })
+        .await
+        .with_context(|| {
+          format!("Failed to inspect container {name}")
+        })?;
+
+      let restart_count = container.restart_count.unwrap_or_default();
+      let initial_restart_count =
+        *restart_counts.entry(name.as_str()).or_insert(restart_count);
+      if restart_count > initial_restart_count {
+        return Err(anyhow!(
+          "Container {name} restarted {} time/s",
+          restart_count - initial_restart_count
+        ));
+      }
+
+      let state = container.state.unwrap_or_default();
+      match state.status {
+        ContainerStateStatusEnum::Running => {}
+        ContainerStateStatusEnum::Exited
+        | ContainerStateStatusEnum::Dead => {
+          return Err(anyhow!(
+            "Container {name} is {} | exit code: {}",
+            state.status,
+            state.exit_code.unwrap_or_default()
+          ));
+        }
+        status => {
+          pending.push(format!("{name} is {status}"));
+          continue;
+        }
+      }
+
+      match state
+        .health
+        .map(|health| health.status)
+        .unwrap_or_default()
+      {
+        HealthStatusEnum::Empty | HealthStatusEnum::None => {}
+        HealthStatusEnum::Healthy => has_healthcheck = true,
+        HealthStatusEnum::Starting => {
+          has_healthcheck = true;
+          pending.push(format!("{name} is starting"));
+        }
+        HealthStatusEnum::Unhealthy => {
+          return Err(anyhow!("Container {name} is unhealthy"));
+        }
+      }
+    }
+
+    if pending.is_empty() && !url.is_empty() {
+      let log = periphery
+        .request(ProbeHttp {
+          url: url.to_string(),
+        })
+        .await
+        .context("Failed to probe health check url")?;
+      if !log.success {
+        pending.push(log.stderr);
+      }
+    }
+
+    let now = komodo_timestamp();
+    let has_signal = has_healthcheck || !url.is_empty();
+
+    if pending.is_empty() && (has_signal || now >= deadline) {
+      return Ok(format!(
+        "Healthy after {:.1}s: {}",
+        (now - start) as f64 / 1_000.0,
+        containers.join(", ")
+      ));
+    }
+
+    if now >= deadline {
+      return Err(anyhow!(
+        "Not healthy after {timeout_secs}s | {}",
+        pending.join(" | ")
+      ));
+    }
+
+    tokio::time::sleep(HEALTH_CHECK_POLL_INTERVAL).await;
+  }
+}
@@ -0,0 +1,302 @@
+use std::{
+  collections::{HashMap, HashSet},
+  sync::{Mutex, OnceLock},
+};
+
+use anyhow::Context;
+use async_timing_util::{ONE_DAY_MS, wait_until_timelength};
+use database::mungos::{find::find_collect, mongodb::bson::doc};
+use futures_util::future::join_all;
+use komodo_client::entities::{
+  ResourceTarget, komodo_timestamp, log_archive::ArchivedLog,
+  server::Server,
+};
+use periphery_client::api::container::GetContainerLogSince;
+
+use crate::{
+  config::{core_config, monitoring_interval},
+  state::{
+    all_resources_cache, db_client, deployment_status_cache,
+    stack_status_cache,
+  },
+};
+
+use super::periphery_client;
+
+/// The max number of lines to archive per container per request.
+const MAX_LINES_PER_CYCLE: u64 = 5000;
+/// The span of the log read by each request,
+/// so catching up on a long log never re-reads all of it.
+const LOG_WINDOW_MS: i64 = 10 * 60 * 1000;
+/// The max number of windows read per container per cycle.
+const MAX_WINDOWS_PER_CYCLE: usize = 12;
+
+/// A container which has log archiving enabled.
+struct ArchiveTarget {
+  target: ResourceTarget,
+  server: Server,
+  container: String,
+  /// Unix timestamp in milliseconds
+  created: i64,
+}
+
+/// (target, container) -> timestamp of the latest archived line.
+fn archived_until()
+-> &'static Mutex<HashMap<(ResourceTarget, String), i64>> {
+  static ARCHIVED_UNTIL: OnceLock<
+    Mutex<HashMap<(ResourceTarget, String), i64>>,
+  > = OnceLock::new();
+  ARCHIVED_UNTIL.get_or_init(Default::default)
+}
+
+/// Periodically pulls new log lines from the containers
+/// of Deployments / Stacks with `archive_logs` enabled,
+/// and stores them on the database.
+pub fn spawn_log_archive_loop() {
+  tokio::spawn(async move {
+    let interval = monitoring_interval();
+    loop {
+      wait_until_timelength(interval, 2000).await;
+      if let Err(e) = archive_logs().await {
+        error!("Failed to archive container logs | {e:#}");
+      }
+    }
+  });
+}
+
+async fn archive_logs() -> anyhow::Result<()> {
+  let targets = get_archive_targets().await?;
+  if targets.is_empty() {
+    return Ok(());
+  }
+  let handles = targets.into_iter().map(|target| async move {
+    if let Err(e) = archive_container_logs(&target).await {
+      warn!(
+        "Failed to archive logs for container {} on Server {} | {e:#}",
+        target.container, target.server.name
+      );
+    }
+  });
+  join_all(handles).await;
+  Ok(())
+}
+
+async fn get_archive_targets() -> anyhow::Result<Vec<ArchiveTarget>> {
+  let (deployments, stacks) = tokio::try_join!(
+    async {
+      find_collect(
+        &db_client().deployments,
+        doc! { "config.archive_logs": true },
+        None,
+      )
+      .await
+      .context("Failed to get deployments from db")
+    },
+    async {
+      find_collect(
+        &db_client().stacks,
+        doc! { "config.archive_logs": true },
+        None,
+      )
+      .await
+      .context("Failed to get stacks from db")
+    },
+  )?;
+
+  let resources = all_resources_cache().load();
+  let mut targets = Vec::new();
+
+  for deployment in deployments {
+    let Some(server) =
+      resources.servers.get(&deployment.config.server_id)
+    else {
+      continue;
+    };
+    let Some(container) = deployment_status_cache()
+      .get(&deployment.id)
+      .await
+      .and_then(|status| status.curr.container.clone())
+    else {
+      continue;
+    };
+    targets.push(ArchiveTarget {
+      target: ResourceTarget::Deployment(deployment.id),
+      server: server.clone(),
+      created: container.created.unwrap_or_default() * 1000,
+      container: container.name,
+    });
+  }
+
+  for stack in stacks {
+    let Some(server) = resources.servers.get(&stack.config.server_id)
+    else {
+      continue;
+    };
+    let Some(status) = stack_status_cache().get(&stack.id).await
+    else {
+      continue;
+    };
+    for service in &status.curr.services {
+      let Some(container) = &service.container else {
+        continue;
+      };
+      targets.push(ArchiveTarget {
+        target: ResourceTarget::Stack(stack.id.clone()),
+        server: server.clone(),
+        container: container.name.clone(),
+        created: container.created.unwrap_or_default() * 1000,
+      });
+    }
+  }
+
+  Ok(targets)
+}
+
+async fn archive_container_logs(
+  ArchiveTarget {
+    target,
+    server,
+    container,
+    created,
+  }: &ArchiveTarget,
+) -> anyhow::Result<()> {
+  let key = (target.clone(), container.clone());
+  let cached = archived_until().lock().unwrap().get(&key).copied();
+  let mut since = match cached {
+    Some(since) => since,
+    None => latest_archived_ts(target, container).await?,
+  };
+  let now = komodo_timestamp();
+  // Nothing archived yet, start from the oldest line
+  // which wouldn't immediately be pruned.
+  if since == 0 {
+    let keep_days = core_config().keep_archived_logs_for_days as i64;
+    since = if keep_days > 0 {
+      (*created).max(now - keep_days * ONE_DAY_MS as i64)
+    } else {
+      *created
+    };
+  }
+
+  let periphery = periphery_client(server).await?;
+
+  for _ in 0..MAX_WINDOWS_PER_CYCLE {
+    let until = (since + LOG_WINDOW_MS).min(now);
+    let lines = periphery
+      .request(GetContainerLogSince {
+        name: container.clone(),
+        since,
+        until: Some(until),
+        limit: MAX_LINES_PER_CYCLE,
+      })
+      .await
+      .context("Failed to get container log from periphery")?;
+
+    let full_page = lines.len() as u64 >= MAX_LINES_PER_CYCLE;
+
+    // `--since` is inclusive, skip lines at the boundary timestamp
+    // which were already archived. Lines logged within the same
+    // millisecond are kept unless the same content was archived.
+    let archived =
+      archived_lines_at(target, container, since).await?;
+    let logs = lines
+      .into_iter()
+      .filter(|line| {
+        line.ts > since
+          || (line.ts == since && !archived.contains(&line.line))
+      })
+      .map(|line| ArchivedLog {
+        id: Default::default(),
+        ts: line.ts,
+        target: target.clone(),
+        container: container.clone(),
+        stream: line.stream,
+        line: line.line,
+      })
+      .collect::<Vec<_>>();
+
+    if !logs.is_empty() {
+      db_client()
+        .archived_logs
+        .insert_many(&logs)
+        .await
+        .context("Failed to insert archived logs on db")?;
+    }
+
+    let latest = logs.iter().map(|log| log.ts).max();
+
+    if !full_page {
+      // The whole window was read, continue from the end of it.
+      since = until;
+      archived_until().lock().unwrap().insert(key.clone(), since);
+      if until >= now {
+        break;
+      }
+      continue;
+    }
+
+    // More lines in this window than fit in a page,
+    // continue from the latest archived line next cycle.
+    since = match latest {
+      Some(latest) if latest > since => latest,
+      // The whole page is lines logged at the boundary timestamp,
+      // step past it so archiving can make progress.
+      _ => {
+        warn!(
+          "More than {MAX_LINES_PER_CYCLE} lines logged at {since} for container {container}, skipping the rest of them"
+        );
+        since + 1
+      }
+    };
+    archived_until().lock().unwrap().insert(key, since);
+    break;
+  }
+
+  Ok(())
+}
+
+/// The timestamp of the latest archived line for the container,
+/// or 0 if none have been archived yet.
+async fn latest_archived_ts(
+  target: &ResourceTarget,
+  container: &str,
+) -> anyhow::Result<i64> {
+  let (variant, id) = target.extract_variant_id();
+  let latest = db_client()
+    .archived_logs
+    .find_one(doc! {
+      "target.type": variant.as_ref(),
+      "target.id": id.as_str(),
+      "container": container,
+    })
+    .sort(doc! { "ts": -1 })
+    .await
+    .context("Failed to query db for latest archived log")?;
+  Ok(latest.map(|log| log.ts).unwrap_or_default())
+}
+
+/// The content of lines already archived for the container
+/// at exactly the given timestamp.
+async fn archived_lines_at(
+  target: &ResourceTarget,
+  container: &str,
+  ts: i64,
+) -> anyhow::Result<HashSet<String>> {
+  let (variant, id) = target.extract_variant_id();
+  let lines = find_collect(
+    &db_client().archived_logs,
+    doc! {
+      "target.type": variant.as_ref(),
+      "target.id": id.as_str(),
+      "container": container,
+      "ts": ts,
+    },
+    None,
+  )
+  .await
+  .context("Failed to query db for archived logs")?
+  .into_iter()
+  .map(|log| log.line)
+  .collect();
+  Ok(lines)
+}
@@ -8,6 +8,7 @@ use komodo_client::entities::SwarmOrServer;
 use komodo_client::entities::{
   ResourceTarget,
   build::Build,
+  optional_string,
   permission::{
     Permission, PermissionLevel, SpecificPermission, UserTarget,
   },
@@ -28,9 +29,15 @@ use crate::{
 
 pub mod action_state;
 pub mod all_resources;
+pub mod audit;
 pub mod builder;
 pub mod channel;
+pub mod commit_status;
+pub mod encryption;
+pub mod external_secrets;
+pub mod health_check;
 pub mod image_digest;
+pub mod log_archive;
 pub mod maintenance;
 pub mod matcher;
 pub mod procedure;
@@ -55,10 +62,13 @@ pub fn empty_or_only_spaces(word: &str) -> bool {
 
 /// First checks db for token, then checks core config.
 /// Only errors if db call errors.
-/// Returns (token, use_https)
+///
+/// With `ssh`, returns the account SSH private key instead.
+/// These are only stored on the db accounts.
 pub async fn git_token(
   provider_domain: &str,
   account_username: &str,
+  ssh: bool,
   mut on_https_found: impl FnMut(bool),
 ) -> anyhow::Result<Option<String>> {
   if provider_domain.is_empty() || account_username.is_empty() {
@@ -68,7 +78,14 @@ pub async fn git_token(
     .git_accounts
     .find_one(doc! { "domain": provider_domain, "username": account_username })
     .await
-    .context("failed to query db for git provider accounts")?;
+    .context("failed to query db for git provider accounts")?
+    .map(encryption::decrypt_git_account)
+    .transpose()?;
+  if ssh {
+    return Ok(db_provider.and_then(|provider| {
+      optional_string(provider.ssh_private_key)
+    }));
+  }
   if let Some(provider) = db_provider {
     on_https_found(provider.https);
     return Ok(Some(provider.token));
@@ -97,6 +114,7 @@ pub async fn stack_git_token(
     return git_token(
       &repo.config.git_provider,
       &repo.config.git_account,
+      repo.config.git_ssh,
       |https| repo.config.git_https = https,
     )
     .await
@@ -110,6 +128,7 @@ pub async fn stack_git_token(
   git_token(
     &stack.config.git_provider,
     &stack.config.git_account,
+    stack.config.git_ssh,
     |https| stack.config.git_https = https,
   )
   .await
@@ -129,6 +148,7 @@ pub async fn build_git_token(
     return git_token(
       &repo.config.git_provider,
       &repo.config.git_account,
+      repo.config.git_ssh,
       |https| repo.config.git_https = https,
     )
     .await
@@ -142,6 +162,7 @@ pub async fn build_git_token(
   git_token(
     &build.config.git_provider,
     &build.config.git_account,
+    build.config.git_ssh,
     |https| build.config.git_https = https,
   )
   .await
@@ -163,7 +184,9 @@ pub async fn registry_token(
     .registry_accounts
     .find_one(doc! { "domain": provider_domain, "username": account_username })
     .await
-    .context("failed to query db for docker registry accounts")?;
+    .context("failed to query db for docker registry accounts")?
+    .map(encryption::decrypt_registry_account)
+    .transpose()?;
   if let Some(provider) = provider {
     return Ok(Some(provider.token));
   }
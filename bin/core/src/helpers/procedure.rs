@@ -350,6 +350,15 @@ async fn execute_execution(
     Execution::DestroyDeployment(req) => {
       resolve_execute!(DestroyDeployment, req)
     }
+    Execution::RollbackDeployment(req) => {
+      resolve_execute!(RollbackDeployment, req)
+    }
+    Execution::BackupDeploymentVolumes(req) => {
+      resolve_execute!(BackupDeploymentVolumes, req)
+    }
+    Execution::RestoreDeploymentVolumes(req) => {
+      resolve_execute!(RestoreDeploymentVolumes, req)
+    }
     Execution::CloneRepo(req) => resolve_execute!(CloneRepo, req),
     Execution::PullRepo(req) => resolve_execute!(PullRepo, req),
     Execution::BuildRepo(req) => resolve_execute!(BuildRepo, req),
@@ -429,9 +438,18 @@ async fn execute_execution(
     Execution::DestroyStack(req) => {
       resolve_execute!(DestroyStack, req)
     }
+    Execution::RollbackStack(req) => {
+      resolve_execute!(RollbackStack, req)
+    }
     Execution::RunStackService(req) => {
       resolve_execute!(RunStackService, req)
     }
+    Execution::BackupStackVolumes(req) => {
+      resolve_execute!(BackupStackVolumes, req)
+    }
+    Execution::RestoreStackVolumes(req) => {
+      resolve_execute!(RestoreStackVolumes, req)
+    }
     Execution::TestAlerter(req) => resolve_execute!(TestAlerter, req),
     Execution::SendAlert(req) => resolve_execute!(SendAlert, req),
     Execution::RemoveSwarmNodes(req) => {
@@ -470,6 +488,9 @@ async fn execute_execution(
     Execution::BackupCoreDatabase(req) => {
       resolve_execute!(BackupCoreDatabase, req)
     }
+    Execution::RestoreCoreDatabase(req) => {
+      resolve_execute!(RestoreCoreDatabase, req)
+    }
     Execution::GlobalAutoUpdate(req) => {
       resolve_execute!(GlobalAutoUpdate, req)
     }
@@ -479,6 +500,9 @@ async fn execute_execution(
     Execution::RotateCoreKeys(req) => {
       resolve_execute!(RotateCoreKeys, req)
     }
+    Execution::RotateEncryptionKey(req) => {
+      resolve_execute!(RotateEncryptionKey, req)
+    }
   };
 
   if update.success {
@@ -711,9 +735,11 @@ pub fn replace_procedure_stage_ids_with_names(
               | Execution::BatchDestroyStack(_)
               | Execution::ClearRepoCache(_)
               | Execution::BackupCoreDatabase(_)
+              | Execution::RestoreCoreDatabase(_)
               | Execution::GlobalAutoUpdate(_)
               | Execution::RotateAllServerKeys(_)
               | Execution::RotateCoreKeys(_)
+              | Execution::RotateEncryptionKey(_)
               | Execution::Sleep(_) => {}
             }
           };
@@ -732,6 +758,9 @@ pub fn replace_procedure_stage_ids_with_names(
         UnpauseDeployment => deployment, deployments;
         StopDeployment => deployment, deployments;
         DestroyDeployment => deployment, deployments;
+        RollbackDeployment => deployment, deployments;
+        BackupDeploymentVolumes => deployment, deployments;
+        RestoreDeploymentVolumes => deployment, deployments;
         CloneRepo => repo, repos;
         PullRepo => repo, repos;
         BuildRepo => repo, repos;
@@ -768,7 +797,10 @@ pub fn replace_procedure_stage_ids_with_names(
         UnpauseStack => stack, stacks;
         StopStack => stack, stacks;
         DestroyStack => stack, stacks;
+        RollbackStack => stack, stacks;
         RunStackService => stack, stacks;
+        BackupStackVolumes => stack, stacks;
+        RestoreStackVolumes => stack, stacks;
         TestAlerter => alerter, alerters;
         RemoveSwarmNodes => swarm, swarms;
         UpdateSwarmNode => swarm, swarms;
@@ -14,8 +14,13 @@ pub fn spawn_prune_loop() {
   tokio::spawn(async move {
     loop {
       wait_until_timelength(Timelength::OneDay, 5000).await;
-      let (images_res, stats_res, alerts_res) =
-        tokio::join!(prune_images(), prune_stats(), prune_alerts());
+      let (images_res, stats_res, alerts_res, logs_res, audit_res) = tokio::join!(
+        prune_images(),
+        prune_stats(),
+        prune_alerts(),
+        prune_archived_logs(),
+        prune_audit_events()
+      );
       if let Err(e) = images_res {
         error!("error in pruning images | {e:#}");
       }
@@ -25,6 +30,12 @@ pub fn spawn_prune_loop() {
       if let Err(e) = alerts_res {
         error!("error in pruning alerts | {e:#}");
       }
+      if let Err(e) = logs_res {
+        error!("error in pruning archived logs | {e:#}");
+      }
+      if let Err(e) = audit_res {
+        error!("error in pruning audit events | {e:#}");
+      }
     }
   });
 }
@@ -101,3 +112,41 @@ async fn prune_alerts() -> anyhow::Result<()> {
   }
   Ok(())
 }
+
+async fn prune_archived_logs() -> anyhow::Result<()> {
+  if core_config().keep_archived_logs_for_days == 0 {
+    return Ok(());
+  }
+  let delete_before_ts = (unix_timestamp_ms()
+    - core_config().keep_archived_logs_for_days as u128 * ONE_DAY_MS)
+    as i64;
+  let res = db_client()
+    .archived_logs
+    .delete_many(doc! {
+      "ts": { "$lt": delete_before_ts }
+    })
+    .await?;
+  if res.deleted_count > 0 {
+    info!("deleted {} archived logs from db", res.deleted_count);
+  }
+  Ok(())
+}
+
+async fn prune_audit_events() -> anyhow::Result<()> {
+  if core_config().keep_audit_events_for_days == 0 {
+    return Ok(());
+  }
+  let delete_before_ts = (unix_timestamp_ms()
+    - core_config().keep_audit_events_for_days as u128 * ONE_DAY_MS)
+    as i64;
+  let res = db_client()
+    .audit_events
+    .delete_many(doc! {
+      "ts": { "$lt": delete_before_ts }
+    })
+    .await?;
+  if res.deleted_count > 0 {
+    info!("deleted {} audit events from db", res.deleted_count);
+  }
+  Ok(())
+}
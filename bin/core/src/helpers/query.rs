@@ -38,7 +38,10 @@ use mogh_auth_server::provider::oidc::SubjectIdentifier;
 
 use crate::{
   config::core_config,
-  helpers::swarm::swarm_request,
+  helpers::{
+    encryption::{decrypt_variable, decrypt_variables},
+    swarm::swarm_request,
+  },
   permission::get_user_permission_on_resource,
   resource::{self, KomodoResource},
   stack::compose_container_match_regex,
@@ -354,14 +357,15 @@ pub fn id_or_username_filter(id_or_username: &str) -> Document {
 }
 
 pub async fn get_variable(name: &str) -> anyhow::Result<Variable> {
-  db_client()
+  let variable = db_client()
     .variables
     .find_one(doc! { "name": &name })
     .await
     .context("failed at call to db")?
     .with_context(|| {
       format!("no variable found with given name: {name}")
-    })
+    })?;
+  decrypt_variable(variable)
 }
 
 pub async fn get_latest_update(
@@ -395,6 +399,7 @@ pub async fn get_variables_and_secrets()
   let variables = find_collect(&db_client().variables, None, None)
     .await
     .context("failed to get all variables from db")?;
+  let variables = decrypt_variables(variables)?;
   let mut secrets = core_config().secrets.clone();
 
   // extend secrets with secret variables
@@ -589,3 +594,16 @@ pub async fn find_oidc_user(
     .await
     .context("Failed at find user query from database")
 }
+
+pub async fn find_ldap_user(
+  dn: &str,
+) -> anyhow::Result<Option<User>> {
+  db_client()
+    .users
+    .find_one(doc! {
+      "config.type": "Ldap",
+      "config.data.dn": dn
+    })
+    .await
+    .context("Failed at find user query from database")
+}
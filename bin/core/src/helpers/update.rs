@@ -3,7 +3,7 @@ use std::time::Duration;
 use anyhow::Context;
 use database::mungos::{
   by_id::{find_one_by_id, update_one_by_id},
-  mongodb::bson::to_document,
+  mongodb::bson::{doc, to_document},
 };
 use komodo_client::entities::{
   Operation, ResourceTarget,
@@ -23,7 +23,9 @@ use komodo_client::entities::{
 };
 
 use crate::{
-  api::execute::ExecuteRequest, resource, state::db_client,
+  api::{execute::ExecuteRequest, metrics::record_completed_run},
+  resource,
+  state::db_client,
 };
 
 use super::channel::update_channel;
@@ -55,6 +57,7 @@ pub async fn add_update(
     .as_object_id()
     .context("inserted_id is not object id")?
     .to_string();
+  record_completed_run(&update);
   let id = update.id.clone();
   let update = update_list_item(update).await?;
   let _ = send_update(update).await;
@@ -80,6 +83,7 @@ pub async fn update_update(update: Update) -> anyhow::Result<()> {
   update_one_by_id(&db_client().updates, &update.id, database::mungos::update::Update::Set(to_document(&update)?), None)
     .await
     .context("failed to update the update on db. the update build process was deleted")?;
+  record_completed_run(&update);
   let update = update_list_item(update).await?;
   let _ = send_update(update).await;
   Ok(())
@@ -216,6 +220,9 @@ pub async fn init_execution_update(
       (UnpauseDeployment, Deployment, deployment),
       (StopDeployment, Deployment, deployment),
       (DestroyDeployment, Deployment, deployment),
+      (RollbackDeployment, Deployment, deployment),
+      (BackupDeploymentVolumes, Deployment, deployment),
+      (RestoreDeploymentVolumes, Deployment, deployment),
       // Build
       (RunBuild, Build, build),
       (CancelBuild, Build, build),
@@ -231,7 +238,10 @@ pub async fn init_execution_update(
       // Resource Sync
       (RunSync, ResourceSync, sync),
       // Stack (simple)
+      (RollbackStack, Stack, stack),
       (RunStackService, Stack, stack),
+      (BackupStackVolumes, Stack, stack),
+      (RestoreStackVolumes, Stack, stack),
       // Alerter
       (TestAlerter, Alerter, alerter),
     ],
@@ -263,9 +273,11 @@ pub async fn init_execution_update(
       SendAlert,
       ClearRepoCache,
       BackupCoreDatabase,
+      RestoreCoreDatabase,
       GlobalAutoUpdate,
       RotateAllServerKeys,
       RotateCoreKeys,
+      RotateEncryptionKey,
     ],
   );
 
@@ -294,3 +306,42 @@ pub async fn poll_update_until_complete(
     }
   }
 }
+
+/// Gets the last successful deploy prior to the most recent deploy
+/// for the target, which has a `deployed_config` to roll back to.
+///
+/// If the most recent deploy failed (eg. a failed health check),
+/// this is the last successful deploy.
+pub async fn get_rollback_update(
+  target: &ResourceTarget,
+  operations: &[Operation],
+) -> anyhow::Result<Update> {
+  let (variant, id) = target.extract_variant_id();
+  let operations =
+    operations.iter().map(Operation::as_ref).collect::<Vec<_>>();
+  let mut query = doc! {
+    "target.type": variant.as_ref(),
+    "target.id": id.as_str(),
+    "operation": { "$in": operations },
+    "status": UpdateStatus::Complete.to_string(),
+  };
+  let latest = db_client()
+    .updates
+    .find_one(query.clone())
+    .sort(doc! { "start_ts": -1 })
+    .await
+    .context("Failed to query db for latest deploy")?
+    .context("No deploy found to roll back from")?;
+  query.insert("start_ts", doc! { "$lt": latest.start_ts });
+  query.insert("success", true);
+  query.insert("deployed_config", doc! { "$exists": true });
+  db_client()
+    .updates
+    .find_one(query)
+    .sort(doc! { "start_ts": -1 })
+    .await
+    .context("Failed to query db for previous deploy")?
+    .context(
+      "No previous successful deploy with recorded config to roll back to",
+    )
+}
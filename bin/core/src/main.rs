@@ -58,8 +58,23 @@ async fn app() -> anyhow::Result<()> {
     let _ = &auth::JWT_PROVIDER;
     // Init db_client check to crash on db init failure
     state::init_db_client().await;
+    // Run after db connection, to check for encrypted values
+    // before generating a missing key.
+    if let Err(e) =
+      helpers::encryption::generate_missing_encryption_key().await
+    {
+      error!("FATAL: Invalid 'encryption_key' | {e:#} | Exiting...");
+      std::process::exit(1)
+    }
+    // Init encryption keys to crash on invalid key.
+    if let Some(id) =
+      helpers::encryption::encryption_keys().current_id()
+    {
+      info!("Encryption Key: {id}");
+    }
     // Run after db connection.
     startup::on_startup().await;
+    api::metrics::init_run_totals().await;
 
     // Spawn background tasks
     monitor::spawn_monitoring_loops();
@@ -71,6 +86,7 @@ async fn app() -> anyhow::Result<()> {
     resource::spawn_action_state_refresh_loop();
     schedule::spawn_schedule_executor();
     helpers::prune::spawn_prune_loop();
+    helpers::log_archive::spawn_log_archive_loop();
   }
   .instrument(startup_span)
   .await;
@@ -0,0 +1,391 @@
+use std::{
+  collections::{HashMap, HashSet},
+  sync::OnceLock,
+};
+
+use anyhow::Context;
+use database::mungos::{find::find_collect, mongodb::bson::doc};
+use komodo_client::entities::{
+  ResourceTarget, ResourceTargetVariant,
+  alert::{Alert, AlertData, AlertDataVariant, SeverityLevel},
+  docker::container::ContainerStats,
+  server::ServerHealthState,
+};
+
+use crate::{
+  monitor::alert::{
+    AlertBuffer,
+    server::{
+      SendAlerts, ThresholdAlert, open_new_alerts, resolve_alerts,
+      threshold_alert, update_alerts,
+    },
+  },
+  state::{db_client, deployment_status_cache, stack_status_cache},
+};
+
+/// Container alerts close once usage drops
+/// this many percent below the warning threshold.
+const ALERT_PERCENTAGE_THRESHOLD: f64 = 5.0;
+
+type OpenContainerAlertMap =
+  HashMap<(ResourceTarget, AlertDataVariant, String), Alert>;
+
+/// Container alert buffer instance
+fn alert_buffer() -> &'static AlertBuffer {
+  static BUFFER: OnceLock<AlertBuffer> = OnceLock::new();
+  BUFFER.get_or_init(AlertBuffer::new)
+}
+
+/// The alert thresholds configured on a Deployment / Stack
+struct Thresholds {
+  cpu_warning: f64,
+  cpu_critical: f64,
+  mem_warning: f64,
+  mem_critical: f64,
+}
+
+/// A container to check against the thresholds
+struct ContainerToCheck<'a> {
+  resource_type: ResourceTargetVariant,
+  id: &'a str,
+  name: &'a str,
+  server_id: &'a str,
+  container: &'a str,
+  stats: &'a ContainerStats,
+  send: bool,
+  /// Whether the container's server is in a maintenance window
+  in_maintenance: bool,
+  thresholds: &'a Thresholds,
+}
+
+pub async fn alert_containers(
+  ts: i64,
+  server_names: &HashMap<String, String>,
+  servers_in_maintenance: &HashSet<String>,
+) {
+  if let Err(e) =
+    alert_containers_inner(ts, server_names, servers_in_maintenance)
+      .await
+  {
+    error!("Failed to check container alerts | {e:#}");
+  }
+}
+
+async fn alert_containers_inner(
+  ts: i64,
+  server_names: &HashMap<String, String>,
+  servers_in_maintenance: &HashSet<String>,
+) -> anyhow::Result<()> {
+  let threshold_filter = doc! {
+    "$or": [
+      { "config.container_cpu_warning": { "$gt": 0 } },
+      { "config.container_mem_warning": { "$gt": 0 } },
+    ]
+  };
+
+  let (deployments, stacks, open_alerts) = tokio::try_join!(
+    async {
+      find_collect(
+        &db_client().deployments,
+        threshold_filter.clone(),
+        None,
+      )
+      .await
+      .context("Failed to get deployments from db")
+    },
+    async {
+      find_collect(
+        &db_client().stacks,
+        threshold_filter.clone(),
+        None,
+      )
+      .await
+      .context("Failed to get stacks from db")
+    },
+    get_open_alerts(),
+  )?;
+
+  let mut alerts_to_open = Vec::<(Alert, SendAlerts)>::new();
+  let mut alerts_to_update = Vec::<(Alert, SendAlerts)>::new();
+  let mut alerts_to_close = Vec::<(Alert, SendAlerts)>::new();
+
+  let mut checked = Vec::new();
+
+  for deployment in &deployments {
+    let Some(status) =
+      deployment_status_cache().get(&deployment.id).await
+    else {
+      continue;
+    };
+    let Some(container) = &status.curr.container else {
+      continue;
+    };
+    let Some(stats) = &container.stats else {
+      continue;
+    };
+    let thresholds = Thresholds {
+      cpu_warning: deployment.config.container_cpu_warning,
+      cpu_critical: deployment.config.container_cpu_critical,
+      mem_warning: deployment.config.container_mem_warning,
+      mem_critical: deployment.config.container_mem_critical,
+    };
+    let container = ContainerToCheck {
+      resource_type: ResourceTargetVariant::Deployment,
+      id: &deployment.id,
+      name: &deployment.name,
+      server_id: &deployment.config.server_id,
+      container: &container.name,
+      stats,
+      send: deployment.config.send_alerts,
+      in_maintenance: servers_in_maintenance
+        .contains(&deployment.config.server_id),
+      thresholds: &thresholds,
+    };
+    checked.push((
+      ResourceTarget::Deployment(deployment.id.clone()),
+      container.container.to_string(),
+    ));
+    check_container(
+      ts,
+      container,
+      server_names,
+      &open_alerts,
+      &mut alerts_to_open,
+      &mut alerts_to_update,
+      &mut alerts_to_close,
+    );
+  }
+
+  for stack in &stacks {
+    let Some(status) = stack_status_cache().get(&stack.id).await
+    else {
+      continue;
+    };
+    let thresholds = Thresholds {
+      cpu_warning: stack.config.container_cpu_warning,
+      cpu_critical: stack.config.container_cpu_critical,
+      mem_warning: stack.config.container_mem_warning,
+      mem_critical: stack.config.container_mem_critical,
+    };
+    for service in &status.curr.services {
+      let Some(container) = &service.container else {
+        continue;
+      };
+      let Some(stats) = &container.stats else {
+        continue;
+      };
+      let container = ContainerToCheck {
+        resource_type: ResourceTargetVariant::Stack,
+        id: &stack.id,
+        name: &stack.name,
+        server_id: &stack.config.server_id,
+        container: &container.name,
+        stats,
+        send: stack.config.send_alerts,
+        in_maintenance: servers_in_maintenance
+          .contains(&stack.config.server_id),
+        thresholds: &thresholds,
+      };
+      checked.push((
+        ResourceTarget::Stack(stack.id.clone()),
+        container.container.to_string(),
+      ));
+      check_container(
+        ts,
+        container,
+        server_names,
+        &open_alerts,
+        &mut alerts_to_open,
+        &mut alerts_to_update,
+        &mut alerts_to_close,
+      );
+    }
+  }
+
+  // Close open alerts on containers which are no longer
+  // running, or whose resource no longer has thresholds configured.
+  for ((target, _, container), alert) in &open_alerts {
+    if !checked.iter().any(|(checked_target, checked_container)| {
+      checked_target == target && checked_container == container
+    }) {
+      alerts_to_close.push((alert.clone(), false));
+    }
+  }
+
+  tokio::join!(
+    open_new_alerts(&alerts_to_open),
+    update_alerts(&alerts_to_update),
+    resolve_alerts(&alerts_to_close),
+  );
+
+  Ok(())
+}
+
+fn check_container(
+  ts: i64,
+  ContainerToCheck {
+    resource_type,
+    id,
+    name,
+    server_id,
+    container,
+    stats,
+    send,
+    in_maintenance,
+    thresholds,
+  }: ContainerToCheck<'_>,
+  server_names: &HashMap<String, String>,
+  open_alerts: &OpenContainerAlertMap,
+  alerts_to_open: &mut Vec<(Alert, SendAlerts)>,
+  alerts_to_update: &mut Vec<(Alert, SendAlerts)>,
+  alerts_to_close: &mut Vec<(Alert, SendAlerts)>,
+) {
+  let target = match resource_type {
+    ResourceTargetVariant::Stack => {
+      ResourceTarget::Stack(id.to_string())
+    }
+    _ => ResourceTarget::Deployment(id.to_string()),
+  };
+  let buffer_key = format!("{id}:{container}");
+  let server_name = server_names.get(server_id).cloned();
+  let server_id =
+    (!server_id.is_empty()).then(|| server_id.to_string());
+
+  // ===================
+  // CONTAINER CPU
+  // ===================
+  if thresholds.cpu_warning > 0.0 {
+    let percentage = parse_perc(&stats.cpu_perc);
+    threshold_alert(
+      ThresholdAlert {
+        ts,
+        id: &buffer_key,
+        target: target.clone(),
+        variant: AlertDataVariant::ContainerCpu,
+        health: &health(
+          percentage,
+          thresholds.cpu_warning,
+          thresholds.cpu_critical,
+        ),
+        open: open_alerts.get(&(
+          target.clone(),
+          AlertDataVariant::ContainerCpu,
+          container.to_string(),
+        )),
+        send,
+        in_maintenance,
+        data: AlertData::ContainerCpu {
+          resource_type,
+          id: id.to_string(),
+          name: name.to_string(),
+          server_id: server_id.clone(),
+          server_name: server_name.clone(),
+          container: container.to_string(),
+          percentage,
+        },
+      },
+      alert_buffer(),
+      alerts_to_open,
+      alerts_to_update,
+      alerts_to_close,
+    );
+  }
+
+  // ===================
+  // CONTAINER MEM
+  // ===================
+  if thresholds.mem_warning > 0.0 {
+    let percentage = parse_perc(&stats.mem_perc);
+    threshold_alert(
+      ThresholdAlert {
+        ts,
+        id: &buffer_key,
+        target: target.clone(),
+        variant: AlertDataVariant::ContainerMem,
+        health: &health(
+          percentage,
+          thresholds.mem_warning,
+          thresholds.mem_critical,
+        ),
+        open: open_alerts.get(&(
+          target,
+          AlertDataVariant::ContainerMem,
+          container.to_string(),
+        )),
+        send,
+        in_maintenance,
+        data: AlertData::ContainerMem {
+          resource_type,
+          id: id.to_string(),
+          name: name.to_string(),
+          server_id,
+          server_name,
+          container: container.to_string(),
+          percentage,
+          usage: stats.mem_usage.clone(),
+        },
+      },
+      alert_buffer(),
+      alerts_to_open,
+      alerts_to_update,
+      alerts_to_close,
+    );
+  }
+}
+
+fn health(
+  percentage: f64,
+  warning: f64,
+  critical: f64,
+) -> ServerHealthState {
+  let mut health = ServerHealthState::default();
+  if critical > 0.0 && percentage >= critical {
+    health.level = SeverityLevel::Critical;
+  } else if percentage >= warning {
+    health.level = SeverityLevel::Warning;
+  } else if percentage < warning - ALERT_PERCENTAGE_THRESHOLD {
+    health.should_close_alert = true;
+  }
+  health
+}
+
+/// Docker stats report percentages like `12.34%`
+fn parse_perc(perc: &str) -> f64 {
+  perc
+    .trim()
+    .trim_end_matches('%')
+    .parse()
+    .unwrap_or_default()
+}
+
+async fn get_open_alerts() -> anyhow::Result<OpenContainerAlertMap> {
+  let alerts = find_collect(
+    &db_client().alerts,
+    doc! {
+      "resolved": false,
+      "data.type": { "$in": ["ContainerCpu", "ContainerMem"] },
+    },
+    None,
+  )
+  .await
+  .context("Failed to get open container alerts from db")?;
+
+  let map = alerts
+    .into_iter()
+    .filter_map(|alert| {
+      let container = match &alert.data {
+        AlertData::ContainerCpu { container, .. }
+        | AlertData::ContainerMem { container, .. } => {
+          container.clone()
+        }
+        _ => return None,
+      };
+      Some((
+        (alert.target.clone(), (&alert.data).into(), container),
+        alert,
+      ))
+    })
+    .collect();
+
+  Ok(map)
+}
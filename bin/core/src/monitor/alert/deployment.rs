@@ -83,6 +83,7 @@ pub async fn alert_deployments(
         level: SeverityLevel::Warning,
         resolved: true,
         resolved_ts: ts.into(),
+        escalation: Default::default(),
         target,
         data,
         ts,
@@ -0,0 +1,159 @@
+use std::str::FromStr;
+
+use anyhow::Context;
+use database::mungos::{
+  find::find_collect,
+  mongodb::bson::{Document, doc, oid::ObjectId},
+};
+use futures_util::future::join_all;
+use komodo_client::entities::{
+  alert::{Alert, SeverityLevel},
+  alerter::Alerter,
+};
+
+use crate::{
+  alert::{alerter_accepts_alert, send_alert_to_alerter},
+  state::db_client,
+};
+
+const MINUTE_MS: i64 = 60_000;
+
+/// Re-sends open alerts on alerters with a repeat interval,
+/// and escalates them to another alerter if they stay
+/// open without being acknowledged.
+pub async fn escalate_alerts(ts: i64) {
+  if let Err(e) = escalate_alerts_inner(ts).await {
+    error!("Failed to escalate alerts | {e:#}");
+  }
+}
+
+async fn escalate_alerts_inner(ts: i64) -> anyhow::Result<()> {
+  let alerters =
+    find_collect(&db_client().alerters, Document::new(), None)
+      .await
+      .context("Failed to get alerters from db")?;
+
+  if !alerters.iter().any(|alerter| {
+    alerter.config.enabled && alerter.config.escalation.is_enabled()
+  }) {
+    return Ok(());
+  }
+
+  let alerts = find_collect(
+    &db_client().alerts,
+    doc! {
+      "resolved": false,
+      "escalation.acknowledged": { "$ne": true },
+    },
+    None,
+  )
+  .await
+  .context("Failed to get open alerts from db")?;
+
+  let handles = alerts
+    .iter()
+    .filter(|alert| alert.level != SeverityLevel::Ok)
+    .map(|alert| escalate_alert(ts, &alerters, alert));
+
+  join_all(handles).await;
+
+  Ok(())
+}
+
+async fn escalate_alert(
+  ts: i64,
+  alerters: &[Alerter],
+  alert: &Alert,
+) {
+  let mut set = Document::new();
+  let mut escalated_to = Vec::<String>::new();
+
+  for alerter in alerters {
+    let policy = &alerter.config.escalation;
+    if !policy.is_enabled() || !alerter_accepts_alert(alerter, alert)
+    {
+      continue;
+    }
+
+    // ==================
+    //  REPEAT
+    // ==================
+    if policy.repeat_interval > 0 {
+      let last_notified = alert
+        .escalation
+        .notified
+        .get(&alerter.id)
+        .copied()
+        .unwrap_or(alert.ts);
+      if ts - last_notified >= policy.repeat_interval * MINUTE_MS {
+        // Only recorded when sent, so failures are retried.
+        match send_alert_to_alerter(alerter, alert).await {
+          Ok(_) => {
+            set.insert(
+              format!("escalation.notified.{}", alerter.id),
+              ts,
+            );
+          }
+          Err(e) => warn!("Failed to send repeat alert | {e:#}"),
+        }
+      }
+    }
+
+    // ==================
+    //  ESCALATE
+    // ==================
+    if policy.escalate_after == 0
+      || policy.escalate_to.is_empty()
+      || ts - alert.ts < policy.escalate_after * MINUTE_MS
+    {
+      continue;
+    }
+    let Some(target) = alerters.iter().find(|target| {
+      target.id == policy.escalate_to
+        || target.name == policy.escalate_to
+    }) else {
+      warn!(
+        "Alerter {} escalates to {}, but no alerter matches",
+        alerter.name, policy.escalate_to
+      );
+      continue;
+    };
+    if alert.escalation.escalated_to.contains(&target.id)
+      || escalated_to.contains(&target.id)
+    {
+      continue;
+    }
+    // Only recorded when sent, so failures are retried.
+    match send_alert_to_alerter(target, alert).await {
+      Ok(_) => escalated_to.push(target.id.clone()),
+      Err(e) => warn!("Failed to send escalated alert | {e:#}"),
+    }
+  }
+
+  if set.is_empty() && escalated_to.is_empty() {
+    return;
+  }
+
+  let Ok(id) = ObjectId::from_str(&alert.id) else {
+    return;
+  };
+
+  let mut update = Document::new();
+  if !set.is_empty() {
+    update.insert("$set", set);
+  }
+  if !escalated_to.is_empty() {
+    update.insert(
+      "$addToSet",
+      doc! { "escalation.escalated_to": { "$each": escalated_to } },
+    );
+  }
+
+  if let Err(e) = db_client()
+    .alerts
+    .update_one(doc! { "_id": id }, update)
+    .await
+  {
+    warn!("Failed to update alert escalation state | {e:#}");
+  }
+}
@@ -1,15 +1,27 @@
-use std::{collections::HashMap, sync::Mutex};
+use std::{
+  collections::{HashMap, HashSet},
+  sync::Mutex,
+};
 
 use anyhow::Context;
+use database::mungos::mongodb::bson::{Document, doc, to_bson};
 use komodo_client::entities::{
-  alert::AlertDataVariant, permission::PermissionLevel,
-  resource::ResourceQuery, server::Server, swarm::Swarm,
+  alert::{Alert, AlertDataVariant},
+  permission::PermissionLevel,
+  resource::ResourceQuery,
+  server::Server,
+  swarm::Swarm,
   user::system_user,
 };
 
-use crate::resource;
+use crate::{
+  alert::flush_alert_digests,
+  helpers::maintenance::is_in_maintenance, resource,
+};
 
+mod container;
 mod deployment;
+mod escalation;
 mod server;
 mod stack;
 mod swarm;
@@ -24,12 +36,47 @@ pub async fn check_alerts(ts: i64) {
   let (servers, server_names) =
     server.inspect_err(|e| error!("{e:#}")).unwrap_or_default();
 
+  // Container alerts follow the maintenance windows of their server.
+  let servers_in_maintenance = servers
+    .values()
+    .filter(|server| {
+      is_in_maintenance(&server.config.maintenance_windows, ts)
+    })
+    .map(|server| server.id.clone())
+    .collect::<HashSet<_>>();
+
   tokio::join!(
     swarm::alert_swarms(ts, swarms),
     server::alert_servers(ts, servers),
     deployment::alert_deployments(ts, &swarm_names, &server_names),
-    stack::alert_stacks(ts, &swarm_names, &server_names)
+    stack::alert_stacks(ts, &swarm_names, &server_names),
+    container::alert_containers(
+      ts,
+      &server_names,
+      &servers_in_maintenance,
+    ),
   );
+
+  tokio::join!(
+    escalation::escalate_alerts(ts),
+    flush_alert_digests(ts)
+  );
+}
+
+/// The `$set` update for an open alert changed by the monitor.
+/// Only includes the fields the monitor owns, so it can't overwrite
+/// concurrent changes to the rest of the alert, like acknowledgement.
+fn monitor_alert_update(alert: &Alert) -> anyhow::Result<Document> {
+  Ok(doc! {
+    "$set": {
+      "level": to_bson(&alert.level)
+        .context("failed to convert alert level to bson")?,
+      "data": to_bson(&alert.data)
+        .context("failed to convert alert data to bson")?,
+      "resolved": alert.resolved,
+      "resolved_ts": alert.resolved_ts,
+    }
+  })
 }
 
 async fn get_all_swarms_map()
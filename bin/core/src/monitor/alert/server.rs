@@ -7,23 +7,26 @@ use database::mongo_indexed::Indexed;
 use database::mungos::{
   bulk_update::{self, BulkUpdate},
   find::find_collect,
-  mongodb::bson::{doc, oid::ObjectId, to_bson},
+  mongodb::bson::{doc, oid::ObjectId},
 };
 use komodo_client::entities::{
   ResourceTarget,
   alert::{Alert, AlertData, AlertDataVariant, SeverityLevel},
   komodo_timestamp, optional_string,
-  server::{Server, ServerState},
+  server::{Server, ServerHealthState, ServerState},
 };
 
 use crate::{
   alert::send_alerts,
   helpers::maintenance::is_in_maintenance,
-  monitor::alert::AlertBuffer,
+  monitor::{
+    alert::AlertBuffer,
+    helpers::{load_average_per_core, network_mb_per_sec},
+  },
   state::{db_client, server_status_cache},
 };
 
-type SendAlerts = bool;
+pub(super) type SendAlerts = bool;
 type OpenAlertMap<T = AlertDataVariant> =
   HashMap<ResourceTarget, HashMap<T, Alert>>;
 type OpenDiskAlertMap = OpenAlertMap<PathBuf>;
@@ -92,6 +95,7 @@ pub async fn alert_servers(
             ts,
             resolved: false,
             resolved_ts: None,
+            escalation: Default::default(),
             level: SeverityLevel::Critical,
             target: ResourceTarget::Server(server_status.id.clone()),
             data: AlertData::ServerUnreachable {
@@ -185,6 +189,7 @@ pub async fn alert_servers(
             ts,
             resolved: false,
             resolved_ts: None,
+            escalation: Default::default(),
             level: SeverityLevel::Warning,
             target: ResourceTarget::Server(server_status.id.clone()),
             data: AlertData::ServerVersionMismatch {
@@ -290,6 +295,7 @@ pub async fn alert_servers(
             ts,
             resolved: false,
             resolved_ts: None,
+            escalation: Default::default(),
             level: health.cpu.level,
             target: ResourceTarget::Server(server_status.id.clone()),
             data: AlertData::ServerCpu {
@@ -391,6 +397,7 @@ pub async fn alert_servers(
             ts,
             resolved: false,
             resolved_ts: None,
+            escalation: Default::default(),
             level: health.mem.level,
             target: ResourceTarget::Server(server_status.id.clone()),
             data: AlertData::ServerMem {
@@ -472,6 +479,87 @@ pub async fn alert_servers(
         .reset(server_status.id.clone(), AlertDataVariant::ServerMem),
     }
 
+    // ===================
+    // SERVER LOAD
+    // ===================
+    let load_average = server_status
+      .system_stats
+      .as_ref()
+      .map(|s| s.load_average.five)
+      .unwrap_or_default();
+    let per_core = load_average_per_core(
+      load_average,
+      server_status
+        .system_info
+        .as_ref()
+        .and_then(|info| info.core_count),
+    );
+    threshold_alert(
+      ThresholdAlert {
+        ts,
+        id: &server_status.id,
+        target: ResourceTarget::Server(server_status.id.clone()),
+        variant: AlertDataVariant::ServerLoadAverage,
+        health: &health.load,
+        open: server_alerts.and_then(|alerts| {
+          alerts.get(&AlertDataVariant::ServerLoadAverage)
+        }),
+        send: server.config.send_load_alerts,
+        in_maintenance,
+        data: AlertData::ServerLoadAverage {
+          id: server_status.id.clone(),
+          name: server.name.clone(),
+          region: optional_string(&server.config.region),
+          load_average,
+          per_core,
+        },
+      },
+      buffer,
+      &mut alerts_to_open,
+      &mut alerts_to_update,
+      &mut alerts_to_close,
+    );
+
+    // ===================
+    // SERVER NETWORK
+    // ===================
+    let (ingress_mb, egress_mb) = server_status
+      .system_stats
+      .as_ref()
+      .map(|s| {
+        network_mb_per_sec(
+          s.network_ingress_bytes,
+          s.network_egress_bytes,
+          s.polling_rate,
+        )
+      })
+      .unwrap_or_default();
+    threshold_alert(
+      ThresholdAlert {
+        ts,
+        id: &server_status.id,
+        target: ResourceTarget::Server(server_status.id.clone()),
+        variant: AlertDataVariant::ServerNetwork,
+        health: &health.network,
+        open: server_alerts.and_then(|alerts| {
+          alerts.get(&AlertDataVariant::ServerNetwork)
+        }),
+        send: server.config.send_network_alerts,
+        in_maintenance,
+        data: AlertData::ServerNetwork {
+          id: server_status.id.clone(),
+          name: server.name.clone(),
+          region: optional_string(&server.config.region),
+          ingress_mb,
+          egress_mb,
+        },
+      },
+      buffer,
+      &mut alerts_to_open,
+      &mut alerts_to_update,
+      &mut alerts_to_close,
+    );
+
     // ===================
     // SERVER DISK
     // ===================
@@ -518,6 +606,7 @@ pub async fn alert_servers(
               ts,
               resolved: false,
               resolved_ts: None,
+              escalation: Default::default(),
               level: health.level,
               target: ResourceTarget::Server(
                 server_status.id.clone(),
@@ -615,7 +704,105 @@ pub async fn alert_servers(
   );
 }
 
-async fn open_new_alerts(alerts: &[(Alert, SendAlerts)]) {
+/// Inputs for a simple threshold alert, like
+/// the Server LOAD / NETWORK or Container CPU / MEM alerts.
+pub(super) struct ThresholdAlert<'a> {
+  pub ts: i64,
+  /// Used with the variant as the alert buffer key
+  pub id: &'a str,
+  pub target: ResourceTarget,
+  pub variant: AlertDataVariant,
+  pub health: &'a ServerHealthState,
+  /// The currently open alert, if any
+  pub open: Option<&'a Alert>,
+  /// Whether alerts are enabled for this target
+  pub send: bool,
+  pub in_maintenance: bool,
+  /// The latest alert data
+  pub data: AlertData,
+}
+
+/// Decide whether to open, update, or close a threshold alert,
+/// following the same rules as the Server CPU / MEM alerts.
+pub(super) fn threshold_alert(
+  ThresholdAlert {
+    ts,
+    id,
+    target,
+    variant,
+    health,
+    open,
+    send,
+    in_maintenance,
+    data,
+  }: ThresholdAlert<'_>,
+  buffer: &AlertBuffer,
+  alerts_to_open: &mut Vec<(Alert, SendAlerts)>,
+  alerts_to_update: &mut Vec<(Alert, SendAlerts)>,
+  alerts_to_close: &mut Vec<(Alert, SendAlerts)>,
+) {
+  match (health.level, open.cloned(), health.should_close_alert, send)
+  {
+    (
+      SeverityLevel::Warning | SeverityLevel::Critical,
+      None,
+      _,
+      false,
+    ) => {}
+    (
+      SeverityLevel::Warning | SeverityLevel::Critical,
+      None,
+      _,
+      true,
+    ) => {
+      if !in_maintenance
+        && buffer.ready_to_open(id.to_string(), variant)
+      {
+        let alert = Alert {
+          id: Default::default(),
+          ts,
+          resolved: false,
+          resolved_ts: None,
+          escalation: Default::default(),
+          level: health.level,
+          target,
+          data,
+        };
+        alerts_to_open.push((alert, send));
+      }
+    }
+    (
+      SeverityLevel::Warning | SeverityLevel::Critical,
+      Some(mut alert),
+      _,
+      true,
+    ) => {
+      // modify alert level only if it has increased and not in maintenance
+      if !in_maintenance && alert.level < health.level {
+        alert.level = health.level;
+        alert.data = data;
+        alerts_to_update.push((alert, send));
+      }
+    }
+    (
+      SeverityLevel::Warning | SeverityLevel::Critical,
+      Some(alert),
+      _,
+      false,
+    ) => {
+      alerts_to_close.push((alert, send));
+    }
+    (SeverityLevel::Ok, Some(mut alert), true, _) => {
+      alert.data = data;
+      alerts_to_close.push((alert, send))
+    }
+    (SeverityLevel::Ok, _, _, _) => {
+      buffer.reset(id.to_string(), variant)
+    }
+  }
+}
+
+pub(super) async fn open_new_alerts(alerts: &[(Alert, SendAlerts)]) {
   if alerts.is_empty() {
     return;
   }
@@ -662,7 +849,7 @@ async fn open_new_alerts(alerts: &[(Alert, SendAlerts)]) {
   send_alerts(&alerts).await
 }
 
-async fn update_alerts(alerts: &[(Alert, SendAlerts)]) {
+pub(super) async fn update_alerts(alerts: &[(Alert, SendAlerts)]) {
   if alerts.is_empty() {
     return;
   }
@@ -671,7 +858,7 @@ async fn update_alerts(alerts: &[(Alert, SendAlerts)]) {
     let updates = alerts.iter().map(|(alert, _)| {
         let update = BulkUpdate {
           query: doc! { "_id": ObjectId::from_str(&alert.id).context("failed to convert alert id to ObjectId")? },
-          update: doc! { "$set": to_bson(alert).context("failed to convert alert to bson")? }
+          update: super::monitor_alert_update(alert)?
         };
         anyhow::Ok(update)
       })
@@ -709,7 +896,7 @@ async fn update_alerts(alerts: &[(Alert, SendAlerts)]) {
   }
 }
 
-async fn resolve_alerts(alerts: &[(Alert, SendAlerts)]) {
+pub(super) async fn resolve_alerts(alerts: &[(Alert, SendAlerts)]) {
   if alerts.is_empty() {
     return;
   }
@@ -78,6 +78,7 @@ pub async fn alert_stacks(
         level: SeverityLevel::Warning,
         resolved: true,
         resolved_ts: ts.into(),
+        escalation: Default::default(),
         target,
         data,
         ts,
@@ -5,7 +5,7 @@ use database::mongo_indexed::Indexed;
 use database::mungos::{
   bulk_update::{self, BulkUpdate},
   find::find_collect,
-  mongodb::bson::{doc, oid::ObjectId, to_bson},
+  mongodb::bson::{doc, oid::ObjectId},
 };
 use komodo_client::entities::{
   ResourceTarget,
@@ -99,6 +99,7 @@ pub async fn alert_swarms(
             ts,
             resolved: false,
             resolved_ts: None,
+            escalation: Default::default(),
             level: SeverityLevel::Critical,
             target: ResourceTarget::Swarm(swarm_status.id.clone()),
             data: AlertData::SwarmUnhealthy {
@@ -225,7 +226,7 @@ async fn update_alerts(alerts: &[(Alert, SendAlerts)]) {
     let updates = alerts.iter().map(|(alert, _)| {
         let update = BulkUpdate {
           query: doc! { "_id": ObjectId::from_str(&alert.id).context("failed to convert alert id to ObjectId")? },
-          update: doc! { "$set": to_bson(alert).context("failed to convert alert to bson")? }
+          update: super::monitor_alert_update(alert)?
         };
         anyhow::Ok(update)
       })
@@ -1,4 +1,6 @@
+use async_timing_util::get_timelength_in_ms;
 use komodo_client::entities::{
+  Timelength,
   alert::SeverityLevel,
   deployment::{Deployment, DeploymentState},
   docker::DockerLists,
@@ -27,8 +29,9 @@ pub async fn insert_server_status(
   docker: Option<DockerLists>,
   err: impl Into<Option<Serror>>,
 ) {
-  let health =
-    system_stats.as_ref().map(|s| get_server_health(server, s));
+  let health = system_stats
+    .as_ref()
+    .map(|s| get_server_health(server, system_info.as_ref(), s));
   server_status_cache()
     .insert(
       server.id.clone(),
@@ -112,14 +115,58 @@ pub async fn insert_repos_status_unknown(repos: Vec<Repo>) {
 }
 
 const ALERT_PERCENTAGE_THRESHOLD: f32 = 5.0;
+/// Load / network alerts close once below this ratio of the warning threshold.
+const LOAD_CLOSE_RATIO: f64 = 0.9;
+const NETWORK_CLOSE_RATIO: f64 = 0.9;
+const BYTES_PER_MB: f64 = 1048576.0;
+
+/// The load average divided by the number of cpu cores.
+/// If the core count is unknown, the raw load average is used.
+pub fn load_average_per_core(
+  load_average: f64,
+  core_count: Option<u32>,
+) -> f64 {
+  match core_count {
+    Some(cores) if cores > 0 => load_average / cores as f64,
+    _ => load_average,
+  }
+}
+
+/// Periphery reports network bytes transferred since the last poll.
+/// Converts them into (ingress, egress) MB/s.
+pub fn network_mb_per_sec(
+  ingress_bytes: f64,
+  egress_bytes: f64,
+  polling_rate: Timelength,
+) -> (f64, f64) {
+  let secs = get_timelength_in_ms(
+    polling_rate
+      .to_string()
+      .parse()
+      .unwrap_or(async_timing_util::Timelength::FiveSeconds),
+  ) as f64
+    / 1000.0;
+  if secs <= 0.0 {
+    return (0.0, 0.0);
+  }
+  (
+    ingress_bytes / BYTES_PER_MB / secs,
+    egress_bytes / BYTES_PER_MB / secs,
+  )
+}
 
 fn get_server_health(
   server: &Server,
+  system_info: Option<&SystemInformation>,
   SystemStats {
     cpu_perc,
+    load_average,
     mem_used_gb,
     mem_total_gb,
     disks,
+    network_ingress_bytes,
+    network_egress_bytes,
+    polling_rate,
     ..
   }: &SystemStats,
 ) -> ServerHealth {
@@ -130,6 +177,10 @@ fn get_server_health(
     mem_critical,
     disk_warning,
     disk_critical,
+    load_warning,
+    load_critical,
+    network_warning,
+    network_critical,
     ..
   } = &server.config;
   let mut health = ServerHealth::default();
@@ -153,6 +204,32 @@ fn get_server_health(
     health.mem.should_close_alert = true
   }
 
+  let load_per_core = load_average_per_core(
+    load_average.five,
+    system_info.and_then(|info| info.core_count),
+  );
+  if load_per_core >= *load_critical {
+    health.load.level = SeverityLevel::Critical
+  } else if load_per_core >= *load_warning {
+    health.load.level = SeverityLevel::Warning
+  } else if load_per_core < load_warning * LOAD_CLOSE_RATIO {
+    health.load.should_close_alert = true
+  }
+
+  let (ingress_mb, egress_mb) = network_mb_per_sec(
+    *network_ingress_bytes,
+    *network_egress_bytes,
+    *polling_rate,
+  );
+  let network_mb = ingress_mb + egress_mb;
+  if network_mb >= *network_critical {
+    health.network.level = SeverityLevel::Critical
+  } else if network_mb >= *network_warning {
+    health.network.level = SeverityLevel::Warning
+  } else if network_mb < network_warning * NETWORK_CLOSE_RATIO {
+    health.network.should_close_alert = true
+  }
+
   for SingleDiskUsage {
     mount,
     used_gb,
@@ -41,6 +41,7 @@ mod record;
 mod resources;
 mod swarm;
 
+pub use helpers::network_mb_per_sec;
 pub use swarm::refresh_swarm_cache;
 
 const ADDITIONAL_MS: u128 = 500;
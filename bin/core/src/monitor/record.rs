@@ -28,6 +28,9 @@ pub async fn record_server_stats(ts: i64) {
         disks: stats.disks.clone(),
         network_ingress_bytes: stats.network_ingress_bytes,
         network_egress_bytes: stats.network_egress_bytes,
+        network_usage_interface: stats
+          .network_usage_interface
+          .clone(),
       })
     })
     .collect::<Vec<_>>();
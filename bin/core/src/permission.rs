@@ -1,8 +1,10 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, str::FromStr};
 
 use anyhow::{Context, anyhow};
 use database::{
-  bson::Document, mongo_indexed::doc, mungos::find::find_collect,
+  bson::{Document, oid::ObjectId},
+  mongo_indexed::doc,
+  mungos::find::find_collect,
 };
 use futures_util::{FutureExt, future::BoxFuture};
 use indexmap::IndexSet;
@@ -12,6 +14,7 @@ use komodo_client::{
     ResourceTarget,
     action::Action,
     alerter::Alerter,
+    api_key::ApiKeyScopes,
     build::Build,
     builder::Builder,
     deployment::Deployment,
@@ -44,6 +47,8 @@ pub async fn get_check_permissions<T: KomodoResource>(
 ) -> anyhow::Result<Resource<T::Config, T::Info>> {
   let resource = get::<T>(id_or_name).await?;
 
+  check_api_key_scope::<T>(user, &resource)?;
+
   // Allow all if admin
   if user.admin {
     return Ok(resource);
@@ -82,6 +87,67 @@ pub async fn get_check_permissions<T: KomodoResource>(
   }
 }
 
+/// Scoped api keys can only access the resources in scope,
+/// regardless of the user permissions.
+fn check_api_key_scope<T: KomodoResource>(
+  user: &User,
+  resource: &Resource<T::Config, T::Info>,
+) -> anyhow::Result<()> {
+  let Some(scopes) = &user.api_key_scopes else {
+    return Ok(());
+  };
+  if scopes.allows_resource(
+    T::resource_type(),
+    &resource.id,
+    &resource.name,
+    &resource.tags,
+  ) {
+    Ok(())
+  } else {
+    Err(anyhow!(
+      "Api key is not scoped to access this {}",
+      T::resource_type()
+    ))
+  }
+}
+
+/// Adds the api key resource scope to the resource query filters.
+fn with_api_key_scope<T: KomodoResource>(
+  filters: Option<Document>,
+  user: &User,
+) -> Option<Document> {
+  let Some(scopes) = user
+    .api_key_scopes
+    .as_ref()
+    .filter(|scopes| scopes.restricts_resources())
+  else {
+    return filters;
+  };
+  let resource_type = T::resource_type();
+  let ids_or_names = scopes
+    .resources
+    .iter()
+    .map(|target| target.extract_variant_id())
+    .filter(|(variant, _)| *variant == resource_type)
+    .map(|(_, id_or_name)| id_or_name.clone())
+    .collect::<Vec<_>>();
+  let ids = ids_or_names
+    .iter()
+    .filter_map(|id| ObjectId::from_str(id).ok())
+    .collect::<Vec<_>>();
+  let scope = doc! {
+    "$or": [
+      { "_id": { "$in": ids } },
+      { "name": { "$in": ids_or_names } },
+      { "tags": { "$in": scopes.tags.clone() } },
+    ]
+  };
+  match filters {
+    Some(filters) => Some(doc! { "$and": [filters, scope] }),
+    None => Some(scope),
+  }
+}
+
 pub fn get_user_permission_on_resource<'a, T: KomodoResource>(
   user: &'a User,
   resource_id: &'a str,
@@ -181,6 +247,8 @@ pub async fn list_resources_for_user<T: KomodoResource>(
   user: &User,
   permission: PermissionLevelAndSpecifics,
 ) -> anyhow::Result<Vec<Resource<T::Config, T::Info>>> {
+  let filters = with_api_key_scope::<T>(filters.into(), user);
+
   // Check admin
   if user.admin {
     return list_all_resources::<T>(filters).await;
@@ -310,6 +378,16 @@ pub async fn list_resource_ids_for_user<T: KomodoResource>(
   user: &User,
   permission: PermissionLevelAndSpecifics,
 ) -> anyhow::Result<Option<Vec<String>>> {
+  // Scoped api keys always need to filter by resource id
+  if has_resource_scoped_api_key(user) {
+    let ids = list_resources_for_user::<T>(filters, user, permission)
+      .await?
+      .into_iter()
+      .map(|resource| resource.id)
+      .collect();
+    return Ok(Some(ids));
+  }
+
   // Check admin
   if user.admin {
     return Ok(None);
@@ -365,7 +443,9 @@ pub async fn user_resource_target_query(
   user: &User,
   incoming_query: Option<Document>,
 ) -> anyhow::Result<Option<Document>> {
-  if user.admin || core_config().transparent_mode {
+  if (user.admin || core_config().transparent_mode)
+    && !has_resource_scoped_api_key(user)
+  {
     Ok(incoming_query)
   } else {
     let swarm_query = list_resource_ids_for_user::<Swarm>(
@@ -559,6 +639,15 @@ pub async fn user_resource_target_query(
   }
 }
 
+/// Whether the request uses an api key which can only access some resources.
+/// These always need the resource checks, even for admins.
+pub fn has_resource_scoped_api_key(user: &User) -> bool {
+  user
+    .api_key_scopes
+    .as_ref()
+    .is_some_and(ApiKeyScopes::restricts_resources)
+}
+
 pub async fn check_user_target_access(
   target: &ResourceTarget,
   user: &User,
@@ -558,7 +558,7 @@ pub async fn create<T: KomodoResource>(
 
 pub async fn update<T: KomodoResource>(
   id_or_name: &str,
-  mut config: T::PartialConfig,
+  config: T::PartialConfig,
   user: &User,
 ) -> anyhow::Result<Resource<T::Config, T::Info>> {
   let resource = get_check_permissions::<T>(
@@ -572,7 +572,30 @@ pub async fn update<T: KomodoResource>(
     return Err(anyhow!("{} busy", T::resource_type()));
   }
 
-  T::validate_update_config(&resource.id, &mut config, user).await?;
+  update_config::<T>(resource, config, user, user).await
+}
+
+/// Restores a config the resource was previously deployed with,
+/// for rollbacks. Only needs the Execute permission the caller
+/// already checked, rather than Write. The config is validated as the
+/// system user, as it was already validated when first written.
+/// Still records the update with the config diff, operated by `user`.
+pub async fn restore_config<T: KomodoResource>(
+  resource: Resource<T::Config, T::Info>,
+  config: T::PartialConfig,
+  user: &User,
+) -> anyhow::Result<Resource<T::Config, T::Info>> {
+  update_config::<T>(resource, config, system_user(), user).await
+}
+
+async fn update_config<T: KomodoResource>(
+  resource: Resource<T::Config, T::Info>,
+  mut config: T::PartialConfig,
+  validate_as: &User,
+  user: &User,
+) -> anyhow::Result<Resource<T::Config, T::Info>> {
+  T::validate_update_config(&resource.id, &mut config, validate_as)
+    .await?;
 
   // Gets a diff object.
   let diff = resource.config.partial_diff(config);
@@ -619,7 +642,7 @@ pub async fn update<T: KomodoResource>(
   .context("Failed to export resource toml after update");
 
   let mut update = make_update(
-    resource_target::<T>(id),
+    resource_target::<T>(id.clone()),
     T::update_operation(),
     user,
   );
@@ -637,7 +660,7 @@ pub async fn update<T: KomodoResource>(
       .push_simple_log("Failed export", format_serror(&e.into())),
   }
 
-  let updated = get::<T>(id_or_name).await?;
+  let updated = get::<T>(&id).await?;
 
   T::post_update(&updated, &mut update).await?;
 
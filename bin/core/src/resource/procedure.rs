@@ -284,6 +284,9 @@ async fn validate_config(
           (UnpauseDeployment, Deployment, deployment),
           (StopDeployment, Deployment, deployment),
           (DestroyDeployment, Deployment, deployment),
+          (RollbackDeployment, Deployment, deployment),
+          (BackupDeploymentVolumes, Deployment, deployment),
+          (RestoreDeploymentVolumes, Deployment, deployment),
           // Repo
           (CloneRepo, Repo, repo),
           (PullRepo, Repo, repo),
@@ -323,7 +326,10 @@ async fn validate_config(
           (UnpauseStack, Stack, stack),
           (StopStack, Stack, stack),
           (DestroyStack, Stack, stack),
+          (RollbackStack, Stack, stack),
           (RunStackService, Stack, stack),
+          (BackupStackVolumes, Stack, stack),
+          (RestoreStackVolumes, Stack, stack),
           // Alerter
           (TestAlerter, Alerter, alerter),
           // Swarm
@@ -355,9 +361,11 @@ async fn validate_config(
         admin_only: [
           (ClearRepoCache, "Non admin user cannot clear repo cache"),
           (BackupCoreDatabase, "Non admin user cannot trigger core database backup"),
+          (RestoreCoreDatabase, "Non admin user cannot trigger core database restore"),
           (GlobalAutoUpdate, "Non admin user cannot trigger global auto update"),
           (RotateAllServerKeys, "Non admin user cannot trigger rotate all server keys"),
           (RotateCoreKeys, "Non admin user cannot trigger rotate core keys"),
+          (RotateEncryptionKey, "Non admin user cannot trigger rotate encryption key"),
         ],
       );
     }
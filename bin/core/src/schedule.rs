@@ -69,6 +69,7 @@ pub fn spawn_schedule_executor() {
                       target: ResourceTarget::Action(id.clone()),
                       ts: komodo_timestamp(),
                       resolved_ts: Some(komodo_timestamp()),
+                      escalation: Default::default(),
                       resolved: true,
                       level: SeverityLevel::Ok,
                       data: AlertData::ScheduleRun {
@@ -141,6 +142,7 @@ pub fn spawn_schedule_executor() {
                       target: ResourceTarget::Procedure(id.clone()),
                       ts: komodo_timestamp(),
                       resolved_ts: Some(komodo_timestamp()),
+                      escalation: Default::default(),
                       resolved: true,
                       level: SeverityLevel::Ok,
                       data: AlertData::ScheduleRun {
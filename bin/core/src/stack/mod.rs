@@ -11,6 +11,7 @@ use crate::{
 };
 
 pub mod execute;
+pub mod preview;
 pub mod remote;
 pub mod services;
 
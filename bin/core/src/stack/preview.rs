@@ -0,0 +1,163 @@
+use anyhow::{Context, anyhow};
+use database::mungos::mongodb::bson::doc;
+use komodo_client::{
+  api::execute::DeployStack,
+  entities::{
+    stack::{Stack, StackConfig},
+    user::git_webhook_user,
+  },
+};
+use mogh_resolver::Resolve;
+use uuid::Uuid;
+
+use crate::{
+  api::execute::{ExecuteArgs, ExecuteRequest},
+  helpers::update::init_execution_update,
+  resource,
+  state::db_client,
+};
+
+/// Replaced with the preview id (eg `pr-12`) in all the
+/// preview Stack config fields which support interpolation.
+pub const PREVIEW_ID_VARIABLE: &str = "[[PREVIEW_ID]]";
+
+fn preview_id(number: u64) -> String {
+  format!("pr-{number}")
+}
+
+fn preview_stack_name(template: &Stack, number: u64) -> String {
+  format!("{}-{}", template.name, preview_id(number))
+}
+
+/// The template config, pointed at the pull request branch,
+/// with a unique compose project name and the preview id filled in.
+fn preview_stack_config(
+  template: &Stack,
+  number: u64,
+  branch: &str,
+) -> StackConfig {
+  let preview_id = preview_id(number);
+  let mut config = template.config.clone();
+  config.branch = branch.to_string();
+  config.commit = String::new();
+  config.project_name =
+    format!("{}-{preview_id}", template.project_name(true));
+  // Absolute clone paths would be shared with the template.
+  if !config.clone_path.is_empty() {
+    config.clone_path = format!(
+      "{}/{preview_id}",
+      config.clone_path.trim_end_matches('/')
+    );
+  }
+  for field in [
+    &mut config.file_contents,
+    &mut config.environment,
+    &mut config.pre_deploy.command,
+    &mut config.post_deploy.command,
+    &mut config.compose_cmd_wrapper,
+  ]
+  .into_iter()
+  .chain(&mut config.extra_args)
+  .chain(&mut config.build_extra_args)
+  {
+    *field = field.replace(PREVIEW_ID_VARIABLE, &preview_id);
+  }
+  // The preview is updated by the template's pull request webhook.
+  config.webhook_enabled = false;
+  config
+}
+
+/// Finds the preview Stack by name, making sure it was created
+/// from the template so unrelated Stacks are never touched.
+async fn find_preview_stack(
+  template: &Stack,
+  name: &str,
+) -> anyhow::Result<Option<Stack>> {
+  let Some(stack) = db_client()
+    .stacks
+    .find_one(doc! { "name": name })
+    .await
+    .context("Failed to query db for preview stack")?
+  else {
+    return Ok(None);
+  };
+  if stack.info.preview_template.as_ref() != Some(&template.id) {
+    return Err(anyhow!(
+      "Stack {name} exists but is not a preview of template {}",
+      template.name
+    ));
+  }
+  Ok(Some(stack))
+}
+
+/// Creates the preview Stack for the pull request from the template
+/// if it doesn't exist yet, then deploys it.
+pub async fn deploy_preview_stack(
+  template: &Stack,
+  number: u64,
+  branch: &str,
+) -> anyhow::Result<()> {
+  let name = preview_stack_name(template, number);
+  let user = git_webhook_user().to_owned();
+
+  let stack = match find_preview_stack(template, &name).await? {
+    Some(stack) => stack,
+    None => {
+      info!(
+        "Creating preview stack {name} from template {}",
+        template.name
+      );
+      let stack = resource::create::<Stack>(
+        &name,
+        preview_stack_config(template, number, branch).into(),
+        None,
+        &user,
+      )
+      .await
+      .map_err(|e| e.error)?;
+      db_client()
+        .stacks
+        .update_one(
+          doc! { "name": &name },
+          doc! { "$set": { "info.preview_template": &template.id } },
+        )
+        .await
+        .context("Failed to record preview template on db")?;
+      stack
+    }
+  };
+
+  let req = ExecuteRequest::DeployStack(DeployStack {
+    stack: stack.id,
+    services: Vec::new(),
+    stop_time: None,
+  });
+  let update = init_execution_update(&req, &user).await?;
+  let ExecuteRequest::DeployStack(req) = req else {
+    unreachable!()
+  };
+  req
+    .resolve(&ExecuteArgs {
+      user,
+      update,
+      task_id: Uuid::new_v4(),
+    })
+    .await
+    .map_err(|e| e.error)?;
+  Ok(())
+}
+
+/// Deletes the preview Stack for the pull request, if it exists.
+/// Deleting the Stack also destroys it.
+pub async fn delete_preview_stack(
+  template: &Stack,
+  number: u64,
+) -> anyhow::Result<()> {
+  let name = preview_stack_name(template, number);
+  if find_preview_stack(template, &name).await?.is_none() {
+    return Ok(());
+  }
+  info!("Deleting preview stack {name}");
+  resource::delete::<Stack>(&name, git_webhook_user()).await?;
+  Ok(())
+}
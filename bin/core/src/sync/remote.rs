@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use anyhow::{Context, anyhow};
 use komodo_client::entities::{
   RepoExecutionArgs, RepoExecutionResponse,
@@ -65,39 +67,17 @@ async fn get_files_on_host(
 
 async fn get_repo(
   sync: &ResourceSync,
-  mut clone_args: RepoExecutionArgs,
+  clone_args: RepoExecutionArgs,
 ) -> anyhow::Result<RemoteResources> {
-  let access_token = if let Some(account) = &clone_args.account {
-    git_token(&clone_args.provider, account, |https| clone_args.https = https)
-      .await
-      .with_context(
-        || format!("Failed to get git token in call to db. Stopping run. | {} | {account}", clone_args.provider),
-      )?
-  } else {
-    None
-  };
-
-  let repo_path =
-    clone_args.unique_path(&core_config().repo_directory)?;
-  clone_args.destination = Some(repo_path.display().to_string());
-
   let (
+    repo_path,
     RepoExecutionResponse {
       mut logs,
       commit_hash,
       commit_message,
       ..
     },
-    _,
-  ) = git::pull_or_clone(
-    clone_args,
-    &core_config().repo_directory,
-    access_token,
-  )
-  .await
-  .with_context(|| {
-    format!("Failed to update resource repo at {repo_path:?}")
-  })?;
+  ) = pull_sync_repo(clone_args).await?;
 
   // Ensure clone / pull successful,
   // propogate error log -> 'errored' and return.
@@ -168,3 +148,72 @@ async fn get_ui_defined(
     message: None,
   })
 }
+
+/// The directory containing the Sync files.
+/// Repo based Syncs are pulled to the latest commit first.
+pub async fn sync_files_directory(
+  sync: &ResourceSync,
+  repo: Option<&Repo>,
+) -> anyhow::Result<PathBuf> {
+  if sync.config.files_on_host {
+    return Ok(
+      core_config()
+        .sync_directory
+        .join(to_path_compatible_name(&sync.name)),
+    );
+  }
+  let clone_args: RepoExecutionArgs = if let Some(repo) = repo {
+    repo.into()
+  } else if !sync.config.repo.is_empty() {
+    sync.into()
+  } else {
+    return Err(anyhow!(
+      "Sync {} has no files on host or repo",
+      sync.name
+    ));
+  };
+  let (repo_path, RepoExecutionResponse { logs, .. }) =
+    pull_sync_repo(clone_args).await?;
+  if let Some(failure) = logs.iter().find(|log| !log.success) {
+    return Err(anyhow!(
+      "Repo clone / pull failed at {} | {}",
+      failure.stage,
+      failure.combined()
+    ));
+  }
+  Ok(repo_path)
+}
+
+async fn pull_sync_repo(
+  mut clone_args: RepoExecutionArgs,
+) -> anyhow::Result<(PathBuf, RepoExecutionResponse)> {
+  let access_token = if let Some(account) = &clone_args.account {
+    git_token(&clone_args.provider, account, clone_args.ssh, |https| clone_args.https = https)
+      .await
+      .with_context(
+        || format!("Failed to get git token in call to db. Stopping run. | {} | {account}", clone_args.provider),
+      )?
+  } else {
+    None
+  };
+
+  let repo_path =
+    clone_args.unique_path(&core_config().repo_directory)?;
+  clone_args.destination = Some(repo_path.display().to_string());
+  clone_args.ssh_known_hosts =
+    core_config().git_ssh_known_hosts.clone();
+  clone_args.ssh_accept_new_host_keys =
+    core_config().git_ssh_accept_new_host_keys;
+
+  let (res, _) = git::pull_or_clone(
+    clone_args,
+    &core_config().repo_directory,
+    access_token,
+  )
+  .await
+  .with_context(|| {
+    format!("Failed to update resource repo at {repo_path:?}")
+  })?;
+
+  Ok((repo_path, res))
+}
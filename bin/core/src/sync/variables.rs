@@ -11,7 +11,10 @@ use komodo_client::{
 };
 use mogh_resolver::Resolve;
 
-use crate::{api::write::WriteArgs, state::db_client};
+use crate::{
+  api::write::WriteArgs, helpers::encryption::decrypt_variable,
+  state::db_client,
+};
 
 use super::toml::TOML_PRETTY_OPTIONS;
 
@@ -38,8 +41,8 @@ pub async fn get_updates_for_view(
     .await
     .context("failed to query db for variables")?
     .into_iter()
-    .map(|v| (v.name.clone(), v))
-    .collect::<HashMap<_, _>>();
+    .map(|v| decrypt_variable(v).map(|v| (v.name.clone(), v)))
+    .collect::<anyhow::Result<HashMap<_, _>>>()?;
 
   let mut diffs = Vec::<DiffData>::new();
 
@@ -86,8 +89,8 @@ pub async fn get_updates_for_execution(
     .await
     .context("failed to query db for variables")?
     .into_iter()
-    .map(|v| (v.name.clone(), v))
-    .collect::<HashMap<_, _>>();
+    .map(|v| decrypt_variable(v).map(|v| (v.name.clone(), v)))
+    .collect::<anyhow::Result<HashMap<_, _>>>()?;
 
   let mut to_create = Vec::<Variable>::new();
   let mut to_update = Vec::<ToUpdateItem>::new();
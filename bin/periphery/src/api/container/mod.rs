@@ -1,13 +1,17 @@
-use anyhow::Context;
+use std::{sync::OnceLock, time::Duration};
+
+use anyhow::{Context, anyhow};
 use command::{
   run_komodo_shell_command, run_komodo_standard_command,
 };
+use formatting::format_serror;
 use futures_util::future::join_all;
 use komodo_client::entities::{
   docker::{
     container::{Container, ContainerListItem, ContainerStats},
     stats::FullContainerStats,
   },
+  log_archive::{ContainerLogLine, LogStream},
   update::Log,
 };
 use mogh_resolver::Resolve;
@@ -103,6 +107,119 @@ impl Resolve<crate::api::Args> for GetContainerLogSearch {
 
 //
 
+impl Resolve<crate::api::Args> for GetContainerLogSince {
+  async fn resolve(
+    self,
+    _: &crate::api::Args,
+  ) -> anyhow::Result<Vec<ContainerLogLine>> {
+    let GetContainerLogSince {
+      name,
+      since,
+      until,
+      limit,
+    } = self;
+    let until = until
+      .map(|until| format!(" --until {}", docker_timestamp(until)))
+      .unwrap_or_default();
+    let command = format!(
+      "docker logs {name} --since {}{until} --timestamps",
+      docker_timestamp(since)
+    );
+    let log = run_komodo_standard_command(
+      "Get container log since",
+      None,
+      command,
+    )
+    .await;
+    if !log.success {
+      return Err(anyhow!(
+        "Failed to get container log | {}",
+        log.stderr
+      ));
+    }
+    let mut lines = parse_log_lines(&log.stdout, LogStream::Stdout)
+      .chain(parse_log_lines(&log.stderr, LogStream::Stderr))
+      .collect::<Vec<_>>();
+    lines.sort_by_key(|line| line.ts);
+    // Keep the oldest lines, so the caller can page forward
+    // from the latest returned timestamp without gaps.
+    lines.truncate(limit as usize);
+    Ok(lines)
+  }
+}
+
+/// Formats a unix timestamp in milliseconds
+/// for `docker logs --since` / `--until`.
+fn docker_timestamp(ts: i64) -> String {
+  format!("{}.{:03}", ts / 1000, ts % 1000)
+}
+
+/// Parses lines output by `docker logs --timestamps`,
+/// which are prefixed with an RFC3339 timestamp.
+fn parse_log_lines(
+  output: &str,
+  stream: LogStream,
+) -> impl Iterator<Item = ContainerLogLine> + '_ {
+  output.lines().filter_map(move |line| {
+    let (ts, line) = line.split_once(' ').unwrap_or((line, ""));
+    let ts = chrono::DateTime::parse_from_rfc3339(ts)
+      .ok()?
+      .timestamp_millis();
+    Some(ContainerLogLine {
+      ts,
+      stream,
+      line: line.to_string(),
+    })
+  })
+}
+
+//
+
+/// The max time to wait on a [ProbeHttp] response.
+const PROBE_HTTP_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn probe_http_client() -> &'static reqwest::Client {
+  static PROBE_HTTP_CLIENT: OnceLock<reqwest::Client> =
+    OnceLock::new();
+  PROBE_HTTP_CLIENT.get_or_init(|| {
+    reqwest::Client::builder()
+      .timeout(PROBE_HTTP_TIMEOUT)
+      .build()
+      .expect("Failed to build http probe client")
+  })
+}
+
+impl Resolve<crate::api::Args> for ProbeHttp {
+  async fn resolve(
+    self,
+    _: &crate::api::Args,
+  ) -> anyhow::Result<Log> {
+    let stage = "Http Probe";
+    let log = match probe_http_client().get(&self.url).send().await {
+      Ok(res) => {
+        let status = res.status();
+        let msg = format!("GET {} | {status}", self.url);
+        if status.is_success() {
+          Log::simple(stage, msg)
+        } else {
+          Log::error(stage, msg)
+        }
+      }
+      Err(e) => Log::error(
+        stage,
+        format_serror(
+          &anyhow::Error::from(e)
+            .context(format!("Failed to reach {}", self.url))
+            .into(),
+        ),
+      ),
+    };
+    Ok(log)
+  }
+}
+
+//
+
 impl Resolve<crate::api::Args> for GetContainerStats {
   async fn resolve(
     self,
@@ -17,7 +17,10 @@ use mogh_resolver::Resolve;
 use periphery_client::api::docker::*;
 
 use crate::{
-  docker::{docker_login, image::get_image_digest_from_registry},
+  docker::{
+    docker_login, image::get_image_digest_from_registry,
+    volume_backup,
+  },
   state::docker_client,
 };
 
@@ -354,3 +357,70 @@ impl Resolve<crate::api::Args> for PruneVolumes {
     )
   }
 }
+
+//
+
+impl Resolve<crate::api::Args> for BackupContainerVolumes {
+  #[instrument(
+    "BackupContainerVolumes",
+    skip_all,
+    fields(
+      id = args.id.to_string(),
+      core = args.core,
+      name = self.name,
+      containers = format!("{:?}", self.containers),
+      mode = self.mode.to_string(),
+    )
+  )]
+  async fn resolve(
+    self,
+    args: &crate::api::Args,
+  ) -> anyhow::Result<Vec<Log>> {
+    let client = docker_client().load();
+    let client = client
+      .iter()
+      .next()
+      .context("Could not connect to docker client")?;
+    volume_backup::backup_volumes(
+      client,
+      &self.name,
+      &self.containers,
+      self.mode,
+      self.skip_bind_mounts,
+    )
+    .await
+  }
+}
+
+//
+
+impl Resolve<crate::api::Args> for RestoreContainerVolumes {
+  #[instrument(
+    "RestoreContainerVolumes",
+    skip_all,
+    fields(
+      id = args.id.to_string(),
+      core = args.core,
+      name = self.name,
+      backup = self.backup,
+      containers = format!("{:?}", self.containers),
+    )
+  )]
+  async fn resolve(
+    self,
+    args: &crate::api::Args,
+  ) -> anyhow::Result<Vec<Log>> {
+    let client = docker_client().load();
+    let client = client
+      .iter()
+      .next()
+      .context("Could not connect to docker client")?;
+    volume_backup::restore_volumes(
+      client,
+      &self.name,
+      self.backup,
+      &self.containers,
+    )
+    .await
+  }
+}
@@ -49,7 +49,7 @@ impl Resolve<crate::api::Args> for CloneRepo {
     args: &crate::api::Args,
   ) -> anyhow::Result<PeripheryRepoExecutionResponse> {
     let CloneRepo {
-      args,
+      mut args,
       git_token,
       environment,
       env_file_path,
@@ -60,6 +60,10 @@ impl Resolve<crate::api::Args> for CloneRepo {
     } = self;
 
     let token = crate::helpers::git_token(git_token, &args)?;
+    args.ssh_known_hosts =
+      periphery_config().git_ssh_known_hosts.clone();
+    args.ssh_accept_new_host_keys =
+      periphery_config().git_ssh_accept_new_host_keys;
     let root_repo_dir = default_folder(args.default_folder)?;
 
     let res = git::clone(args, &root_repo_dir, token).await?;
@@ -95,7 +99,7 @@ impl Resolve<crate::api::Args> for PullRepo {
     args: &crate::api::Args,
   ) -> anyhow::Result<PeripheryRepoExecutionResponse> {
     let PullRepo {
-      args,
+      mut args,
       git_token,
       environment,
       env_file_path,
@@ -105,6 +109,10 @@ impl Resolve<crate::api::Args> for PullRepo {
     } = self;
 
     let token = crate::helpers::git_token(git_token, &args)?;
+    args.ssh_known_hosts =
+      periphery_config().git_ssh_known_hosts.clone();
+    args.ssh_accept_new_host_keys =
+      periphery_config().git_ssh_accept_new_host_keys;
     let parent_dir = default_folder(args.default_folder)?;
 
     let res = git::pull(args, &parent_dir, token).await?;
@@ -140,7 +148,7 @@ impl Resolve<crate::api::Args> for PullOrCloneRepo {
     args: &crate::api::Args,
   ) -> anyhow::Result<PeripheryRepoExecutionResponse> {
     let PullOrCloneRepo {
-      args,
+      mut args,
       git_token,
       environment,
       env_file_path,
@@ -151,6 +159,10 @@ impl Resolve<crate::api::Args> for PullOrCloneRepo {
     } = self;
 
     let token = crate::helpers::git_token(git_token, &args)?;
+    args.ssh_known_hosts =
+      periphery_config().git_ssh_known_hosts.clone();
+    args.ssh_accept_new_host_keys =
+      periphery_config().git_ssh_accept_new_host_keys;
     let parent_dir = default_folder(args.default_folder)?;
 
     let (res, cloned) =
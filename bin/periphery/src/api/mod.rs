@@ -88,6 +88,8 @@ pub enum PeripheryRequest {
   InspectContainer(InspectContainer),
   GetContainerLog(GetContainerLog),
   GetContainerLogSearch(GetContainerLogSearch),
+  GetContainerLogSince(GetContainerLogSince),
+  ProbeHttp(ProbeHttp),
   GetContainerStats(GetContainerStats),
   GetContainerStatsList(GetContainerStatsList),
   GetFullContainerStats(GetFullContainerStats),
@@ -132,6 +134,8 @@ pub enum PeripheryRequest {
   // Volume (Write)
   DeleteVolume(DeleteVolume),
   PruneVolumes(PruneVolumes),
+  BackupContainerVolumes(BackupContainerVolumes),
+  RestoreContainerVolumes(RestoreContainerVolumes),
 
   // All in one (Write)
   PruneSystem(PruneSystem),
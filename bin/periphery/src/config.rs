@@ -3,7 +3,10 @@ use std::{path::PathBuf, sync::OnceLock};
 use clap::Parser;
 use colored::Colorize;
 use komodo_client::entities::{
-  config::periphery::{CliArgs, Env, PeripheryConfig},
+  config::{
+    BackupRemoteConfig,
+    periphery::{CliArgs, Env, PeripheryConfig},
+  },
   logger::{LogConfig, LogLevel},
 };
 use mogh_config::ConfigLoader;
@@ -108,6 +111,9 @@ pub fn periphery_config() -> &'static PeripheryConfig {
       repo_dir: env.periphery_repo_dir.or(config.repo_dir),
       stack_dir: env.periphery_stack_dir.or(config.stack_dir),
       build_dir: env.periphery_build_dir.or(config.build_dir),
+      volume_backup_dir: env
+        .periphery_volume_backup_dir
+        .or(config.volume_backup_dir),
       default_terminal_command: env
         .periphery_default_terminal_command
         .unwrap_or(config.default_terminal_command),
@@ -126,6 +132,62 @@ pub fn periphery_config() -> &'static PeripheryConfig {
       legacy_compose_cli: env
         .periphery_legacy_compose_cli
         .unwrap_or(config.legacy_compose_cli),
+      volume_backup_image: env
+        .periphery_volume_backup_image
+        .unwrap_or(config.volume_backup_image),
+      max_volume_backups: env
+        .periphery_max_volume_backups
+        .unwrap_or(config.max_volume_backups),
+      volume_backup_remote: BackupRemoteConfig {
+        url: env
+          .periphery_volume_backup_remote_url
+          .unwrap_or(config.volume_backup_remote.url),
+        s3_endpoint: env
+          .periphery_volume_backup_remote_s3_endpoint
+          .unwrap_or(config.volume_backup_remote.s3_endpoint),
+        s3_region: env
+          .periphery_volume_backup_remote_s3_region
+          .unwrap_or(config.volume_backup_remote.s3_region),
+        s3_path_style: env
+          .periphery_volume_backup_remote_s3_path_style
+          .unwrap_or(config.volume_backup_remote.s3_path_style),
+        s3_access_key_id: maybe_read_item_from_file(
+          env.periphery_volume_backup_remote_s3_access_key_id_file,
+          env.periphery_volume_backup_remote_s3_access_key_id,
+        )
+        .unwrap_or(config.volume_backup_remote.s3_access_key_id),
+        s3_secret_access_key: maybe_read_item_from_file(
+          env
+            .periphery_volume_backup_remote_s3_secret_access_key_file,
+          env.periphery_volume_backup_remote_s3_secret_access_key,
+        )
+        .unwrap_or(config.volume_backup_remote.s3_secret_access_key),
+        username: env
+          .periphery_volume_backup_remote_username
+          .unwrap_or(config.volume_backup_remote.username),
+        password: maybe_read_item_from_file(
+          env.periphery_volume_backup_remote_password_file,
+          env.periphery_volume_backup_remote_password,
+        )
+        .unwrap_or(config.volume_backup_remote.password),
+        sftp_private_key: env
+          .periphery_volume_backup_remote_sftp_private_key
+          .unwrap_or(config.volume_backup_remote.sftp_private_key),
+        sftp_known_hosts: env
+          .periphery_volume_backup_remote_sftp_known_hosts
+          .unwrap_or(config.volume_backup_remote.sftp_known_hosts),
+        max_backups: env
+          .periphery_volume_backup_remote_max_backups
+          .unwrap_or(config.volume_backup_remote.max_backups),
+        max_backup_age_days: env
+          .periphery_volume_backup_remote_max_backup_age_days
+          .unwrap_or(config.volume_backup_remote.max_backup_age_days),
+        encryption_key: maybe_read_item_from_file(
+          env.periphery_volume_backup_remote_encryption_key_file,
+          env.periphery_volume_backup_remote_encryption_key,
+        )
+        .unwrap_or(config.volume_backup_remote.encryption_key),
+      },
       logging: LogConfig {
         level: args
           .log_level
@@ -180,6 +242,12 @@ pub fn periphery_config() -> &'static PeripheryConfig {
         .or(config.ssl_cert_file),
       secrets: config.secrets,
       git_providers: config.git_providers,
+      git_ssh_known_hosts: env
+        .periphery_git_ssh_known_hosts
+        .or(config.git_ssh_known_hosts),
+      git_ssh_accept_new_host_keys: env
+        .periphery_git_ssh_accept_new_host_keys
+        .unwrap_or(config.git_ssh_accept_new_host_keys),
       docker_registries: config.docker_registries,
     }
   })
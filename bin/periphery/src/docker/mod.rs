@@ -13,6 +13,7 @@ pub mod image;
 pub mod secret;
 pub mod stack;
 pub mod stats;
+pub mod volume_backup;
 
 mod container;
 mod network;
@@ -0,0 +1,580 @@
+//! Backup / restore of the volumes and bind mounts of containers.
+//! Each mount is archived by a short lived helper container
+//! running `volume_backup_image`, so nothing extra needs
+//! to be installed on the host.
+//!
+//! Backups are written to `<volume_backup_dir>/<name>/<timestamp>`,
+//! with one `.tar.gz` archive per mount.
+
+use std::{
+  borrow::Cow,
+  collections::BTreeSet,
+  fmt::Write as _,
+  path::{Path, PathBuf},
+};
+
+use anyhow::{Context, anyhow};
+use backup_remote::{BACKUP_FOLDER_FORMAT, BackupRemote};
+use chrono::{Local, NaiveDateTime};
+use command::run_komodo_standard_command;
+use formatting::format_serror;
+use komodo_client::entities::{
+  VolumeBackupMode, all_logs_success,
+  docker::{MountTypeEnum, container::Container},
+  update::Log,
+};
+use shell_escape::unix::escape;
+
+use crate::config::periphery_config;
+
+use super::DockerClient;
+
+/// A volume or bind mount of the containers.
+struct BackupMount {
+  /// The volume name, or bind mount host path.
+  source: String,
+  /// The archive file name in the backup folder.
+  archive: String,
+}
+
+pub async fn backup_volumes(
+  client: &DockerClient,
+  name: &str,
+  containers: &[String],
+  mode: VolumeBackupMode,
+  skip_bind_mounts: bool,
+) -> anyhow::Result<Vec<Log>> {
+  let config = periphery_config();
+  let resource_folder = resource_backup_folder(name)?;
+
+  let inspected = inspect_containers(client, containers).await?;
+  let mounts = container_mounts(&inspected, skip_bind_mounts).await?;
+  if mounts.is_empty() {
+    return Ok(vec![Log::simple(
+      "Backup Volumes",
+      String::from("No volumes or bind mounts to back up"),
+    )]);
+  }
+
+  let backup = Local::now().format(BACKUP_FOLDER_FORMAT).to_string();
+  let backup_folder = resource_folder.join(&backup);
+  tokio::fs::create_dir_all(&backup_folder)
+    .await
+    .with_context(|| {
+      format!("Failed to create backup folder {backup_folder:?}")
+    })?;
+
+  let mut logs = Vec::new();
+
+  // Only the running containers need to be suspended / resumed.
+  let running = running_containers(containers, &inspected);
+  let suspend = match mode {
+    VolumeBackupMode::Stop => Some((
+      ("Stop Containers", "stop"),
+      ("Start Containers", "start"),
+    )),
+    VolumeBackupMode::Pause => Some((
+      ("Pause Containers", "pause"),
+      ("Unpause Containers", "unpause"),
+    )),
+    VolumeBackupMode::Live => None,
+  }
+  .filter(|_| !running.is_empty());
+
+  if let Some(((stage, command), _)) = suspend {
+    logs.push(
+      run_komodo_standard_command(
+        stage,
+        None,
+        format!("docker {command} {}", running.join(" ")),
+      )
+      .await,
+    );
+  }
+
+  let mut archives = Vec::new();
+  if all_logs_success(&logs) {
+    let folder = backup_folder.to_string_lossy();
+    for mount in &mounts {
+      let log = run_komodo_standard_command(
+        &format!("Backup {}", mount.source),
+        None,
+        format!(
+          "docker run --rm -v {}:/mnt/source:ro -v {}:/backup {} tar -czf /backup/{} -C /mnt source",
+          escape(Cow::Borrowed(mount.source.as_str())),
+          escape(folder.clone()),
+          config.volume_backup_image,
+          mount.archive,
+        ),
+      )
+      .await;
+      if log.success {
+        archives.push(backup_folder.join(&mount.archive));
+      }
+      logs.push(log);
+    }
+  }
+
+  // Always try to resume the containers, even after failure.
+  if let Some((_, (stage, command))) = suspend {
+    logs.push(
+      run_komodo_standard_command(
+        stage,
+        None,
+        format!("docker {command} {}", running.join(" ")),
+      )
+      .await,
+    );
+  }
+
+  if !all_logs_success(&logs) {
+    return Ok(logs);
+  }
+
+  logs.push(Log::simple(
+    "Backup Volumes",
+    format!(
+      "Backed up {} volumes / bind mounts to {backup_folder:?}",
+      archives.len()
+    ),
+  ));
+
+  if let Err(e) =
+    prune_local_backups(&resource_folder, config.max_volume_backups)
+      .await
+  {
+    logs.push(Log::error("Prune Backups", format_serror(&e.into())));
+  }
+
+  if let Some(log) = upload_backup(name, &backup, archives).await {
+    logs.push(log);
+  }
+
+  Ok(logs)
+}
+
+pub async fn restore_volumes(
+  client: &DockerClient,
+  name: &str,
+  backup: Option<String>,
+  containers: &[String],
+) -> anyhow::Result<Vec<Log>> {
+  let config = periphery_config();
+  let resource_folder = resource_backup_folder(name)?;
+
+  let remote = if config.volume_backup_remote.url.is_empty() {
+    None
+  } else {
+    Some(
+      BackupRemote::connect(&config.volume_backup_remote)
+        .await?
+        .scoped(name),
+    )
+  };
+
+  let backup = match backup {
+    Some(backup) => {
+      if !is_backup_name(&backup) {
+        return Err(anyhow!(
+          "Invalid backup '{backup}', expected format {BACKUP_FOLDER_FORMAT}"
+        ));
+      }
+      backup
+    }
+    None => {
+      let mut backups = list_local_backups(&resource_folder).await?;
+      if let Some(remote) = &remote {
+        backups.extend(remote.list_backups().await?);
+      }
+      backups.into_iter().max().with_context(|| {
+        format!("No volume backups found for {name}")
+      })?
+    }
+  };
+
+  let mut logs = Vec::new();
+
+  let backup_folder = resource_folder.join(&backup);
+  if !backup_folder.exists() {
+    let remote = remote.as_ref().with_context(|| {
+      format!("Volume backup {backup} not found at {backup_folder:?}")
+    })?;
+    remote.download(Some(&backup), &resource_folder).await?;
+    logs.push(Log::simple(
+      "Download Backup",
+      format!(
+        "Downloaded backup {backup} from {}",
+        config.volume_backup_remote.url
+      ),
+    ));
+  }
+
+  let inspected = inspect_containers(client, containers).await?;
+  let mounts = container_mounts(&inspected, false)
+    .await?
+    .into_iter()
+    .filter(|mount| backup_folder.join(&mount.archive).exists())
+    .collect::<Vec<_>>();
+  if mounts.is_empty() {
+    logs.push(Log::error(
+      "Restore Volumes",
+      format!(
+        "Backup {backup} has no archives matching the volumes / bind mounts of the containers"
+      ),
+    ));
+    return Ok(logs);
+  }
+
+  // The containers must not write to the volumes during restore.
+  let running = running_containers(containers, &inspected);
+  if !running.is_empty() {
+    logs.push(
+      run_komodo_standard_command(
+        "Stop Containers",
+        None,
+        format!("docker stop {}", running.join(" ")),
+      )
+      .await,
+    );
+  }
+
+  if all_logs_success(&logs) {
+    let folder = backup_folder.to_string_lossy();
+    for mount in &mounts {
+      logs.push(
+        run_komodo_standard_command(
+          &format!("Restore {}", mount.source),
+          None,
+          format!(
+            "docker run --rm -v {}:/mnt/source -v {}:/backup:ro {} sh -c \"{}\"",
+            escape(Cow::Borrowed(mount.source.as_str())),
+            escape(folder.clone()),
+            config.volume_backup_image,
+            restore_script(&mount.archive),
+          ),
+        )
+        .await,
+      );
+    }
+  }
+
+  // Always try to start the containers again, even after failure.
+  if !running.is_empty() {
+    logs.push(
+      run_komodo_standard_command(
+        "Start Containers",
+        None,
+        format!("docker start {}", running.join(" ")),
+      )
+      .await,
+    );
+  }
+
+  if all_logs_success(&logs) {
+    logs.push(Log::simple(
+      "Restore Volumes",
+      format!(
+        "Restored {} volumes / bind mounts from backup {backup}",
+        mounts.len()
+      ),
+    ));
+  }
+
+  Ok(logs)
+}
+
+/// The archive is extracted to a temporary folder inside the mount first,
+/// so the existing data is only replaced after `tar` succeeds.
+/// Keeping it on the same filesystem makes the swap a cheap rename.
+fn restore_script(archive: &str) -> String {
+  const TEMP: &str = "/mnt/source/.komodo-restore";
+  format!(
+    "rm -rf {TEMP} && mkdir {TEMP} && if tar -xzf /backup/{archive} -C {TEMP}; then find /mnt/source -mindepth 1 -maxdepth 1 ! -path {TEMP} -exec rm -rf {{}} + && find {TEMP}/source -mindepth 1 -maxdepth 1 -exec mv {{}} /mnt/source/ \\; && rm -rf {TEMP}; else rm -rf {TEMP}; exit 1; fi"
+  )
+}
+
+/// `<volume_backup_dir>/<name>`
+fn resource_backup_folder(name: &str) -> anyhow::Result<PathBuf> {
+  if name.is_empty() || name.split('/').any(|part| part == "..") {
+    return Err(anyhow!("Invalid volume backup name '{name}'"));
+  }
+  Ok(periphery_config().volume_backup_dir().join(name))
+}
+
+async fn inspect_containers(
+  client: &DockerClient,
+  containers: &[String],
+) -> anyhow::Result<Vec<Container>> {
+  let mut inspected = Vec::with_capacity(containers.len());
+  for container in containers {
+    inspected.push(
+      client.inspect_container(container).await.with_context(
+        || format!("Failed to inspect container {container}"),
+      )?,
+    );
+  }
+  Ok(inspected)
+}
+
+/// The names of containers which are running and not paused.
+fn running_containers<'a>(
+  names: &'a [String],
+  containers: &[Container],
+) -> Vec<&'a str> {
+  names
+    .iter()
+    .zip(containers)
+    .filter(|(_, container)| {
+      container.state.as_ref().is_some_and(|state| {
+        state.running.unwrap_or_default()
+          && !state.paused.unwrap_or_default()
+      })
+    })
+    .map(|(name, _)| name.as_str())
+    .collect()
+}
+
+/// The writable volumes / bind mounts across all the containers, deduplicated.
+async fn container_mounts(
+  containers: &[Container],
+  skip_bind_mounts: bool,
+) -> anyhow::Result<Vec<BackupMount>> {
+  let mut mounts = Vec::<BackupMount>::new();
+  for mount in containers.iter().flat_map(|c| &c.mounts) {
+    // Read only mounts are not owned by the containers.
+    if mount.rw == Some(false) {
+      continue;
+    }
+    let mount = match mount.typ {
+      MountTypeEnum::Volume => {
+        let Some(name) = &mount.name else {
+          continue;
+        };
+        BackupMount {
+          source: name.clone(),
+          archive: format!("volume_{}.tar.gz", sanitize(name)),
+        }
+      }
+      MountTypeEnum::Bind if !skip_bind_mounts => {
+        let Some(source) = &mount.source else {
+          continue;
+        };
+        if mounts.iter().any(|m| &m.source == source) {
+          continue;
+        }
+        match check_bind_mount(source).await? {
+          BindMountCheck::Directory => {}
+          // Only directories can be restored in place.
+          // This skips single file mounts, like configs or the docker socket.
+          BindMountCheck::File => continue,
+          BindMountCheck::Protected => {
+            return Err(anyhow!(
+              "Refusing to back up or restore bind mount {source}, it contains the root directory or volume backup directory of Periphery"
+            ));
+          }
+        }
+        BackupMount {
+          source: source.clone(),
+          archive: format!("bind_{}.tar.gz", sanitize(source)),
+        }
+      }
+      _ => continue,
+    };
+    if !mounts.iter().any(|m| m.source == mount.source) {
+      mounts.push(mount);
+    }
+  }
+  Ok(mounts)
+}
+
+enum BindMountCheck {
+  Directory,
+  File,
+  /// The path is `/`, or an ancestor of the Periphery
+  /// root directory or `volume_backup_dir`.
+  /// Restoring these would overwrite Periphery itself,
+  /// or the backups being restored from.
+  Protected,
+}
+
+/// Run in the helper container with the bind mount source at
+/// `/mnt/source`, and the protected directories under `/mnt/protected`.
+/// Comparing device and inode resolves any symlinks in the host paths.
+const CHECK_BIND_MOUNT_SCRIPT: &str = r#"if [ ! -d /mnt/source ]; then echo file; exit; fi; source=$(stat -c %d:%i /mnt/source); for dir in /mnt/protected/*; do if [ "$(stat -c %d:%i "$dir")" = "$source" ]; then echo protected; exit; fi; done; echo directory"#;
+
+/// Bind mount sources are paths on the host, which Periphery
+/// may not see when running in a container. So they are checked
+/// inside the helper container, like the backup itself.
+async fn check_bind_mount(
+  source: &str,
+) -> anyhow::Result<BindMountCheck> {
+  let config = periphery_config();
+  // Make sure the backup directory exists to be mounted.
+  let backup_dir = config.volume_backup_dir();
+  tokio::fs::create_dir_all(&backup_dir).await.with_context(
+    || format!("Failed to create backup directory {backup_dir:?}"),
+  )?;
+  let dirs = [config.root_directory.clone(), backup_dir];
+  let protected = protected_paths(&dirs);
+  // The '--mount' options are comma separated.
+  if source.contains(',')
+    || protected
+      .iter()
+      .any(|dir| dir.to_string_lossy().contains(','))
+  {
+    return Err(anyhow!(
+      "Cannot check bind mount {source}, paths containing ',' are not supported. Use 'skip_bind_mounts' to back up the volumes only."
+    ));
+  }
+  let mut command = format!(
+    "docker run --rm --mount {}",
+    escape(
+      format!(
+        "type=bind,source={source},target=/mnt/source,readonly"
+      )
+      .into()
+    )
+  );
+  for (i, dir) in protected.iter().enumerate() {
+    let _ = write!(
+      &mut command,
+      " --mount {}",
+      escape(
+        format!(
+          "type=bind,source={},target=/mnt/protected/{i},readonly",
+          dir.display()
+        )
+        .into()
+      )
+    );
+  }
+  let _ = write!(
+    &mut command,
+    " {} sh -c {}",
+    config.volume_backup_image,
+    escape(CHECK_BIND_MOUNT_SCRIPT.into())
+  );
+  let log = run_komodo_standard_command(
+    &format!("Check {source}"),
+    None,
+    command,
+  )
+  .await;
+  if !log.success {
+    return Err(anyhow!(
+      "Failed to check bind mount {source} | {}",
+      log.stderr.trim()
+    ));
+  }
+  match log.stdout.trim() {
+    "directory" => Ok(BindMountCheck::Directory),
+    "file" => Ok(BindMountCheck::File),
+    "protected" => Ok(BindMountCheck::Protected),
+    stdout => Err(anyhow!(
+      "Failed to check bind mount {source}, unexpected output '{stdout}'"
+    )),
+  }
+}
+
+/// The directories and all their ancestors, up to `/`.
+/// A bind mount of any of these would contain the directories.
+fn protected_paths(dirs: &[PathBuf]) -> BTreeSet<&Path> {
+  dirs
+    .iter()
+    .filter(|dir| dir.is_absolute())
+    .flat_map(|dir| dir.ancestors())
+    .collect()
+}
+
+fn sanitize(source: &str) -> String {
+  source
+    .trim_matches('/')
+    .chars()
+    .map(|c| {
+      if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+        c
+      } else {
+        '_'
+      }
+    })
+    .collect()
+}
+
+fn is_backup_name(name: &str) -> bool {
+  NaiveDateTime::parse_from_str(name, BACKUP_FOLDER_FORMAT).is_ok()
+}
+
+/// Backup folder names, ordered from oldest -> newest.
+async fn list_local_backups(
+  resource_folder: &Path,
+) -> anyhow::Result<Vec<String>> {
+  let mut dir = match tokio::fs::read_dir(resource_folder).await {
+    Ok(dir) => dir,
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+      return Ok(Vec::new());
+    }
+    Err(e) => {
+      return Err(anyhow::Error::from(e).context(format!(
+        "Failed to read backup folder {resource_folder:?}"
+      )));
+    }
+  };
+  let mut backups = Vec::new();
+  while let Some(entry) = dir.next_entry().await? {
+    let Ok(name) = entry.file_name().into_string() else {
+      continue;
+    };
+    if entry.file_type().await?.is_dir() && is_backup_name(&name) {
+      backups.push(name);
+    }
+  }
+  backups.sort();
+  Ok(backups)
+}
+
+async fn prune_local_backups(
+  resource_folder: &Path,
+  max_backups: u16,
+) -> anyhow::Result<()> {
+  if max_backups == 0 {
+    return Ok(());
+  }
+  let backups = list_local_backups(resource_folder).await?;
+  let excess = backups.len().saturating_sub(max_backups as usize);
+  for backup in &backups[..excess] {
+    let path = resource_folder.join(backup);
+    tokio::fs::remove_dir_all(&path)
+      .await
+      .with_context(|| format!("Failed to delete backup {path:?}"))?;
+  }
+  Ok(())
+}
+
+/// Uploads the backup to `volume_backup_remote`, if configured.
+async fn upload_backup(
+  name: &str,
+  backup: &str,
+  archives: Vec<PathBuf>,
+) -> Option<Log> {
+  let config = &periphery_config().volume_backup_remote;
+  if config.url.is_empty() {
+    return None;
+  }
+  let res = async {
+    let remote = BackupRemote::connect(config).await?.scoped(name);
+    remote.upload(backup, archives).await?;
+    remote.prune().await
+  }
+  .await;
+  let log = match res {
+    Ok(_) => Log::simple(
+      "Upload Backup",
+      format!("Uploaded backup {backup} to {}", config.url),
+    ),
+    Err(e) => Log::error(
+      "Upload Backup",
+      format_serror(&e.context("Failed to upload backup").into()),
+    ),
+  };
+  Some(log)
+}
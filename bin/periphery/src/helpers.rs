@@ -251,6 +251,11 @@ pub fn git_token(
   if core_token.is_some() {
     return Ok(core_token);
   }
+  // Config accounts only have http(s) tokens.
+  // Without a key from Core, use the host's default ssh identity.
+  if args.ssh {
+    return Ok(None);
+  }
   let Some(account) = &args.account else {
     return Ok(None);
   };
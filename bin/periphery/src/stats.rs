@@ -2,8 +2,8 @@ use std::cmp::Ordering;
 
 use async_timing_util::wait_until_timelength;
 use komodo_client::entities::stats::{
-  SingleDiskUsage, SystemInformation, SystemLoadAverage,
-  SystemProcess, SystemStats,
+  SingleDiskUsage, SingleNetworkInterfaceUsage, SystemInformation,
+  SystemLoadAverage, SystemProcess, SystemStats,
 };
 use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
 
@@ -83,12 +83,21 @@ impl StatsClient {
 
     let mut network_ingress_bytes: u64 = 0;
     let mut network_egress_bytes: u64 = 0;
+    let mut network_usage_interface =
+      Vec::with_capacity(self.networks.len());
 
-    for (_, network) in self.networks.iter() {
+    for (name, network) in self.networks.iter() {
       network_ingress_bytes += network.received();
       network_egress_bytes += network.transmitted();
+      network_usage_interface.push(SingleNetworkInterfaceUsage {
+        name: name.clone(),
+        ingress_bytes: network.received() as f64,
+        egress_bytes: network.transmitted() as f64,
+      });
     }
 
+    network_usage_interface.sort_by(|a, b| a.name.cmp(&b.name));
+
     let load_avg = System::load_average();
 
     SystemStats {
@@ -103,6 +112,7 @@ impl StatsClient {
       mem_total_gb: total_mem as f64 / BYTES_PER_GB,
       network_ingress_bytes: network_ingress_bytes as f64,
       network_egress_bytes: network_egress_bytes as f64,
+      network_usage_interface,
       disks: self.get_disks(),
       polling_rate: self.stats.polling_rate,
       refresh_ts: self.stats.refresh_ts,
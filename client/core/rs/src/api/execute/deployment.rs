@@ -3,7 +3,9 @@ use mogh_resolver::Resolve;
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
-use crate::entities::{TerminationSignal, update::Update};
+use crate::entities::{
+  TerminationSignal, VolumeBackupMode, update::Update,
+};
 
 use super::{BatchExecutionResponse, KomodoExecuteRequest};
 
@@ -309,6 +311,131 @@ pub struct DestroyDeployment {
 
 //
 
+#[cfg(feature = "utoipa")]
+#[utoipa::path(
+  post,
+  path = "/RollbackDeployment",
+  description = "Rolls back the target deployment to the previously deployed configuration.",
+  request_body(content = RollbackDeployment),
+  responses(
+    (status = 200, description = "The update", body = Update),
+  ),
+)]
+pub fn rollback_deployment() {}
+
+/// Rolls back the target deployment to the configuration
+/// and image of the previous successful deploy. Response: [Update].
+///
+/// 1. Finds the last successful deploy before the most recent deploy.
+/// 2. Restores the deployment config (including the resolved image tag / build version)
+/// recorded on that deploy.
+/// 3. Redeploys the deployment, see [Deploy].
+///
+/// Note. Only `Execute` permission on the deployment is required.
+/// The restored configuration is still recorded as an `UpdateDeployment`.
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, PartialEq, Resolve, Parser,
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(mogh_error::Error)]
+pub struct RollbackDeployment {
+  /// Name or id
+  pub deployment: String,
+  /// Override the default termination signal specified in the deployment.
+  /// Only used when deployment needs to be taken down before redeploy.
+  pub stop_signal: Option<TerminationSignal>,
+  /// Override the default termination max time.
+  /// Only used when deployment needs to be taken down before redeploy.
+  pub stop_time: Option<i32>,
+}
+
+//
+
+#[cfg(feature = "utoipa")]
+#[utoipa::path(
+  post,
+  path = "/BackupDeploymentVolumes",
+  description = "Backs up the volumes and bind mounts of the target deployment.",
+  request_body(content = BackupDeploymentVolumes),
+  responses(
+    (status = 200, description = "The update", body = Update),
+  ),
+)]
+pub fn backup_deployment_volumes() {}
+
+/// Backs up the volumes and bind mounts of the target deployment
+/// on its server. Response: [Update].
+///
+/// Each volume / bind mount is archived with `tar`, and written to
+/// the Periphery `volume_backup_dir`, and `volume_backup_remote` if configured.
+///
+/// Note. Not supported for Swarm mode Deployments.
+#[typeshare]
+#[derive(
+  Debug, Clone, PartialEq, Serialize, Deserialize, Resolve, Parser,
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(mogh_error::Error)]
+pub struct BackupDeploymentVolumes {
+  /// Name or id
+  pub deployment: String,
+  /// How to handle the running container during the backup.
+  /// Default: `Stop`
+  #[serde(default)]
+  #[arg(long, short = 'm', default_value_t = VolumeBackupMode::Stop)]
+  pub mode: VolumeBackupMode,
+  /// Only back up named volumes, skipping bind mounts.
+  #[serde(default)]
+  #[arg(long, short = 's', default_value_t = false)]
+  pub skip_bind_mounts: bool,
+}
+
+//
+
+#[cfg(feature = "utoipa")]
+#[utoipa::path(
+  post,
+  path = "/RestoreDeploymentVolumes",
+  description = "Restores the volumes and bind mounts of the target deployment from a backup.",
+  request_body(content = RestoreDeploymentVolumes),
+  responses(
+    (status = 200, description = "The update", body = Update),
+  ),
+)]
+pub fn restore_deployment_volumes() {}
+
+/// Restores the volumes and bind mounts of the target deployment
+/// from a backup made with [BackupDeploymentVolumes]. Response: [Update].
+///
+/// The container is stopped during the restore,
+/// and the existing contents of the volumes are replaced.
+///
+/// Note. Not supported for Swarm mode Deployments.
+#[typeshare]
+#[derive(
+  Debug, Clone, PartialEq, Serialize, Deserialize, Resolve, Parser,
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(mogh_error::Error)]
+pub struct RestoreDeploymentVolumes {
+  /// Name or id
+  pub deployment: String,
+  /// The backup to restore, eg `2025-08-04_05-05-53`.
+  /// If empty, will restore the most recent backup.
+  #[serde(default)]
+  #[arg(long, short = 'b', default_value_t = String::new())]
+  pub backup: String,
+}
+
+//
+
 #[cfg(feature = "utoipa")]
 #[utoipa::path(
   post,
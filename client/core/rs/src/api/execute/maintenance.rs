@@ -67,6 +67,62 @@ pub struct BackupCoreDatabase {}
 
 //
 
+#[cfg(feature = "utoipa")]
+#[utoipa::path(
+  post,
+  path = "/RestoreCoreDatabase",
+  description = "Restores the Komodo Core database from a backup.",
+  request_body(content = RestoreCoreDatabase),
+  responses(
+    (status = 200, description = "The update", body = Update),
+  ),
+)]
+pub fn restore_core_database() {}
+
+/// **Admin only.** Restores the Komodo Core database from a backup
+/// taken with [BackupCoreDatabase]. Response: [Update].
+///
+/// Documents in the backup are inserted, or overwrite the existing
+/// document with the same id. Documents not in the backup are left as is.
+/// Use the filters to restore only part of the backup, for example
+/// some deleted Stacks and their Permissions, without rolling back
+/// the Updates / Stats.
+///
+/// https://komo.do/docs/setup/backup
+#[typeshare]
+#[derive(
+  Debug, Clone, PartialEq, Serialize, Deserialize, Resolve, Parser,
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(mogh_error::Error)]
+pub struct RestoreCoreDatabase {
+  /// The backup folder to restore, eg `2025-08-01_05-04-53`.
+  /// If empty, uses the most recent backup.
+  #[serde(default)]
+  #[arg(long, short = 'r', default_value_t = String::new())]
+  pub restore_folder: String,
+  /// Only restore these collections, eg `Stack` or `Permission`.
+  /// If empty, restores all collections.
+  #[serde(default)]
+  #[arg(name = "collection", long, short = 'c')]
+  pub collections: Vec<String>,
+  /// Only restore the documents with these ids or resource names,
+  /// along with documents targeting them, such as their Permissions.
+  /// If empty, restores all documents.
+  #[serde(default)]
+  #[arg(name = "resource", long, short = 's')]
+  pub resources: Vec<String>,
+  /// Only log what would be inserted or overwritten,
+  /// without writing to the database.
+  #[serde(default)]
+  #[arg(long, short = 'n', default_value_t = false)]
+  pub dry_run: bool,
+}
+
+//
+
 #[cfg(feature = "utoipa")]
 #[utoipa::path(
   post,
@@ -166,3 +222,35 @@ pub struct RotateCoreKeys {
   #[clap(long, short, alias = "f", default_value_t = false)]
   pub force: bool,
 }
+
+//
+
+#[cfg(feature = "utoipa")]
+#[utoipa::path(
+  post,
+  path = "/RotateEncryptionKey",
+  description = "Re-encrypts all stored secrets with the current encryption key.",
+  request_body(content = RotateEncryptionKey),
+  responses(
+    (status = 200, description = "The update", body = Update),
+  ),
+)]
+pub fn rotate_encryption_key() {}
+
+/// **Admin only.** Re-encrypts all secret Variables and
+/// git provider / docker registry account tokens with the
+/// current Core `encryption_key`.
+/// Response: [Update].
+///
+/// Values encrypted with one of the `previous_encryption_keys`
+/// are re-encrypted, and unencrypted values are encrypted.
+/// If no `encryption_key` is configured, all values are decrypted.
+#[typeshare]
+#[derive(
+  Debug, Clone, PartialEq, Serialize, Deserialize, Resolve, Parser,
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(mogh_error::Error)]
+pub struct RotateEncryptionKey {}
@@ -99,7 +99,10 @@ pub enum Execution {
   StopStack(StopStack),
   DestroyStack(DestroyStack),
   BatchDestroyStack(BatchDestroyStack),
+  RollbackStack(RollbackStack),
   RunStackService(RunStackService),
+  BackupStackVolumes(BackupStackVolumes),
+  RestoreStackVolumes(RestoreStackVolumes),
 
   // DEPLOYMENT
   /// Deploy the target deployment. (alias: `dp`)
@@ -114,6 +117,9 @@ pub enum Execution {
   StopDeployment(StopDeployment),
   DestroyDeployment(DestroyDeployment),
   BatchDestroyDeployment(BatchDestroyDeployment),
+  RollbackDeployment(RollbackDeployment),
+  BackupDeploymentVolumes(BackupDeploymentVolumes),
+  RestoreDeploymentVolumes(RestoreDeploymentVolumes),
 
   // BUILD
   /// Run the target build. (alias: `build`, `bd`)
@@ -201,11 +207,14 @@ pub enum Execution {
     alias = "backup"
   )]
   BackupCoreDatabase(BackupCoreDatabase),
+  #[clap(alias = "restore-database", alias = "restore-db")]
+  RestoreCoreDatabase(RestoreCoreDatabase),
   #[clap(alias = "auto-update")]
   GlobalAutoUpdate(GlobalAutoUpdate),
   #[clap(alias = "rotate-keys")]
   RotateAllServerKeys(RotateAllServerKeys),
   RotateCoreKeys(RotateCoreKeys),
+  RotateEncryptionKey(RotateEncryptionKey),
 
   // SLEEP
   Sleep(Sleep),
@@ -51,7 +51,10 @@ use crate::api::execute;
     execute::unpause_stack,
     execute::stop_stack,
     execute::destroy_stack,
+    execute::rollback_stack,
     execute::run_stack_service,
+    execute::backup_stack_volumes,
+    execute::restore_stack_volumes,
     execute::batch_destroy_stack,
     // deployment
     execute::deploy,
@@ -63,6 +66,9 @@ use crate::api::execute;
     execute::unpause_deployment,
     execute::stop_deployment,
     execute::destroy_deployment,
+    execute::rollback_deployment,
+    execute::backup_deployment_volumes,
+    execute::restore_deployment_volumes,
     execute::batch_destroy_deployment,
     // build
     execute::run_build,
@@ -90,9 +96,11 @@ use crate::api::execute;
     // maintenance
     execute::clear_repo_cache,
     execute::backup_core_database,
+    execute::restore_core_database,
     execute::global_auto_update,
     execute::rotate_all_server_keys,
     execute::rotate_core_keys,
+    execute::rotate_encryption_key,
   ),
 )]
 pub struct KomodoExecuteApi;
@@ -1,4 +1,4 @@
-use crate::entities::update::Update;
+use crate::entities::{VolumeBackupMode, update::Update};
 use anyhow::Context;
 use clap::ArgAction::SetTrue;
 use clap::Parser;
@@ -423,6 +423,50 @@ pub struct DestroyStack {
 
 //
 
+#[cfg(feature = "utoipa")]
+#[utoipa::path(
+  post,
+  path = "/RollbackStack",
+  description = "Rolls back the target stack to the previously deployed configuration.",
+  request_body(content = RollbackStack),
+  responses(
+    (status = 200, description = "The update", body = Update),
+  ),
+)]
+pub fn rollback_stack() {}
+
+/// Rolls back the target stack to the configuration
+/// of the previous successful deploy. Response: [Update].
+///
+/// 1. Finds the last successful full stack deploy before the most recent deploy.
+/// 2. Restores the stack config recorded on that deploy.
+/// For stacks with a configured `repo`, the config is pinned to the deployed commit hash,
+/// which remains until it is cleared from the config.
+/// 3. Redeploys the stack, see [DeployStack].
+///
+/// Note. Stacks using `files_on_host` or a `linked_repo` will be redeployed
+/// with the files currently on the host / in the linked repo.
+///
+/// Note. Only `Execute` permission on the stack is required.
+/// The restored configuration is still recorded as an `UpdateStack`.
+#[typeshare]
+#[derive(
+  Debug, Clone, PartialEq, Serialize, Deserialize, Resolve, Parser,
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(mogh_error::Error)]
+pub struct RollbackStack {
+  /// Id or name
+  pub stack: String,
+  /// Override the default termination max time.
+  /// Only used if the stack needs to be taken down first.
+  pub stop_time: Option<i32>,
+}
+
+//
+
 #[cfg(feature = "utoipa")]
 #[utoipa::path(
   post,
@@ -487,6 +531,96 @@ fn env_parser(args: &str) -> anyhow::Result<HashMap<String, String>> {
 
 //
 
+#[cfg(feature = "utoipa")]
+#[utoipa::path(
+  post,
+  path = "/BackupStackVolumes",
+  description = "Backs up the volumes and bind mounts of the target stack.",
+  request_body(content = BackupStackVolumes),
+  responses(
+    (status = 200, description = "The update", body = Update),
+  ),
+)]
+pub fn backup_stack_volumes() {}
+
+/// Backs up the volumes and bind mounts of the target stack
+/// on its server. Response: [Update].
+///
+/// Each volume / bind mount is archived with `tar`, and written to
+/// the Periphery `volume_backup_dir`, and `volume_backup_remote` if configured.
+///
+/// Note. Not supported for Swarm mode Stacks.
+#[typeshare]
+#[derive(
+  Debug, Clone, PartialEq, Serialize, Deserialize, Resolve, Parser,
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(mogh_error::Error)]
+pub struct BackupStackVolumes {
+  /// Id or name
+  pub stack: String,
+  /// Filter to only back up the mounts of specific services.
+  /// If empty, will back up the mounts of all services.
+  #[serde(default)]
+  pub services: Vec<String>,
+  /// How to handle the running containers during the backup.
+  /// Default: `Stop`
+  #[serde(default)]
+  #[arg(long, short = 'm', default_value_t = VolumeBackupMode::Stop)]
+  pub mode: VolumeBackupMode,
+  /// Only back up named volumes, skipping bind mounts.
+  #[serde(default)]
+  #[arg(long, short = 's', default_value_t = false)]
+  pub skip_bind_mounts: bool,
+}
+
+//
+
+#[cfg(feature = "utoipa")]
+#[utoipa::path(
+  post,
+  path = "/RestoreStackVolumes",
+  description = "Restores the volumes and bind mounts of the target stack from a backup.",
+  request_body(content = RestoreStackVolumes),
+  responses(
+    (status = 200, description = "The update", body = Update),
+  ),
+)]
+pub fn restore_stack_volumes() {}
+
+/// Restores the volumes and bind mounts of the target stack
+/// from a backup made with [BackupStackVolumes]. Response: [Update].
+///
+/// The containers are stopped during the restore,
+/// and the existing contents of the volumes are replaced.
+///
+/// Note. Not supported for Swarm mode Stacks.
+#[typeshare]
+#[derive(
+  Debug, Clone, PartialEq, Serialize, Deserialize, Resolve, Parser,
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(mogh_error::Error)]
+pub struct RestoreStackVolumes {
+  /// Id or name
+  pub stack: String,
+  /// The backup to restore, eg `2025-08-04_05-05-53`.
+  /// If empty, will restore the most recent backup.
+  #[serde(default)]
+  #[arg(long, short = 'b', default_value_t = String::new())]
+  pub backup: String,
+  /// Filter to only restore the mounts of specific services.
+  /// If empty, will restore the mounts of all services.
+  #[serde(default)]
+  pub services: Vec<String>,
+}
+
+//
+
 #[cfg(feature = "utoipa")]
 #[utoipa::path(
   post,
@@ -0,0 +1,36 @@
+//! # LDAP / Active Directory login
+//!
+//! - `GET /ldap/options` returns [LdapLoginOptions].
+//! - `POST /ldap/login` with body [LdapLogin] returns [LdapLoginResponse].
+//!
+//! The returned JWT is used the same way as the JWT from the `/auth` api.
+
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+/// Whether LDAP login is enabled on Komodo Core.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct LdapLoginOptions {
+  pub enabled: bool,
+}
+
+/// Login with LDAP / Active Directory credentials.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct LdapLogin {
+  /// The directory username, matched with `ldap_user_filter`.
+  pub username: String,
+  /// The directory password.
+  pub password: String,
+}
+
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct LdapLoginResponse {
+  /// The JWT to authenticate further requests.
+  pub jwt: String,
+}
@@ -44,6 +44,8 @@
 //! ## Modules
 //!
 //! - [auth]: Requests relating to logging in / obtaining authentication tokens.
+//! - [ldap]: Login with LDAP / Active Directory credentials.
+//! - [oidc]: OIDC login which syncs UserGroups with the groups claim.
 //! - [read]: Read only requests which retrieve data from Komodo.
 //! - [execute]: Run actions on Komodo resources, eg [execute::RunBuild].
 //! - [mod@write]: Requests which alter data, like create / update / delete resources.
@@ -63,6 +65,8 @@
 //! ```
 
 pub mod execute;
+pub mod ldap;
+pub mod oidc;
 pub mod read;
 pub mod terminal;
 pub mod write;
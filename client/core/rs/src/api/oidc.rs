@@ -0,0 +1,31 @@
+//! # OIDC login with group sync
+//!
+//! Used instead of the `/auth` OIDC login when `oidc_group_sync` is enabled.
+//!
+//! - `GET /oidc/options` returns [OidcLoginOptions].
+//! - `GET /oidc/login` redirects the browser to the OIDC provider.
+//!   The provider redirects back to `/oidc/callback`, which then
+//!   redirects to Komodo with `oidc_redeem_ready=true`.
+//! - `POST /oidc/redeem` returns [OidcLoginResponse]
+//!   using the same browser session.
+//!
+//! The returned JWT is used the same way as the JWT from the `/auth` api.
+
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+/// Whether OIDC login goes through `/oidc/login` on Komodo Core.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct OidcLoginOptions {
+  pub enabled: bool,
+}
+
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct OidcLoginResponse {
+  /// The JWT to authenticate further requests.
+  pub jwt: String,
+}
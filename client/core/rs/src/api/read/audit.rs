@@ -0,0 +1,67 @@
+use mogh_resolver::Resolve;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::{
+  I64, ResourceTarget, U64,
+  audit::{AuditEvent, AuditOperation},
+};
+
+use super::KomodoReadRequest;
+
+//
+
+#[cfg(feature = "utoipa")]
+#[utoipa::path(
+  post,
+  path = "/ListAuditEvents",
+  description = "**Admin only.** List audit events matching the filters.",
+  request_body(content = ListAuditEvents),
+  responses(
+    (status = 200, description = "The paginated audit events", body = ListAuditEventsResponse),
+  ),
+)]
+pub fn list_audit_events() {}
+
+/// **Admin only.** List audit events matching the filters,
+/// sorted by timestamp descending.
+/// Response: [ListAuditEventsResponse].
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Resolve)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[empty_traits(KomodoReadRequest)]
+#[response(ListAuditEventsResponse)]
+#[error(mogh_error::Error)]
+pub struct ListAuditEvents {
+  /// Only include events by this user. Accepts id or username.
+  pub user: Option<String>,
+  /// Only include events from this source IP.
+  pub ip: Option<String>,
+  /// Only include events of this operation.
+  pub operation: Option<AuditOperation>,
+  /// Only include events about this resource. Accepts id or name.
+  pub target: Option<ResourceTarget>,
+  /// Only include successful (`true`) or failed (`false`) events.
+  pub success: Option<bool>,
+  /// Only include events at or after this
+  /// unix timestamp in milliseconds.
+  pub start_ts: Option<I64>,
+  /// Only include events at or before this
+  /// unix timestamp in milliseconds.
+  pub end_ts: Option<I64>,
+  /// Retrieve older results by incrementing the page.
+  /// `page: 0` is default, and returns the most recent results.
+  #[serde(default)]
+  pub page: U64,
+}
+
+/// Response for [ListAuditEvents].
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ListAuditEventsResponse {
+  pub events: Vec<AuditEvent>,
+  /// If more events exist, the next page will be given here.
+  /// Otherwise it will be `null`
+  pub next_page: Option<I64>,
+}
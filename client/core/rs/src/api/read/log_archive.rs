@@ -0,0 +1,70 @@
+use mogh_resolver::Resolve;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::{
+  I64, ResourceTarget, U64, log_archive::ArchivedLog,
+};
+
+use super::KomodoReadRequest;
+
+//
+
+#[cfg(feature = "utoipa")]
+#[utoipa::path(
+  post,
+  path = "/SearchArchivedLogs",
+  description = "Search the archived container logs of a Deployment or Stack.",
+  request_body(content = SearchArchivedLogs),
+  responses(
+    (status = 200, description = "The paginated archived log lines", body = SearchArchivedLogsResponse),
+  ),
+)]
+pub fn search_archived_logs() {}
+
+/// Search the archived container logs of a Deployment or Stack,
+/// sorted by timestamp descending. The Deployment / Stack must have
+/// `archive_logs` enabled for logs to be archived.
+/// Response: [SearchArchivedLogsResponse].
+///
+/// Note. This call will fail if the user does not have
+/// `Read` + `Logs` permission on the target.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[empty_traits(KomodoReadRequest)]
+#[response(SearchArchivedLogsResponse)]
+#[error(mogh_error::Error)]
+pub struct SearchArchivedLogs {
+  /// The Deployment or Stack target. Accepts id or name.
+  pub target: ResourceTarget,
+  /// Only include lines from this container.
+  /// Useful to filter to a specific Stack service.
+  pub container: Option<String>,
+  /// Only include lines logged at or after this
+  /// unix timestamp in milliseconds.
+  pub start_ts: Option<I64>,
+  /// Only include lines logged at or before this
+  /// unix timestamp in milliseconds.
+  pub end_ts: Option<I64>,
+  /// Only include lines matching this regex.
+  pub regex: Option<String>,
+  /// Make the regex match case insensitive.
+  #[serde(default)]
+  pub case_insensitive: bool,
+  /// Retrieve older results by incrementing the page.
+  /// `page: 0` is default, and returns the most recent results.
+  #[serde(default)]
+  pub page: U64,
+}
+
+/// Response for [SearchArchivedLogs].
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SearchArchivedLogsResponse {
+  pub logs: Vec<ArchivedLog>,
+  /// If more logs exist, the next page will be given here.
+  /// Otherwise it will be `null`
+  pub next_page: Option<I64>,
+}
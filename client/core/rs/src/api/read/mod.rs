@@ -5,10 +5,12 @@ use typeshare::typeshare;
 mod action;
 mod alert;
 mod alerter;
+mod audit;
 mod build;
 mod builder;
 mod deployment;
 mod docker;
+mod log_archive;
 mod onboarding_key;
 mod permission;
 mod procedure;
@@ -30,10 +32,12 @@ mod variable;
 pub use action::*;
 pub use alert::*;
 pub use alerter::*;
+pub use audit::*;
 pub use build::*;
 pub use builder::*;
 pub use deployment::*;
 pub use docker::*;
+pub use log_archive::*;
 pub use onboarding_key::*;
 pub use permission::*;
 pub use procedure::*;
@@ -155,6 +155,10 @@ use crate::api::read;
     // alert
     read::list_alerts,
     read::get_alert,
+    // log archive
+    read::search_archived_logs,
+    // audit
+    read::list_audit_events,
     // user
     read::list_api_keys,
     read::list_api_keys_for_service_user,
@@ -30,3 +30,33 @@ pub struct CloseAlert {
   /// The id of the Alert to close.
   pub id: String,
 }
+
+//
+
+#[cfg(feature = "utoipa")]
+#[utoipa::path(
+  post,
+  path = "/AcknowledgeAlert",
+  description = "Acknowledge the Alert at the given id, stopping repeat notifications and escalation.",
+  request_body(content = AcknowledgeAlert),
+  responses(
+    (status = 200, description = "Alert acknowledged", body = NoData),
+  ),
+)]
+pub fn acknowledge_alert() {}
+
+/// Acknowledge the Alert at the given id.
+/// Acknowledged alerts are no longer repeated or escalated
+/// by the alerter escalation policies.
+/// Requires execute permissions on the alert target.
+/// Response: [NoData]
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, Resolve)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[empty_traits(KomodoWriteRequest)]
+#[response(NoData)]
+#[error(mogh_error::Error)]
+pub struct AcknowledgeAlert {
+  /// The id of the Alert to acknowledge.
+  pub id: String,
+}
@@ -3,7 +3,7 @@ use mogh_resolver::Resolve;
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
-use crate::entities::{I64, NoData};
+use crate::entities::{I64, NoData, api_key::ApiKeyScopes};
 
 use super::KomodoWriteRequest;
 
@@ -38,6 +38,9 @@ pub struct CreateApiKeyForServiceUser {
   /// Default is 0, which means no expiry.
   #[serde(default)]
   pub expires: I64,
+  /// Optionally restrict what the api key can be used for.
+  #[serde(default)]
+  pub scopes: ApiKeyScopes,
 }
 
 #[typeshare]
@@ -71,3 +74,39 @@ pub struct DeleteApiKeyForServiceUser {
 
 #[typeshare]
 pub type DeleteApiKeyForServiceUserResponse = NoData;
+
+//
+
+#[cfg(feature = "utoipa")]
+#[utoipa::path(
+  post,
+  path = "/UpdateApiKeyScopes",
+  description = "Update the scopes of an api key.",
+  request_body(content = UpdateApiKeyScopes),
+  responses(
+    (status = 200, description = "The api key scopes were updated", body = NoData),
+  ),
+)]
+pub fn update_api_key_scopes() {}
+
+/// Update the scopes of an api key.
+/// Users can update their own api keys,
+/// and admins can update the api keys of service users.
+/// Can't be called using a scoped api key.
+/// Response: [NoData].
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[empty_traits(KomodoWriteRequest)]
+#[response(UpdateApiKeyScopesResponse)]
+#[error(mogh_error::Error)]
+pub struct UpdateApiKeyScopes {
+  /// The api key
+  pub key: String,
+  /// The new scopes. Empty scopes remove all restrictions.
+  #[serde(default)]
+  pub scopes: ApiKeyScopes,
+}
+
+#[typeshare]
+pub type UpdateApiKeyScopesResponse = NoData;
@@ -104,6 +104,7 @@ use crate::api::write;
     write::batch_delete_all_terminals,
     // alert
     write::close_alert,
+    write::acknowledge_alert,
     // tags
     write::create_tag,
     write::delete_tag,
@@ -132,10 +133,12 @@ use crate::api::write;
     // api key
     write::create_api_key_for_service_user,
     write::delete_api_key_for_service_user,
+    write::update_api_key_scopes,
     // provider
     write::create_git_provider_account,
     write::update_git_provider_account,
     write::delete_git_provider_account,
+    write::generate_git_provider_account_ssh_key,
     write::create_docker_registry_account,
     write::update_docker_registry_account,
     write::delete_docker_registry_account,
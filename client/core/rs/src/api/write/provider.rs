@@ -101,6 +101,39 @@ pub type DeleteGitProviderAccountResponse = GitProviderAccount;
 
 //
 
+#[cfg(feature = "utoipa")]
+#[utoipa::path(
+  post,
+  path = "/GenerateGitProviderAccountSshKey",
+  description = "**Admin only.** Generate a new SSH key for a git provider account.",
+  request_body(content = GenerateGitProviderAccountSshKey),
+  responses(
+    (status = 200, description = "The updated account", body = GenerateGitProviderAccountSshKeyResponse),
+  ),
+)]
+pub fn generate_git_provider_account_ssh_key() {}
+
+/// **Admin only.** Generate a new ed25519 SSH key for a git provider account,
+/// replacing any existing key. Add the returned `ssh_public_key`
+/// as a deploy key on the git provider.
+/// Response: [GitProviderAccount].
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[empty_traits(KomodoWriteRequest)]
+#[response(GenerateGitProviderAccountSshKeyResponse)]
+#[error(mogh_error::Error)]
+pub struct GenerateGitProviderAccountSshKey {
+  /// The id of the git provider account.
+  pub id: String,
+}
+
+#[typeshare]
+pub type GenerateGitProviderAccountSshKeyResponse =
+  GitProviderAccount;
+
+//
+
 #[cfg(feature = "utoipa")]
 #[utoipa::path(
   post,
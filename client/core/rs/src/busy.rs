@@ -40,6 +40,8 @@ impl Busy for DeploymentActionState {
       || self.stopping
       || self.destroying
       || self.renaming
+      || self.backing_up
+      || self.restoring
   }
 }
 
@@ -52,6 +54,8 @@ impl Busy for StackActionState {
       || self.unpausing
       || self.stopping
       || self.destroying
+      || self.backing_up
+      || self.restoring
   }
 }
 
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumDiscriminants, EnumString};
@@ -54,6 +54,38 @@ pub struct Alert {
 
   /// The timestamp of alert resolution
   pub resolved_ts: Option<I64>,
+
+  /// Acknowledgement and escalation state of the alert.
+  #[serde(default)]
+  pub escalation: AlertEscalation,
+}
+
+/// Acknowledgement and escalation state of an alert.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct AlertEscalation {
+  /// Whether a user has acknowledged the alert.
+  /// Acknowledged alerts are no longer repeated or escalated.
+  #[serde(default)]
+  pub acknowledged: bool,
+
+  /// The id of the user who acknowledged the alert
+  #[serde(default)]
+  pub acknowledged_by: Option<String>,
+
+  /// The timestamp the alert was acknowledged
+  #[serde(default)]
+  pub acknowledged_ts: Option<I64>,
+
+  /// The last time a repeat notification was sent
+  /// for the alert, keyed by alerter id.
+  #[serde(default)]
+  pub notified: HashMap<String, I64>,
+
+  /// The ids of the alerters the alert has been escalated to.
+  #[serde(default)]
+  pub escalated_to: Vec<String>,
 }
 
 /// The variants of data related to the alert.
@@ -153,6 +185,34 @@ pub enum AlertData {
     total_gb: f64,
   },
 
+  /// A server has a high sustained load average.
+  ServerLoadAverage {
+    /// The id of the server
+    id: String,
+    /// The name of the server
+    name: String,
+    /// The region of the server
+    region: Option<String>,
+    /// The 5 minute load average
+    load_average: f64,
+    /// The 5 minute load average per cpu core
+    per_core: f64,
+  },
+
+  /// A server has high network throughput.
+  ServerNetwork {
+    /// The id of the server
+    id: String,
+    /// The name of the server
+    name: String,
+    /// The region of the server
+    region: Option<String>,
+    /// The network ingress in MB/s
+    ingress_mb: f64,
+    /// The network egress in MB/s
+    egress_mb: f64,
+  },
+
   /// A server has a version mismatch with the core.
   ServerVersionMismatch {
     /// The id of the server
@@ -282,6 +342,44 @@ pub enum AlertData {
     images: Vec<String>,
   },
 
+  /// A Deployment / Stack container has high CPU usage.
+  ContainerCpu {
+    /// The type of resource, Deployment or Stack
+    resource_type: ResourceTargetVariant,
+    /// The id of the resource
+    id: String,
+    /// The name of the resource
+    name: String,
+    /// The server id of server that the container is on
+    server_id: Option<String>,
+    /// The server name
+    server_name: Option<String>,
+    /// The container name
+    container: String,
+    /// The container cpu usage percentage
+    percentage: f64,
+  },
+
+  /// A Deployment / Stack container has high memory usage.
+  ContainerMem {
+    /// The type of resource, Deployment or Stack
+    resource_type: ResourceTargetVariant,
+    /// The id of the resource
+    id: String,
+    /// The name of the resource
+    name: String,
+    /// The server id of server that the container is on
+    server_id: Option<String>,
+    /// The server name
+    server_name: Option<String>,
+    /// The container name
+    container: String,
+    /// The container memory usage percentage (of its limit)
+    percentage: f64,
+    /// The container memory usage, eg `1.2GiB / 4GiB`
+    usage: String,
+  },
+
   /// An AWS builder failed to terminate.
   AwsBuilderTerminationFailed {
     /// The id of the aws instance which failed to terminate
@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use strum::{AsRefStr, Display, EnumDiscriminants, EnumString};
 use typeshare::typeshare;
 
-use crate::entities::MaintenanceWindow;
+use crate::entities::{I64, MaintenanceWindow};
 
 use super::{
   ResourceTarget,
@@ -81,6 +81,18 @@ pub struct AlerterConfig {
   #[serde(default)]
   #[builder(default)]
   pub maintenance_windows: Vec<MaintenanceWindow>,
+
+  /// Repeat and escalate notifications for alerts
+  /// which stay open without being acknowledged.
+  #[serde(default)]
+  #[builder(default)]
+  pub escalation: AlertEscalationPolicy,
+
+  /// Group alerts into a single combined message
+  /// to prevent notification storms.
+  #[serde(default)]
+  #[builder(default)]
+  pub grouping: AlertGroupingPolicy,
 }
 
 impl AlerterConfig {
@@ -99,10 +111,67 @@ impl Default for AlerterConfig {
       resources: Default::default(),
       except_resources: Default::default(),
       maintenance_windows: Default::default(),
+      escalation: Default::default(),
+      grouping: Default::default(),
     }
   }
 }
 
+/// Policy for repeating and escalating open alerts.
+/// Only applies to unresolved alerts which have
+/// not been acknowledged with `AcknowledgeAlert`.
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Default, PartialEq,
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct AlertEscalationPolicy {
+  /// Re-send the alert every N minutes while it stays open.
+  /// 0 disables repeat notifications.
+  #[serde(default)]
+  pub repeat_interval: I64,
+
+  /// Escalate the alert to another alerter if it stays
+  /// open for N minutes. 0 disables escalation.
+  #[serde(default)]
+  pub escalate_after: I64,
+
+  /// The alerter (id or name) to escalate to.
+  #[serde(default)]
+  pub escalate_to: String,
+}
+
+impl AlertEscalationPolicy {
+  pub fn is_enabled(&self) -> bool {
+    self.repeat_interval > 0
+      || (self.escalate_after > 0 && !self.escalate_to.is_empty())
+  }
+}
+
+/// Policy for grouping alerts into combined digest messages.
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Default, PartialEq,
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct AlertGroupingPolicy {
+  /// Collect alerts and send them as one combined message,
+  /// instead of one message per alert.
+  #[serde(default)]
+  pub enabled: bool,
+
+  /// Collect alerts for N minutes before sending the digest.
+  /// 0 sends one digest per monitoring cycle.
+  #[serde(default)]
+  pub window: I64,
+
+  /// Don't send Deployment / Stack alerts when
+  /// the Server they are on is unreachable.
+  /// The `ServerUnreachable` alert is still sent.
+  #[serde(default)]
+  pub hide_unreachable_children: bool,
+}
+
 #[cfg(feature = "utoipa")]
 impl utoipa::PartialSchema for PartialAlerterConfig {
   fn schema()
@@ -165,6 +234,13 @@ pub enum AlerterEndpoint {
 
   /// Send alert to Pushover
   Pushover(PushoverAlerterEndpoint),
+
+  /// Send a templated request to any http endpoint,
+  /// with custom headers and optional HMAC signing.
+  Webhook(WebhookAlerterEndpoint),
+
+  /// Send alert by email over SMTP
+  Smtp(SmtpAlerterEndpoint),
 }
 
 impl Default for AlerterEndpoint {
@@ -309,6 +385,224 @@ fn default_pushover_url() -> String {
   )
 }
 
+/// Configuration for a generic Webhook alerter.
+#[typeshare]
+#[derive(
+  Debug, Clone, PartialEq, Serialize, Deserialize, Builder,
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct WebhookAlerterEndpoint {
+  /// The http/s endpoint to send the request to.
+  /// Supports `[[VARIABLE]]` / `[[SECRET]]` interpolation.
+  #[serde(default = "default_webhook_url")]
+  #[builder(default = "default_webhook_url()")]
+  pub url: String,
+
+  /// The http method to use. Default: `POST`
+  #[serde(default)]
+  #[builder(default)]
+  pub method: WebhookAlerterMethod,
+
+  /// Extra headers to attach to the request, one per line.
+  /// Supports `[[VARIABLE]]` / `[[SECRET]]` interpolation.
+  /// ```text
+  /// Authorization: Bearer [[OPSGENIE_TOKEN]]
+  /// X-Custom-Header: value
+  /// ```
+  #[serde(default)]
+  #[builder(default)]
+  pub headers: String,
+
+  /// The Content-Type of the rendered body.
+  /// Default: `application/json`
+  #[serde(default = "default_webhook_content_type")]
+  #[builder(default = "default_webhook_content_type()")]
+  pub content_type: String,
+
+  /// Template for the request body.
+  /// If empty, the full Alert serialized to JSON is sent.
+  ///
+  /// Placeholders are written as `{{path}}`:
+  /// - `{{message}}`: The standard formatted alert message.
+  /// - `{{level}}`: The alert severity, eg `CRITICAL`.
+  /// - `{{variant}}`: The alert type, eg `ServerCpu`.
+  /// - `{{ts}}`: The unix timestamp (ms) the alert was opened.
+  /// - `{{resolved}}`: Whether the alert is resolved.
+  /// - `{{target.type}}`, `{{target.id}}`: The alert target.
+  /// - `{{data.<field>}}`: Any field on the alert data, eg `{{data.name}}`.
+  ///
+  /// When the Content-Type contains `json`, string values are escaped
+  /// so they can be placed inside JSON string literals.
+  #[serde(default)]
+  #[builder(default)]
+  pub body: String,
+
+  /// If provided, the rendered body will be signed with HMAC-SHA256
+  /// using this secret, and the hex encoded signature attached
+  /// as `X-Komodo-Signature: sha256=<signature>`.
+  /// Supports `[[SECRET]]` interpolation.
+  #[serde(default)]
+  #[builder(default)]
+  pub signing_secret: String,
+}
+
+impl Default for WebhookAlerterEndpoint {
+  fn default() -> Self {
+    Self {
+      url: default_webhook_url(),
+      method: Default::default(),
+      headers: Default::default(),
+      content_type: default_webhook_content_type(),
+      body: Default::default(),
+      signing_secret: Default::default(),
+    }
+  }
+}
+
+fn default_webhook_url() -> String {
+  String::from("http://localhost:7000")
+}
+
+fn default_webhook_content_type() -> String {
+  String::from("application/json")
+}
+
+/// The http method used by the Webhook alerter.
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  PartialEq,
+  Eq,
+  Clone,
+  Copy,
+  Default,
+  Display,
+  EnumString,
+  AsRefStr,
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub enum WebhookAlerterMethod {
+  #[default]
+  #[serde(rename = "POST")]
+  #[strum(serialize = "POST")]
+  Post,
+  #[serde(rename = "PUT")]
+  #[strum(serialize = "PUT")]
+  Put,
+  #[serde(rename = "PATCH")]
+  #[strum(serialize = "PATCH")]
+  Patch,
+}
+
+/// Configuration for an Smtp (email) alerter.
+#[typeshare]
+#[derive(
+  Debug, Clone, PartialEq, Serialize, Deserialize, Builder,
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SmtpAlerterEndpoint {
+  /// The SMTP server host, eg `smtp.example.com`
+  #[serde(default = "default_smtp_host")]
+  #[builder(default = "default_smtp_host()")]
+  pub host: String,
+
+  /// The SMTP server port.
+  /// Default: `587`
+  #[serde(default = "default_smtp_port")]
+  #[builder(default = "default_smtp_port()")]
+  pub port: u16,
+
+  /// How to secure the connection to the SMTP server.
+  /// Default: `StartTls`
+  #[serde(default)]
+  #[builder(default)]
+  pub security: SmtpSecurity,
+
+  /// The username to authenticate with.
+  /// If empty, will not authenticate.
+  /// Supports `[[VARIABLE]]` / `[[SECRET]]` interpolation.
+  #[serde(default)]
+  #[builder(default)]
+  pub username: String,
+
+  /// The password to authenticate with.
+  /// Supports `[[VARIABLE]]` / `[[SECRET]]` interpolation.
+  #[serde(default)]
+  #[builder(default)]
+  pub password: String,
+
+  /// The sender address, eg `Komodo <komodo@example.com>`
+  #[serde(default)]
+  #[builder(default)]
+  pub from: String,
+
+  /// The recipient addresses.
+  #[serde(default)]
+  #[builder(default)]
+  pub to: Vec<String>,
+
+  /// Prefix added to the subject of every email.
+  /// Default: `[Komodo]`
+  #[serde(default = "default_smtp_subject_prefix")]
+  #[builder(default = "default_smtp_subject_prefix()")]
+  pub subject_prefix: String,
+}
+
+impl Default for SmtpAlerterEndpoint {
+  fn default() -> Self {
+    Self {
+      host: default_smtp_host(),
+      port: default_smtp_port(),
+      security: Default::default(),
+      username: Default::default(),
+      password: Default::default(),
+      from: Default::default(),
+      to: Default::default(),
+      subject_prefix: default_smtp_subject_prefix(),
+    }
+  }
+}
+
+fn default_smtp_host() -> String {
+  String::from("localhost")
+}
+
+fn default_smtp_port() -> u16 {
+  587
+}
+
+fn default_smtp_subject_prefix() -> String {
+  String::from("[Komodo]")
+}
+
+/// How the Smtp alerter secures the server connection.
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  PartialEq,
+  Eq,
+  Clone,
+  Copy,
+  Default,
+  Display,
+  EnumString,
+  AsRefStr,
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub enum SmtpSecurity {
+  /// Plain connection, no TLS. Only for local relays.
+  None,
+  /// Upgrade a plain connection with STARTTLS (usually port 587).
+  #[default]
+  StartTls,
+  /// Implicit TLS from the start of the connection (usually port 465).
+  Tls,
+}
+
 // QUERY
 #[typeshare]
 pub type AlerterQuery = ResourceQuery<AlerterQuerySpecifics>;
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
-use super::I64;
+use super::{I64, ResourceTarget, ResourceTargetVariant};
 
 /// An api key used to authenticate requests via request headers.
 #[typeshare]
@@ -31,6 +31,11 @@ pub struct ApiKey {
 
   /// Expiry of key, or 0 if never expires
   pub expires: I64,
+
+  /// Optionally restrict what the api key can be used for.
+  /// By default, the key has all permissions of its user.
+  #[serde(default)]
+  pub scopes: ApiKeyScopes,
 }
 
 impl ApiKey {
@@ -38,3 +43,76 @@ impl ApiKey {
     self.secret.clear()
   }
 }
+
+/// Restrictions on an [ApiKey].
+/// Each restriction is only applied when it is non-empty.
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Default, PartialEq,
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ApiKeyScopes {
+  /// The allowed requests. Either a request type,
+  /// `read`, `write` or `execute`,
+  /// or a specific request name, eg `DeployStack`.
+  #[serde(default)]
+  pub requests: Vec<String>,
+
+  /// The allowed resources, by id or name.
+  /// Resources with any of the `tags` are also allowed.
+  /// When set, requests which don't target resources,
+  /// such as `ListVariables`, must be named in `requests`.
+  #[serde(default)]
+  pub resources: Vec<ResourceTarget>,
+
+  /// Resources with any of these tags (id or name) are allowed.
+  #[serde(default)]
+  pub tags: Vec<String>,
+
+  /// The allowed source IPs, in CIDR notation, eg `10.0.0.0/8`.
+  /// Plain IP addresses are also accepted.
+  #[serde(default)]
+  pub ips: Vec<String>,
+}
+
+impl ApiKeyScopes {
+  pub fn is_empty(&self) -> bool {
+    self.requests.is_empty()
+      && self.resources.is_empty()
+      && self.tags.is_empty()
+      && self.ips.is_empty()
+  }
+
+  /// Whether the key can only access some resources.
+  pub fn restricts_resources(&self) -> bool {
+    !self.resources.is_empty() || !self.tags.is_empty()
+  }
+
+  /// `kind` is `read`, `write` or `execute`,
+  /// and `request` is the request name, eg `DeployStack`.
+  pub fn allows_request(&self, kind: &str, request: &str) -> bool {
+    self.requests.is_empty()
+      || self.requests.iter().any(|allowed| {
+        allowed.eq_ignore_ascii_case(kind) || allowed == request
+      })
+  }
+
+  /// `tags` are the tag ids on the resource.
+  /// The scope `tags` must already be resolved to ids.
+  pub fn allows_resource(
+    &self,
+    variant: ResourceTargetVariant,
+    id: &str,
+    name: &str,
+    tags: &[String],
+  ) -> bool {
+    !self.restricts_resources()
+      || self.resources.iter().any(|allowed| {
+        let (allowed_variant, id_or_name) =
+          allowed.extract_variant_id();
+        allowed_variant == variant
+          && (id_or_name == id || id_or_name == name)
+      })
+      || tags.iter().any(|tag| self.tags.contains(tag))
+  }
+}
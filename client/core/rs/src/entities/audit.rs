@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, Display, EnumString};
+use typeshare::typeshare;
+
+use crate::entities::{I64, MongoId, ResourceTarget};
+
+/// A security relevant event recorded by Core,
+/// such as a login, api key change or terminal session.
+/// List with [ListAuditEvents][crate::api::read::ListAuditEvents].
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[cfg_attr(
+  feature = "mongo",
+  derive(mongo_indexed::derive::MongoIndexed)
+)]
+#[cfg_attr(feature = "mongo", doc_index({ "target.type": 1 }))]
+#[cfg_attr(feature = "mongo", doc_index({ "target.id": 1 }))]
+pub struct AuditEvent {
+  /// The Mongo ID of the audit event.
+  #[serde(
+    default,
+    rename = "_id",
+    skip_serializing_if = "String::is_empty",
+    with = "bson::serde_helpers::hex_string_as_object_id"
+  )]
+  pub id: MongoId,
+
+  /// Unix timestamp in milliseconds of the event
+  #[cfg_attr(feature = "mongo", index)]
+  pub ts: I64,
+
+  /// The kind of event
+  #[cfg_attr(feature = "mongo", index)]
+  pub operation: AuditOperation,
+
+  /// The specific request, eg `LoginLocalUser`
+  /// or `UpdatePermissionOnTarget`
+  pub request: String,
+
+  /// Whether the request succeeded
+  pub success: bool,
+
+  /// The id of the user making the request.
+  /// Empty if unknown, eg for logins with an unknown username.
+  #[cfg_attr(feature = "mongo", index)]
+  pub user_id: String,
+
+  /// The username of the user making the request,
+  /// or the username attempted for logins.
+  #[cfg_attr(feature = "mongo", index)]
+  pub username: String,
+
+  /// The source IP of the request
+  #[cfg_attr(feature = "mongo", index)]
+  pub ip: String,
+
+  /// The resource the event is about, if any
+  pub target: Option<ResourceTarget>,
+
+  /// The name of the target resource at the time of the event
+  #[serde(default)]
+  pub target_name: String,
+
+  /// Additional details, eg the request parameters or error
+  #[serde(default)]
+  pub details: String,
+}
+
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  Default,
+  Display,
+  EnumString,
+  AsRefStr,
+  PartialEq,
+  Eq,
+  Clone,
+  Copy,
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub enum AuditOperation {
+  /// Login attempts with any login provider
+  #[default]
+  Login,
+  /// Other changes to a users own login,
+  /// eg changing password or 2FA
+  ManageLogin,
+  /// Create a user or service user, or delete a user
+  ManageUser,
+  CreateApiKey,
+  DeleteApiKey,
+  UpdateApiKey,
+  /// Changes to user permissions and user group membership
+  UpdatePermission,
+  /// Reading the value of secret variables
+  ReadSecret,
+  /// Opening a terminal session, or executing a terminal command
+  Terminal,
+}
@@ -25,6 +25,23 @@ pub struct CreateApiKey {
   /// This requires existing km credentials.
   #[arg(long, short = 'a', default_value_t = false)]
   pub use_api: bool,
+  /// Restrict the api key to requests of type `read`, `write`, `execute`,
+  /// or specific requests such as `DeployStack`.
+  /// Can be specified multiple times. (alias `r`)
+  #[arg(name = "request", long, short = 'r')]
+  pub requests: Vec<String>,
+  /// Restrict the api key to resources, as `Type:id_or_name`,
+  /// eg `Stack:my-stack`. Can be specified multiple times.
+  #[arg(name = "resource", long)]
+  pub resources: Vec<String>,
+  /// Restrict the api key to resources with the tag (id or name).
+  /// Can be specified multiple times. (alias `t`)
+  #[arg(name = "tag", long, short = 't')]
+  pub tags: Vec<String>,
+  /// Restrict the api key to source IPs, in CIDR notation.
+  /// Can be specified multiple times.
+  #[arg(name = "ip", long)]
+  pub ips: Vec<String>,
 }
 
 /// Create a new onboarding key.
@@ -27,6 +27,24 @@ pub enum DatabaseCommand {
     /// Example: `2025-08-01_05-04-53`
     #[arg(long, short = 'r')]
     restore_folder: Option<PathBuf>,
+    /// Optionally restore from a remote backup target
+    /// instead of the backups folder, eg `s3://bucket/prefix`.
+    /// Uses the other `backup_remote` settings for credentials / decryption.
+    #[arg(long)]
+    from: Option<String>,
+    /// Only restore a particular collection, eg `Stack` or `Permission`.
+    /// Can be specified multiple times. (alias `c`)
+    #[arg(name = "collection", long, short = 'c')]
+    collections: Vec<String>,
+    /// Only restore the documents with a particular id or name,
+    /// along with documents targeting them, such as their Permissions.
+    /// Can be specified multiple times. (alias `s`)
+    #[arg(name = "resource", long, short = 's')]
+    resources: Vec<String>,
+    /// Only print what would be inserted or overwritten,
+    /// without writing to the database. (alias `n`)
+    #[arg(long, short = 'n', default_value_t = false)]
+    dry_run: bool,
     /// Whether to index the target database. Default: true
     #[arg(long, short = 'i', default_value_t = true)]
     index: bool,
@@ -36,6 +54,7 @@ pub enum DatabaseCommand {
   },
   /// Prunes database backups if there are greater than
   /// the configured `max_backups` (KOMODO_CLI_MAX_BACKUPS).
+  /// Also prunes the `backup_remote`, if configured.
   Prune {
     /// Optionally provide a specific backups folder.
     /// Default: `/backups`
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use crate::{
   deserializers::string_list_deserializer,
   entities::{
-    config::{DatabaseConfig, empty_or_redacted},
+    config::{BackupRemoteConfig, DatabaseConfig, empty_or_redacted},
     logger::{LogConfig, LogLevel, StdioLogMode},
   },
 };
@@ -87,6 +87,43 @@ pub struct Env {
   #[serde(alias = "komodo_cli_database_copy_db_name")]
   pub komodo_cli_database_target_db_name: Option<String>,
 
+  // BACKUP REMOTE
+  /// Override `backup_remote.url`
+  pub komodo_cli_backup_remote_url: Option<String>,
+  /// Override `backup_remote.s3_endpoint`
+  pub komodo_cli_backup_remote_s3_endpoint: Option<String>,
+  /// Override `backup_remote.s3_region`
+  pub komodo_cli_backup_remote_s3_region: Option<String>,
+  /// Override `backup_remote.s3_path_style`
+  pub komodo_cli_backup_remote_s3_path_style: Option<bool>,
+  /// Override `backup_remote.s3_access_key_id`
+  pub komodo_cli_backup_remote_s3_access_key_id: Option<String>,
+  /// Override `backup_remote.s3_access_key_id` from file
+  pub komodo_cli_backup_remote_s3_access_key_id_file: Option<PathBuf>,
+  /// Override `backup_remote.s3_secret_access_key`
+  pub komodo_cli_backup_remote_s3_secret_access_key: Option<String>,
+  /// Override `backup_remote.s3_secret_access_key` from file
+  pub komodo_cli_backup_remote_s3_secret_access_key_file:
+    Option<PathBuf>,
+  /// Override `backup_remote.username`
+  pub komodo_cli_backup_remote_username: Option<String>,
+  /// Override `backup_remote.password`
+  pub komodo_cli_backup_remote_password: Option<String>,
+  /// Override `backup_remote.password` from file
+  pub komodo_cli_backup_remote_password_file: Option<PathBuf>,
+  /// Override `backup_remote.sftp_private_key`
+  pub komodo_cli_backup_remote_sftp_private_key: Option<String>,
+  /// Override `backup_remote.sftp_known_hosts`
+  pub komodo_cli_backup_remote_sftp_known_hosts: Option<String>,
+  /// Override `backup_remote.max_backups`
+  pub komodo_cli_backup_remote_max_backups: Option<u16>,
+  /// Override `backup_remote.max_backup_age_days`
+  pub komodo_cli_backup_remote_max_backup_age_days: Option<u16>,
+  /// Override `backup_remote.encryption_key`
+  pub komodo_cli_backup_remote_encryption_key: Option<String>,
+  /// Override `backup_remote.encryption_key` from file
+  pub komodo_cli_backup_remote_encryption_key_file: Option<PathBuf>,
+
   // LOGGING
   /// Override `logging.level`
   pub komodo_cli_logging_level: Option<LogLevel>,
@@ -217,6 +254,13 @@ pub struct CliConfig {
   /// if there are more backups than `max_backups`
   #[serde(default = "default_max_backups")]
   pub max_backups: u16,
+  /// Optionally upload backups to a remote target,
+  /// such as S3-compatible storage, SFTP or WebDAV.
+  #[serde(
+    default,
+    skip_serializing_if = "BackupRemoteConfig::is_default"
+  )]
+  pub backup_remote: BackupRemoteConfig,
   // Same as Core
   /// Configure database connection
   #[serde(
@@ -285,6 +329,7 @@ impl Default for CliConfig {
       table_borders: Default::default(),
       backups_folder: default_backups_folder(),
       max_backups: default_max_backups(),
+      backup_remote: Default::default(),
       database: default_database_config(),
       database_target: default_database_config(),
       host: Default::default(),
@@ -311,6 +356,7 @@ impl CliConfig {
       table_borders: self.table_borders,
       backups_folder: self.backups_folder.clone(),
       max_backups: self.max_backups,
+      backup_remote: self.backup_remote.sanitized(),
       database_target: self.database_target.sanitized(),
       host: self.host.clone(),
       database: self.database.sanitized(),
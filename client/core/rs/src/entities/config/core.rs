@@ -109,12 +109,36 @@ pub struct Env {
   pub komodo_keep_stats_for_days: Option<u64>,
   /// Override `keep_alerts_for_days`
   pub komodo_keep_alerts_for_days: Option<u64>,
+  /// Override `keep_archived_logs_for_days`
+  pub komodo_keep_archived_logs_for_days: Option<u64>,
+  /// Override `keep_audit_events_for_days`
+  pub komodo_keep_audit_events_for_days: Option<u64>,
   /// Override `webhook_secret`
   pub komodo_webhook_secret: Option<String>,
   /// Override `webhook_secret` with file
   pub komodo_webhook_secret_file: Option<PathBuf>,
   /// Override `webhook_base_url`
   pub komodo_webhook_base_url: Option<String>,
+  /// Override `webhook_generic_signature_header`
+  pub komodo_webhook_generic_signature_header: Option<String>,
+  /// Override `webhook_generic_signature_prefix`
+  pub komodo_webhook_generic_signature_prefix: Option<String>,
+  /// Override `webhook_generic_algorithm`
+  pub komodo_webhook_generic_algorithm: Option<WebhookHmacAlgorithm>,
+  /// Override `webhook_generic_branch_path`
+  pub komodo_webhook_generic_branch_path: Option<String>,
+  /// Override `metrics_enabled`
+  pub komodo_metrics_enabled: Option<bool>,
+  /// Override `metrics_token`
+  pub komodo_metrics_token: Option<String>,
+  /// Override `metrics_token` with file
+  pub komodo_metrics_token_file: Option<PathBuf>,
+  /// Override `audit_export_file`
+  pub komodo_audit_export_file: Option<String>,
+  /// Override `audit_export_syslog`
+  pub komodo_audit_export_syslog: Option<String>,
+  /// Override `audit_export_url`
+  pub komodo_audit_export_url: Option<String>,
 
   /// Override `transparent_mode`
   pub komodo_transparent_mode: Option<bool>,
@@ -170,6 +194,47 @@ pub struct Env {
   pub komodo_oidc_additional_audiences: Option<Vec<String>>,
   /// Override `oidc_additional_audiences` from file
   pub komodo_oidc_additional_audiences_file: Option<PathBuf>,
+  /// Override `oidc_group_sync`
+  pub komodo_oidc_group_sync: Option<bool>,
+  /// Override `oidc_groups_claim`
+  pub komodo_oidc_groups_claim: Option<String>,
+  /// Override `oidc_group_prefix`
+  pub komodo_oidc_group_prefix: Option<String>,
+  /// Override `oidc_admin_group`
+  pub komodo_oidc_admin_group: Option<String>,
+
+  /// Override `ldap_enabled`
+  pub komodo_ldap_enabled: Option<bool>,
+  /// Override `ldap_url`
+  pub komodo_ldap_url: Option<String>,
+  /// Override `ldap_start_tls`
+  pub komodo_ldap_start_tls: Option<bool>,
+  /// Override `ldap_tls_insecure`
+  pub komodo_ldap_tls_insecure: Option<bool>,
+  /// Override `ldap_bind_dn`
+  pub komodo_ldap_bind_dn: Option<String>,
+  /// Override `ldap_bind_password`
+  pub komodo_ldap_bind_password: Option<String>,
+  /// Override `ldap_bind_password` from file
+  pub komodo_ldap_bind_password_file: Option<PathBuf>,
+  /// Override `ldap_user_base_dn`
+  pub komodo_ldap_user_base_dn: Option<String>,
+  /// Override `ldap_user_filter`
+  pub komodo_ldap_user_filter: Option<String>,
+  /// Override `ldap_username_attribute`
+  pub komodo_ldap_username_attribute: Option<String>,
+  /// Override `ldap_email_attribute`
+  pub komodo_ldap_email_attribute: Option<String>,
+  /// Override `ldap_group_sync`
+  pub komodo_ldap_group_sync: Option<bool>,
+  /// Override `ldap_group_base_dn`
+  pub komodo_ldap_group_base_dn: Option<String>,
+  /// Override `ldap_group_filter`
+  pub komodo_ldap_group_filter: Option<String>,
+  /// Override `ldap_group_name_attribute`
+  pub komodo_ldap_group_name_attribute: Option<String>,
+  /// Override `ldap_group_prefix`
+  pub komodo_ldap_group_prefix: Option<String>,
 
   /// Override `google_oauth.enabled`
   pub komodo_google_oauth_enabled: Option<bool>,
@@ -285,8 +350,77 @@ pub struct Env {
   pub komodo_sync_directory: Option<PathBuf>,
   /// Override `repo_directory`
   pub komodo_repo_directory: Option<PathBuf>,
+  /// Override `git_ssh_known_hosts`
+  pub komodo_git_ssh_known_hosts: Option<PathBuf>,
+  /// Override `git_ssh_accept_new_host_keys`
+  pub komodo_git_ssh_accept_new_host_keys: Option<bool>,
+
+  /// Override `encryption_key`
+  pub komodo_encryption_key: Option<String>,
+  /// Override `encryption_key` from file
+  pub komodo_encryption_key_file: Option<PathBuf>,
+  /// Override `previous_encryption_keys`
+  pub komodo_previous_encryption_keys: Option<Vec<String>>,
+  /// Override `previous_encryption_keys` from file
+  pub komodo_previous_encryption_keys_file: Option<PathBuf>,
+
+  /// Override `vault_address`
+  pub komodo_vault_address: Option<String>,
+  /// Override `vault_token`
+  pub komodo_vault_token: Option<String>,
+  /// Override `vault_token` from file
+  pub komodo_vault_token_file: Option<PathBuf>,
+  /// Override `vault_namespace`
+  pub komodo_vault_namespace: Option<String>,
+  /// Override `secret_files_directory`
+  pub komodo_secret_files_directory: Option<PathBuf>,
+  /// Override `sops_directory`
+  pub komodo_sops_directory: Option<PathBuf>,
   /// Override `action_directory`
   pub komodo_action_directory: Option<PathBuf>,
+  /// Override `backups_folder`.
+  /// Same variable as the Komodo CLI, which takes the backups.
+  pub komodo_cli_backups_folder: Option<PathBuf>,
+}
+
+fn default_oidc_groups_claim() -> String {
+  String::from("groups")
+}
+
+fn default_oidc_group_prefix() -> String {
+  String::from("oidc-")
+}
+
+fn default_ldap_user_filter() -> String {
+  String::from("(uid={username})")
+}
+
+fn default_ldap_username_attribute() -> String {
+  String::from("uid")
+}
+
+fn default_ldap_email_attribute() -> String {
+  String::from("mail")
+}
+
+fn default_ldap_group_filter() -> String {
+  String::from("(member={user_dn})")
+}
+
+fn default_ldap_group_prefix() -> String {
+  String::from("ldap-")
+}
+
+fn default_ldap_group_name_attribute() -> String {
+  String::from("cn")
+}
+
+fn default_webhook_generic_signature_header() -> String {
+  String::from("x-signature")
+}
+
+fn default_webhook_generic_branch_path() -> String {
+  String::from("$.ref")
 }
 
 fn default_core_config_paths() -> Vec<PathBuf> {
@@ -525,6 +659,123 @@ pub struct CoreConfig {
   #[serde(default, skip_serializing_if = "Vec::is_empty")]
   pub oidc_additional_audiences: Vec<String>,
 
+  /// Sync the user's UserGroup membership with the `oidc_groups_claim`
+  /// on every OIDC login. The user is added to the UserGroups
+  /// named `{oidc_group_prefix}{group}` for their groups,
+  /// and removed from the other UserGroups with the prefix.
+  ///
+  /// Logins then go through Komodo Core at `/oidc/login`,
+  /// so `{host}/oidc/callback` must be an allowed redirect uri
+  /// on the OIDC provider. This login doesn't support 2FA,
+  /// so users with 2FA enabled can only login with OIDC
+  /// if they allow external logins to skip 2FA.
+  #[serde(default)]
+  pub oidc_group_sync: bool,
+
+  /// The ID token claim containing the user's groups.
+  /// Default: `groups`
+  #[serde(default = "default_oidc_groups_claim")]
+  pub oidc_groups_claim: String,
+
+  /// The prefix of the UserGroups managed by the OIDC group sync.
+  /// Group `devs` maps to UserGroup `oidc-devs`.
+  /// UserGroups without the prefix are never changed by the sync.
+  /// Must not be empty.
+  /// Default: `oidc-`
+  #[serde(default = "default_oidc_group_prefix")]
+  pub oidc_group_prefix: String,
+
+  /// Users in this group are made admin on login,
+  /// and users not in it have admin removed.
+  /// Super admins are never changed. Requires `oidc_group_sync`.
+  #[serde(default)]
+  pub oidc_admin_group: String,
+
+  // ========
+  // = LDAP =
+  // ========
+  /// Enable login with an LDAP / Active Directory server.
+  #[serde(default)]
+  pub ldap_enabled: bool,
+
+  /// The LDAP server url.
+  /// Use `ldaps://` for LDAP over TLS.
+  /// Eg. `ldap://ldap.example.internal:389`
+  #[serde(default)]
+  pub ldap_url: String,
+
+  /// Upgrade the `ldap://` connection with StartTLS.
+  #[serde(default)]
+  pub ldap_start_tls: bool,
+
+  /// Skip verification of the LDAP server TLS certificate.
+  #[serde(default)]
+  pub ldap_tls_insecure: bool,
+
+  /// The DN of the service account used to search for users and groups.
+  /// If empty, searches use an anonymous bind.
+  /// Eg. `cn=komodo,ou=services,dc=example,dc=com`
+  #[serde(default)]
+  pub ldap_bind_dn: String,
+
+  /// The password of the `ldap_bind_dn` service account.
+  #[serde(default)]
+  pub ldap_bind_password: String,
+
+  /// The base DN to search for users under.
+  /// Eg. `ou=users,dc=example,dc=com`
+  #[serde(default)]
+  pub ldap_user_base_dn: String,
+
+  /// The filter used to find the user logging in.
+  /// `{username}` is replaced with the (escaped) login username.
+  /// For Active Directory, use `(sAMAccountName={username})`.
+  /// Default: `(uid={username})`
+  #[serde(default = "default_ldap_user_filter")]
+  pub ldap_user_filter: String,
+
+  /// The user attribute used as the Komodo username.
+  /// Default: `uid`
+  #[serde(default = "default_ldap_username_attribute")]
+  pub ldap_username_attribute: String,
+
+  /// The user attribute containing the user email.
+  /// Default: `mail`
+  #[serde(default = "default_ldap_email_attribute")]
+  pub ldap_email_attribute: String,
+
+  /// Sync the user's UserGroup membership with their
+  /// LDAP groups on every login. The user is added to the UserGroups
+  /// named `{ldap_group_prefix}{group}` for their LDAP groups,
+  /// and removed from the other UserGroups with the prefix.
+  #[serde(default)]
+  pub ldap_group_sync: bool,
+
+  /// The base DN to search for groups under.
+  /// Eg. `ou=groups,dc=example,dc=com`
+  #[serde(default)]
+  pub ldap_group_base_dn: String,
+
+  /// The filter used to find the user's groups.
+  /// `{user_dn}` and `{username}` are replaced with the (escaped)
+  /// user DN and login username.
+  /// Default: `(member={user_dn})`
+  #[serde(default = "default_ldap_group_filter")]
+  pub ldap_group_filter: String,
+
+  /// The group attribute matched against UserGroup names.
+  /// Default: `cn`
+  #[serde(default = "default_ldap_group_name_attribute")]
+  pub ldap_group_name_attribute: String,
+
+  /// The prefix of the UserGroups managed by the LDAP group sync.
+  /// LDAP group `devs` maps to UserGroup `ldap-devs`.
+  /// UserGroups without the prefix are never changed by the sync.
+  /// Must not be empty.
+  /// Default: `ldap-`
+  #[serde(default = "default_ldap_group_prefix")]
+  pub ldap_group_prefix: String,
+
   // =========
   // = Oauth =
   // =========
@@ -589,6 +840,61 @@ pub struct CoreConfig {
   #[serde(default)]
   pub webhook_base_url: String,
 
+  /// The header containing the request signature
+  /// for the `/listener/generic` webhook integration.
+  /// Default: `x-signature`
+  #[serde(default = "default_webhook_generic_signature_header")]
+  pub webhook_generic_signature_header: String,
+
+  /// A prefix to strip from the signature header value before
+  /// comparing, eg `sha256=`. Default: empty (none)
+  #[serde(default)]
+  pub webhook_generic_signature_prefix: String,
+
+  /// The HMAC algorithm used to sign generic webhook requests.
+  /// The signature must be hex encoded. Default: `sha256`
+  #[serde(default)]
+  pub webhook_generic_algorithm: WebhookHmacAlgorithm,
+
+  /// A JSONPath to the pushed branch in the generic webhook body,
+  /// eg `$.push.changes[0].new.name`. Only dot-notation keys and
+  /// array indices are supported. Default: `$.ref`
+  #[serde(default = "default_webhook_generic_branch_path")]
+  pub webhook_generic_branch_path: String,
+
+  // ===========
+  // = Metrics =
+  // ===========
+  /// Serve Prometheus / OpenMetrics formatted metrics at `/metrics`.
+  /// Default: false
+  #[serde(default)]
+  pub metrics_enabled: bool,
+
+  /// A static token Prometheus can use to scrape `/metrics`,
+  /// passed as `Authorization: Bearer <metrics_token>`.
+  /// Admin user api keys are always accepted as well.
+  #[serde(default)]
+  pub metrics_token: String,
+
+  // =========
+  // = Audit =
+  // =========
+  /// Also append audit events to this file, one JSON object per line.
+  /// Default: empty (disabled)
+  #[serde(default)]
+  pub audit_export_file: String,
+
+  /// Also send audit events to this syslog server over UDP,
+  /// eg `syslog.example.com:514`.
+  /// Default: empty (disabled)
+  #[serde(default)]
+  pub audit_export_syslog: String,
+
+  /// Also POST audit events as JSON to this url.
+  /// Default: empty (disabled)
+  #[serde(default)]
+  pub audit_export_url: String,
+
   // ===========
   // = Logging =
   // ===========
@@ -622,6 +928,18 @@ pub struct CoreConfig {
   #[serde(default = "default_prune_days")]
   pub keep_alerts_for_days: u64,
 
+  /// Number of days to keep archived container logs, or 0 to disable pruning.
+  /// Logs older than this number of days are deleted on a daily cycle
+  /// Default: 7
+  #[serde(default = "default_keep_archived_logs_for_days")]
+  pub keep_archived_logs_for_days: u64,
+
+  /// Number of days to keep audit events, or 0 to disable pruning.
+  /// Events older than this number of days are deleted on a daily cycle
+  /// Default: 365
+  #[serde(default = "default_keep_audit_events_for_days")]
+  pub keep_audit_events_for_days: u64,
+
   // ==================
   // = Poll Intervals =
   // ==================
@@ -655,6 +973,18 @@ pub struct CoreConfig {
   )]
   pub git_providers: Vec<GitProvider>,
 
+  /// Path to a known_hosts file used to verify git provider
+  /// host keys when Core clones repos over ssh.
+  /// Default: none, the default known_hosts (`~/.ssh/known_hosts`) is used.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub git_ssh_known_hosts: Option<PathBuf>,
+
+  /// Accept and store the host keys of git providers
+  /// not yet in the known_hosts on first use (`StrictHostKeyChecking=accept-new`).
+  /// Default: false, unknown host keys are rejected (`StrictHostKeyChecking=yes`).
+  #[serde(default)]
+  pub git_ssh_accept_new_host_keys: bool,
+
   // ======================
   // = Registry Providers =
   // ======================
@@ -676,6 +1006,58 @@ pub struct CoreConfig {
   #[serde(default, skip_serializing_if = "HashMap::is_empty")]
   pub secrets: HashMap<String, String>,
 
+  /// The master key used to encrypt secret Variable values and
+  /// git provider / docker registry account tokens on the database.
+  /// A base64 encoded 32 byte key, eg from `openssl rand -base64 32`.
+  /// To load from file, use `encryption_key = "file:/path/to/encryption.key"`.
+  /// If a file is specified and does not exist, a key will be generated at the path,
+  /// unless the database already has encrypted values.
+  ///
+  /// Run `RotateEncryptionKey` after changing the key
+  /// to re-encrypt the stored values.
+  ///
+  /// Default: empty (values stored unencrypted)
+  #[serde(default)]
+  pub encryption_key: String,
+
+  /// Previous master keys, still used to decrypt stored values
+  /// until `RotateEncryptionKey` has re-encrypted them with `encryption_key`.
+  /// Supports the same formats as `encryption_key`.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub previous_encryption_keys: Vec<String>,
+
+  /// The HashiCorp Vault address, eg `https://vault.example.com:8200`.
+  /// Enables `[[vault:<path>#<key>]]` secret references,
+  /// eg `[[vault:kv/data/app#password]]`.
+  /// Default: empty (disabled)
+  #[serde(default)]
+  pub vault_address: String,
+
+  /// The token used to read secrets from Vault.
+  #[serde(default)]
+  pub vault_token: String,
+
+  /// Optional Vault Enterprise namespace.
+  #[serde(default)]
+  pub vault_namespace: String,
+
+  /// A directory containing one secret per file, such as
+  /// Docker secrets mounted at `/run/secrets`.
+  /// Enables `[[file:<file name>]]` secret references.
+  /// Default: empty (disabled)
+  #[serde(default)]
+  pub secret_files_directory: PathBuf,
+
+  /// A directory containing SOPS encrypted files.
+  /// Enables `[[sops:<file path>#<key>]]` secret references,
+  /// decrypted using the `sops` binary.
+  /// Files in Resource Sync repos are always available
+  /// with `[[sops:<sync>:<file path>#<key>]]`,
+  /// given Execute permission on the Sync.
+  /// Default: empty (disabled)
+  #[serde(default)]
+  pub sops_directory: PathBuf,
+
   // =======
   // = SSL =
   // =======
@@ -712,6 +1094,13 @@ pub struct CoreConfig {
   #[serde(default = "default_action_directory")]
   pub action_directory: PathBuf,
 
+  /// The folder containing the database backups,
+  /// used by `RestoreCoreDatabase`. Should match the
+  /// Komodo CLI `backups_folder` used by `BackupCoreDatabase`.
+  /// Default: `/backups`
+  #[serde(default = "default_backups_folder")]
+  pub backups_folder: PathBuf,
+
   /// The path to the built ui folder.
   #[serde(default = "default_ui_path")]
   pub ui_path: String,
@@ -779,10 +1168,22 @@ fn default_action_directory() -> PathBuf {
   PathBuf::from("/action-cache")
 }
 
+fn default_backups_folder() -> PathBuf {
+  PathBuf::from("/backups")
+}
+
 fn default_prune_days() -> u64 {
   14
 }
 
+fn default_keep_archived_logs_for_days() -> u64 {
+  7
+}
+
+fn default_keep_audit_events_for_days() -> u64 {
+  365
+}
+
 fn default_poll_interval() -> Timelength {
   Timelength::OneHour
 }
@@ -837,6 +1238,25 @@ impl Default for CoreConfig {
       oidc_client_secret: Default::default(),
       oidc_use_full_email: Default::default(),
       oidc_additional_audiences: Default::default(),
+      oidc_group_sync: Default::default(),
+      oidc_groups_claim: default_oidc_groups_claim(),
+      oidc_group_prefix: default_oidc_group_prefix(),
+      oidc_admin_group: Default::default(),
+      ldap_enabled: Default::default(),
+      ldap_url: Default::default(),
+      ldap_start_tls: Default::default(),
+      ldap_tls_insecure: Default::default(),
+      ldap_bind_dn: Default::default(),
+      ldap_bind_password: Default::default(),
+      ldap_user_base_dn: Default::default(),
+      ldap_user_filter: default_ldap_user_filter(),
+      ldap_username_attribute: default_ldap_username_attribute(),
+      ldap_email_attribute: default_ldap_email_attribute(),
+      ldap_group_sync: Default::default(),
+      ldap_group_base_dn: Default::default(),
+      ldap_group_filter: default_ldap_group_filter(),
+      ldap_group_name_attribute: default_ldap_group_name_attribute(),
+      ldap_group_prefix: default_ldap_group_prefix(),
       google_oauth: Default::default(),
       github_oauth: Default::default(),
       auth_rate_limit_disabled: Default::default(),
@@ -849,17 +1269,41 @@ impl Default for CoreConfig {
       session_allow_cross_site: Default::default(),
       webhook_secret: Default::default(),
       webhook_base_url: Default::default(),
+      webhook_generic_signature_header:
+        default_webhook_generic_signature_header(),
+      webhook_generic_signature_prefix: Default::default(),
+      webhook_generic_algorithm: Default::default(),
+      webhook_generic_branch_path:
+        default_webhook_generic_branch_path(),
+      metrics_enabled: Default::default(),
+      metrics_token: Default::default(),
+      audit_export_file: Default::default(),
+      audit_export_syslog: Default::default(),
+      audit_export_url: Default::default(),
       logging: Default::default(),
       pretty_startup_config: Default::default(),
       unsafe_unsanitized_startup_config: Default::default(),
       keep_stats_for_days: default_prune_days(),
       keep_alerts_for_days: default_prune_days(),
+      keep_archived_logs_for_days:
+        default_keep_archived_logs_for_days(),
+      keep_audit_events_for_days: default_keep_audit_events_for_days(
+      ),
       resource_poll_interval: default_poll_interval(),
       monitoring_interval: default_monitoring_interval(),
       aws: Default::default(),
       git_providers: Default::default(),
+      git_ssh_known_hosts: Default::default(),
+      git_ssh_accept_new_host_keys: Default::default(),
       docker_registries: Default::default(),
       secrets: Default::default(),
+      encryption_key: Default::default(),
+      previous_encryption_keys: Default::default(),
+      vault_address: Default::default(),
+      vault_token: Default::default(),
+      vault_namespace: Default::default(),
+      secret_files_directory: Default::default(),
+      sops_directory: Default::default(),
       ssl_enabled: Default::default(),
       ssl_key_file: default_ssl_key_file(),
       ssl_cert_file: default_ssl_cert_file(),
@@ -868,6 +1312,7 @@ impl Default for CoreConfig {
       sync_directory: default_sync_directory(),
       repo_directory: default_repo_directory(),
       action_directory: default_action_directory(),
+      backups_folder: default_backups_folder(),
     }
   }
 }
@@ -897,6 +1342,8 @@ impl CoreConfig {
       monitoring_interval: config.monitoring_interval,
       keep_stats_for_days: config.keep_stats_for_days,
       keep_alerts_for_days: config.keep_alerts_for_days,
+      keep_archived_logs_for_days: config.keep_archived_logs_for_days,
+      keep_audit_events_for_days: config.keep_audit_events_for_days,
       logging: config.logging,
       pretty_startup_config: config.pretty_startup_config,
       unsafe_unsanitized_startup_config: config
@@ -932,6 +1379,27 @@ impl CoreConfig {
         .iter()
         .map(|aud| empty_or_redacted(aud))
         .collect(),
+      oidc_group_sync: config.oidc_group_sync,
+      oidc_groups_claim: config.oidc_groups_claim,
+      oidc_group_prefix: config.oidc_group_prefix,
+      oidc_admin_group: config.oidc_admin_group,
+      ldap_enabled: config.ldap_enabled,
+      ldap_url: config.ldap_url,
+      ldap_start_tls: config.ldap_start_tls,
+      ldap_tls_insecure: config.ldap_tls_insecure,
+      ldap_bind_dn: config.ldap_bind_dn,
+      ldap_bind_password: empty_or_redacted(
+        &config.ldap_bind_password,
+      ),
+      ldap_user_base_dn: config.ldap_user_base_dn,
+      ldap_user_filter: config.ldap_user_filter,
+      ldap_username_attribute: config.ldap_username_attribute,
+      ldap_email_attribute: config.ldap_email_attribute,
+      ldap_group_sync: config.ldap_group_sync,
+      ldap_group_base_dn: config.ldap_group_base_dn,
+      ldap_group_filter: config.ldap_group_filter,
+      ldap_group_name_attribute: config.ldap_group_name_attribute,
+      ldap_group_prefix: config.ldap_group_prefix,
       google_oauth: NamedOauthConfig {
         enabled: config.google_oauth.enabled,
         client_id: empty_or_redacted(&config.google_oauth.client_id),
@@ -956,6 +1424,17 @@ impl CoreConfig {
       session_allow_cross_site: config.session_allow_cross_site,
       webhook_secret: empty_or_redacted(&config.webhook_secret),
       webhook_base_url: config.webhook_base_url,
+      webhook_generic_signature_header: config
+        .webhook_generic_signature_header,
+      webhook_generic_signature_prefix: config
+        .webhook_generic_signature_prefix,
+      webhook_generic_algorithm: config.webhook_generic_algorithm,
+      webhook_generic_branch_path: config.webhook_generic_branch_path,
+      metrics_enabled: config.metrics_enabled,
+      metrics_token: empty_or_redacted(&config.metrics_token),
+      audit_export_file: config.audit_export_file,
+      audit_export_syslog: config.audit_export_syslog,
+      audit_export_url: config.audit_export_url,
       database: config.database.sanitized(),
       aws: AwsCredentials {
         access_key_id: empty_or_redacted(&config.aws.access_key_id),
@@ -968,6 +1447,27 @@ impl CoreConfig {
         .into_iter()
         .map(|(id, secret)| (id, empty_or_redacted(&secret)))
         .collect(),
+      encryption_key: if self.encryption_key.starts_with("file:") {
+        self.encryption_key.clone()
+      } else {
+        empty_or_redacted(&self.encryption_key)
+      },
+      previous_encryption_keys: config
+        .previous_encryption_keys
+        .iter()
+        .map(|key| {
+          if key.starts_with("file:") {
+            key.clone()
+          } else {
+            empty_or_redacted(key)
+          }
+        })
+        .collect(),
+      vault_address: config.vault_address,
+      vault_token: empty_or_redacted(&config.vault_token),
+      vault_namespace: config.vault_namespace,
+      secret_files_directory: config.secret_files_directory,
+      sops_directory: config.sops_directory,
       git_providers: config
         .git_providers
         .into_iter()
@@ -978,6 +1478,9 @@ impl CoreConfig {
           provider
         })
         .collect(),
+      git_ssh_known_hosts: config.git_ssh_known_hosts,
+      git_ssh_accept_new_host_keys: config
+        .git_ssh_accept_new_host_keys,
       docker_registries: config
         .docker_registries
         .into_iter()
@@ -996,6 +1499,7 @@ impl CoreConfig {
       ui_index_force_no_cache: config.ui_index_force_no_cache,
       repo_directory: config.repo_directory,
       action_directory: config.action_directory,
+      backups_folder: config.backups_folder,
       sync_directory: config.sync_directory,
     }
   }
@@ -1005,6 +1509,28 @@ impl CoreConfig {
       && !self.oidc_provider.is_empty()
       && !self.oidc_client_id.is_empty()
   }
+
+  /// Whether OIDC logins go through the Komodo Core
+  /// login flow, which reads the groups claim.
+  pub fn oidc_group_sync_enabled(&self) -> bool {
+    self.oidc_enabled() && self.oidc_group_sync
+  }
+
+  pub fn ldap_enabled(&self) -> bool {
+    self.ldap_enabled
+      && !self.ldap_url.is_empty()
+      && !self.ldap_user_base_dn.is_empty()
+  }
+}
+
+/// HMAC algorithms supported by the generic webhook integration.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookHmacAlgorithm {
+  Sha1,
+  #[default]
+  Sha256,
+  Sha512,
 }
 
 /// Provide AWS credentials for Komodo to use.
@@ -106,6 +106,97 @@ impl DatabaseConfig {
   }
 }
 
+/// Optionally upload database backups to a remote target,
+/// so they survive loss of the host.
+///
+/// The target is selected by the `url` scheme:
+/// 1. `s3://bucket/optional/prefix` (any S3-compatible storage, eg MinIO)
+/// 2. `sftp://host[:port]/path`
+/// 3. `webdav://host/path` or `webdavs://host/path` (https)
+#[derive(
+  Debug, Clone, Default, PartialEq, Serialize, Deserialize,
+)]
+pub struct BackupRemoteConfig {
+  /// The remote target url. Leave empty to disable remote backups.
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub url: String,
+  /// S3 only. Custom endpoint for S3-compatible storage,
+  /// eg `http://minio:9000`. Leave empty to use AWS.
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub s3_endpoint: String,
+  /// S3 only. The bucket region. Default: `us-east-1`
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub s3_region: String,
+  /// S3 only. Use path style bucket addressing.
+  /// Usually required for MinIO.
+  #[serde(default)]
+  pub s3_path_style: bool,
+  /// S3 only. The access key id.
+  /// If empty, uses the default AWS credential chain.
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub s3_access_key_id: String,
+  /// S3 only. The secret access key.
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub s3_secret_access_key: String,
+  /// SFTP / WebDAV only. The username to log in with.
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub username: String,
+  /// SFTP / WebDAV only. The password to log in with.
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub password: String,
+  /// SFTP only. Path to a private key to log in with,
+  /// used instead of `password`.
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub sftp_private_key: String,
+  /// SFTP only. Path to a `known_hosts` file used to verify the server.
+  /// Required for SFTP. Hosts not in the file are trusted on first use,
+  /// and their key is added to the file.
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub sftp_known_hosts: String,
+  /// Specify the maximum number of backups to keep on the remote,
+  /// or 0 to keep all of them.
+  #[serde(default)]
+  pub max_backups: u16,
+  /// Delete backups on the remote older than this many days,
+  /// or 0 to keep all of them.
+  /// The most recent backup is never deleted.
+  #[serde(default)]
+  pub max_backup_age_days: u16,
+  /// Optionally encrypt backup files before upload (AES-256-GCM).
+  /// Base64 encoded 32 byte key, or `file:/path/to/key`.
+  /// Generate one with `openssl rand -base64 32`.
+  ///
+  /// The key is required to restore, store it separately from the backups.
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub encryption_key: String,
+}
+
+impl BackupRemoteConfig {
+  pub fn sanitized(&self) -> BackupRemoteConfig {
+    BackupRemoteConfig {
+      url: self.url.clone(),
+      s3_endpoint: self.s3_endpoint.clone(),
+      s3_region: self.s3_region.clone(),
+      s3_path_style: self.s3_path_style,
+      s3_access_key_id: empty_or_redacted(&self.s3_access_key_id),
+      s3_secret_access_key: empty_or_redacted(
+        &self.s3_secret_access_key,
+      ),
+      username: self.username.clone(),
+      password: empty_or_redacted(&self.password),
+      sftp_private_key: self.sftp_private_key.clone(),
+      sftp_known_hosts: self.sftp_known_hosts.clone(),
+      max_backups: self.max_backups,
+      max_backup_age_days: self.max_backup_age_days,
+      encryption_key: empty_or_redacted(&self.encryption_key),
+    }
+  }
+
+  pub fn is_default(&self) -> bool {
+    self == &BackupRemoteConfig::default()
+  }
+}
+
 #[typeshare]
 #[derive(
   Debug,
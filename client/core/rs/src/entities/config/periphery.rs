@@ -29,7 +29,8 @@ use crate::{
 };
 
 use super::{
-  DockerRegistry, GitProvider, ProviderAccount, empty_or_redacted,
+  BackupRemoteConfig, DockerRegistry, GitProvider, ProviderAccount,
+  empty_or_redacted,
 };
 
 /// # Periphery Command Line Arguments.
@@ -174,6 +175,8 @@ pub struct Env {
   pub periphery_stack_dir: Option<PathBuf>,
   /// Override `build_dir`
   pub periphery_build_dir: Option<PathBuf>,
+  /// Override `volume_backup_dir`
+  pub periphery_volume_backup_dir: Option<PathBuf>,
   /// Override `default_terminal_command`
   pub periphery_default_terminal_command: Option<String>,
   /// Override `disable_terminals`
@@ -187,6 +190,54 @@ pub struct Env {
   pub periphery_container_stats_polling_rate: Option<Timelength>,
   /// Override `legacy_compose_cli`
   pub periphery_legacy_compose_cli: Option<bool>,
+  /// Override `git_ssh_known_hosts`
+  pub periphery_git_ssh_known_hosts: Option<PathBuf>,
+  /// Override `git_ssh_accept_new_host_keys`
+  pub periphery_git_ssh_accept_new_host_keys: Option<bool>,
+  /// Override `volume_backup_image`
+  pub periphery_volume_backup_image: Option<String>,
+  /// Override `max_volume_backups`
+  pub periphery_max_volume_backups: Option<u16>,
+
+  // VOLUME BACKUP REMOTE
+  /// Override `volume_backup_remote.url`
+  pub periphery_volume_backup_remote_url: Option<String>,
+  /// Override `volume_backup_remote.s3_endpoint`
+  pub periphery_volume_backup_remote_s3_endpoint: Option<String>,
+  /// Override `volume_backup_remote.s3_region`
+  pub periphery_volume_backup_remote_s3_region: Option<String>,
+  /// Override `volume_backup_remote.s3_path_style`
+  pub periphery_volume_backup_remote_s3_path_style: Option<bool>,
+  /// Override `volume_backup_remote.s3_access_key_id`
+  pub periphery_volume_backup_remote_s3_access_key_id: Option<String>,
+  /// Override `volume_backup_remote.s3_access_key_id` from file
+  pub periphery_volume_backup_remote_s3_access_key_id_file:
+    Option<PathBuf>,
+  /// Override `volume_backup_remote.s3_secret_access_key`
+  pub periphery_volume_backup_remote_s3_secret_access_key:
+    Option<String>,
+  /// Override `volume_backup_remote.s3_secret_access_key` from file
+  pub periphery_volume_backup_remote_s3_secret_access_key_file:
+    Option<PathBuf>,
+  /// Override `volume_backup_remote.username`
+  pub periphery_volume_backup_remote_username: Option<String>,
+  /// Override `volume_backup_remote.password`
+  pub periphery_volume_backup_remote_password: Option<String>,
+  /// Override `volume_backup_remote.password` from file
+  pub periphery_volume_backup_remote_password_file: Option<PathBuf>,
+  /// Override `volume_backup_remote.sftp_private_key`
+  pub periphery_volume_backup_remote_sftp_private_key: Option<String>,
+  /// Override `volume_backup_remote.sftp_known_hosts`
+  pub periphery_volume_backup_remote_sftp_known_hosts: Option<String>,
+  /// Override `volume_backup_remote.max_backups`
+  pub periphery_volume_backup_remote_max_backups: Option<u16>,
+  /// Override `volume_backup_remote.max_backup_age_days`
+  pub periphery_volume_backup_remote_max_backup_age_days: Option<u16>,
+  /// Override `volume_backup_remote.encryption_key`
+  pub periphery_volume_backup_remote_encryption_key: Option<String>,
+  /// Override `volume_backup_remote.encryption_key` from file
+  pub periphery_volume_backup_remote_encryption_key_file:
+    Option<PathBuf>,
 
   // LOGGING
   /// Override `logging.level`
@@ -361,6 +412,12 @@ pub struct PeripheryConfig {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub build_dir: Option<PathBuf>,
 
+  /// The system directory where volume backups will be written.
+  /// If not provided, will default to `${root_directory}/volume-backups`.
+  /// Default: empty
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub volume_backup_dir: Option<PathBuf>,
+
   /// Configure the default terminal command
   /// when one isn't provided.
   /// Default: `bash`
@@ -397,6 +454,24 @@ pub struct PeripheryConfig {
   #[serde(default)]
   pub legacy_compose_cli: bool,
 
+  /// The image used to archive / extract volumes
+  /// during volume backup and restore. Must include `sh`, `tar` and `stat`.
+  /// Default: `alpine`
+  #[serde(default = "default_volume_backup_image")]
+  pub volume_backup_image: String,
+
+  /// Specify the maximum number of volume backups to keep
+  /// in `volume_backup_dir` for each Stack / Deployment,
+  /// or 0 to disable pruning.
+  /// Default: `14`
+  #[serde(default = "default_max_volume_backups")]
+  pub max_volume_backups: u16,
+
+  /// Optionally also upload volume backups to a remote target,
+  /// such as S3-compatible storage, SFTP or WebDAV.
+  #[serde(default)]
+  pub volume_backup_remote: BackupRemoteConfig,
+
   /// Logging configuration
   #[serde(default)]
   pub logging: LogConfig,
@@ -424,6 +499,18 @@ pub struct PeripheryConfig {
   #[serde(default, alias = "git_provider")]
   pub git_providers: ForgivingVec<GitProvider>,
 
+  /// Path to a known_hosts file used to verify git provider
+  /// host keys when cloning over ssh.
+  /// Default: none, the default known_hosts (`~/.ssh/known_hosts`) is used.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub git_ssh_known_hosts: Option<PathBuf>,
+
+  /// Accept and store the host keys of git providers
+  /// not yet in the known_hosts on first use (`StrictHostKeyChecking=accept-new`).
+  /// Default: false, unknown host keys are rejected (`StrictHostKeyChecking=yes`).
+  #[serde(default)]
+  pub git_ssh_accept_new_host_keys: bool,
+
   /// Configure docker credentials used to push / pull images.
   /// Supports any docker image repository.
   #[serde(default, alias = "docker_registry")]
@@ -454,6 +541,14 @@ fn default_container_stats_polling_rate() -> Timelength {
   Timelength::ThirtySeconds
 }
 
+fn default_volume_backup_image() -> String {
+  String::from("alpine")
+}
+
+fn default_max_volume_backups() -> u16 {
+  14
+}
+
 fn default_ssl_enabled() -> bool {
   true
 }
@@ -475,6 +570,7 @@ impl Default for PeripheryConfig {
       repo_dir: None,
       stack_dir: None,
       build_dir: None,
+      volume_backup_dir: None,
       default_terminal_command: default_default_terminal_command(),
       disable_terminals: Default::default(),
       disable_container_terminals: Default::default(),
@@ -482,6 +578,9 @@ impl Default for PeripheryConfig {
       container_stats_polling_rate:
         default_container_stats_polling_rate(),
       legacy_compose_cli: Default::default(),
+      volume_backup_image: default_volume_backup_image(),
+      max_volume_backups: default_max_volume_backups(),
+      volume_backup_remote: Default::default(),
       logging: Default::default(),
       pretty_startup_config: Default::default(),
       allowed_ips: Default::default(),
@@ -489,6 +588,8 @@ impl Default for PeripheryConfig {
       exclude_disk_mounts: Default::default(),
       secrets: Default::default(),
       git_providers: Default::default(),
+      git_ssh_known_hosts: None,
+      git_ssh_accept_new_host_keys: Default::default(),
       docker_registries: Default::default(),
       ssl_enabled: default_ssl_enabled(),
       ssl_key_file: None,
@@ -526,12 +627,16 @@ impl PeripheryConfig {
       repo_dir: self.repo_dir.clone(),
       stack_dir: self.stack_dir.clone(),
       build_dir: self.build_dir.clone(),
+      volume_backup_dir: self.volume_backup_dir.clone(),
       default_terminal_command: self.default_terminal_command.clone(),
       disable_terminals: self.disable_terminals,
       disable_container_terminals: self.disable_container_terminals,
       stats_polling_rate: self.stats_polling_rate,
       container_stats_polling_rate: self.container_stats_polling_rate,
       legacy_compose_cli: self.legacy_compose_cli,
+      volume_backup_image: self.volume_backup_image.clone(),
+      max_volume_backups: self.max_volume_backups,
+      volume_backup_remote: self.volume_backup_remote.sanitized(),
       logging: self.logging.clone(),
       pretty_startup_config: self.pretty_startup_config,
       allowed_ips: self.allowed_ips.clone(),
@@ -576,6 +681,8 @@ impl PeripheryConfig {
             .collect(),
         })
         .collect(),
+      git_ssh_known_hosts: self.git_ssh_known_hosts.clone(),
+      git_ssh_accept_new_host_keys: self.git_ssh_accept_new_host_keys,
       ssl_enabled: self.ssl_enabled,
       ssl_key_file: self.ssl_key_file.clone(),
       ssl_cert_file: self.ssl_cert_file.clone(),
@@ -632,6 +739,14 @@ impl PeripheryConfig {
     }
   }
 
+  pub fn volume_backup_dir(&self) -> PathBuf {
+    if let Some(dir) = &self.volume_backup_dir {
+      dir.to_owned()
+    } else {
+      self.root_directory.join("volume-backups")
+    }
+  }
+
   pub fn ssl_key_file(&self) -> PathBuf {
     if let Some(dir) = &self.ssl_key_file {
       dir.into()
@@ -146,6 +146,85 @@ pub struct DeploymentConfig {
   #[partial_default(default_send_alerts())]
   pub send_alerts: bool,
 
+  /// The container CPU usage percentage which triggers a WARNING
+  /// `ContainerCpu` alert. Docker reports CPU usage relative to
+  /// one core, so this may go above 100. 0 disables the alert.
+  #[serde(default)]
+  #[builder(default)]
+  pub container_cpu_warning: f64,
+
+  /// The container CPU usage percentage which triggers a CRITICAL
+  /// `ContainerCpu` alert. 0 disables the CRITICAL level.
+  #[serde(default)]
+  #[builder(default)]
+  pub container_cpu_critical: f64,
+
+  /// The container memory usage percentage (of its memory limit)
+  /// which triggers a WARNING `ContainerMem` alert. 0 disables the alert.
+  #[serde(default)]
+  #[builder(default)]
+  pub container_mem_warning: f64,
+
+  /// The container memory usage percentage (of its memory limit)
+  /// which triggers a CRITICAL `ContainerMem` alert.
+  /// 0 disables the CRITICAL level.
+  #[serde(default)]
+  #[builder(default)]
+  pub container_mem_critical: f64,
+
+  /// Whether Core should archive the container logs,
+  /// so they remain searchable after the container is removed.
+  /// See `keep_archived_logs_for_days` in the core config.
+  #[serde(default)]
+  #[builder(default)]
+  pub archive_logs: bool,
+
+  /// How the container is replaced on redeploy.
+  ///
+  /// - `Recreate`: Stop and remove the old container, then start the new one.
+  /// - `BlueGreen`: Start the new container under a temporary name,
+  ///   wait for it to be healthy (using `health_check_timeout` and `health_check_url`),
+  ///   then remove the old container and rename the new one.
+  ///   The old container keeps running if the new one fails.
+  ///   Both containers run at the same time, so the container
+  ///   must not bind fixed host ports (eg. `host` network or `ports`).
+  ///
+  /// Note. Only used in Container (non-Swarm) mode.
+  #[serde(default)]
+  #[builder(default)]
+  pub deploy_strategy: DeployStrategy,
+
+  /// Verify the container after deploy, marking the deploy failed
+  /// if it is not running (and `healthy`, if it has a Docker healthcheck)
+  /// within `health_check_timeout`.
+  ///
+  /// Note. Only supported for Server (non-Swarm) Deployments.
+  #[serde(default)]
+  #[builder(default)]
+  pub health_check: bool,
+
+  /// The max time in seconds to wait for the container to become healthy.
+  /// If neither the container has a Docker healthcheck nor a
+  /// `health_check_url` is configured, the container must
+  /// stay running for this whole duration.
+  #[serde(default = "default_health_check_timeout")]
+  #[builder(default = "default_health_check_timeout()")]
+  #[partial_default(default_health_check_timeout())]
+  pub health_check_timeout: i32,
+
+  /// Optional url which must return a 2xx response for the
+  /// deploy to be healthy. The request is sent from the Periphery host,
+  /// eg `http://localhost:8080/health`.
+  #[serde(default)]
+  #[builder(default)]
+  pub health_check_url: String,
+
+  /// Automatically roll back to the previous deploy
+  /// if the health check fails. See `RollbackDeployment`.
+  #[serde(default)]
+  #[builder(default)]
+  pub rollback_on_health_failure: bool,
+
   /// Configure quick links that are displayed in the resource header
   #[serde(default)]
   #[builder(default)]
@@ -278,6 +357,10 @@ fn default_termination_timeout() -> i32 {
   10
 }
 
+fn default_health_check_timeout() -> i32 {
+  60
+}
+
 fn default_network() -> String {
   String::from("host")
 }
@@ -294,6 +377,16 @@ impl Default for DeploymentConfig {
       poll_for_updates: Default::default(),
       auto_update: Default::default(),
       send_alerts: default_send_alerts(),
+      container_cpu_warning: Default::default(),
+      container_cpu_critical: Default::default(),
+      container_mem_warning: Default::default(),
+      container_mem_critical: Default::default(),
+      archive_logs: Default::default(),
+      deploy_strategy: Default::default(),
+      health_check: Default::default(),
+      health_check_timeout: default_health_check_timeout(),
+      health_check_url: Default::default(),
+      rollback_on_health_failure: Default::default(),
       links: Default::default(),
       network: default_network(),
       restart: Default::default(),
@@ -516,6 +609,31 @@ pub enum RestartMode {
   UnlessStopped,
 }
 
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  PartialEq,
+  Hash,
+  Eq,
+  Clone,
+  Copy,
+  Default,
+  Display,
+  EnumString,
+  AsRefStr,
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub enum DeployStrategy {
+  /// Stop and remove the old container, then start the new one.
+  #[default]
+  Recreate,
+  /// Start the new container alongside the old one,
+  /// and only replace the old one once the new one is healthy.
+  BlueGreen,
+}
+
 #[typeshare]
 #[derive(
   Serialize,
@@ -565,6 +683,8 @@ pub struct DeploymentActionState {
   pub stopping: bool,
   pub destroying: bool,
   pub renaming: bool,
+  pub backing_up: bool,
+  pub restoring: bool,
 }
 
 #[typeshare]
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use strum::Display;
+use typeshare::typeshare;
+
+use crate::entities::{I64, MongoId, ResourceTarget};
+
+/// A single container log line archived by Core.
+/// Collected for Deployments / Stacks with `archive_logs` enabled.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[cfg_attr(
+  feature = "mongo",
+  derive(mongo_indexed::derive::MongoIndexed)
+)]
+#[cfg_attr(feature = "mongo", doc_index({ "target.type": 1 }))]
+#[cfg_attr(feature = "mongo", doc_index({ "target.id": 1 }))]
+pub struct ArchivedLog {
+  /// The Mongo ID of the log line.
+  #[serde(
+    default,
+    rename = "_id",
+    skip_serializing_if = "String::is_empty",
+    with = "bson::serde_helpers::hex_string_as_object_id"
+  )]
+  pub id: MongoId,
+
+  /// Unix timestamp in milliseconds the line was logged
+  #[cfg_attr(feature = "mongo", index)]
+  pub ts: I64,
+
+  /// The Deployment / Stack which owns the container
+  pub target: ResourceTarget,
+
+  /// The name of the container which logged the line
+  #[cfg_attr(feature = "mongo", index)]
+  pub container: String,
+
+  /// Whether the line was logged to stdout or stderr
+  pub stream: LogStream,
+
+  /// The log line content
+  pub line: String,
+}
+
+/// A container log line, as reported by Periphery.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ContainerLogLine {
+  /// Unix timestamp in milliseconds the line was logged
+  pub ts: I64,
+  /// Whether the line was logged to stdout or stderr
+  pub stream: LogStream,
+  /// The log line content
+  pub line: String,
+}
+
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  Default,
+  Display,
+  PartialEq,
+  Eq,
+  Clone,
+  Copy,
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum LogStream {
+  #[default]
+  Stdout,
+  Stderr,
+}
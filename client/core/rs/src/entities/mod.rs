@@ -29,6 +29,8 @@ pub mod alert;
 pub mod alerter;
 /// Subtypes of [ApiKey][api_key::ApiKey].
 pub mod api_key;
+/// Subtypes of [AuditEvent][audit::AuditEvent].
+pub mod audit;
 /// Subtypes of [Build][build::Build].
 pub mod build;
 /// Subtypes of [Builder][builder::Builder].
@@ -39,6 +41,8 @@ pub mod config;
 pub mod deployment;
 /// Networks, Images, Containers.
 pub mod docker;
+/// Subtypes of [ArchivedLog][log_archive::ArchivedLog].
+pub mod log_archive;
 /// Subtypes of [LogConfig][logger::LogConfig].
 pub mod logger;
 /// Subtypes of [CreationKey][creation_key::CreationKey]
@@ -555,6 +559,18 @@ pub struct RepoExecutionArgs {
   pub provider: String,
   /// Use https (vs http).
   pub https: bool,
+  /// Use ssh (vs http(s)). The access token is then
+  /// the SSH private key, if any.
+  #[serde(default)]
+  pub ssh: bool,
+  /// The known_hosts file used to verify the provider host key over ssh.
+  /// Configured by the host running the git commands, not sent over the wire.
+  #[serde(skip)]
+  pub ssh_known_hosts: Option<PathBuf>,
+  /// Accept unknown provider host keys on first use over ssh.
+  /// Configured by the host running the git commands, not sent over the wire.
+  #[serde(skip)]
+  pub ssh_accept_new_host_keys: bool,
   /// Configure the account used to access repo (if private)
   pub account: Option<String>,
   /// Full repo identifier. {namespace}/{repo_name}
@@ -569,6 +585,19 @@ pub struct RepoExecutionArgs {
   /// The default folder to use.
   /// Depends on the resource type.
   pub default_folder: DefaultRepoFolder,
+  /// Clone with `--depth`. 0 clones the full history.
+  #[serde(default)]
+  pub depth: i32,
+  /// Folders to check out with `git sparse-checkout` (cone mode).
+  /// `None` checks out the whole repo.
+  #[serde(default)]
+  pub sparse_paths: Option<Vec<String>>,
+  /// Init / update submodules recursively.
+  #[serde(default)]
+  pub submodules: bool,
+  /// Run `git lfs pull`.
+  #[serde(default)]
+  pub lfs: bool,
 }
 
 impl RepoExecutionArgs {
@@ -587,6 +616,14 @@ impl RepoExecutionArgs {
     &self,
     access_token: Option<&str>,
   ) -> anyhow::Result<String> {
+    let repo = self
+      .repo
+      .as_ref()
+      .context("resource has no repo attached")?;
+    if self.ssh {
+      // The SSH key is passed to git separately, see `git::ssh`.
+      return Ok(format!("ssh://git@{}/{repo}", self.provider));
+    }
     let access_token_at = match access_token {
       Some(token) => match token.split_once(':') {
         Some((username, token)) => format!(
@@ -601,10 +638,6 @@ impl RepoExecutionArgs {
       None => String::new(),
     };
     let protocol = if self.https { "https" } else { "http" };
-    let repo = self
-      .repo
-      .as_ref()
-      .context("resource has no repo attached")?;
     Ok(format!(
       "{protocol}://{access_token_at}{}/{repo}",
       self.provider
@@ -637,6 +670,9 @@ impl From<&self::stack::Stack> for RepoExecutionArgs {
       provider: optional_string(&stack.config.git_provider)
         .unwrap_or_else(|| String::from("github.com")),
       https: stack.config.git_https,
+      ssh: stack.config.git_ssh,
+      ssh_known_hosts: None,
+      ssh_accept_new_host_keys: false,
       account: optional_string(&stack.config.git_account),
       repo: optional_string(&stack.config.repo),
       branch: optional_string(&stack.config.branch)
@@ -644,6 +680,10 @@ impl From<&self::stack::Stack> for RepoExecutionArgs {
       commit: optional_string(&stack.config.commit),
       destination: optional_string(&stack.config.clone_path),
       default_folder: DefaultRepoFolder::Stacks,
+      depth: stack.config.git_depth,
+      sparse_paths: stack.git_sparse_paths(),
+      submodules: stack.config.git_submodules,
+      lfs: stack.config.git_lfs,
     }
   }
 }
@@ -655,6 +695,9 @@ impl From<&self::build::Build> for RepoExecutionArgs {
       provider: optional_string(&build.config.git_provider)
         .unwrap_or_else(|| String::from("github.com")),
       https: build.config.git_https,
+      ssh: build.config.git_ssh,
+      ssh_known_hosts: None,
+      ssh_accept_new_host_keys: false,
       account: optional_string(&build.config.git_account),
       repo: optional_string(&build.config.repo),
       branch: optional_string(&build.config.branch)
@@ -662,6 +705,11 @@ impl From<&self::build::Build> for RepoExecutionArgs {
       commit: optional_string(&build.config.commit),
       destination: None,
       default_folder: DefaultRepoFolder::Builds,
+      depth: build.config.git_depth,
+      sparse_paths: (!build.config.git_sparse_paths.is_empty())
+        .then(|| build.config.git_sparse_paths.clone()),
+      submodules: build.config.git_submodules,
+      lfs: build.config.git_lfs,
     }
   }
 }
@@ -673,6 +721,9 @@ impl From<&self::repo::Repo> for RepoExecutionArgs {
       provider: optional_string(&repo.config.git_provider)
         .unwrap_or_else(|| String::from("github.com")),
       https: repo.config.git_https,
+      ssh: repo.config.git_ssh,
+      ssh_known_hosts: None,
+      ssh_accept_new_host_keys: false,
       account: optional_string(&repo.config.git_account),
       repo: optional_string(&repo.config.repo),
       branch: optional_string(&repo.config.branch)
@@ -680,6 +731,11 @@ impl From<&self::repo::Repo> for RepoExecutionArgs {
       commit: optional_string(&repo.config.commit),
       destination: optional_string(&repo.config.path),
       default_folder: DefaultRepoFolder::Repos,
+      depth: repo.config.git_depth,
+      sparse_paths: (!repo.config.git_sparse_paths.is_empty())
+        .then(|| repo.config.git_sparse_paths.clone()),
+      submodules: repo.config.git_submodules,
+      lfs: repo.config.git_lfs,
     }
   }
 }
@@ -691,6 +747,9 @@ impl From<&self::sync::ResourceSync> for RepoExecutionArgs {
       provider: optional_string(&sync.config.git_provider)
         .unwrap_or_else(|| String::from("github.com")),
       https: sync.config.git_https,
+      ssh: sync.config.git_ssh,
+      ssh_known_hosts: None,
+      ssh_accept_new_host_keys: false,
       account: optional_string(&sync.config.git_account),
       repo: optional_string(&sync.config.repo),
       branch: optional_string(&sync.config.branch)
@@ -698,6 +757,11 @@ impl From<&self::sync::ResourceSync> for RepoExecutionArgs {
       commit: optional_string(&sync.config.commit),
       destination: None,
       default_folder: DefaultRepoFolder::NotApplicable,
+      depth: sync.config.git_depth,
+      sparse_paths: (!sync.config.git_sparse_paths.is_empty())
+        .then(|| sync.config.git_sparse_paths.clone()),
+      submodules: sync.config.git_submodules,
+      lfs: sync.config.git_lfs,
     }
   }
 }
@@ -1221,7 +1285,10 @@ pub enum Operation {
   UnpauseStack,
   StopStack,
   DestroyStack,
+  RollbackStack,
   RunStackService,
+  BackupStackVolumes,
+  RestoreStackVolumes,
   CheckStackForUpdate,
 
   // Stack (Service)
@@ -1247,6 +1314,9 @@ pub enum Operation {
   UnpauseDeployment,
   StopDeployment,
   DestroyDeployment,
+  RollbackDeployment,
+  BackupDeploymentVolumes,
+  RestoreDeploymentVolumes,
   CheckDeploymentForUpdate,
 
   // Build
@@ -1308,9 +1378,11 @@ pub enum Operation {
   // Maintenance
   ClearRepoCache,
   BackupCoreDatabase,
+  RestoreCoreDatabase,
   GlobalAutoUpdate,
   RotateAllServerKeys,
   RotateCoreKeys,
+  RotateEncryptionKey,
 
   // Variable
   CreateVariable,
@@ -1378,6 +1450,34 @@ pub enum TerminationSignal {
   SigTerm,
 }
 
+/// How containers are handled while their volumes are backed up.
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  PartialEq,
+  Hash,
+  Eq,
+  Clone,
+  Copy,
+  Default,
+  Display,
+  EnumString,
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub enum VolumeBackupMode {
+  /// Stop the running containers, and start them again after.
+  /// Ensures consistent backups, at the cost of some downtime.
+  #[default]
+  Stop,
+  /// Pause the running containers, and unpause them after.
+  Pause,
+  /// Back up the volumes while the containers keep running.
+  /// Files being written during the backup may be inconsistent.
+  Live,
+}
+
 /// Used to reference a specific resource across all resource types
 #[typeshare]
 #[derive(
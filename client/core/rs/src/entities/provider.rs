@@ -51,6 +51,15 @@ pub struct GitProviderAccount {
   /// If the database / host can be accessed this is insecure.
   #[serde(default)]
   pub token: String,
+  /// The SSH private key (OpenSSH format) in plain text on the db.
+  /// Used instead of the token for resources cloning over ssh.
+  /// If the database / host can be accessed this is insecure.
+  #[serde(default)]
+  pub ssh_private_key: String,
+  /// The SSH public key matching `ssh_private_key`,
+  /// to add as a deploy key on the git provider.
+  #[serde(default)]
+  pub ssh_public_key: String,
 }
 
 fn default_git_domain() -> String {
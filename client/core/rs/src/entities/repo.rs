@@ -135,13 +135,47 @@ pub struct RepoConfig {
   pub git_provider: String,
 
   /// Whether to use https to clone the repo (versus http). Default: true
-  ///
-  /// Note. Komodo does not currently support cloning repos via ssh.
   #[serde(default = "default_git_https")]
   #[builder(default = "default_git_https()")]
   #[partial_default(default_git_https())]
   pub git_https: bool,
 
+  /// Whether to clone the repo over ssh (`ssh://git@{provider}/{repo}`)
+  /// rather than http(s). Authenticates with the SSH key
+  /// of the git account, or the host's default ssh identity
+  /// if the account has no SSH key.
+  #[serde(default)]
+  #[builder(default)]
+  pub git_ssh: bool,
+
+  /// Clone with `--depth {git_depth}`, skipping older history.
+  /// Use 0 to clone the full history.
+  #[serde(default)]
+  #[builder(default)]
+  pub git_depth: i32,
+
+  /// Only check out these folders of the repo (`git sparse-checkout`, cone mode).
+  /// Files at the repo root are always checked out.
+  /// Leave empty to check out the whole repo.
+  #[serde(default, deserialize_with = "string_list_deserializer")]
+  #[partial_attr(serde(
+    default,
+    deserialize_with = "option_string_list_deserializer"
+  ))]
+  #[builder(default)]
+  pub git_sparse_paths: Vec<String>,
+
+  /// Initialize and update the repo's submodules (recursively) after clone / pull.
+  #[serde(default)]
+  #[builder(default)]
+  pub git_submodules: bool,
+
+  /// Run `git lfs pull` after clone / pull to download Git LFS files.
+  /// Requires `git-lfs` to be installed on the host.
+  #[serde(default)]
+  #[builder(default)]
+  pub git_lfs: bool,
+
   /// The git account used to access private repos.
   /// Passing empty string can only clone public repos.
   ///
@@ -188,6 +222,26 @@ pub struct RepoConfig {
   #[builder(default)]
   pub webhook_secret: String,
 
+  /// Only trigger the webhook when the push changes files matching these
+  /// paths, relative to the repo root. Supports wildcard (`services/api/*`)
+  /// and regex (`\^services/.*\.ya?ml$\`) patterns.
+  /// Prefix with `!` to exclude paths. Leave empty to trigger on any push.
+  #[serde(default, deserialize_with = "string_list_deserializer")]
+  #[partial_attr(serde(
+    default,
+    deserialize_with = "option_string_list_deserializer"
+  ))]
+  #[builder(default)]
+  pub webhook_path_filters: Vec<String>,
+
+  /// Report the run status (pending / success / failure) of the commit
+  /// to the git provider's commit status api, linking to the Komodo Update.
+  /// Requires a git account with a token.
+  /// Supports Github, Gitlab and Gitea / Forgejo.
+  #[serde(default)]
+  #[builder(default)]
+  pub commit_status: bool,
+
   /// Command to be run after the repo is cloned.
   /// The path is relative to the root of the repo.
   #[serde(default)]
@@ -274,6 +328,11 @@ impl Default for RepoConfig {
       builder_id: Default::default(),
       git_provider: default_git_provider(),
       git_https: default_git_https(),
+      git_ssh: Default::default(),
+      git_depth: Default::default(),
+      git_sparse_paths: Default::default(),
+      git_submodules: Default::default(),
+      git_lfs: Default::default(),
       repo: Default::default(),
       branch: default_branch(),
       commit: Default::default(),
@@ -287,6 +346,8 @@ impl Default for RepoConfig {
       skip_secret_interp: Default::default(),
       webhook_enabled: default_webhook_enabled(),
       webhook_secret: Default::default(),
+      webhook_path_filters: Default::default(),
+      commit_status: Default::default(),
     }
   }
 }
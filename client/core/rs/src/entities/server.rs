@@ -249,6 +249,42 @@ pub struct ServerConfig {
   #[partial_default(default_disk_critical())]
   pub disk_critical: f64,
 
+  /// Whether to send alerts about the servers sustained load average.
+  #[serde(default)]
+  #[builder(default)]
+  pub send_load_alerts: bool,
+
+  /// The 5 minute load average per cpu core which triggers WARNING state for LOAD.
+  #[serde(default = "default_load_warning")]
+  #[builder(default = "default_load_warning()")]
+  #[partial_default(default_load_warning())]
+  pub load_warning: f64,
+
+  /// The 5 minute load average per cpu core which triggers CRITICAL state for LOAD.
+  #[serde(default = "default_load_critical")]
+  #[builder(default = "default_load_critical()")]
+  #[partial_default(default_load_critical())]
+  pub load_critical: f64,
+
+  /// Whether to send alerts about the servers network throughput.
+  #[serde(default)]
+  #[builder(default)]
+  pub send_network_alerts: bool,
+
+  /// The combined ingress + egress throughput in MB/s
+  /// which triggers WARNING state for NETWORK.
+  #[serde(default = "default_network_warning")]
+  #[builder(default = "default_network_warning()")]
+  #[partial_default(default_network_warning())]
+  pub network_warning: f64,
+
+  /// The combined ingress + egress throughput in MB/s
+  /// which triggers CRITICAL state for NETWORK.
+  #[serde(default = "default_network_critical")]
+  #[builder(default = "default_network_critical()")]
+  #[partial_default(default_network_critical())]
+  pub network_critical: f64,
+
   /// Scheduled maintenance windows during which alerts will be suppressed.
   #[serde(default)]
   #[builder(default)]
@@ -310,6 +346,22 @@ fn default_disk_critical() -> f64 {
   95.0
 }
 
+fn default_load_warning() -> f64 {
+  2.0
+}
+
+fn default_load_critical() -> f64 {
+  4.0
+}
+
+fn default_network_warning() -> f64 {
+  100.0
+}
+
+fn default_network_critical() -> f64 {
+  500.0
+}
+
 impl Default for ServerConfig {
   fn default() -> Self {
     Self {
@@ -335,6 +387,12 @@ impl Default for ServerConfig {
       mem_critical: default_mem_critical(),
       disk_warning: default_disk_warning(),
       disk_critical: default_disk_critical(),
+      send_load_alerts: Default::default(),
+      load_warning: default_load_warning(),
+      load_critical: default_load_critical(),
+      send_network_alerts: Default::default(),
+      network_warning: default_network_warning(),
+      network_critical: default_network_critical(),
       maintenance_windows: Default::default(),
     }
   }
@@ -368,6 +426,10 @@ pub struct ServerHealthState {
 pub struct ServerHealth {
   pub cpu: ServerHealthState,
   pub mem: ServerHealthState,
+  #[serde(default)]
+  pub load: ServerHealthState,
+  #[serde(default)]
+  pub network: ServerHealthState,
   #[cfg_attr(feature = "utoipa", schema(value_type = HashMap<String, ServerHealthState>))]
   pub disks: HashMap<PathBuf, ServerHealthState>,
 }
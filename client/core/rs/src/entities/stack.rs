@@ -1,4 +1,8 @@
-use std::{collections::HashMap, sync::OnceLock};
+use std::{
+  collections::HashMap,
+  path::{Component, Path, PathBuf},
+  sync::OnceLock,
+};
 
 use anyhow::Context;
 use bson::{Document, doc};
@@ -113,6 +117,29 @@ impl Stack {
     res.into_iter().collect()
   }
 
+  /// The folders to check out with `git sparse-checkout`,
+  /// or `None` to check out the whole repo.
+  /// Cone mode treats every entry as a folder, so the default
+  /// uses the folders containing the files, never the files themselves.
+  pub fn git_sparse_paths(&self) -> Option<Vec<String>> {
+    if !self.config.git_sparse_paths.is_empty() {
+      return Some(self.config.git_sparse_paths.clone());
+    }
+    if !self.config.git_sparse_checkout {
+      return None;
+    }
+    let run_directory = Path::new(&self.config.run_directory);
+    let mut res = IndexSet::new();
+    res.insert(sparse_checkout_folder(run_directory));
+    for path in self.all_tracked_file_paths() {
+      if let Some(parent) = run_directory.join(path).parent() {
+        res.insert(sparse_checkout_folder(parent));
+      }
+    }
+    // Files at the repo root are always checked out.
+    Some(res.into_iter().filter(|f| !f.is_empty()).collect())
+  }
+
   pub fn all_file_dependencies(&self) -> Vec<StackFileDependency> {
     let mut res = self
       .compose_file_paths()
@@ -131,6 +158,21 @@ impl Stack {
   }
 }
 
+/// Normalizes a folder relative to the repo root for `git sparse-checkout`.
+fn sparse_checkout_folder(path: &Path) -> String {
+  let mut res = PathBuf::new();
+  for component in path.components() {
+    match component {
+      Component::Normal(component) => res.push(component),
+      Component::ParentDir => {
+        res.pop();
+      }
+      _ => {}
+    }
+  }
+  res.to_string_lossy().into_owned()
+}
+
 fn default_stack_file_paths() -> &'static [String] {
   static DEFAULT_FILE_PATHS: OnceLock<Vec<String>> = OnceLock::new();
   DEFAULT_FILE_PATHS
@@ -283,6 +325,10 @@ pub struct StackInfo {
   pub latest_hash: Option<String>,
   /// Latest commit message, or null
   pub latest_message: Option<String>,
+
+  /// If the Stack is a pull request preview, the id of the
+  /// template Stack which created it, or null.
+  pub preview_template: Option<String>,
 }
 
 #[typeshare(serialized_as = "Partial<StackConfig>")]
@@ -393,13 +439,53 @@ pub struct StackConfig {
   pub git_provider: String,
 
   /// Whether to use https to clone the repo (versus http). Default: true
-  ///
-  /// Note. Komodo does not currently support cloning repos via ssh.
   #[serde(default = "default_git_https")]
   #[builder(default = "default_git_https()")]
   #[partial_default(default_git_https())]
   pub git_https: bool,
 
+  /// Whether to clone the repo over ssh (`ssh://git@{provider}/{repo}`)
+  /// rather than http(s). Authenticates with the SSH key
+  /// of the git account, or the host's default ssh identity
+  /// if the account has no SSH key.
+  #[serde(default)]
+  #[builder(default)]
+  pub git_ssh: bool,
+
+  /// Clone with `--depth {git_depth}`, skipping older history.
+  /// Use 0 to clone the full history.
+  #[serde(default)]
+  #[builder(default)]
+  pub git_depth: i32,
+
+  /// Only check out the folders the Stack uses (`git sparse-checkout`, cone mode).
+  /// If `git_sparse_paths` is empty, uses the folders of the `run_directory`,
+  /// compose files, env files and config files.
+  #[serde(default)]
+  #[builder(default)]
+  pub git_sparse_checkout: bool,
+
+  /// Only check out these folders of the repo (`git sparse-checkout`, cone mode).
+  /// Files at the repo root are always checked out.
+  #[serde(default, deserialize_with = "string_list_deserializer")]
+  #[partial_attr(serde(
+    default,
+    deserialize_with = "option_string_list_deserializer"
+  ))]
+  #[builder(default)]
+  pub git_sparse_paths: Vec<String>,
+
+  /// Initialize and update the repo's submodules (recursively) after clone / pull.
+  #[serde(default)]
+  #[builder(default)]
+  pub git_submodules: bool,
+
+  /// Run `git lfs pull` after clone / pull to download Git LFS files.
+  /// Requires `git-lfs` to be installed on the host.
+  #[serde(default)]
+  #[builder(default)]
+  pub git_lfs: bool,
+
   /// The git account used to access private repos.
   /// Passing empty string can only clone public repos.
   ///
@@ -449,6 +535,34 @@ pub struct StackConfig {
   #[builder(default)]
   pub webhook_secret: String,
 
+  /// Only trigger the webhook when the push changes files matching these
+  /// paths, relative to the repo root. Supports wildcard (`services/api/*`)
+  /// and regex (`\^services/.*\.ya?ml$\`) patterns.
+  /// Prefix with `!` to exclude paths. Leave empty to trigger on any push.
+  #[serde(default, deserialize_with = "string_list_deserializer")]
+  #[partial_attr(serde(
+    default,
+    deserialize_with = "option_string_list_deserializer"
+  ))]
+  #[builder(default)]
+  pub webhook_path_filters: Vec<String>,
+
+  /// Report the run status (pending / success / failure) of the commit
+  /// to the git provider's commit status api, linking to the Komodo Update.
+  /// Requires a git account with a token.
+  /// Supports Github, Gitlab and Gitea / Forgejo.
+  #[serde(default)]
+  #[builder(default)]
+  pub commit_status: bool,
+
+  /// Report deploys to the git provider's deployments api,
+  /// using the Stack name as the environment, linking to the Komodo Update.
+  /// Requires a git account with a token.
+  /// Supports Github (Deployments) and Gitlab (Environments).
+  #[serde(default)]
+  #[builder(default)]
+  pub deployment_status: bool,
+
   /// By default, the Stack will `DeployStackIfChanged`.
   /// If this option is enabled, will always run `DeployStack` without diffing.
   #[serde(default)]
@@ -516,6 +630,71 @@ pub struct StackConfig {
   #[partial_default(default_send_alerts())]
   pub send_alerts: bool,
 
+  /// The container CPU usage percentage which triggers a WARNING
+  /// `ContainerCpu` alert. Docker reports CPU usage relative to
+  /// one core, so this may go above 100. 0 disables the alert.
+  #[serde(default)]
+  #[builder(default)]
+  pub container_cpu_warning: f64,
+
+  /// The container CPU usage percentage which triggers a CRITICAL
+  /// `ContainerCpu` alert. 0 disables the CRITICAL level.
+  #[serde(default)]
+  #[builder(default)]
+  pub container_cpu_critical: f64,
+
+  /// The container memory usage percentage (of its memory limit)
+  /// which triggers a WARNING `ContainerMem` alert. 0 disables the alert.
+  #[serde(default)]
+  #[builder(default)]
+  pub container_mem_warning: f64,
+
+  /// The container memory usage percentage (of its memory limit)
+  /// which triggers a CRITICAL `ContainerMem` alert.
+  /// 0 disables the CRITICAL level.
+  #[serde(default)]
+  #[builder(default)]
+  pub container_mem_critical: f64,
+
+  /// Whether Core should archive the service containers logs,
+  /// so they remain searchable after the container is removed.
+  /// See `keep_archived_logs_for_days` in the core config.
+  #[serde(default)]
+  #[builder(default)]
+  pub archive_logs: bool,
+
+  /// Verify the service containers after deploy, marking the deploy failed
+  /// if any are not running (and `healthy`, if they have a Docker healthcheck)
+  /// within `health_check_timeout`.
+  ///
+  /// Note. Only supported for Server (non-Swarm) Stacks,
+  /// and only checked for full Stack deploys.
+  #[serde(default)]
+  #[builder(default)]
+  pub health_check: bool,
+
+  /// The max time in seconds to wait for the containers to become healthy.
+  /// If neither the containers have a Docker healthcheck nor a
+  /// `health_check_url` is configured, the containers must
+  /// stay running for this whole duration.
+  #[serde(default = "default_health_check_timeout")]
+  #[builder(default = "default_health_check_timeout()")]
+  #[partial_default(default_health_check_timeout())]
+  pub health_check_timeout: i32,
+
+  /// Optional url which must return a 2xx response for the
+  /// deploy to be healthy. The request is sent from the Periphery host,
+  /// eg `http://localhost:8080/health`.
+  #[serde(default)]
+  #[builder(default)]
+  pub health_check_url: String,
+
+  /// Automatically roll back to the previous deploy
+  /// if the health check fails. See `RollbackStack`.
+  #[serde(default)]
+  #[builder(default)]
+  pub rollback_on_health_failure: bool,
+
   /// Used with `registry_account` to login to a registry before docker compose up.
   #[serde(default)]
   #[builder(default)]
@@ -668,6 +847,10 @@ fn default_send_alerts() -> bool {
   true
 }
 
+fn default_health_check_timeout() -> i32 {
+  60
+}
+
 fn default_wrapper_include() -> Vec<String> {
   vec![]
 }
@@ -705,6 +888,12 @@ impl Default for StackConfig {
       linked_repo: Default::default(),
       git_provider: default_git_provider(),
       git_https: default_git_https(),
+      git_ssh: Default::default(),
+      git_depth: Default::default(),
+      git_sparse_checkout: Default::default(),
+      git_sparse_paths: Default::default(),
+      git_submodules: Default::default(),
+      git_lfs: Default::default(),
       repo: Default::default(),
       branch: default_branch(),
       commit: Default::default(),
@@ -713,8 +902,20 @@ impl Default for StackConfig {
       git_account: Default::default(),
       webhook_enabled: default_webhook_enabled(),
       webhook_secret: Default::default(),
+      webhook_path_filters: Default::default(),
+      commit_status: Default::default(),
+      deployment_status: Default::default(),
       webhook_force_deploy: Default::default(),
       send_alerts: default_send_alerts(),
+      container_cpu_warning: Default::default(),
+      container_cpu_critical: Default::default(),
+      container_mem_warning: Default::default(),
+      container_mem_critical: Default::default(),
+      archive_logs: Default::default(),
+      health_check: Default::default(),
+      health_check_timeout: default_health_check_timeout(),
+      health_check_url: Default::default(),
+      rollback_on_health_failure: Default::default(),
       links: Default::default(),
     }
   }
@@ -807,6 +1008,8 @@ pub struct StackActionState {
   pub unpausing: bool,
   pub stopping: bool,
   pub destroying: bool,
+  pub backing_up: bool,
+  pub restoring: bool,
 }
 
 #[typeshare]
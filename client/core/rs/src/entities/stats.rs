@@ -62,9 +62,9 @@ pub struct SystemStatsRecord {
   /// Total network egress in bytes
   #[serde(default)]
   pub network_egress_bytes: f64,
-  // /// Network usage by interface name (ingress, egress in bytes)
-  // #[serde(default)]
-  // pub network_usage_interface: Vec<SingleNetworkInterfaceUsage>, // interface -> (ingress, egress)
+  /// Network usage by interface name (ingress, egress in bytes)
+  #[serde(default)]
+  pub network_usage_interface: Vec<SingleNetworkInterfaceUsage>,
 }
 
 /// Realtime system stats data.
@@ -89,15 +89,15 @@ pub struct SystemStats {
   pub mem_total_gb: f64,
   /// Breakdown of individual disks, ie their usages, sizes, and mount points
   pub disks: Vec<SingleDiskUsage>,
-  /// Network ingress usage in MB
+  /// Network ingress usage in bytes since the last poll
   #[serde(default)]
   pub network_ingress_bytes: f64,
-  /// Network egress usage in MB
+  /// Network egress usage in bytes since the last poll
   #[serde(default)]
   pub network_egress_bytes: f64,
-  // /// Network usage by interface name (ingress, egress in bytes)
-  // #[serde(default)]
-  // pub network_usage_interface: Vec<SingleNetworkInterfaceUsage>, // interface -> (ingress, egress)
+  /// Network usage by interface name (ingress, egress in bytes)
+  #[serde(default)]
+  pub network_usage_interface: Vec<SingleNetworkInterfaceUsage>,
   // metadata
   /// The rate the system stats are being polled from the system
   pub polling_rate: Timelength,
@@ -201,13 +201,47 @@ pub struct ResourceSyncConfig {
   pub git_provider: String,
 
   /// Whether to use https to clone the repo (versus http). Default: true
-  ///
-  /// Note. Komodo does not currently support cloning repos via ssh.
   #[serde(default = "default_git_https")]
   #[builder(default = "default_git_https()")]
   #[partial_default(default_git_https())]
   pub git_https: bool,
 
+  /// Whether to clone the repo over ssh (`ssh://git@{provider}/{repo}`)
+  /// rather than http(s). Authenticates with the SSH key
+  /// of the git account, or the host's default ssh identity
+  /// if the account has no SSH key.
+  #[serde(default)]
+  #[builder(default)]
+  pub git_ssh: bool,
+
+  /// Clone with `--depth {git_depth}`, skipping older history.
+  /// Use 0 to clone the full history.
+  #[serde(default)]
+  #[builder(default)]
+  pub git_depth: i32,
+
+  /// Only check out these folders of the repo (`git sparse-checkout`, cone mode).
+  /// Files at the repo root are always checked out.
+  /// Leave empty to check out the whole repo.
+  #[serde(default, deserialize_with = "string_list_deserializer")]
+  #[partial_attr(serde(
+    default,
+    deserialize_with = "option_string_list_deserializer"
+  ))]
+  #[builder(default)]
+  pub git_sparse_paths: Vec<String>,
+
+  /// Initialize and update the repo's submodules (recursively) after clone / pull.
+  #[serde(default)]
+  #[builder(default)]
+  pub git_submodules: bool,
+
+  /// Run `git lfs pull` after clone / pull to download Git LFS files.
+  /// Requires `git-lfs` to be installed on the host.
+  #[serde(default)]
+  #[builder(default)]
+  pub git_lfs: bool,
+
   /// The Github repo used as the source of the build.
   #[serde(default)]
   #[builder(default)]
@@ -245,6 +279,18 @@ pub struct ResourceSyncConfig {
   #[builder(default)]
   pub webhook_secret: String,
 
+  /// Only trigger the webhook when the push changes files matching these
+  /// paths, relative to the repo root. Supports wildcard (`services/api/*`)
+  /// and regex (`\^services/.*\.ya?ml$\`) patterns.
+  /// Prefix with `!` to exclude paths. Leave empty to trigger on any push.
+  #[serde(default, deserialize_with = "string_list_deserializer")]
+  #[partial_attr(serde(
+    default,
+    deserialize_with = "option_string_list_deserializer"
+  ))]
+  #[builder(default)]
+  pub webhook_path_filters: Vec<String>,
+
   /// Files are available on the Komodo Core host.
   /// Specify the file / folder with [ResourceSyncConfig::resource_path].
   #[serde(default)]
@@ -370,6 +416,11 @@ impl Default for ResourceSyncConfig {
       linked_repo: Default::default(),
       git_provider: default_git_provider(),
       git_https: default_git_https(),
+      git_ssh: Default::default(),
+      git_depth: Default::default(),
+      git_sparse_paths: Default::default(),
+      git_submodules: Default::default(),
+      git_lfs: Default::default(),
       repo: Default::default(),
       branch: default_branch(),
       commit: Default::default(),
@@ -385,6 +436,7 @@ impl Default for ResourceSyncConfig {
       delete: Default::default(),
       webhook_enabled: default_webhook_enabled(),
       webhook_secret: Default::default(),
+      webhook_path_filters: Default::default(),
       pending_alert: default_pending_alert(),
     }
   }
@@ -79,6 +79,11 @@ pub struct Update {
   /// If the update is for resource config update, give the current (at time of Update) toml contents
   #[serde(default, skip_serializing_if = "String::is_empty")]
   pub current_toml: String,
+  /// If the update is for a deploy, the JSON serialized resource config
+  /// which was deployed (before variable / secret interpolation).
+  /// Used to roll back to this deploy.
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub deployed_config: String,
 }
 
 impl Update {
@@ -9,7 +9,7 @@ use typeshare::typeshare;
 use crate::entities::{I64, MongoId};
 
 use super::{
-  JsonValue, ResourceTargetVariant,
+  JsonValue, ResourceTargetVariant, api_key::ApiKeyScopes,
   permission::PermissionLevelAndSpecifics,
 };
 
@@ -96,6 +96,11 @@ pub struct User {
 
   #[serde(default)]
   pub updated_at: I64,
+
+  /// The scopes of the api key used to authenticate the request.
+  /// Only attached to request users, never stored.
+  #[serde(skip)]
+  pub api_key_scopes: Option<ApiKeyScopes>,
 }
 
 fn default_external_skip_2fa() -> bool {
@@ -139,6 +144,7 @@ impl User {
       totp: Default::default(),
       passkey: Default::default(),
       external_skip_2fa: Default::default(),
+      api_key_scopes: None,
     }
   }
 }
@@ -188,6 +194,9 @@ pub enum UserConfig {
   /// User that logs in via Oidc provider
   Oidc { provider: String, user_id: String },
 
+  /// User that logs in via LDAP / Active Directory
+  Ldap { dn: String, email: String },
+
   /// Non-human managed user, can have it's own permissions / api keys
   Service { description: String },
 }
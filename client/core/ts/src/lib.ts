@@ -10,6 +10,11 @@ import {
   ConnectTerminalQuery,
   ExecuteRequest,
   ExecuteTerminalBody,
+  LdapLogin,
+  LdapLoginOptions,
+  LdapLoginResponse,
+  OidcLoginOptions,
+  OidcLoginResponse,
   ReadRequest,
   Update,
   UpdateListItem,
@@ -60,7 +65,7 @@ export function KomodoClient(url: string, options: InitOptions) {
   const auth = MoghAuthClient(url + "/auth", state.jwt);
 
   const request = <Params = undefined, Res = unknown>(
-    path: "/user" | "/read" | "/execute" | "/write",
+    path: "/user" | "/ldap" | "/oidc" | "/read" | "/execute" | "/write",
     type: string,
     params: Params,
     method = "POST",
@@ -118,6 +123,30 @@ export function KomodoClient(url: string, options: InitOptions) {
   const getUser = async () =>
     await request<undefined, User>("/user", "", undefined, "GET");
 
+  const ldap_options = async () =>
+    await request<undefined, LdapLoginOptions>(
+      "/ldap",
+      "options",
+      undefined,
+      "GET",
+    );
+
+  const ldap_login = async (params: LdapLogin) =>
+    await request<LdapLogin, LdapLoginResponse>("/ldap", "login", params);
+
+  const oidc_options = async () =>
+    await request<undefined, OidcLoginOptions>(
+      "/oidc",
+      "options",
+      undefined,
+      "GET",
+    );
+
+  const oidc_login = () => location.replace(`${url}/oidc/login`);
+
+  const oidc_redeem = async () =>
+    await request<undefined, OidcLoginResponse>("/oidc", "redeem", undefined);
+
   const read = async <
     T extends ReadRequest["type"],
     Req extends Extract<ReadRequest, { type: T }>,
@@ -344,6 +373,52 @@ export function KomodoClient(url: string, options: InitOptions) {
      * https://docs.rs/komodo_client/latest/komodo_client/api/user/index.html
      */
     getUser,
+    /**
+     * Get whether LDAP / Active Directory login is enabled.
+     *
+     * ```
+     * const { enabled } = await komodo.ldap_options();
+     * ```
+     */
+    ldap_options,
+    /**
+     * Login with LDAP / Active Directory credentials.
+     *
+     * ```
+     * const { jwt } = await komodo.ldap_login({
+     *   username: "test-user",
+     *   password: "test-pass"
+     * });
+     * ```
+     *
+     * https://docs.rs/komodo_client/latest/komodo_client/api/ldap/index.html
+     */
+    ldap_login,
+    /**
+     * Get whether OIDC login goes through Komodo Core,
+     * which syncs UserGroups with the groups claim.
+     *
+     * ```
+     * const { enabled } = await komodo.oidc_options();
+     * ```
+     */
+    oidc_options,
+    /**
+     * Start the OIDC login with group sync in the browser.
+     * Komodo redirects back with `oidc_redeem_ready=true`,
+     * then call `oidc_redeem` to get the JWT.
+     *
+     * https://docs.rs/komodo_client/latest/komodo_client/api/oidc/index.html
+     */
+    oidc_login,
+    /**
+     * Get the JWT after the OIDC login with group sync.
+     *
+     * ```
+     * const { jwt } = await komodo.oidc_redeem();
+     * ```
+     */
+    oidc_redeem,
     /**
      * Call the `/read` api.
      *
@@ -176,6 +176,12 @@ export type ReadResponses = {
   ListAlerts: Types.ListAlertsResponse;
   GetAlert: Types.GetAlertResponse;
 
+  // ==== LOG ARCHIVE ====
+  SearchArchivedLogs: Types.SearchArchivedLogsResponse;
+
+  // ==== AUDIT ====
+  ListAuditEvents: Types.ListAuditEventsResponse;
+
   // ==== VARIABLE ====
   GetVariable: Types.GetVariableResponse;
   ListVariables: Types.ListVariablesResponse;
@@ -309,6 +315,7 @@ export type WriteResponses = {
   UpdateServiceUserDescription: Types.UpdateServiceUserDescriptionResponse;
   CreateApiKeyForServiceUser: Types.CreateApiKeyForServiceUserResponse;
   DeleteApiKeyForServiceUser: Types.DeleteApiKeyForServiceUserResponse;
+  UpdateApiKeyScopes: Types.UpdateApiKeyScopesResponse;
 
   // ==== USER GROUP ====
   CreateUserGroup: Types.UserGroup;
@@ -342,12 +349,14 @@ export type WriteResponses = {
   CreateGitProviderAccount: Types.CreateGitProviderAccountResponse;
   UpdateGitProviderAccount: Types.UpdateGitProviderAccountResponse;
   DeleteGitProviderAccount: Types.DeleteGitProviderAccountResponse;
+  GenerateGitProviderAccountSshKey: Types.GenerateGitProviderAccountSshKeyResponse;
   CreateDockerRegistryAccount: Types.CreateDockerRegistryAccountResponse;
   UpdateDockerRegistryAccount: Types.UpdateDockerRegistryAccountResponse;
   DeleteDockerRegistryAccount: Types.DeleteDockerRegistryAccountResponse;
 
   // ==== ALERT ====
   CloseAlert: Types.NoData;
+  AcknowledgeAlert: Types.NoData;
 };
 
 export type ExecuteResponses = {
@@ -365,7 +374,10 @@ export type ExecuteResponses = {
   UnpauseStack: Types.Update;
   DestroyStack: Types.Update;
   BatchDestroyStack: Types.BatchExecutionResponse;
+  RollbackStack: Types.Update;
   RunStackService: Types.Update;
+  BackupStackVolumes: Types.Update;
+  RestoreStackVolumes: Types.Update;
 
   // ==== DEPLOYMENT ====
   Deploy: Types.Update;
@@ -378,6 +390,9 @@ export type ExecuteResponses = {
   StopDeployment: Types.Update;
   DestroyDeployment: Types.Update;
   BatchDestroyDeployment: Types.BatchExecutionResponse;
+  RollbackDeployment: Types.Update;
+  BackupDeploymentVolumes: Types.Update;
+  RestoreDeploymentVolumes: Types.Update;
 
   // ==== BUILD ====
   RunBuild: Types.Update;
@@ -446,7 +461,9 @@ export type ExecuteResponses = {
   // ==== MAINTENANCE ====
   ClearRepoCache: Types.Update;
   BackupCoreDatabase: Types.Update;
+  RestoreCoreDatabase: Types.Update;
   GlobalAutoUpdate: Types.Update;
   RotateAllServerKeys: Types.Update;
   RotateCoreKeys: Types.Update;
+  RotateEncryptionKey: Types.Update;
 };
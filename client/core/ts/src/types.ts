@@ -227,7 +227,14 @@ export type AlerterEndpoint =
 	/** Send alert to Ntfy */
 	| { type: "Ntfy", params: NtfyAlerterEndpoint }
 	/** Send alert to Pushover */
-	| { type: "Pushover", params: PushoverAlerterEndpoint };
+	| { type: "Pushover", params: PushoverAlerterEndpoint }
+	/**
+	 * Send a templated request to any http endpoint,
+	 * with custom headers and optional HMAC signing.
+	 */
+	| { type: "Webhook", params: WebhookAlerterEndpoint }
+	/** Send alert by email over SMTP */
+	| { type: "Smtp", params: SmtpAlerterEndpoint };
 
 /** Used to reference a specific resource across all resource types */
 export type ResourceTarget = 
@@ -286,6 +293,46 @@ export interface MaintenanceWindow {
 	enabled: boolean;
 }
 
+/**
+ * Policy for repeating and escalating open alerts.
+ * Only applies to unresolved alerts which have
+ * not been acknowledged with `AcknowledgeAlert`.
+ */
+export interface AlertEscalationPolicy {
+	/**
+	 * Re-send the alert every N minutes while it stays open.
+	 * 0 disables repeat notifications.
+	 */
+	repeat_interval?: I64;
+	/**
+	 * Escalate the alert to another alerter if it stays
+	 * open for N minutes. 0 disables escalation.
+	 */
+	escalate_after?: I64;
+	/** The alerter (id or name) to escalate to. */
+	escalate_to?: string;
+}
+
+/** Policy for grouping alerts into combined digest messages. */
+export interface AlertGroupingPolicy {
+	/**
+	 * Collect alerts and send them as one combined message,
+	 * instead of one message per alert.
+	 */
+	enabled?: boolean;
+	/**
+	 * Collect alerts for N minutes before sending the digest.
+	 * 0 sends one digest per monitoring cycle.
+	 */
+	window?: I64;
+	/**
+	 * Don't send Deployment / Stack alerts when
+	 * the Server they are on is unreachable.
+	 * The `ServerUnreachable` alert is still sent.
+	 */
+	hide_unreachable_children?: boolean;
+}
+
 export interface AlerterConfig {
 	/** Whether the alerter is enabled */
 	enabled?: boolean;
@@ -309,6 +356,16 @@ export interface AlerterConfig {
 	except_resources?: ResourceTarget[];
 	/** Scheduled maintenance windows during which alerts will be suppressed. */
 	maintenance_windows?: MaintenanceWindow[];
+	/**
+	 * Repeat and escalate notifications for alerts
+	 * which stay open without being acknowledged.
+	 */
+	escalation?: AlertEscalationPolicy;
+	/**
+	 * Group alerts into a single combined message
+	 * to prevent notification storms.
+	 */
+	grouping?: AlertGroupingPolicy;
 }
 
 export type Alerter = Resource<AlerterConfig, undefined>;
@@ -428,7 +485,10 @@ export enum Operation {
 	UnpauseStack = "UnpauseStack",
 	StopStack = "StopStack",
 	DestroyStack = "DestroyStack",
+	RollbackStack = "RollbackStack",
 	RunStackService = "RunStackService",
+	BackupStackVolumes = "BackupStackVolumes",
+	RestoreStackVolumes = "RestoreStackVolumes",
 	CheckStackForUpdate = "CheckStackForUpdate",
 	DeployStackService = "DeployStackService",
 	PullStackService = "PullStackService",
@@ -450,6 +510,9 @@ export enum Operation {
 	UnpauseDeployment = "UnpauseDeployment",
 	StopDeployment = "StopDeployment",
 	DestroyDeployment = "DestroyDeployment",
+	RollbackDeployment = "RollbackDeployment",
+	BackupDeploymentVolumes = "BackupDeploymentVolumes",
+	RestoreDeploymentVolumes = "RestoreDeploymentVolumes",
 	CheckDeploymentForUpdate = "CheckDeploymentForUpdate",
 	CreateBuild = "CreateBuild",
 	UpdateBuild = "UpdateBuild",
@@ -495,9 +558,11 @@ export enum Operation {
 	SendAlert = "SendAlert",
 	ClearRepoCache = "ClearRepoCache",
 	BackupCoreDatabase = "BackupCoreDatabase",
+	RestoreCoreDatabase = "RestoreCoreDatabase",
 	GlobalAutoUpdate = "GlobalAutoUpdate",
 	RotateAllServerKeys = "RotateAllServerKeys",
 	RotateCoreKeys = "RotateCoreKeys",
+	RotateEncryptionKey = "RotateEncryptionKey",
 	CreateVariable = "CreateVariable",
 	UpdateVariableValue = "UpdateVariableValue",
 	DeleteVariable = "DeleteVariable",
@@ -589,6 +654,12 @@ export interface Update {
 	prev_toml?: string;
 	/** If the update is for resource config update, give the current (at time of Update) toml contents */
 	current_toml?: string;
+	/**
+	 * If the update is for a deploy, the JSON serialized resource config
+	 * which was deployed (before variable / secret interpolation).
+	 * Used to roll back to this deploy.
+	 */
+	deployed_config?: string;
 }
 
 export type BoxUpdate = Update;
@@ -658,10 +729,33 @@ export interface BuildConfig {
 	git_provider: string;
 	/**
 	 * Whether to use https to clone the repo (versus http). Default: true
-	 * 
-	 * Note. Komodo does not currently support cloning repos via ssh.
 	 */
 	git_https: boolean;
+	/**
+	 * Whether to clone the repo over ssh (`ssh://git@{provider}/{repo}`)
+	 * rather than http(s). Authenticates with the SSH key
+	 * of the git account, or the host's default ssh identity
+	 * if the account has no SSH key.
+	 */
+	git_ssh?: boolean;
+	/**
+	 * Clone with `--depth {git_depth}`, skipping older history.
+	 * Use 0 to clone the full history.
+	 */
+	git_depth?: number;
+	/**
+	 * Only check out these folders of the repo (`git sparse-checkout`, cone mode).
+	 * Files at the repo root are always checked out.
+	 * Leave empty to check out the whole repo.
+	 */
+	git_sparse_paths?: string[];
+	/** Initialize and update the repo's submodules (recursively) after clone / pull. */
+	git_submodules?: boolean;
+	/**
+	 * Run `git lfs pull` after clone / pull to download Git LFS files.
+	 * Requires `git-lfs` to be installed on the host.
+	 */
+	git_lfs?: boolean;
 	/**
 	 * The git account used to access private repos.
 	 * Passing empty string can only clone public repos.
@@ -683,6 +777,20 @@ export interface BuildConfig {
 	 * If its an empty string, use the default secret from the config.
 	 */
 	webhook_secret?: string;
+	/**
+	 * Only trigger the webhook when the push changes files matching these
+	 * paths, relative to the repo root. Supports wildcard (`services/api/*`)
+	 * and regex (`\^services/.*\.ya?ml$\`) patterns.
+	 * Prefix with `!` to exclude paths. Leave empty to trigger on any push.
+	 */
+	webhook_path_filters?: string[];
+	/**
+	 * Report the run status (pending / success / failure) of the commit
+	 * to the git provider's commit status api, linking to the Komodo Update.
+	 * Requires a git account with a token.
+	 * Supports Github, Gitlab and Gitea / Forgejo.
+	 */
+	commit_status?: boolean;
 	/**
 	 * If this is checked, the build will source the files on the host.
 	 * Use `build_path` and `dockerfile_path` to specify the path on the host.
@@ -870,7 +978,10 @@ export type Execution =
 	| { type: "StopStack", params: StopStack }
 	| { type: "DestroyStack", params: DestroyStack }
 	| { type: "BatchDestroyStack", params: BatchDestroyStack }
+	| { type: "RollbackStack", params: RollbackStack }
 	| { type: "RunStackService", params: RunStackService }
+	| { type: "BackupStackVolumes", params: BackupStackVolumes }
+	| { type: "RestoreStackVolumes", params: RestoreStackVolumes }
 	/** Deploy the target deployment. (alias: `dp`) */
 	| { type: "Deploy", params: Deploy }
 	| { type: "BatchDeploy", params: BatchDeploy }
@@ -882,6 +993,9 @@ export type Execution =
 	| { type: "StopDeployment", params: StopDeployment }
 	| { type: "DestroyDeployment", params: DestroyDeployment }
 	| { type: "BatchDestroyDeployment", params: BatchDestroyDeployment }
+	| { type: "RollbackDeployment", params: RollbackDeployment }
+	| { type: "BackupDeploymentVolumes", params: BackupDeploymentVolumes }
+	| { type: "RestoreDeploymentVolumes", params: RestoreDeploymentVolumes }
 	/** Run the target build. (alias: `build`, `bd`) */
 	| { type: "RunBuild", params: RunBuild }
 	| { type: "BatchRunBuild", params: BatchRunBuild }
@@ -939,9 +1053,11 @@ export type Execution =
 	| { type: "RemoveSwarmSecrets", params: RemoveSwarmSecrets }
 	| { type: "ClearRepoCache", params: ClearRepoCache }
 	| { type: "BackupCoreDatabase", params: BackupCoreDatabase }
+	| { type: "RestoreCoreDatabase", params: RestoreCoreDatabase }
 	| { type: "GlobalAutoUpdate", params: GlobalAutoUpdate }
 	| { type: "RotateAllServerKeys", params: RotateAllServerKeys }
 	| { type: "RotateCoreKeys", params: RotateCoreKeys }
+	| { type: "RotateEncryptionKey", params: RotateEncryptionKey }
 	| { type: "Sleep", params: Sleep };
 
 /** Allows to enable / disabled procedures in the sequence / parallel vec on the fly */
@@ -1084,6 +1200,17 @@ export interface GitProviderAccount {
 	 * If the database / host can be accessed this is insecure.
 	 */
 	token?: string;
+	/**
+	 * The SSH private key (OpenSSH format) in plain text on the db.
+	 * Used instead of the token for resources cloning over ssh.
+	 * If the database / host can be accessed this is insecure.
+	 */
+	ssh_private_key?: string;
+	/**
+	 * The SSH public key matching `ssh_private_key`,
+	 * to add as a deploy key on the git provider.
+	 */
+	ssh_public_key?: string;
 }
 
 export type CreateGitProviderAccountResponse = GitProviderAccount;
@@ -1107,6 +1234,11 @@ export type UserConfig =
 	| { type: "Oidc", data: {
 	provider: string;
 	user_id: string;
+}}
+	/** User that logs in via LDAP / Active Directory */
+	| { type: "Ldap", data: {
+	dn: string;
+	email: string;
 }}
 	/** Non-human managed user, can have it's own permissions / api keys */
 	| { type: "Service", data: {
@@ -1285,6 +1417,33 @@ export enum RestartMode {
 	UnlessStopped = "unless-stopped",
 }
 
+export enum DeployStrategy {
+	/** Stop and remove the old container, then start the new one. */
+	Recreate = "Recreate",
+	/**
+	 * Start the new container alongside the old one,
+	 * and only replace the old one once the new one is healthy.
+	 */
+	BlueGreen = "BlueGreen",
+}
+
+/** How the Smtp alerter secures the server connection. */
+export enum SmtpSecurity {
+	/** Plain connection, no TLS. Only for local relays. */
+	None = "None",
+	/** Upgrade a plain connection with STARTTLS (usually port 587). */
+	StartTls = "StartTls",
+	/** Implicit TLS from the start of the connection (usually port 465). */
+	Tls = "Tls",
+}
+
+/** The http method used by the Webhook alerter. */
+export enum WebhookAlerterMethod {
+	Post = "POST",
+	Put = "PUT",
+	Patch = "PATCH",
+}
+
 export enum TerminationSignal {
 	SigHup = "SIGHUP",
 	SigInt = "SIGINT",
@@ -1336,6 +1495,74 @@ export interface DeploymentConfig {
 	auto_update?: boolean;
 	/** Whether to send ContainerStateChange alerts for this deployment. */
 	send_alerts: boolean;
+	/**
+	 * The container CPU usage percentage which triggers a WARNING
+	 * `ContainerCpu` alert. Docker reports CPU usage relative to
+	 * one core, so this may go above 100. 0 disables the alert.
+	 */
+	container_cpu_warning?: number;
+	/**
+	 * The container CPU usage percentage which triggers a CRITICAL
+	 * `ContainerCpu` alert. 0 disables the CRITICAL level.
+	 */
+	container_cpu_critical?: number;
+	/**
+	 * The container memory usage percentage (of its memory limit)
+	 * which triggers a WARNING `ContainerMem` alert. 0 disables the alert.
+	 */
+	container_mem_warning?: number;
+	/**
+	 * The container memory usage percentage (of its memory limit)
+	 * which triggers a CRITICAL `ContainerMem` alert.
+	 * 0 disables the CRITICAL level.
+	 */
+	container_mem_critical?: number;
+	/**
+	 * Whether Core should archive the container logs,
+	 * so they remain searchable after the container is removed.
+	 * See `keep_archived_logs_for_days` in the core config.
+	 */
+	archive_logs?: boolean;
+	/**
+	 * How the container is replaced on redeploy.
+	 * 
+	 * - `Recreate`: Stop and remove the old container, then start the new one.
+	 * - `BlueGreen`: Start the new container under a temporary name,
+	 * wait for it to be healthy (using `health_check_timeout` and `health_check_url`),
+	 * then remove the old container and rename the new one.
+	 * The old container keeps running if the new one fails.
+	 * Both containers run at the same time, so the container
+	 * must not bind fixed host ports (eg. `host` network or `ports`).
+	 * 
+	 * Note. Only used in Container (non-Swarm) mode.
+	 */
+	deploy_strategy?: DeployStrategy;
+	/**
+	 * Verify the container after deploy, marking the deploy failed
+	 * if it is not running (and `healthy`, if it has a Docker healthcheck)
+	 * within `health_check_timeout`.
+	 * 
+	 * Note. Only supported for Server (non-Swarm) Deployments.
+	 */
+	health_check?: boolean;
+	/**
+	 * The max time in seconds to wait for the container to become healthy.
+	 * If neither the container has a Docker healthcheck nor a
+	 * `health_check_url` is configured, the container must
+	 * stay running for this whole duration.
+	 */
+	health_check_timeout?: number;
+	/**
+	 * Optional url which must return a 2xx response for the
+	 * deploy to be healthy. The request is sent from the Periphery host,
+	 * eg `http://localhost:8080/health`.
+	 */
+	health_check_url?: string;
+	/**
+	 * Automatically roll back to the previous deploy
+	 * if the health check fails. See `RollbackDeployment`.
+	 */
+	rollback_on_health_failure?: boolean;
 	/** Configure quick links that are displayed in the resource header */
 	links?: string[];
 	/**
@@ -1492,6 +1719,8 @@ export type ExportResourcesToTomlResponse = TomlResponse;
 
 export type FindUserResponse = User;
 
+export type GenerateGitProviderAccountSshKeyResponse = GitProviderAccount;
+
 export interface GenericResourcesInnerNamedResourceSpec {
 	Kind?: string;
 	Value?: string;
@@ -1614,6 +1843,32 @@ export type AlertData =
 	used_gb: number;
 	/** The total size of the disk in GB */
 	total_gb: number;
+}}
+	/** A server has a high sustained load average. */
+	| { type: "ServerLoadAverage", data: {
+	/** The id of the server */
+	id: string;
+	/** The name of the server */
+	name: string;
+	/** The region of the server */
+	region?: string;
+	/** The 5 minute load average */
+	load_average: number;
+	/** The 5 minute load average per cpu core */
+	per_core: number;
+}}
+	/** A server has high network throughput. */
+	| { type: "ServerNetwork", data: {
+	/** The id of the server */
+	id: string;
+	/** The name of the server */
+	name: string;
+	/** The region of the server */
+	region?: string;
+	/** The network ingress in MB/s */
+	ingress_mb: number;
+	/** The network egress in MB/s */
+	egress_mb: number;
 }}
 	/** A server has a version mismatch with the core. */
 	| { type: "ServerVersionMismatch", data: {
@@ -1738,6 +1993,42 @@ export type AlertData =
 	swarm_name?: string;
 	/** One or more images that were updated */
 	images: string[];
+}}
+	/** A Deployment / Stack container has high CPU usage. */
+	| { type: "ContainerCpu", data: {
+	/** The type of resource, Deployment or Stack */
+	resource_type: ResourceTarget["type"];
+	/** The id of the resource */
+	id: string;
+	/** The name of the resource */
+	name: string;
+	/** The server id of server that the container is on */
+	server_id?: string;
+	/** The server name */
+	server_name?: string;
+	/** The container name */
+	container: string;
+	/** The container cpu usage percentage */
+	percentage: number;
+}}
+	/** A Deployment / Stack container has high memory usage. */
+	| { type: "ContainerMem", data: {
+	/** The type of resource, Deployment or Stack */
+	resource_type: ResourceTarget["type"];
+	/** The id of the resource */
+	id: string;
+	/** The name of the resource */
+	name: string;
+	/** The server id of server that the container is on */
+	server_id?: string;
+	/** The server name */
+	server_name?: string;
+	/** The container name */
+	container: string;
+	/** The container memory usage percentage (of its limit) */
+	percentage: number;
+	/** The container memory usage, eg `1.2GiB / 4GiB` */
+	usage: string;
 }}
 	/** An AWS builder failed to terminate. */
 	| { type: "AwsBuilderTerminationFailed", data: {
@@ -1803,6 +2094,26 @@ export type AlertData =
 	details?: string;
 }};
 
+/** Acknowledgement and escalation state of an alert. */
+export interface AlertEscalation {
+	/**
+	 * Whether a user has acknowledged the alert.
+	 * Acknowledged alerts are no longer repeated or escalated.
+	 */
+	acknowledged?: boolean;
+	/** The id of the user who acknowledged the alert */
+	acknowledged_by?: string;
+	/** The timestamp the alert was acknowledged */
+	acknowledged_ts?: I64;
+	/**
+	 * The last time a repeat notification was sent
+	 * for the alert, keyed by alerter id.
+	 */
+	notified?: Record<string, I64>;
+	/** The ids of the alerters the alert has been escalated to. */
+	escalated_to?: string[];
+}
+
 /** Representation of an alert in the system. */
 export interface Alert {
 	/**
@@ -1823,6 +2134,8 @@ export interface Alert {
 	data: AlertData;
 	/** The timestamp of alert resolution */
 	resolved_ts?: I64;
+	/** Acknowledgement and escalation state of the alert. */
+	escalation?: AlertEscalation;
 }
 
 export type GetAlertResponse = Alert;
@@ -1852,6 +2165,8 @@ export interface DeploymentActionState {
 	stopping: boolean;
 	destroying: boolean;
 	renaming: boolean;
+	backing_up: boolean;
+	restoring: boolean;
 }
 
 export type GetDeploymentActionStateResponse = DeploymentActionState;
@@ -1983,10 +2298,33 @@ export interface RepoConfig {
 	git_provider: string;
 	/**
 	 * Whether to use https to clone the repo (versus http). Default: true
-	 * 
-	 * Note. Komodo does not currently support cloning repos via ssh.
 	 */
 	git_https: boolean;
+	/**
+	 * Whether to clone the repo over ssh (`ssh://git@{provider}/{repo}`)
+	 * rather than http(s). Authenticates with the SSH key
+	 * of the git account, or the host's default ssh identity
+	 * if the account has no SSH key.
+	 */
+	git_ssh?: boolean;
+	/**
+	 * Clone with `--depth {git_depth}`, skipping older history.
+	 * Use 0 to clone the full history.
+	 */
+	git_depth?: number;
+	/**
+	 * Only check out these folders of the repo (`git sparse-checkout`, cone mode).
+	 * Files at the repo root are always checked out.
+	 * Leave empty to check out the whole repo.
+	 */
+	git_sparse_paths?: string[];
+	/** Initialize and update the repo's submodules (recursively) after clone / pull. */
+	git_submodules?: boolean;
+	/**
+	 * Run `git lfs pull` after clone / pull to download Git LFS files.
+	 * Requires `git-lfs` to be installed on the host.
+	 */
+	git_lfs?: boolean;
 	/**
 	 * The git account used to access private repos.
 	 * Passing empty string can only clone public repos.
@@ -2016,6 +2354,20 @@ export interface RepoConfig {
 	 * If its an empty string, use the default secret from the config.
 	 */
 	webhook_secret?: string;
+	/**
+	 * Only trigger the webhook when the push changes files matching these
+	 * paths, relative to the repo root. Supports wildcard (`services/api/*`)
+	 * and regex (`\^services/.*\.ya?ml$\`) patterns.
+	 * Prefix with `!` to exclude paths. Leave empty to trigger on any push.
+	 */
+	webhook_path_filters?: string[];
+	/**
+	 * Report the run status (pending / success / failure) of the commit
+	 * to the git provider's commit status api, linking to the Komodo Update.
+	 * Requires a git account with a token.
+	 * Supports Github, Gitlab and Gitea / Forgejo.
+	 */
+	commit_status?: boolean;
 	/**
 	 * Command to be run after the repo is cloned.
 	 * The path is relative to the root of the repo.
@@ -2080,10 +2432,33 @@ export interface ResourceSyncConfig {
 	git_provider: string;
 	/**
 	 * Whether to use https to clone the repo (versus http). Default: true
-	 * 
-	 * Note. Komodo does not currently support cloning repos via ssh.
 	 */
 	git_https: boolean;
+	/**
+	 * Whether to clone the repo over ssh (`ssh://git@{provider}/{repo}`)
+	 * rather than http(s). Authenticates with the SSH key
+	 * of the git account, or the host's default ssh identity
+	 * if the account has no SSH key.
+	 */
+	git_ssh?: boolean;
+	/**
+	 * Clone with `--depth {git_depth}`, skipping older history.
+	 * Use 0 to clone the full history.
+	 */
+	git_depth?: number;
+	/**
+	 * Only check out these folders of the repo (`git sparse-checkout`, cone mode).
+	 * Files at the repo root are always checked out.
+	 * Leave empty to check out the whole repo.
+	 */
+	git_sparse_paths?: string[];
+	/** Initialize and update the repo's submodules (recursively) after clone / pull. */
+	git_submodules?: boolean;
+	/**
+	 * Run `git lfs pull` after clone / pull to download Git LFS files.
+	 * Requires `git-lfs` to be installed on the host.
+	 */
+	git_lfs?: boolean;
 	/** The Github repo used as the source of the build. */
 	repo?: string;
 	/** The branch of the repo. */
@@ -2105,6 +2480,13 @@ export interface ResourceSyncConfig {
 	 * If its an empty string, use the default secret from the config.
 	 */
 	webhook_secret?: string;
+	/**
+	 * Only trigger the webhook when the push changes files matching these
+	 * paths, relative to the repo root. Supports wildcard (`services/api/*`)
+	 * and regex (`\^services/.*\.ya?ml$\`) patterns.
+	 * Prefix with `!` to exclude paths. Leave empty to trigger on any push.
+	 */
+	webhook_path_filters?: string[];
 	/**
 	 * Files are available on the Komodo Core host.
 	 * Specify the file / folder with [ResourceSyncConfig::resource_path].
@@ -2341,6 +2723,24 @@ export interface ServerConfig {
 	disk_warning: number;
 	/** The percentage threshhold which triggers CRITICAL state for DISK. */
 	disk_critical: number;
+	/** Whether to send alerts about the servers sustained load average. */
+	send_load_alerts?: boolean;
+	/** The 5 minute load average per cpu core which triggers WARNING state for LOAD. */
+	load_warning: number;
+	/** The 5 minute load average per cpu core which triggers CRITICAL state for LOAD. */
+	load_critical: number;
+	/** Whether to send alerts about the servers network throughput. */
+	send_network_alerts?: boolean;
+	/**
+	 * The combined ingress + egress throughput in MB/s
+	 * which triggers WARNING state for NETWORK.
+	 */
+	network_warning: number;
+	/**
+	 * The combined ingress + egress throughput in MB/s
+	 * which triggers CRITICAL state for NETWORK.
+	 */
+	network_critical: number;
 	/** Scheduled maintenance windows during which alerts will be suppressed. */
 	maintenance_windows?: MaintenanceWindow[];
 }
@@ -2372,6 +2772,8 @@ export interface StackActionState {
 	unpausing: boolean;
 	stopping: boolean;
 	destroying: boolean;
+	backing_up: boolean;
+	restoring: boolean;
 }
 
 export type GetStackActionStateResponse = StackActionState;
@@ -2480,10 +2882,38 @@ export interface StackConfig {
 	git_provider: string;
 	/**
 	 * Whether to use https to clone the repo (versus http). Default: true
-	 * 
-	 * Note. Komodo does not currently support cloning repos via ssh.
 	 */
 	git_https: boolean;
+	/**
+	 * Whether to clone the repo over ssh (`ssh://git@{provider}/{repo}`)
+	 * rather than http(s). Authenticates with the SSH key
+	 * of the git account, or the host's default ssh identity
+	 * if the account has no SSH key.
+	 */
+	git_ssh?: boolean;
+	/**
+	 * Clone with `--depth {git_depth}`, skipping older history.
+	 * Use 0 to clone the full history.
+	 */
+	git_depth?: number;
+	/**
+	 * Only check out the folders the Stack uses (`git sparse-checkout`, cone mode).
+	 * If `git_sparse_paths` is empty, uses the folders of the `run_directory`,
+	 * compose files, env files and config files.
+	 */
+	git_sparse_checkout?: boolean;
+	/**
+	 * Only check out these folders of the repo (`git sparse-checkout`, cone mode).
+	 * Files at the repo root are always checked out.
+	 */
+	git_sparse_paths?: string[];
+	/** Initialize and update the repo's submodules (recursively) after clone / pull. */
+	git_submodules?: boolean;
+	/**
+	 * Run `git lfs pull` after clone / pull to download Git LFS files.
+	 * Requires `git-lfs` to be installed on the host.
+	 */
+	git_lfs?: boolean;
 	/**
 	 * The git account used to access private repos.
 	 * Passing empty string can only clone public repos.
@@ -2515,6 +2945,27 @@ export interface StackConfig {
 	 * If its an empty string, use the default secret from the config.
 	 */
 	webhook_secret?: string;
+	/**
+	 * Only trigger the webhook when the push changes files matching these
+	 * paths, relative to the repo root. Supports wildcard (`services/api/*`)
+	 * and regex (`\^services/.*\.ya?ml$\`) patterns.
+	 * Prefix with `!` to exclude paths. Leave empty to trigger on any push.
+	 */
+	webhook_path_filters?: string[];
+	/**
+	 * Report the run status (pending / success / failure) of the commit
+	 * to the git provider's commit status api, linking to the Komodo Update.
+	 * Requires a git account with a token.
+	 * Supports Github, Gitlab and Gitea / Forgejo.
+	 */
+	commit_status?: boolean;
+	/**
+	 * Report deploys to the git provider's deployments api,
+	 * using the Stack name as the environment, linking to the Komodo Update.
+	 * Requires a git account with a token.
+	 * Supports Github (Deployments) and Gitlab (Environments).
+	 */
+	deployment_status?: boolean;
 	/**
 	 * By default, the Stack will `DeployStackIfChanged`.
 	 * If this option is enabled, will always run `DeployStack` without diffing.
@@ -2562,6 +3013,61 @@ export interface StackConfig {
 	config_files?: StackFileDependency[];
 	/** Whether to send StackStateChange alerts for this stack. */
 	send_alerts: boolean;
+	/**
+	 * The container CPU usage percentage which triggers a WARNING
+	 * `ContainerCpu` alert. Docker reports CPU usage relative to
+	 * one core, so this may go above 100. 0 disables the alert.
+	 */
+	container_cpu_warning?: number;
+	/**
+	 * The container CPU usage percentage which triggers a CRITICAL
+	 * `ContainerCpu` alert. 0 disables the CRITICAL level.
+	 */
+	container_cpu_critical?: number;
+	/**
+	 * The container memory usage percentage (of its memory limit)
+	 * which triggers a WARNING `ContainerMem` alert. 0 disables the alert.
+	 */
+	container_mem_warning?: number;
+	/**
+	 * The container memory usage percentage (of its memory limit)
+	 * which triggers a CRITICAL `ContainerMem` alert.
+	 * 0 disables the CRITICAL level.
+	 */
+	container_mem_critical?: number;
+	/**
+	 * Whether Core should archive the service containers logs,
+	 * so they remain searchable after the container is removed.
+	 * See `keep_archived_logs_for_days` in the core config.
+	 */
+	archive_logs?: boolean;
+	/**
+	 * Verify the service containers after deploy, marking the deploy failed
+	 * if any are not running (and `healthy`, if they have a Docker healthcheck)
+	 * within `health_check_timeout`.
+	 * 
+	 * Note. Only supported for Server (non-Swarm) Stacks,
+	 * and only checked for full Stack deploys.
+	 */
+	health_check?: boolean;
+	/**
+	 * The max time in seconds to wait for the containers to become healthy.
+	 * If neither the containers have a Docker healthcheck nor a
+	 * `health_check_url` is configured, the containers must
+	 * stay running for this whole duration.
+	 */
+	health_check_timeout?: number;
+	/**
+	 * Optional url which must return a 2xx response for the
+	 * deploy to be healthy. The request is sent from the Periphery host,
+	 * eg `http://localhost:8080/health`.
+	 */
+	health_check_url?: string;
+	/**
+	 * Automatically roll back to the previous deploy
+	 * if the health check fails. See `RollbackStack`.
+	 */
+	rollback_on_health_failure?: boolean;
 	/** Used with `registry_account` to login to a registry before docker compose up. */
 	registry_provider?: string;
 	/** Used with `registry_provider` to login to a registry before docker compose up. */
@@ -2738,6 +3244,11 @@ export interface StackInfo {
 	latest_hash?: string;
 	/** Latest commit message, or null */
 	latest_message?: string;
+	/**
+	 * If the Stack is a pull request preview, the id of the
+	 * template Stack which created it, or null.
+	 */
+	preview_template?: string;
 }
 
 export type Stack = Resource<StackConfig, StackInfo>;
@@ -2831,10 +3342,12 @@ export interface SystemStats {
 	mem_total_gb: number;
 	/** Breakdown of individual disks, ie their usages, sizes, and mount points */
 	disks: SingleDiskUsage[];
-	/** Network ingress usage in MB */
+	/** Network ingress usage in bytes since the last poll */
 	network_ingress_bytes?: number;
-	/** Network egress usage in MB */
+	/** Network egress usage in bytes since the last poll */
 	network_egress_bytes?: number;
+	/** Network usage by interface name (ingress, egress in bytes) */
+	network_usage_interface?: SingleNetworkInterfaceUsage[];
 	/** The rate the system stats are being polled from the system */
 	polling_rate: Timelength;
 	/** Unix timestamp in milliseconds when stats were last polled */
@@ -4951,6 +5464,33 @@ export interface ContainerListItem {
 
 export type ListAllDockerContainersResponse = ContainerListItem[];
 
+/**
+ * Restrictions on an [ApiKey].
+ * Each restriction is only applied when it is non-empty.
+ */
+export interface ApiKeyScopes {
+	/**
+	 * The allowed requests. Either a request type,
+	 * `read`, `write` or `execute`,
+	 * or a specific request name, eg `DeployStack`.
+	 */
+	requests?: string[];
+	/**
+	 * The allowed resources, by id or name.
+	 * Resources with any of the `tags` are also allowed.
+	 * When set, requests which don't target resources,
+	 * such as `ListVariables`, must be named in `requests`.
+	 */
+	resources?: ResourceTarget[];
+	/** Resources with any of these tags (id or name) are allowed. */
+	tags?: string[];
+	/**
+	 * The allowed source IPs, in CIDR notation, eg `10.0.0.0/8`.
+	 * Plain IP addresses are also accepted.
+	 */
+	ips?: string[];
+}
+
 /** An api key used to authenticate requests via request headers. */
 export interface ApiKey {
 	/** Unique key associated with secret */
@@ -4965,6 +5505,11 @@ export interface ApiKey {
 	created_at: I64;
 	/** Expiry of key, or 0 if never expires */
 	expires: I64;
+	/**
+	 * Optionally restrict what the api key can be used for.
+	 * By default, the key has all permissions of its user.
+	 */
+	scopes?: ApiKeyScopes;
 }
 
 export type ListApiKeysForServiceUserResponse = ApiKey[];
@@ -5735,6 +6280,8 @@ export interface SwarmQuerySpecifics {
 
 export type SwarmQuery = ResourceQuery<SwarmQuerySpecifics>;
 
+export type UpdateApiKeyScopesResponse = NoData;
+
 export type UpdateDockerRegistryAccountResponse = DockerRegistryAccount;
 
 export type UpdateGitProviderAccountResponse = GitProviderAccount;
@@ -5872,6 +6419,69 @@ export interface AwsBuilderConfig {
 export interface BackupCoreDatabase {
 }
 
+/** How containers are handled while their volumes are backed up. */
+export enum VolumeBackupMode {
+	/**
+	 * Stop the running containers, and start them again after.
+	 * Ensures consistent backups, at the cost of some downtime.
+	 */
+	Stop = "Stop",
+	/** Pause the running containers, and unpause them after. */
+	Pause = "Pause",
+	/**
+	 * Back up the volumes while the containers keep running.
+	 * Files being written during the backup may be inconsistent.
+	 */
+	Live = "Live",
+}
+
+/**
+ * Backs up the volumes and bind mounts of the target deployment
+ * on its server. Response: [Update].
+ * 
+ * Each volume / bind mount is archived with `tar`, and written to
+ * the Periphery `volume_backup_dir`, and `volume_backup_remote` if configured.
+ * 
+ * Note. Not supported for Swarm mode Deployments.
+ */
+export interface BackupDeploymentVolumes {
+	/** Name or id */
+	deployment: string;
+	/**
+	 * How to handle the running container during the backup.
+	 * Default: `Stop`
+	 */
+	mode?: VolumeBackupMode;
+	/** Only back up named volumes, skipping bind mounts. */
+	skip_bind_mounts?: boolean;
+}
+
+/**
+ * Backs up the volumes and bind mounts of the target stack
+ * on its server. Response: [Update].
+ * 
+ * Each volume / bind mount is archived with `tar`, and written to
+ * the Periphery `volume_backup_dir`, and `volume_backup_remote` if configured.
+ * 
+ * Note. Not supported for Swarm mode Stacks.
+ */
+export interface BackupStackVolumes {
+	/** Id or name */
+	stack: string;
+	/**
+	 * Filter to only back up the mounts of specific services.
+	 * If empty, will back up the mounts of all services.
+	 */
+	services?: string[];
+	/**
+	 * How to handle the running containers during the backup.
+	 * Default: `Stop`
+	 */
+	mode?: VolumeBackupMode;
+	/** Only back up named volumes, skipping bind mounts. */
+	skip_bind_mounts?: boolean;
+}
+
 /** Builds multiple Repos in parallel that match pattern. Response: [BatchExecutionResponse]. */
 export interface BatchBuildRepo {
 	/**
@@ -6261,6 +6871,18 @@ export interface CloneRepo {
 	repo: string;
 }
 
+/**
+ * Acknowledge the Alert at the given id.
+ * Acknowledged alerts are no longer repeated or escalated
+ * by the alerter escalation policies.
+ * Requires execute permissions on the alert target.
+ * Response: [NoData]
+ */
+export interface AcknowledgeAlert {
+	/** The id of the Alert to acknowledge. */
+	id: string;
+}
+
 /**
  * **Admin only.** Close the Alert at the given id.
  * Response: [NoData]
@@ -6710,6 +7332,8 @@ export interface CreateApiKeyForServiceUser {
 	 * Default is 0, which means no expiry.
 	 */
 	expires?: I64;
+	/** Optionally restrict what the api key can be used for. */
+	scopes?: ApiKeyScopes;
 }
 
 /** Create a build. Response: [Build]. */
@@ -7558,6 +8182,17 @@ export interface FullContainerStats {
 	networks?: Record<string, ContainerNetworkStats>;
 }
 
+/**
+ * **Admin only.** Generate a new ed25519 SSH key for a git provider account,
+ * replacing any existing key. Add the returned `ssh_public_key`
+ * as a deploy key on the git provider.
+ * Response: [GitProviderAccount].
+ */
+export interface GenerateGitProviderAccountSshKey {
+	/** The id of the git provider account. */
+	id: string;
+}
+
 /** Get a specific action. Response: [Action]. */
 export interface GetAction {
 	/** Id or name */
@@ -7906,6 +8541,8 @@ export interface SystemStatsRecord {
 	network_ingress_bytes?: number;
 	/** Total network egress in bytes */
 	network_egress_bytes?: number;
+	/** Network usage by interface name (ingress, egress in bytes) */
+	network_usage_interface?: SingleNetworkInterfaceUsage[];
 }
 
 /** Response to [GetHistoricalServerStats]. */
@@ -8483,6 +9120,24 @@ export interface LatestCommit {
 	message: string;
 }
 
+/** Login with LDAP / Active Directory credentials. */
+export interface LdapLogin {
+	/** The directory username, matched with `ldap_user_filter`. */
+	username: string;
+	/** The directory password. */
+	password: string;
+}
+
+/** Whether LDAP login is enabled on Komodo Core. */
+export interface LdapLoginOptions {
+	enabled: boolean;
+}
+
+export interface LdapLoginResponse {
+	/** The JWT to authenticate further requests. */
+	jwt: string;
+}
+
 /** List actions matching optional query. Response: [ListActionsResponse]. */
 export interface ListActions {
 	/** optional structured query to filter actions. */
@@ -8544,6 +9199,191 @@ export interface ListAlertsResponse {
 	next_page?: I64;
 }
 
+export enum LogStream {
+	Stdout = "stdout",
+	Stderr = "stderr",
+}
+
+/**
+ * A single container log line archived by Core.
+ * Collected for Deployments / Stacks with `archive_logs` enabled.
+ */
+export interface ArchivedLog {
+	/** The Mongo ID of the log line. */
+	_id?: MongoId;
+	/** Unix timestamp in milliseconds the line was logged */
+	ts: I64;
+	/** The Deployment / Stack which owns the container */
+	target: ResourceTarget;
+	/** The name of the container which logged the line */
+	container: string;
+	/** Whether the line was logged to stdout or stderr */
+	stream: LogStream;
+	/** The log line content */
+	line: string;
+}
+
+/** A container log line, as reported by Periphery. */
+export interface ContainerLogLine {
+	/** Unix timestamp in milliseconds the line was logged */
+	ts: I64;
+	/** Whether the line was logged to stdout or stderr */
+	stream: LogStream;
+	/** The log line content */
+	line: string;
+}
+
+/**
+ * Search the archived container logs of a Deployment or Stack,
+ * sorted by timestamp descending. The Deployment / Stack must have
+ * `archive_logs` enabled for logs to be archived.
+ * Response: [SearchArchivedLogsResponse].
+ * 
+ * Note. This call will fail if the user does not have
+ * `Read` + `Logs` permission on the target.
+ */
+export interface SearchArchivedLogs {
+	/** The Deployment or Stack target. Accepts id or name. */
+	target: ResourceTarget;
+	/**
+	 * Only include lines from this container.
+	 * Useful to filter to a specific Stack service.
+	 */
+	container?: string;
+	/**
+	 * Only include lines logged at or after this
+	 * unix timestamp in milliseconds.
+	 */
+	start_ts?: I64;
+	/**
+	 * Only include lines logged at or before this
+	 * unix timestamp in milliseconds.
+	 */
+	end_ts?: I64;
+	/** Only include lines matching this regex. */
+	regex?: string;
+	/** Make the regex match case insensitive. */
+	case_insensitive?: boolean;
+	/**
+	 * Retrieve older results by incrementing the page.
+	 * `page: 0` is default, and returns the most recent results.
+	 */
+	page?: U64;
+}
+
+/** Response for [SearchArchivedLogs]. */
+export interface SearchArchivedLogsResponse {
+	logs: ArchivedLog[];
+	/**
+	 * If more logs exist, the next page will be given here.
+	 * Otherwise it will be `null`
+	 */
+	next_page?: I64;
+}
+
+export enum AuditOperation {
+	/** Login attempts with any login provider */
+	Login = "Login",
+	/**
+	 * Other changes to a users own login,
+	 * eg changing password or 2FA
+	 */
+	ManageLogin = "ManageLogin",
+	/** Create a user or service user, or delete a user */
+	ManageUser = "ManageUser",
+	CreateApiKey = "CreateApiKey",
+	DeleteApiKey = "DeleteApiKey",
+	UpdateApiKey = "UpdateApiKey",
+	/** Changes to user permissions and user group membership */
+	UpdatePermission = "UpdatePermission",
+	/** Reading the value of secret variables */
+	ReadSecret = "ReadSecret",
+	/** Opening a terminal session, or executing a terminal command */
+	Terminal = "Terminal",
+}
+
+/**
+ * A security relevant event recorded by Core,
+ * such as a login, api key change or terminal session.
+ * List with [ListAuditEvents][crate::api::read::ListAuditEvents].
+ */
+export interface AuditEvent {
+	/** The Mongo ID of the audit event. */
+	_id?: MongoId;
+	/** Unix timestamp in milliseconds of the event */
+	ts: I64;
+	/** The kind of event */
+	operation: AuditOperation;
+	/**
+	 * The specific request, eg `LoginLocalUser`
+	 * or `UpdatePermissionOnTarget`
+	 */
+	request: string;
+	/** Whether the request succeeded */
+	success: boolean;
+	/**
+	 * The id of the user making the request.
+	 * Empty if unknown, eg for logins with an unknown username.
+	 */
+	user_id: string;
+	/**
+	 * The username of the user making the request,
+	 * or the username attempted for logins.
+	 */
+	username: string;
+	/** The source IP of the request */
+	ip: string;
+	/** The resource the event is about, if any */
+	target?: ResourceTarget;
+	/** The name of the target resource at the time of the event */
+	target_name?: string;
+	/** Additional details, eg the request parameters or error */
+	details?: string;
+}
+
+/**
+ * **Admin only.** List audit events matching the filters,
+ * sorted by timestamp descending.
+ * Response: [ListAuditEventsResponse].
+ */
+export interface ListAuditEvents {
+	/** Only include events by this user. Accepts id or username. */
+	user?: string;
+	/** Only include events from this source IP. */
+	ip?: string;
+	/** Only include events of this operation. */
+	operation?: AuditOperation;
+	/** Only include events about this resource. Accepts id or name. */
+	target?: ResourceTarget;
+	/** Only include successful (`true`) or failed (`false`) events. */
+	success?: boolean;
+	/**
+	 * Only include events at or after this
+	 * unix timestamp in milliseconds.
+	 */
+	start_ts?: I64;
+	/**
+	 * Only include events at or before this
+	 * unix timestamp in milliseconds.
+	 */
+	end_ts?: I64;
+	/**
+	 * Retrieve older results by incrementing the page.
+	 * `page: 0` is default, and returns the most recent results.
+	 */
+	page?: U64;
+}
+
+/** Response for [ListAuditEvents]. */
+export interface ListAuditEventsResponse {
+	events: AuditEvent[];
+	/**
+	 * If more events exist, the next page will be given here.
+	 * Otherwise it will be `null`
+	 */
+	next_page?: I64;
+}
+
 /**
  * List all docker containers on the target servers.
  * Response: [ListDockerContainersResponse].
@@ -9124,6 +9964,16 @@ export interface NtfyAlerterEndpoint {
 	email?: string;
 }
 
+/** Whether OIDC login goes through `/oidc/login` on Komodo Core. */
+export interface OidcLoginOptions {
+	enabled: boolean;
+}
+
+export interface OidcLoginResponse {
+	/** The JWT to authenticate further requests. */
+	jwt: string;
+}
+
 /** Pauses all containers on the target server. Response: [Update] */
 export interface PauseAllContainers {
 	/** Name or id */
@@ -9537,6 +10387,11 @@ export interface RepoExecutionArgs {
 	provider: string;
 	/** Use https (vs http). */
 	https: boolean;
+	/**
+	 * Use ssh (vs http(s)). The access token is then
+	 * the SSH private key, if any.
+	 */
+	ssh?: boolean;
 	/** Configure the account used to access repo (if private) */
 	account?: string;
 	/**
@@ -9555,6 +10410,17 @@ export interface RepoExecutionArgs {
 	 * Depends on the resource type.
 	 */
 	default_folder: DefaultRepoFolder;
+	/** Clone with `--depth`. 0 clones the full history. */
+	depth?: number;
+	/**
+	 * Folders to check out with `git sparse-checkout` (cone mode).
+	 * `None` checks out the whole repo.
+	 */
+	sparse_paths?: string[];
+	/** Init / update submodules recursively. */
+	submodules?: boolean;
+	/** Run `git lfs pull`. */
+	lfs?: boolean;
 }
 
 export interface RepoExecutionResponse {
@@ -9607,6 +10473,138 @@ export interface RestartStack {
 	services?: string[];
 }
 
+/**
+ * **Admin only.** Restores the Komodo Core database from a backup
+ * taken with [BackupCoreDatabase]. Response: [Update].
+ * 
+ * Documents in the backup are inserted, or overwrite the existing
+ * document with the same id. Documents not in the backup are left as is.
+ * Use the filters to restore only part of the backup, for example
+ * some deleted Stacks and their Permissions, without rolling back
+ * the Updates / Stats.
+ * 
+ * https://komo.do/docs/setup/backup
+ */
+export interface RestoreCoreDatabase {
+	/**
+	 * The backup folder to restore, eg `2025-08-01_05-04-53`.
+	 * If empty, uses the most recent backup.
+	 */
+	restore_folder?: string;
+	/**
+	 * Only restore these collections, eg `Stack` or `Permission`.
+	 * If empty, restores all collections.
+	 */
+	collections?: string[];
+	/**
+	 * Only restore the documents with these ids or resource names,
+	 * along with documents targeting them, such as their Permissions.
+	 * If empty, restores all documents.
+	 */
+	resources?: string[];
+	/**
+	 * Only log what would be inserted or overwritten,
+	 * without writing to the database.
+	 */
+	dry_run?: boolean;
+}
+
+/**
+ * Restores the volumes and bind mounts of the target deployment
+ * from a backup made with [BackupDeploymentVolumes]. Response: [Update].
+ * 
+ * The container is stopped during the restore,
+ * and the existing contents of the volumes are replaced.
+ * 
+ * Note. Not supported for Swarm mode Deployments.
+ */
+export interface RestoreDeploymentVolumes {
+	/** Name or id */
+	deployment: string;
+	/**
+	 * The backup to restore, eg `2025-08-04_05-05-53`.
+	 * If empty, will restore the most recent backup.
+	 */
+	backup?: string;
+}
+
+/**
+ * Restores the volumes and bind mounts of the target stack
+ * from a backup made with [BackupStackVolumes]. Response: [Update].
+ * 
+ * The containers are stopped during the restore,
+ * and the existing contents of the volumes are replaced.
+ * 
+ * Note. Not supported for Swarm mode Stacks.
+ */
+export interface RestoreStackVolumes {
+	/** Id or name */
+	stack: string;
+	/**
+	 * The backup to restore, eg `2025-08-04_05-05-53`.
+	 * If empty, will restore the most recent backup.
+	 */
+	backup?: string;
+	/**
+	 * Filter to only restore the mounts of specific services.
+	 * If empty, will restore the mounts of all services.
+	 */
+	services?: string[];
+}
+
+/**
+ * Rolls back the target deployment to the configuration
+ * and image of the previous successful deploy. Response: [Update].
+ * 
+ * 1. Finds the last successful deploy before the most recent deploy.
+ * 2. Restores the deployment config (including the resolved image tag / build version)
+ * recorded on that deploy.
+ * 3. Redeploys the deployment, see [Deploy].
+ * 
+ * Note. Only `Execute` permission on the deployment is required.
+ * The restored configuration is still recorded as an `UpdateDeployment`.
+ */
+export interface RollbackDeployment {
+	/** Name or id */
+	deployment: string;
+	/**
+	 * Override the default termination signal specified in the deployment.
+	 * Only used when deployment needs to be taken down before redeploy.
+	 */
+	stop_signal?: TerminationSignal;
+	/**
+	 * Override the default termination max time.
+	 * Only used when deployment needs to be taken down before redeploy.
+	 */
+	stop_time?: number;
+}
+
+/**
+ * Rolls back the target stack to the configuration
+ * of the previous successful deploy. Response: [Update].
+ * 
+ * 1. Finds the last successful full stack deploy before the most recent deploy.
+ * 2. Restores the stack config recorded on that deploy.
+ * For stacks with a configured `repo`, the config is pinned to the deployed commit hash,
+ * which remains until it is cleared from the config.
+ * 3. Redeploys the stack, see [DeployStack].
+ * 
+ * Note. Stacks using `files_on_host` or a `linked_repo` will be redeployed
+ * with the files currently on the host / in the linked repo.
+ * 
+ * Note. Only `Execute` permission on the stack is required.
+ * The restored configuration is still recorded as an `UpdateStack`.
+ */
+export interface RollbackStack {
+	/** Id or name */
+	stack: string;
+	/**
+	 * Override the default termination max time.
+	 * Only used if the stack needs to be taken down first.
+	 */
+	stop_time?: number;
+}
+
 /**
  * **Admin only.** Rotates all connected Server keys.
  * Response: [Update]. Alias: `rotate-keys`.
@@ -9631,6 +10629,19 @@ export interface RotateCoreKeys {
 	force?: boolean;
 }
 
+/**
+ * **Admin only.** Re-encrypts all secret Variables and
+ * git provider / docker registry account tokens with the
+ * current Core `encryption_key`.
+ * Response: [Update].
+ * 
+ * Values encrypted with one of the `previous_encryption_keys`
+ * are re-encrypted, and unencrypted values are encrypted.
+ * If no `encryption_key` is configured, all values are decrypted.
+ */
+export interface RotateEncryptionKey {
+}
+
 /**
  * Rotates the private / public keys for the server.
  * Response: [Update]
@@ -9925,6 +10936,8 @@ export interface ServerHealth {
 	cpu: ServerHealthState;
 	mem: ServerHealthState;
 	disks: Record<string, ServerHealthState>;
+	load?: ServerHealthState;
+	network?: ServerHealthState;
 }
 
 /**
@@ -9978,6 +10991,42 @@ export interface Sleep {
 	duration_ms?: I64;
 }
 
+/** Configuration for an Smtp (email) alerter. */
+export interface SmtpAlerterEndpoint {
+	/** The SMTP server host, eg `smtp.example.com` */
+	host: string;
+	/**
+	 * The SMTP server port.
+	 * Default: `587`
+	 */
+	port: number;
+	/**
+	 * How to secure the connection to the SMTP server.
+	 * Default: `StartTls`
+	 */
+	security?: SmtpSecurity;
+	/**
+	 * The username to authenticate with.
+	 * If empty, will not authenticate.
+	 * Supports `[[VARIABLE]]` / `[[SECRET]]` interpolation.
+	 */
+	username?: string;
+	/**
+	 * The password to authenticate with.
+	 * Supports `[[VARIABLE]]` / `[[SECRET]]` interpolation.
+	 */
+	password?: string;
+	/** The sender address, eg `Komodo <komodo@example.com>` */
+	from?: string;
+	/** The recipient addresses. */
+	to?: string[];
+	/**
+	 * Prefix added to the subject of every email.
+	 * Default: `[Komodo]`
+	 */
+	subject_prefix: string;
+}
+
 /** Starts all containers on the target server. Response: [Update] */
 export interface StartAllContainers {
 	/** Name or id */
@@ -10196,6 +11245,20 @@ export interface UpdateAlerter {
 	config: _PartialAlerterConfig;
 }
 
+/**
+ * Update the scopes of an api key.
+ * Users can update their own api keys,
+ * and admins can update the api keys of service users.
+ * Can't be called using a scoped api key.
+ * Response: [NoData].
+ */
+export interface UpdateApiKeyScopes {
+	/** The api key */
+	key: string;
+	/** The new scopes. Empty scopes remove all restrictions. */
+	scopes?: ApiKeyScopes;
+}
+
 /**
  * Update the build at the given id, and return the updated build.
  * Response: [Build].
@@ -10585,6 +11648,55 @@ export interface UrlBuilderConfig {
 	passkey?: string;
 }
 
+/** Configuration for a generic Webhook alerter. */
+export interface WebhookAlerterEndpoint {
+	/**
+	 * The http/s endpoint to send the request to.
+	 * Supports `[[VARIABLE]]` / `[[SECRET]]` interpolation.
+	 */
+	url: string;
+	/** The http method to use. Default: `POST` */
+	method?: WebhookAlerterMethod;
+	/**
+	 * Extra headers to attach to the request, one per line.
+	 * Supports `[[VARIABLE]]` / `[[SECRET]]` interpolation.
+	 * ```text
+	 * Authorization: Bearer [[OPSGENIE_TOKEN]]
+	 * X-Custom-Header: value
+	 * ```
+	 */
+	headers?: string;
+	/**
+	 * The Content-Type of the rendered body.
+	 * Default: `application/json`
+	 */
+	content_type: string;
+	/**
+	 * Template for the request body.
+	 * If empty, the full Alert serialized to JSON is sent.
+	 * 
+	 * Placeholders are written as `{{path}}`:
+	 * - `{{message}}`: The standard formatted alert message.
+	 * - `{{level}}`: The alert severity, eg `CRITICAL`.
+	 * - `{{variant}}`: The alert type, eg `ServerCpu`.
+	 * - `{{ts}}`: The unix timestamp (ms) the alert was opened.
+	 * - `{{resolved}}`: Whether the alert is resolved.
+	 * - `{{target.type}}`, `{{target.id}}`: The alert target.
+	 * - `{{data.<field>}}`: Any field on the alert data, eg `{{data.name}}`.
+	 * 
+	 * When the Content-Type contains `json`, string values are escaped
+	 * so they can be placed inside JSON string literals.
+	 */
+	body?: string;
+	/**
+	 * If provided, the rendered body will be signed with HMAC-SHA256
+	 * using this secret, and the hex encoded signature attached
+	 * as `X-Komodo-Signature: sha256=<signature>`.
+	 * Supports `[[SECRET]]` interpolation.
+	 */
+	signing_secret?: string;
+}
+
 /** Update dockerfile contents in Files on Server or Git Repo mode. Response: [Update]. */
 export interface WriteBuildFileContents {
 	/** The name or id of the target Build. */
@@ -10646,7 +11758,10 @@ export type ExecuteRequest =
 	| { type: "UnpauseStack", params: UnpauseStack }
 	| { type: "DestroyStack", params: DestroyStack }
 	| { type: "BatchDestroyStack", params: BatchDestroyStack }
+	| { type: "RollbackStack", params: RollbackStack }
 	| { type: "RunStackService", params: RunStackService }
+	| { type: "BackupStackVolumes", params: BackupStackVolumes }
+	| { type: "RestoreStackVolumes", params: RestoreStackVolumes }
 	| { type: "Deploy", params: Deploy }
 	| { type: "BatchDeploy", params: BatchDeploy }
 	| { type: "PullDeployment", params: PullDeployment }
@@ -10657,6 +11772,9 @@ export type ExecuteRequest =
 	| { type: "StopDeployment", params: StopDeployment }
 	| { type: "DestroyDeployment", params: DestroyDeployment }
 	| { type: "BatchDestroyDeployment", params: BatchDestroyDeployment }
+	| { type: "RollbackDeployment", params: RollbackDeployment }
+	| { type: "BackupDeploymentVolumes", params: BackupDeploymentVolumes }
+	| { type: "RestoreDeploymentVolumes", params: RestoreDeploymentVolumes }
 	| { type: "RunBuild", params: RunBuild }
 	| { type: "BatchRunBuild", params: BatchRunBuild }
 	| { type: "CancelBuild", params: CancelBuild }
@@ -10707,9 +11825,11 @@ export type ExecuteRequest =
 	| { type: "RemoveSwarmSecrets", params: RemoveSwarmSecrets }
 	| { type: "ClearRepoCache", params: ClearRepoCache }
 	| { type: "BackupCoreDatabase", params: BackupCoreDatabase }
+	| { type: "RestoreCoreDatabase", params: RestoreCoreDatabase }
 	| { type: "GlobalAutoUpdate", params: GlobalAutoUpdate }
 	| { type: "RotateAllServerKeys", params: RotateAllServerKeys }
-	| { type: "RotateCoreKeys", params: RotateCoreKeys };
+	| { type: "RotateCoreKeys", params: RotateCoreKeys }
+	| { type: "RotateEncryptionKey", params: RotateEncryptionKey };
 
 /**
  * One representative IANA zone for each distinct base UTC offset in the tz database.
@@ -10930,6 +12050,8 @@ export type ReadRequest =
 	| { type: "ListUpdates", params: ListUpdates }
 	| { type: "ListAlerts", params: ListAlerts }
 	| { type: "GetAlert", params: GetAlert }
+	| { type: "SearchArchivedLogs", params: SearchArchivedLogs }
+	| { type: "ListAuditEvents", params: ListAuditEvents }
 	| { type: "GetVariable", params: GetVariable }
 	| { type: "ListVariables", params: ListVariables }
 	| { type: "GetGitProviderAccount", params: GetGitProviderAccount }
@@ -11081,6 +12203,7 @@ export type WriteRequest =
 	| { type: "UpdateServiceUserDescription", params: UpdateServiceUserDescription }
 	| { type: "CreateApiKeyForServiceUser", params: CreateApiKeyForServiceUser }
 	| { type: "DeleteApiKeyForServiceUser", params: DeleteApiKeyForServiceUser }
+	| { type: "UpdateApiKeyScopes", params: UpdateApiKeyScopes }
 	| { type: "CreateUserGroup", params: CreateUserGroup }
 	| { type: "RenameUserGroup", params: RenameUserGroup }
 	| { type: "DeleteUserGroup", params: DeleteUserGroup }
@@ -11104,10 +12227,12 @@ export type WriteRequest =
 	| { type: "CreateGitProviderAccount", params: CreateGitProviderAccount }
 	| { type: "UpdateGitProviderAccount", params: UpdateGitProviderAccount }
 	| { type: "DeleteGitProviderAccount", params: DeleteGitProviderAccount }
+	| { type: "GenerateGitProviderAccountSshKey", params: GenerateGitProviderAccountSshKey }
 	| { type: "CreateDockerRegistryAccount", params: CreateDockerRegistryAccount }
 	| { type: "UpdateDockerRegistryAccount", params: UpdateDockerRegistryAccount }
 	| { type: "DeleteDockerRegistryAccount", params: DeleteDockerRegistryAccount }
-	| { type: "CloseAlert", params: CloseAlert };
+	| { type: "CloseAlert", params: CloseAlert }
+	| { type: "AcknowledgeAlert", params: AcknowledgeAlert };
 
 export type WsLoginMessage = 
 	| { type: "Jwt", params: {
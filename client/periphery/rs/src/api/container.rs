@@ -1,10 +1,11 @@
 use komodo_client::entities::{
-  SearchCombinator, TerminationSignal,
+  I64, SearchCombinator, TerminationSignal,
   deployment::Deployment,
   docker::{
     container::{Container, ContainerStats},
     stats::FullContainerStats,
   },
+  log_archive::ContainerLogLine,
   update::Log,
 };
 use mogh_resolver::Resolve;
@@ -56,6 +57,44 @@ pub struct GetContainerLogSearch {
 
 //
 
+/// Get the container log lines between the given timestamps,
+/// for Core to archive.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Vec<ContainerLogLine>)]
+#[error(anyhow::Error)]
+pub struct GetContainerLogSince {
+  pub name: String,
+  /// Unix timestamp in milliseconds.
+  /// Only lines logged after this time are returned.
+  pub since: I64,
+  /// Unix timestamp in milliseconds.
+  /// Only lines logged before this time are returned.
+  /// Bounds how much of the log Periphery reads per request.
+  #[serde(default)]
+  pub until: Option<I64>,
+  /// The max number of lines to return.
+  /// If there are more, the oldest lines are returned.
+  #[serde(default = "default_since_limit")]
+  pub limit: u64,
+}
+
+fn default_since_limit() -> u64 {
+  5000
+}
+
+//
+
+/// Sends a GET request to the url from the Periphery host,
+/// returning a successful Log if the response status is 2xx.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Log)]
+#[error(anyhow::Error)]
+pub struct ProbeHttp {
+  pub url: String,
+}
+
+//
+
 #[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
 #[response(ContainerStats)]
 #[error(anyhow::Error)]
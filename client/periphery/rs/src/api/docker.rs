@@ -1,4 +1,5 @@
 use komodo_client::entities::{
+  VolumeBackupMode,
   docker::{
     image::{Image, ImageHistoryResponseItem},
     network::Network,
@@ -146,3 +147,40 @@ pub struct DeleteVolume {
 #[response(Log)]
 #[error(anyhow::Error)]
 pub struct PruneVolumes {}
+
+//
+
+/// Archive the volumes and bind mounts of the containers
+/// into `volume_backup_dir/{name}/{timestamp}`,
+/// and upload them to `volume_backup_remote` if configured.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Vec<Log>)]
+#[error(anyhow::Error)]
+pub struct BackupContainerVolumes {
+  /// The backup folder name, eg. `stacks/my-stack`
+  pub name: String,
+  /// The containers to back up the mounts of.
+  pub containers: Vec<String>,
+  /// How to handle the running containers during the backup.
+  pub mode: VolumeBackupMode,
+  /// Only back up named volumes.
+  pub skip_bind_mounts: bool,
+}
+
+//
+
+/// Restore the volumes and bind mounts of the containers
+/// from a backup made with [BackupContainerVolumes].
+/// The containers are stopped during the restore.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Vec<Log>)]
+#[error(anyhow::Error)]
+pub struct RestoreContainerVolumes {
+  /// The backup folder name, eg. `stacks/my-stack`
+  pub name: String,
+  /// The backup timestamp to restore.
+  /// If not provided, restores the most recent backup.
+  pub backup: Option<String>,
+  /// The containers to restore the mounts of.
+  pub containers: Vec<String>,
+}
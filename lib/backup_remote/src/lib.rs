@@ -0,0 +1,382 @@
+//! Ship backups to a remote target, and fetch them back for restore.
+//! Supports S3-compatible object storage, SFTP and WebDAV.
+//!
+//! Each backup is a folder of files named by the time it was taken:
+//!
+//! `<url>/[<scope>/]<2025-08-04_05-05-53>/<file>[.enc]`
+
+use std::path::{Path, PathBuf};
+
+use aes_gcm::{Aes256Gcm, aead::KeyInit};
+use anyhow::{Context, anyhow};
+use chrono::{Local, NaiveDateTime, TimeDelta};
+use data_encoding::BASE64;
+use komodo_client::entities::config::BackupRemoteConfig;
+use tracing::{info, warn};
+
+mod s3;
+mod sftp;
+mod stream;
+mod webdav;
+
+use stream::{DownloadSink, UploadSource};
+
+/// The format of backup folder names.
+/// Entries on the remote not matching it are ignored.
+pub const BACKUP_FOLDER_FORMAT: &str = "%Y-%m-%d_%H-%M-%S";
+const ENCRYPTED_EXTENSION: &str = ".enc";
+const KEY_LEN: usize = 32;
+
+enum Target {
+  S3(s3::S3Target),
+  Sftp(sftp::SftpTarget),
+  WebDav(webdav::WebDavTarget),
+}
+
+pub struct BackupRemote {
+  url: String,
+  /// Empty, or ends with `/`
+  scope: String,
+  target: Target,
+  cipher: Option<Aes256Gcm>,
+  max_backups: usize,
+  max_backup_age_days: u16,
+}
+
+impl BackupRemote {
+  /// Connects to the remote target configured by `url`.
+  pub async fn connect(
+    config: &BackupRemoteConfig,
+  ) -> anyhow::Result<BackupRemote> {
+    let url = config.url.trim_end_matches('/');
+    let (scheme, path) =
+      url.split_once("://").with_context(|| {
+        format!("Backup remote url '{url}' is missing scheme")
+      })?;
+    let target = match scheme {
+      "s3" => Target::S3(s3::S3Target::new(config, path).await?),
+      "sftp" => {
+        Target::Sftp(sftp::SftpTarget::connect(config, path).await?)
+      }
+      "webdav" => Target::WebDav(webdav::WebDavTarget::new(
+        config, "http", path,
+      )?),
+      "webdavs" => Target::WebDav(webdav::WebDavTarget::new(
+        config, "https", path,
+      )?),
+      scheme => {
+        return Err(anyhow!(
+          "Unsupported backup remote scheme '{scheme}'. Use one of 's3', 'sftp', 'webdav', 'webdavs'."
+        ));
+      }
+    };
+    let cipher = if config.encryption_key.is_empty() {
+      None
+    } else {
+      Some(
+        load_cipher(&config.encryption_key)
+          .context("Invalid backup remote 'encryption_key'")?,
+      )
+    };
+    Ok(BackupRemote {
+      url: url.to_string(),
+      scope: String::new(),
+      target,
+      cipher,
+      max_backups: config.max_backups as usize,
+      max_backup_age_days: config.max_backup_age_days,
+    })
+  }
+
+  /// Keep backups in the `scope` sub folder of the remote,
+  /// so multiple backup sources can share the same remote url.
+  pub fn scoped(mut self, scope: &str) -> BackupRemote {
+    let scope = scope.trim_matches('/');
+    self.scope = if scope.is_empty() {
+      String::new()
+    } else {
+      format!("{scope}/")
+    };
+    self
+  }
+
+  /// Uploads the files into the `backup` folder on the remote.
+  /// Files which don't exist are skipped.
+  pub async fn upload(
+    &self,
+    backup: &str,
+    files: Vec<PathBuf>,
+  ) -> anyhow::Result<()> {
+    info!("Uploading backup {backup} to {}...", self.url);
+
+    let folder = format!("{}{backup}", self.scope);
+    self.target.create_folder(&folder).await?;
+
+    for path in files {
+      let Some(file_name) =
+        path.file_name().and_then(|name| name.to_str())
+      else {
+        continue;
+      };
+      let source =
+        match UploadSource::open(&path, self.cipher.as_ref()).await {
+          Ok(source) => source,
+          Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            continue;
+          }
+          Err(e) => {
+            return Err(anyhow::Error::from(e).context(format!(
+              "Failed to read backup file {path:?}"
+            )));
+          }
+        };
+      let file_name = match &self.cipher {
+        Some(_) => format!("{file_name}{ENCRYPTED_EXTENSION}"),
+        None => file_name.to_string(),
+      };
+      self
+        .target
+        .put_file(&format!("{folder}/{file_name}"), source)
+        .await
+        .with_context(|| {
+          format!("Failed to upload {backup}/{file_name}")
+        })?;
+    }
+
+    info!("Finished uploading backup {backup} ✅");
+
+    Ok(())
+  }
+
+  /// Downloads the files of a backup into `into/<backup>`.
+  /// If `backup` is not provided, downloads the most recent backup.
+  ///
+  /// Returns the downloaded backup folder name.
+  pub async fn download(
+    &self,
+    backup: Option<&str>,
+    into: &Path,
+  ) -> anyhow::Result<PathBuf> {
+    let backup = match backup {
+      Some(backup) => backup.to_string(),
+      None => {
+        self.list_backups().await?.pop().with_context(|| {
+          format!("No backups found on {}", self.url)
+        })?
+      }
+    };
+
+    info!("Downloading backup {backup} from {}...", self.url);
+
+    let backup_folder = into.join(&backup);
+    tokio::fs::create_dir_all(&backup_folder)
+      .await
+      .with_context(|| {
+        format!("Failed to create download folder {backup_folder:?}")
+      })?;
+
+    let folder = format!("{}{backup}", self.scope);
+    let files = self.target.list_files(&folder).await?;
+    if files.is_empty() {
+      return Err(anyhow!(
+        "Backup {backup} not found on {}",
+        self.url
+      ));
+    }
+
+    for file_name in files {
+      let (name, cipher) = match file_name
+        .strip_suffix(ENCRYPTED_EXTENSION)
+      {
+        Some(name) => {
+          let cipher = self.cipher.clone().with_context(|| {
+            format!(
+              "{backup}/{file_name} is encrypted, but no backup remote 'encryption_key' is configured"
+            )
+          })?;
+          (name, Some(cipher))
+        }
+        None => (file_name.as_str(), None),
+      };
+      let mut sink =
+        DownloadSink::create(backup_folder.join(name), cipher)
+          .await?;
+      self
+        .target
+        .get_file(&format!("{folder}/{file_name}"), &mut sink)
+        .await
+        .with_context(|| {
+          format!("Failed to download {backup}/{file_name}")
+        })?;
+      sink.finish().await.with_context(|| {
+        format!("Failed to download {backup}/{file_name}")
+      })?;
+    }
+
+    info!("Finished downloading backup {backup} ✅");
+
+    Ok(PathBuf::from(backup))
+  }
+
+  /// Deletes backups on the remote past `max_backups` or `max_backup_age_days`.
+  /// The most recent backup is always kept.
+  pub async fn prune(&self) -> anyhow::Result<()> {
+    if self.max_backups == 0 && self.max_backup_age_days == 0 {
+      return Ok(());
+    }
+
+    // Ordered from oldest -> newest
+    let mut backups = self.list_backups().await?;
+    // Never delete the most recent backup
+    backups.pop();
+
+    let mut to_delete = Vec::new();
+    if self.max_backups > 0 {
+      let excess =
+        (backups.len() + 1).saturating_sub(self.max_backups);
+      to_delete.extend(backups.drain(..excess));
+    }
+    if self.max_backup_age_days > 0 {
+      let cutoff = Local::now().naive_local()
+        - TimeDelta::days(self.max_backup_age_days as i64);
+      let expired = backups
+        .iter()
+        .take_while(|backup| {
+          NaiveDateTime::parse_from_str(backup, BACKUP_FOLDER_FORMAT)
+            .map(|taken| taken < cutoff)
+            .unwrap_or_default()
+        })
+        .count();
+      to_delete.extend(backups.drain(..expired));
+    }
+
+    if to_delete.is_empty() {
+      info!("No remote backups to prune");
+      return Ok(());
+    }
+
+    info!("Pruning old remote backups: {to_delete:?}");
+
+    for backup in to_delete {
+      if let Err(e) = self
+        .target
+        .delete_folder(&format!("{}{backup}", self.scope))
+        .await
+        .with_context(|| {
+          format!("Failed to delete remote backup {backup}")
+        })
+      {
+        warn!("{e:#}");
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Backup folder names on the remote, ordered from oldest -> newest.
+  /// Entries not named like a backup folder are ignored.
+  pub async fn list_backups(&self) -> anyhow::Result<Vec<String>> {
+    let mut backups = self
+      .target
+      .list_folders(&self.scope)
+      .await
+      .with_context(|| {
+        format!("Failed to list backups on {}", self.url)
+      })?
+      .into_iter()
+      .filter(|name| {
+        NaiveDateTime::parse_from_str(name, BACKUP_FOLDER_FORMAT)
+          .is_ok()
+      })
+      .collect::<Vec<_>>();
+    backups.sort();
+    Ok(backups)
+  }
+}
+
+impl Target {
+  async fn create_folder(&self, folder: &str) -> anyhow::Result<()> {
+    match self {
+      // S3 has no folders
+      Target::S3(_) => Ok(()),
+      Target::Sftp(target) => target.create_folder(folder).await,
+      Target::WebDav(target) => target.create_folder(folder).await,
+    }
+  }
+
+  async fn put_file(
+    &self,
+    key: &str,
+    source: UploadSource,
+  ) -> anyhow::Result<()> {
+    match self {
+      Target::S3(target) => target.put_file(key, source).await,
+      Target::Sftp(target) => target.put_file(key, source).await,
+      Target::WebDav(target) => target.put_file(key, source).await,
+    }
+  }
+
+  async fn get_file(
+    &self,
+    key: &str,
+    sink: &mut DownloadSink,
+  ) -> anyhow::Result<()> {
+    match self {
+      Target::S3(target) => target.get_file(key, sink).await,
+      Target::Sftp(target) => target.get_file(key, sink).await,
+      Target::WebDav(target) => target.get_file(key, sink).await,
+    }
+  }
+
+  async fn list_folders(
+    &self,
+    parent: &str,
+  ) -> anyhow::Result<Vec<String>> {
+    match self {
+      Target::S3(target) => target.list_folders(parent).await,
+      Target::Sftp(target) => target.list_folders(parent).await,
+      Target::WebDav(target) => target.list_folders(parent).await,
+    }
+  }
+
+  async fn list_files(
+    &self,
+    folder: &str,
+  ) -> anyhow::Result<Vec<String>> {
+    match self {
+      Target::S3(target) => target.list_files(folder).await,
+      Target::Sftp(target) => target.list_files(folder).await,
+      Target::WebDav(target) => target.list_files(folder).await,
+    }
+  }
+
+  async fn delete_folder(&self, folder: &str) -> anyhow::Result<()> {
+    match self {
+      Target::S3(target) => target.delete_folder(folder).await,
+      Target::Sftp(target) => target.delete_folder(folder).await,
+      Target::WebDav(target) => target.delete_folder(folder).await,
+    }
+  }
+}
+
+/// Loads a base64 encoded key, or `file:/path/to/key`.
+fn load_cipher(spec: &str) -> anyhow::Result<Aes256Gcm> {
+  let encoded = if let Some(path) = spec.strip_prefix("file:") {
+    std::fs::read_to_string(path).with_context(|| {
+      format!("Failed to read encryption key from {path}")
+    })?
+  } else {
+    spec.to_string()
+  };
+  let key = BASE64
+    .decode(encoded.trim().as_bytes())
+    .context("Encryption key is not valid base64")?;
+  if key.len() != KEY_LEN {
+    return Err(anyhow!(
+      "Encryption key must be {KEY_LEN} bytes, got {}",
+      key.len()
+    ));
+  }
+  Aes256Gcm::new_from_slice(&key)
+    .context("Failed to initialize encryption key")
+}
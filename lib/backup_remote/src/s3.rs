@@ -0,0 +1,303 @@
+use anyhow::Context;
+use aws_config::{BehaviorVersion, Region};
+use aws_credential_types::Credentials;
+use aws_sdk_s3::{
+  Client,
+  primitives::ByteStream,
+  types::{
+    CompletedMultipartUpload, CompletedPart, Delete, ObjectIdentifier,
+  },
+};
+use komodo_client::entities::config::BackupRemoteConfig;
+
+use crate::stream::{DownloadSink, UploadSource};
+
+/// The size of multipart upload parts.
+/// S3 requires all parts but the last to be at least 5 MiB.
+const PART_SIZE: usize = 8 * 1024 * 1024;
+
+pub struct S3Target {
+  client: Client,
+  bucket: String,
+  /// Empty, or ends with `/`
+  prefix: String,
+}
+
+impl S3Target {
+  /// `path`: `bucket/optional/prefix`
+  pub async fn new(
+    config: &BackupRemoteConfig,
+    path: &str,
+  ) -> anyhow::Result<S3Target> {
+    let (bucket, prefix) = path.split_once('/').unwrap_or((path, ""));
+    if bucket.is_empty() {
+      return Err(anyhow::anyhow!(
+        "S3 backup remote url is missing bucket"
+      ));
+    }
+    let region = if config.s3_region.is_empty() {
+      String::from("us-east-1")
+    } else {
+      config.s3_region.clone()
+    };
+    let mut loader = aws_config::defaults(BehaviorVersion::latest())
+      .region(Region::new(region));
+    // Otherwise uses the default AWS credential chain
+    if !config.s3_access_key_id.is_empty() {
+      loader = loader.credentials_provider(Credentials::new(
+        &config.s3_access_key_id,
+        &config.s3_secret_access_key,
+        None,
+        None,
+        "komodo-backup-remote",
+      ));
+    }
+    let sdk_config = loader.load().await;
+    let mut builder = aws_sdk_s3::config::Builder::from(&sdk_config)
+      .force_path_style(config.s3_path_style);
+    if !config.s3_endpoint.is_empty() {
+      builder = builder.endpoint_url(&config.s3_endpoint);
+    }
+    let prefix = prefix.trim_matches('/');
+    Ok(S3Target {
+      client: Client::from_conf(builder.build()),
+      bucket: bucket.to_string(),
+      prefix: if prefix.is_empty() {
+        String::new()
+      } else {
+        format!("{prefix}/")
+      },
+    })
+  }
+
+  /// Files up to `PART_SIZE` are uploaded in a single request,
+  /// larger files with a multipart upload.
+  pub async fn put_file(
+    &self,
+    key: &str,
+    mut source: UploadSource,
+  ) -> anyhow::Result<()> {
+    let key = format!("{}{key}", self.prefix);
+    let part = next_part(&mut source).await?;
+
+    if source.is_done() {
+      self
+        .client
+        .put_object()
+        .bucket(&self.bucket)
+        .key(&key)
+        .body(ByteStream::from(part))
+        .send()
+        .await
+        .context("Failed to put object")?;
+      return Ok(());
+    }
+
+    let upload_id = self
+      .client
+      .create_multipart_upload()
+      .bucket(&self.bucket)
+      .key(&key)
+      .send()
+      .await
+      .context("Failed to create multipart upload")?
+      .upload_id
+      .context("No upload id in create multipart upload response")?;
+
+    let res =
+      self.upload_parts(&key, &upload_id, part, &mut source).await;
+
+    if res.is_err() {
+      // Don't leave the uploaded parts stored on the bucket.
+      let _ = self
+        .client
+        .abort_multipart_upload()
+        .bucket(&self.bucket)
+        .key(&key)
+        .upload_id(&upload_id)
+        .send()
+        .await;
+    }
+
+    res
+  }
+
+  async fn upload_parts(
+    &self,
+    key: &str,
+    upload_id: &str,
+    mut part: Vec<u8>,
+    source: &mut UploadSource,
+  ) -> anyhow::Result<()> {
+    let mut parts = Vec::new();
+    while !part.is_empty() {
+      let part_number = parts.len() as i32 + 1;
+      let e_tag = self
+        .client
+        .upload_part()
+        .bucket(&self.bucket)
+        .key(key)
+        .upload_id(upload_id)
+        .part_number(part_number)
+        .body(ByteStream::from(part))
+        .send()
+        .await
+        .with_context(|| {
+          format!("Failed to upload part {part_number}")
+        })?
+        .e_tag;
+      parts.push(
+        CompletedPart::builder()
+          .part_number(part_number)
+          .set_e_tag(e_tag)
+          .build(),
+      );
+      part = next_part(source).await?;
+    }
+    self
+      .client
+      .complete_multipart_upload()
+      .bucket(&self.bucket)
+      .key(key)
+      .upload_id(upload_id)
+      .multipart_upload(
+        CompletedMultipartUpload::builder()
+          .set_parts(Some(parts))
+          .build(),
+      )
+      .send()
+      .await
+      .context("Failed to complete multipart upload")?;
+    Ok(())
+  }
+
+  pub async fn get_file(
+    &self,
+    key: &str,
+    sink: &mut DownloadSink,
+  ) -> anyhow::Result<()> {
+    let mut body = self
+      .client
+      .get_object()
+      .bucket(&self.bucket)
+      .key(format!("{}{key}", self.prefix))
+      .send()
+      .await
+      .context("Failed to get object")?
+      .body;
+    while let Some(bytes) = body
+      .try_next()
+      .await
+      .context("Failed to read object body")?
+    {
+      sink.write(&bytes).await?;
+    }
+    Ok(())
+  }
+
+  /// `parent`: Empty, or ends with `/`
+  pub async fn list_folders(
+    &self,
+    parent: &str,
+  ) -> anyhow::Result<Vec<String>> {
+    let parent_prefix = format!("{}{parent}", self.prefix);
+    let mut folders = Vec::new();
+    let mut pages = self
+      .client
+      .list_objects_v2()
+      .bucket(&self.bucket)
+      .prefix(&parent_prefix)
+      .delimiter("/")
+      .into_paginator()
+      .send();
+    while let Some(page) = pages.next().await {
+      let page = page.context("Failed to list objects")?;
+      folders.extend(page.common_prefixes().iter().filter_map(
+        |prefix| {
+          prefix
+            .prefix()?
+            .strip_prefix(&parent_prefix)?
+            .strip_suffix('/')
+            .map(str::to_string)
+        },
+      ));
+    }
+    Ok(folders)
+  }
+
+  pub async fn list_files(
+    &self,
+    folder: &str,
+  ) -> anyhow::Result<Vec<String>> {
+    let folder_prefix = format!("{}{folder}/", self.prefix);
+    let mut files = Vec::new();
+    let mut pages = self
+      .client
+      .list_objects_v2()
+      .bucket(&self.bucket)
+      .prefix(&folder_prefix)
+      .into_paginator()
+      .send();
+    while let Some(page) = pages.next().await {
+      let page = page.context("Failed to list objects")?;
+      files.extend(page.contents().iter().filter_map(|object| {
+        object
+          .key()?
+          .strip_prefix(&folder_prefix)
+          .map(str::to_string)
+      }));
+    }
+    Ok(files)
+  }
+
+  pub async fn delete_folder(
+    &self,
+    folder: &str,
+  ) -> anyhow::Result<()> {
+    let folder_prefix = format!("{}{folder}/", self.prefix);
+    let objects = self
+      .list_files(folder)
+      .await?
+      .into_iter()
+      .map(|file| {
+        ObjectIdentifier::builder()
+          .key(format!("{folder_prefix}{file}"))
+          .build()
+          .context("Failed to build object identifier")
+      })
+      .collect::<anyhow::Result<Vec<_>>>()?;
+    if objects.is_empty() {
+      return Ok(());
+    }
+    // Backups have one file per collection,
+    // well under the 1000 object limit per request.
+    self
+      .client
+      .delete_objects()
+      .bucket(&self.bucket)
+      .delete(
+        Delete::builder()
+          .set_objects(Some(objects))
+          .build()
+          .context("Failed to build delete request")?,
+      )
+      .send()
+      .await
+      .context("Failed to delete objects")?;
+    Ok(())
+  }
+}
+
+/// Reads chunks from the source until the part is full,
+/// or the source is done.
+async fn next_part(
+  source: &mut UploadSource,
+) -> anyhow::Result<Vec<u8>> {
+  let mut part = Vec::new();
+  while part.len() < PART_SIZE
+    && let Some(chunk) = source.next_chunk().await?
+  {
+    part.extend(chunk);
+  }
+  Ok(part)
+}
@@ -0,0 +1,294 @@
+use std::{path::PathBuf, sync::Arc};
+
+use anyhow::{Context, anyhow};
+use komodo_client::entities::config::BackupRemoteConfig;
+use russh::{
+  client,
+  keys::{
+    HashAlg, PrivateKeyWithHashAlg, check_known_hosts_path,
+    learn_known_hosts_path, load_secret_key, ssh_key::PublicKey,
+  },
+};
+use russh_sftp::client::SftpSession;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tracing::warn;
+
+use crate::stream::{CHUNK_SIZE, DownloadSink, UploadSource};
+
+pub struct SftpTarget {
+  sftp: SftpSession,
+  /// Kept alive for the lifetime of the sftp session.
+  _session: client::Handle<KnownHosts>,
+  /// Ends with `/`
+  root: String,
+}
+
+impl SftpTarget {
+  /// `path`: `host[:port]/path`
+  pub async fn connect(
+    config: &BackupRemoteConfig,
+    path: &str,
+  ) -> anyhow::Result<SftpTarget> {
+    let (address, root) = path.split_once('/').unwrap_or((path, ""));
+    let (host, port) = match address.rsplit_once(':') {
+      Some((host, port)) => {
+        (host, port.parse::<u16>().context("Invalid SFTP port")?)
+      }
+      None => (address, 22),
+    };
+    if config.username.is_empty() {
+      return Err(anyhow!("SFTP backup remote requires 'username'"));
+    }
+    if config.sftp_known_hosts.is_empty() {
+      return Err(anyhow!(
+        "SFTP backup remote requires 'sftp_known_hosts' to verify the server"
+      ));
+    }
+
+    let handler = KnownHosts {
+      host: host.to_string(),
+      port,
+      known_hosts: PathBuf::from(&config.sftp_known_hosts),
+    };
+    let mut session = client::connect(
+      Arc::new(client::Config::default()),
+      (host, port),
+      handler,
+    )
+    .await
+    .with_context(|| format!("Failed to connect to {host}:{port}"))?;
+
+    let auth = if config.sftp_private_key.is_empty() {
+      session
+        .authenticate_password(&config.username, &config.password)
+        .await
+        .context("Failed to authenticate with password")?
+    } else {
+      let key = load_secret_key(&config.sftp_private_key, None)
+        .with_context(|| {
+          format!(
+            "Failed to load SFTP private key from {}",
+            config.sftp_private_key
+          )
+        })?;
+      let hash_alg = session
+        .best_supported_rsa_hash()
+        .await
+        .context("Failed to negotiate key hash algorithm")?
+        .flatten();
+      session
+        .authenticate_publickey(
+          &config.username,
+          PrivateKeyWithHashAlg::new(Arc::new(key), hash_alg),
+        )
+        .await
+        .context("Failed to authenticate with private key")?
+    };
+    if !auth.success() {
+      return Err(anyhow!(
+        "SFTP authentication failed for user {}",
+        config.username
+      ));
+    }
+
+    let channel = session
+      .channel_open_session()
+      .await
+      .context("Failed to open SSH channel")?;
+    channel
+      .request_subsystem(true, "sftp")
+      .await
+      .context("Failed to request sftp subsystem")?;
+    let sftp = SftpSession::new(channel.into_stream())
+      .await
+      .context("Failed to start SFTP session")?;
+
+    let root = root.trim_end_matches('/');
+    Ok(SftpTarget {
+      sftp,
+      _session: session,
+      root: format!("/{root}/").replace("//", "/"),
+    })
+  }
+
+  pub async fn create_folder(
+    &self,
+    folder: &str,
+  ) -> anyhow::Result<()> {
+    // Create any missing parent directories as well
+    let mut path = self.root.trim_end_matches('/').to_string();
+    for segment in folder.split('/').filter(|s| !s.is_empty()) {
+      path.push('/');
+      path.push_str(segment);
+      if self.sftp.try_exists(&path).await.unwrap_or_default() {
+        continue;
+      }
+      self.sftp.create_dir(&path).await.with_context(|| {
+        format!("Failed to create directory {path}")
+      })?;
+    }
+    Ok(())
+  }
+
+  pub async fn put_file(
+    &self,
+    key: &str,
+    mut source: UploadSource,
+  ) -> anyhow::Result<()> {
+    let path = format!("{}{key}", self.root);
+    let mut file = self
+      .sftp
+      .create(&path)
+      .await
+      .with_context(|| format!("Failed to create file {path}"))?;
+    while let Some(chunk) = source.next_chunk().await? {
+      file
+        .write_all(&chunk)
+        .await
+        .with_context(|| format!("Failed to write file {path}"))?;
+    }
+    file
+      .shutdown()
+      .await
+      .with_context(|| format!("Failed to close file {path}"))
+  }
+
+  pub async fn get_file(
+    &self,
+    key: &str,
+    sink: &mut DownloadSink,
+  ) -> anyhow::Result<()> {
+    let path = format!("{}{key}", self.root);
+    let mut file = self
+      .sftp
+      .open(&path)
+      .await
+      .with_context(|| format!("Failed to open file {path}"))?;
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    loop {
+      let n = file
+        .read(&mut buffer)
+        .await
+        .with_context(|| format!("Failed to read file {path}"))?;
+      if n == 0 {
+        return Ok(());
+      }
+      sink.write(&buffer[..n]).await?;
+    }
+  }
+
+  /// `parent`: Empty, or ends with `/`
+  pub async fn list_folders(
+    &self,
+    parent: &str,
+  ) -> anyhow::Result<Vec<String>> {
+    let path = format!("{}{parent}", self.root);
+    // Nothing uploaded yet
+    if !self.sftp.try_exists(&path).await.unwrap_or_default() {
+      return Ok(Vec::new());
+    }
+    let folders = self
+      .sftp
+      .read_dir(&path)
+      .await
+      .with_context(|| format!("Failed to read directory {path}"))?
+      .filter(|entry| entry.file_type().is_dir())
+      .map(|entry| entry.file_name())
+      .collect();
+    Ok(folders)
+  }
+
+  pub async fn list_files(
+    &self,
+    folder: &str,
+  ) -> anyhow::Result<Vec<String>> {
+    let path = format!("{}{folder}", self.root);
+    let files = self
+      .sftp
+      .read_dir(&path)
+      .await
+      .with_context(|| format!("Failed to read directory {path}"))?
+      .filter(|entry| entry.file_type().is_file())
+      .map(|entry| entry.file_name())
+      .collect();
+    Ok(files)
+  }
+
+  pub async fn delete_folder(
+    &self,
+    folder: &str,
+  ) -> anyhow::Result<()> {
+    let path = format!("{}{folder}", self.root);
+    for file in self.list_files(folder).await? {
+      self
+        .sftp
+        .remove_file(format!("{path}/{file}"))
+        .await
+        .with_context(|| format!("Failed to delete {path}/{file}"))?;
+    }
+    self
+      .sftp
+      .remove_dir(&path)
+      .await
+      .with_context(|| format!("Failed to delete directory {path}"))
+  }
+}
+
+pub struct KnownHosts {
+  host: String,
+  port: u16,
+  known_hosts: PathBuf,
+}
+
+impl client::Handler for KnownHosts {
+  type Error = anyhow::Error;
+
+  /// Verifies the server key against the known hosts file.
+  /// Hosts not in the file yet are trusted on first use,
+  /// and their key is added to the file.
+  async fn check_server_key(
+    &mut self,
+    server_public_key: &PublicKey,
+  ) -> Result<bool, Self::Error> {
+    let KnownHosts {
+      host,
+      port,
+      known_hosts,
+    } = &*self;
+    // Errors if the host is known with a different key.
+    let known = known_hosts.exists()
+      && check_known_hosts_path(
+        host,
+        *port,
+        server_public_key,
+        known_hosts,
+      )
+      .with_context(|| {
+        format!(
+          "Failed to verify server key for {host} against {known_hosts:?}"
+        )
+      })?;
+    if known {
+      return Ok(true);
+    }
+    if let Some(parent) = known_hosts.parent() {
+      std::fs::create_dir_all(parent).with_context(|| {
+        format!("Failed to create directory {parent:?}")
+      })?;
+    }
+    learn_known_hosts_path(
+      host,
+      *port,
+      server_public_key,
+      known_hosts,
+    )
+    .with_context(|| {
+      format!("Failed to add {host} to {known_hosts:?}")
+    })?;
+    warn!(
+      "SFTP host {host}:{port} was not in {known_hosts:?}, TRUSTING ON FIRST USE and adding key {}. Verify this fingerprint with the server, it will be required to match from now on.",
+      server_public_key.fingerprint(HashAlg::Sha256)
+    );
+    Ok(true)
+  }
+}
@@ -0,0 +1,242 @@
+//! Streams backup files to and from the remote in chunks,
+//! so large files are never fully loaded into memory.
+//!
+//! Encrypted files are split into chunks which are sealed
+//! separately with AES-256-GCM:
+//!
+//! `<nonce prefix> || <sealed chunk> || <sealed chunk> ...`
+//!
+//! Each chunk nonce is `<nonce prefix> || <chunk index> || <last flag>`,
+//! so chunks can't be reordered, dropped or truncated undetected.
+
+use std::path::{Path, PathBuf};
+
+use aes_gcm::{Aes256Gcm, Nonce, aead::Aead};
+use anyhow::{Context, anyhow};
+use rand::Rng as _;
+use tokio::{
+  fs::File,
+  io::{AsyncReadExt, AsyncWriteExt},
+};
+
+/// The size of the plaintext chunks.
+pub const CHUNK_SIZE: usize = 64 * 1024;
+const TAG_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const NONCE_PREFIX_LEN: usize = 7;
+
+/// Reads a backup file in chunks, encrypting them if a cipher is given.
+pub struct UploadSource {
+  file: File,
+  /// The bytes which will be uploaded in total.
+  len: u64,
+  cipher: Option<(Aes256Gcm, [u8; NONCE_PREFIX_LEN])>,
+  index: u32,
+  done: bool,
+}
+
+impl UploadSource {
+  pub async fn open(
+    path: &Path,
+    cipher: Option<&Aes256Gcm>,
+  ) -> std::io::Result<UploadSource> {
+    let file = File::open(path).await?;
+    let file_len = file.metadata().await?.len();
+    let cipher = cipher.map(|cipher| {
+      let mut prefix = [0u8; NONCE_PREFIX_LEN];
+      rand::rng().fill_bytes(&mut prefix);
+      (cipher.clone(), prefix)
+    });
+    let len = if cipher.is_some() {
+      // There is always a final chunk, which may be empty.
+      let chunks = file_len / CHUNK_SIZE as u64 + 1;
+      NONCE_PREFIX_LEN as u64 + file_len + chunks * TAG_LEN as u64
+    } else {
+      file_len
+    };
+    Ok(UploadSource {
+      file,
+      len,
+      cipher,
+      index: 0,
+      done: false,
+    })
+  }
+
+  /// The bytes which will be uploaded in total.
+  pub fn upload_len(&self) -> u64 {
+    self.len
+  }
+
+  /// Whether all the chunks have been read.
+  pub fn is_done(&self) -> bool {
+    self.done
+  }
+
+  /// The next chunk to upload, or None when finished.
+  pub async fn next_chunk(
+    &mut self,
+  ) -> anyhow::Result<Option<Vec<u8>>> {
+    if self.done {
+      return Ok(None);
+    }
+    let mut chunk = vec![0u8; CHUNK_SIZE];
+    let mut read = 0;
+    while read < CHUNK_SIZE {
+      let n = self
+        .file
+        .read(&mut chunk[read..])
+        .await
+        .context("Failed to read backup file")?;
+      if n == 0 {
+        break;
+      }
+      read += n;
+    }
+    chunk.truncate(read);
+    let last = read < CHUNK_SIZE;
+    self.done = last;
+
+    let Some((cipher, prefix)) = &self.cipher else {
+      return Ok((!chunk.is_empty()).then_some(chunk));
+    };
+
+    let nonce = chunk_nonce(prefix, self.index, last);
+    let sealed = cipher
+      .encrypt(Nonce::from_slice(&nonce), chunk.as_slice())
+      .map_err(|_| anyhow!("Failed to encrypt backup file"))?;
+    let sealed = if self.index == 0 {
+      [prefix.as_slice(), &sealed].concat()
+    } else {
+      sealed
+    };
+    self.index = self
+      .index
+      .checked_add(1)
+      .context("Backup file is too large to encrypt")?;
+    Ok(Some(sealed))
+  }
+}
+
+/// Writes a downloaded backup file as the bytes arrive,
+/// decrypting them if a cipher is given.
+pub struct DownloadSink {
+  path: PathBuf,
+  file: File,
+  cipher: Option<Aes256Gcm>,
+  prefix: Option<[u8; NONCE_PREFIX_LEN]>,
+  buffer: Vec<u8>,
+  index: u32,
+}
+
+impl DownloadSink {
+  pub async fn create(
+    path: PathBuf,
+    cipher: Option<Aes256Gcm>,
+  ) -> anyhow::Result<DownloadSink> {
+    let file = File::create(&path)
+      .await
+      .with_context(|| format!("Failed to create {path:?}"))?;
+    Ok(DownloadSink {
+      path,
+      file,
+      cipher,
+      prefix: None,
+      buffer: Vec::new(),
+      index: 0,
+    })
+  }
+
+  pub async fn write(&mut self, bytes: &[u8]) -> anyhow::Result<()> {
+    let Some(cipher) = &self.cipher else {
+      return self
+        .file
+        .write_all(bytes)
+        .await
+        .with_context(|| format!("Failed to write {:?}", self.path));
+    };
+
+    self.buffer.extend_from_slice(bytes);
+
+    let prefix = match self.prefix {
+      Some(prefix) => prefix,
+      None if self.buffer.len() < NONCE_PREFIX_LEN => return Ok(()),
+      None => {
+        let mut prefix = [0u8; NONCE_PREFIX_LEN];
+        prefix.copy_from_slice(&self.buffer[..NONCE_PREFIX_LEN]);
+        self.buffer.drain(..NONCE_PREFIX_LEN);
+        *self.prefix.insert(prefix)
+      }
+    };
+
+    const SEALED_CHUNK_SIZE: usize = CHUNK_SIZE + TAG_LEN;
+    // A full chunk is only known not to be the last
+    // once more bytes follow it.
+    while self.buffer.len() > SEALED_CHUNK_SIZE {
+      let chunk = open_chunk(
+        cipher,
+        &prefix,
+        self.index,
+        false,
+        &self.buffer[..SEALED_CHUNK_SIZE],
+      )?;
+      self.buffer.drain(..SEALED_CHUNK_SIZE);
+      self.index = self
+        .index
+        .checked_add(1)
+        .context("Encrypted backup file has too many chunks")?;
+      self.file.write_all(&chunk).await.with_context(|| {
+        format!("Failed to write {:?}", self.path)
+      })?;
+    }
+
+    Ok(())
+  }
+
+  /// Writes the final chunk, which ensures encrypted files
+  /// were not truncated.
+  pub async fn finish(mut self) -> anyhow::Result<()> {
+    if let Some(cipher) = &self.cipher {
+      let prefix =
+        self.prefix.context("Encrypted backup file is too short")?;
+      let chunk =
+        open_chunk(cipher, &prefix, self.index, true, &self.buffer)?;
+      self.file.write_all(&chunk).await.with_context(|| {
+        format!("Failed to write {:?}", self.path)
+      })?;
+    }
+    self
+      .file
+      .flush()
+      .await
+      .with_context(|| format!("Failed to write {:?}", self.path))
+  }
+}
+
+fn chunk_nonce(
+  prefix: &[u8; NONCE_PREFIX_LEN],
+  index: u32,
+  last: bool,
+) -> [u8; NONCE_LEN] {
+  let mut nonce = [0u8; NONCE_LEN];
+  nonce[..NONCE_PREFIX_LEN].copy_from_slice(prefix);
+  nonce[NONCE_PREFIX_LEN..NONCE_LEN - 1]
+    .copy_from_slice(&index.to_be_bytes());
+  nonce[NONCE_LEN - 1] = last as u8;
+  nonce
+}
+
+fn open_chunk(
+  cipher: &Aes256Gcm,
+  prefix: &[u8; NONCE_PREFIX_LEN],
+  index: u32,
+  last: bool,
+  sealed: &[u8],
+) -> anyhow::Result<Vec<u8>> {
+  let nonce = chunk_nonce(prefix, index, last);
+  cipher.decrypt(Nonce::from_slice(&nonce), sealed).map_err(|_| {
+    anyhow!(
+      "Failed to decrypt backup file, the key may be incorrect or the file truncated"
+    )
+  })
+}
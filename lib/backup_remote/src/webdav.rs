@@ -0,0 +1,209 @@
+use anyhow::{Context, anyhow};
+use futures_util::stream::try_unfold;
+use komodo_client::entities::config::BackupRemoteConfig;
+use reqwest::{
+  Body, Method, RequestBuilder, StatusCode, header::CONTENT_LENGTH,
+};
+
+use crate::stream::{DownloadSink, UploadSource};
+
+pub struct WebDavTarget {
+  client: reqwest::Client,
+  /// Ends with `/`
+  base_url: String,
+  username: String,
+  password: String,
+}
+
+impl WebDavTarget {
+  /// `path`: `host[:port]/path`
+  pub fn new(
+    config: &BackupRemoteConfig,
+    protocol: &str,
+    path: &str,
+  ) -> anyhow::Result<WebDavTarget> {
+    Ok(WebDavTarget {
+      client: reqwest::Client::builder()
+        .build()
+        .context("Failed to build WebDAV client")?,
+      base_url: format!(
+        "{protocol}://{}/",
+        path.trim_end_matches('/')
+      ),
+      username: config.username.clone(),
+      password: config.password.clone(),
+    })
+  }
+
+  fn request(&self, method: Method, path: &str) -> RequestBuilder {
+    let request = self
+      .client
+      .request(method, format!("{}{path}", self.base_url));
+    if self.username.is_empty() {
+      request
+    } else {
+      request.basic_auth(&self.username, Some(&self.password))
+    }
+  }
+
+  pub async fn create_folder(
+    &self,
+    folder: &str,
+  ) -> anyhow::Result<()> {
+    // Create any missing parent collections as well
+    let mut path = String::new();
+    for segment in folder.split('/').filter(|s| !s.is_empty()) {
+      path.push_str(segment);
+      path.push('/');
+      let res = self
+        .request(method("MKCOL"), &path)
+        .send()
+        .await
+        .context("Failed to send MKCOL request")?;
+      // 405: Collection already exists
+      if !res.status().is_success()
+        && res.status() != StatusCode::METHOD_NOT_ALLOWED
+      {
+        return Err(anyhow!(
+          "Failed to create collection {path} | {}",
+          res.status()
+        ));
+      }
+    }
+    Ok(())
+  }
+
+  pub async fn put_file(
+    &self,
+    key: &str,
+    source: UploadSource,
+  ) -> anyhow::Result<()> {
+    let len = source.upload_len();
+    let body = try_unfold(source, |mut source| async move {
+      let chunk = source.next_chunk().await?;
+      anyhow::Ok(chunk.map(|chunk| (chunk, source)))
+    });
+    self
+      .request(Method::PUT, key)
+      // Some servers reject chunked uploads without a length
+      .header(CONTENT_LENGTH, len)
+      .body(Body::wrap_stream(body))
+      .send()
+      .await
+      .context("Failed to send PUT request")?
+      .error_for_status()
+      .context("PUT request failed")?;
+    Ok(())
+  }
+
+  pub async fn get_file(
+    &self,
+    key: &str,
+    sink: &mut DownloadSink,
+  ) -> anyhow::Result<()> {
+    let mut res = self
+      .request(Method::GET, key)
+      .send()
+      .await
+      .context("Failed to send GET request")?
+      .error_for_status()
+      .context("GET request failed")?;
+    while let Some(bytes) =
+      res.chunk().await.context("Failed to read response body")?
+    {
+      sink.write(&bytes).await?;
+    }
+    Ok(())
+  }
+
+  /// `parent`: Empty, or ends with `/`
+  pub async fn list_folders(
+    &self,
+    parent: &str,
+  ) -> anyhow::Result<Vec<String>> {
+    self.list(parent).await
+  }
+
+  pub async fn list_files(
+    &self,
+    folder: &str,
+  ) -> anyhow::Result<Vec<String>> {
+    self.list(&format!("{folder}/")).await
+  }
+
+  pub async fn delete_folder(
+    &self,
+    folder: &str,
+  ) -> anyhow::Result<()> {
+    // DELETE on a collection is recursive
+    self
+      .request(Method::DELETE, &format!("{folder}/"))
+      .send()
+      .await
+      .context("Failed to send DELETE request")?
+      .error_for_status()
+      .context("DELETE request failed")?;
+    Ok(())
+  }
+
+  /// Names of the direct children of the collection at `path`.
+  async fn list(&self, path: &str) -> anyhow::Result<Vec<String>> {
+    let url = format!("{}{path}", self.base_url);
+    let collection = reqwest::Url::parse(&url)
+      .with_context(|| format!("Invalid WebDAV url {url}"))?
+      .path()
+      .trim_end_matches('/')
+      .to_string();
+    let res = self
+      .request(method("PROPFIND"), path)
+      .header("Depth", "1")
+      .send()
+      .await
+      .context("Failed to send PROPFIND request")?;
+    // Nothing uploaded yet
+    if res.status() == StatusCode::NOT_FOUND {
+      return Ok(Vec::new());
+    }
+    let body = res
+      .error_for_status()
+      .context("PROPFIND request failed")?
+      .text()
+      .await
+      .context("Failed to read PROPFIND response")?;
+    let names = hrefs(&body)
+      .filter_map(|href| {
+        // Servers may respond with either absolute urls or paths
+        let href = match reqwest::Url::parse(href) {
+          Ok(url) => url.path().to_string(),
+          Err(_) => href.to_string(),
+        };
+        let href = href.trim_end_matches('/');
+        // Skip the collection itself
+        if href == collection {
+          return None;
+        }
+        let name = href.rsplit('/').next()?;
+        (!name.is_empty()).then(|| name.to_string())
+      })
+      .collect();
+    Ok(names)
+  }
+}
+
+fn method(name: &'static str) -> Method {
+  Method::from_bytes(name.as_bytes()).expect("Invalid http method")
+}
+
+/// The text of all `href` elements in a multistatus response,
+/// whatever the namespace prefix.
+fn hrefs(body: &str) -> impl Iterator<Item = &str> {
+  body.split('<').filter_map(|element| {
+    let (tag, text) = element.split_once('>')?;
+    let tag = tag.trim();
+    if tag.starts_with('/') {
+      return None;
+    }
+    let name = tag.rsplit(':').next()?;
+    name.eq_ignore_ascii_case("href").then(|| text.trim())
+  })
+}
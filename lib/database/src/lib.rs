@@ -6,10 +6,12 @@ use komodo_client::entities::{
   alert::Alert,
   alerter::Alerter,
   api_key::ApiKey,
+  audit::AuditEvent,
   build::Build,
   builder::Builder,
   config::DatabaseConfig,
   deployment::Deployment,
+  log_archive::ArchivedLog,
   onboarding_key::OnboardingKey,
   permission::Permission,
   procedure::Procedure,
@@ -26,7 +28,7 @@ use komodo_client::entities::{
   user_group::UserGroup,
   variable::Variable,
 };
-use mongo_indexed::{create_index, create_unique_index};
+use mongo_indexed::{Indexed, create_index, create_unique_index};
 use mungos::{
   by_id::update_one_by_id,
   init::MongoBuilder,
@@ -56,6 +58,8 @@ pub struct Client {
   pub updates: Collection<Update>,
   pub alerts: Collection<Alert>,
   pub stats: Collection<SystemStatsRecord>,
+  pub archived_logs: Collection<ArchivedLog>,
+  pub audit_events: Collection<AuditEvent>,
   // RESOURCES
   pub swarms: Collection<Swarm>,
   pub servers: Collection<Server>,
@@ -94,6 +98,12 @@ impl Client {
       updates: mongo_indexed::collection(&db, true).await?,
       alerts: mongo_indexed::collection(&db, true).await?,
       stats: mongo_indexed::collection(&db, true).await?,
+      archived_logs: capped_collection(
+        &db,
+        ARCHIVED_LOG_MAX_SIZE_BYTES,
+      )
+      .await?,
+      audit_events: mongo_indexed::collection(&db, true).await?,
       // RESOURCES
       swarms: resource_collection(&db, "Swarm").await?,
       servers: resource_collection(&db, "Server").await?,
@@ -242,6 +252,35 @@ pub async fn init(
   Ok(client.database(db_name))
 }
 
+/// The max size of the archived container logs.
+/// Once reached, the oldest logs are dropped to make room.
+const ARCHIVED_LOG_MAX_SIZE_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+/// Creates the collection as capped if it doesn't exist yet,
+/// before creating the indexes.
+async fn capped_collection<T: Indexed + Send + Sync>(
+  db: &Database,
+  max_size_bytes: u64,
+) -> anyhow::Result<Collection<T>> {
+  let name = T::default_collection_name();
+  let exists = db
+    .list_collection_names()
+    .await
+    .context("Failed to list collection names")?
+    .iter()
+    .any(|existing| existing == name);
+  if !exists {
+    db.create_collection(name)
+      .capped(true)
+      .size(max_size_bytes)
+      .await
+      .with_context(|| {
+        format!("Failed to create capped collection {name}")
+      })?;
+  }
+  mongo_indexed::collection(db, true).await
+}
+
 async fn resource_collection<T: Send + Sync>(
   db: &Database,
   collection_name: &str,
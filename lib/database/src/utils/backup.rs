@@ -1,5 +1,5 @@
 use std::{
-  path::Path,
+  path::{Path, PathBuf},
   sync::{Arc, atomic},
 };
 
@@ -17,10 +17,11 @@ use tokio::io::{AsyncWriteExt, BufWriter};
 use tokio_util::codec::{FramedWrite, LinesCodec};
 use tracing::{error, info, warn};
 
+/// Returns the created backup folder.
 pub async fn backup(
   db: &Database,
   backups_folder: &Path,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<PathBuf> {
   let collections = db
     .list_collection_names()
     .await
@@ -138,6 +139,6 @@ pub async fn backup(
     Err(anyhow!("Finished backing up database with errors 🚨"))
   } else {
     info!("Finished backing up database ✅");
-    Ok(())
+    Ok(now_backups_folder)
   }
 }
@@ -1,7 +1,10 @@
 mod backup;
 mod copy;
+mod remote;
 mod restore;
 
 pub use backup::backup;
+pub use backup_remote::BackupRemote;
 pub use copy::copy;
-pub use restore::restore;
+pub use remote::{download_backup, upload_backup};
+pub use restore::{CollectionRestore, RestoreFilter, restore};
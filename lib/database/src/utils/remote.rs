@@ -0,0 +1,55 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use backup_remote::BackupRemote;
+
+/// Uploads a backup folder created by [super::backup],
+/// along with the top level `Stats.gz`.
+pub async fn upload_backup(
+  remote: &BackupRemote,
+  backups_folder: &Path,
+  backup_folder: &Path,
+) -> anyhow::Result<()> {
+  let backup = backup_folder
+    .file_name()
+    .and_then(|name| name.to_str())
+    .context("Backup folder has no name")?;
+
+  // Stats may not exist on a fresh database,
+  // missing files are skipped.
+  let mut files = vec![backups_folder.join("Stats.gz")];
+  let mut backup_dir =
+    tokio::fs::read_dir(backup_folder).await.with_context(|| {
+      format!("Failed to read backup folder {backup_folder:?}")
+    })?;
+  while let Some(entry) = backup_dir
+    .next_entry()
+    .await
+    .context("Failed to read backup folder entry")?
+  {
+    files.push(entry.path());
+  }
+
+  remote.upload(backup, files).await
+}
+
+/// Downloads a backup into `into`, laid out like the local backups folder
+/// so it can be passed to [super::restore].
+/// If `backup` is not provided, downloads the most recent backup.
+///
+/// Returns the downloaded backup folder name.
+pub async fn download_backup(
+  remote: &BackupRemote,
+  backup: Option<&str>,
+  into: &Path,
+) -> anyhow::Result<PathBuf> {
+  let backup = remote.download(backup, into).await?;
+  // Restore reads Stats from the top level
+  let stats = into.join(&backup).join("Stats.gz");
+  if tokio::fs::try_exists(&stats).await.unwrap_or_default() {
+    tokio::fs::rename(&stats, into.join("Stats.gz"))
+      .await
+      .context("Failed to move downloaded Stats")?;
+  }
+  Ok(backup)
+}
@@ -1,6 +1,10 @@
-use std::path::{Path, PathBuf};
+use std::{
+  collections::HashSet,
+  path::{Path, PathBuf},
+  sync::Arc,
+};
 
-use anyhow::Context;
+use anyhow::{Context, anyhow};
 use async_compression::tokio::bufread::GzipDecoder;
 use futures_util::{
   StreamExt, TryStreamExt, stream::FuturesUnordered,
@@ -9,18 +13,99 @@ use mungos::{
   bulk_update::{BulkUpdate, bulk_update_retry_too_big},
   mongodb::{
     Database,
-    bson::{Document, doc},
+    bson::{Document, doc, oid::ObjectId},
   },
 };
 use tokio::io::BufReader;
 use tokio_util::codec::{FramedRead, LinesCodec};
 use tracing::{error, info, warn};
 
+/// Limits the restore to part of the backup.
+/// The default restores everything.
+#[derive(Debug, Clone, Default)]
+pub struct RestoreFilter {
+  /// Only restore these collections, eg `Stack` or `Permission`.
+  /// Empty restores all collections.
+  pub collections: Vec<String>,
+  /// Only restore documents with these ids or resource names,
+  /// along with documents targeting them, such as their Permissions.
+  /// Empty restores all documents.
+  pub resources: Vec<String>,
+  /// Only report what would be inserted or overwritten,
+  /// without writing to the database.
+  pub dry_run: bool,
+}
+
+/// The maximum document names listed in a [CollectionRestore].
+const MAX_LISTED: usize = 50;
+
+/// The collections of Komodo resources,
+/// which `RestoreFilter::resources` names are matched against.
+const RESOURCE_COLLECTIONS: &[&str] = &[
+  "Swarm",
+  "Server",
+  "Stack",
+  "Deployment",
+  "Build",
+  "Repo",
+  "Procedure",
+  "Action",
+  "Builder",
+  "Alerter",
+  "ResourceSync",
+];
+
+/// What was (or would be) restored in a collection.
+#[derive(Debug, Clone, Default)]
+pub struct CollectionRestore {
+  pub collection: String,
+  /// Documents which did not exist on the database.
+  pub inserted: usize,
+  /// Documents which existed, and were overwritten with the backup.
+  pub overwritten: usize,
+  /// The names (or ids) of the inserted documents, up to 50.
+  pub inserted_names: Vec<String>,
+  /// The names (or ids) of the overwritten documents, up to 50.
+  pub overwritten_names: Vec<String>,
+  pub error: Option<String>,
+}
+
+impl CollectionRestore {
+  pub fn summary(&self, dry_run: bool) -> String {
+    let (insert, overwrite) = if dry_run {
+      ("Would insert", "would overwrite")
+    } else {
+      ("Inserted", "overwrote")
+    };
+    let mut summary = format!(
+      "[{}]: {insert} {}, {overwrite} {}",
+      self.collection, self.inserted, self.overwritten
+    );
+    for (label, count, names) in [
+      ("insert", self.inserted, &self.inserted_names),
+      ("overwrite", self.overwritten, &self.overwritten_names),
+    ] {
+      if names.is_empty() {
+        continue;
+      }
+      summary.push_str(&format!("\n  {label}: {}", names.join(", ")));
+      if count > names.len() {
+        summary.push_str(&format!(
+          " ... and {} more",
+          count - names.len()
+        ));
+      }
+    }
+    summary
+  }
+}
+
 pub async fn restore(
   db: &Database,
   backups_folder: &Path,
   restore_folder: Option<&Path>,
-) -> anyhow::Result<()> {
+  filter: &RestoreFilter,
+) -> anyhow::Result<Vec<CollectionRestore>> {
   // Get the specific dated folder to restore contents of
   let restore_folder = if let Some(restore_folder) = restore_folder {
     backups_folder.join(restore_folder)
@@ -35,100 +120,308 @@ pub async fn restore(
   let restore_files =
     get_restore_files(backups_folder, &restore_folder).await?;
 
+  let ids = if filter.resources.is_empty() {
+    None
+  } else {
+    Some(Arc::new(
+      matching_ids(
+        &restore_files,
+        &filter.resources,
+        &filter.collections,
+      )
+      .await?,
+    ))
+  };
+
+  let restore_files = restore_files
+    .into_iter()
+    .filter(|(collection, _)| {
+      filter.collections.is_empty()
+        || filter.collections.contains(collection)
+    })
+    .collect::<Vec<_>>();
+
   let mut handles = restore_files
     .into_iter()
     .map(|(collection, restore_file)| {
       let db = db.clone();
-      async {
+      let ids = ids.clone();
+      let dry_run = filter.dry_run;
+      async move {
         let col = collection.clone();
         tokio::join!(
           async { col },
           tokio::spawn(async move {
-            let res = async {
-              let mut buffer = Vec::<BulkUpdate>::new();
-              // The update collection is bigger than others,
-              // can hit the max bson limit on the bulk upsert call without this.
-              let max_buffer = if collection == "Update" {
-                1_000
-              } else {
-                10_000
-              };
-              let mut count = 0;
-
-              let file = tokio::fs::File::open(&restore_file)
-                .await
-                .with_context(|| format!("Failed to open file {restore_file:?}"))?;
-
-              let mut reader = FramedRead::new(
-                GzipDecoder::new(BufReader::new(file)),
-                LinesCodec::new()
-              );
-
-              while let Some(line) = reader.try_next()
-                .await
-                .context("Failed to get next line")?
-              {
-                if line.is_empty() {
-                  continue;
-                }
-                let document = match serde_json::from_str::<Document>(&line)
-                  .context("Failed to deserialize line")
-                {
-                  Ok(doc) => doc,
-                  Err(e) => {
-                    warn!("{e:#}");
-                    continue;
-                  }
-                };
-                let Some(id) = document.get("_id").and_then(|id| id.as_object_id()) else {
-                  continue;
-                };
-                count += 1;
-                buffer.push(BulkUpdate { query: doc! { "_id": id }, update: doc! { "$set": document } });
-                if buffer.len() >= max_buffer {
-                  if let Err(e) = bulk_update_retry_too_big(&db, &collection, &buffer, true).await.context("Failed to flush documents")
-                  {
-                    error!("Failed to flush document batch in {collection} collection | {e:#}");
-                  };
-                  buffer.clear();
-                }
-              }
-              if !buffer.is_empty() {
-                bulk_update_retry_too_big(&db, &collection, &buffer, true).await.context("Failed to flush documents")?;
-              }
-              anyhow::Ok(count)
-            }.await;
-            match res {
-              Ok(count) => {
-                if count > 0 {
-                  info!("[{collection}]: Restored {count} items");
-                }
-              }
-              Err(e) => {
-                error!("[{collection}]: {e:#}");
-              }
+            let mut report = CollectionRestore {
+              collection: collection.clone(),
+              ..Default::default()
+            };
+            let res = restore_collection(
+              &db,
+              &restore_file,
+              ids.as_deref(),
+              dry_run,
+              &mut report,
+            )
+            .await;
+            if let Err(e) = res {
+              error!("[{collection}]: {e:#}");
+              report.error = Some(format!("{e:#}"));
+            } else if report.inserted + report.overwritten > 0 {
+              info!("{}", report.summary(dry_run));
             }
+            report
           })
         )
       }
     })
     .collect::<FuturesUnordered<_>>();
 
+  let mut reports = Vec::new();
   loop {
     match handles.next().await {
-      Some((_collection, Ok(()))) => {}
+      Some((_collection, Ok(report))) => reports.push(report),
       Some((collection, Err(e))) => {
         error!("[{collection}]: {e:#}");
+        reports.push(CollectionRestore {
+          collection,
+          error: Some(format!("{e:#}")),
+          ..Default::default()
+        });
       }
       None => break,
     }
   }
 
-  info!("Finished restoring database ✅");
+  reports.sort_by(|a, b| a.collection.cmp(&b.collection));
+
+  if filter.dry_run {
+    info!("Finished restore dry run ✅");
+  } else {
+    info!("Finished restoring database ✅");
+  }
+
+  Ok(reports)
+}
+
+async fn restore_collection(
+  db: &Database,
+  restore_file: &Path,
+  ids: Option<&HashSet<String>>,
+  dry_run: bool,
+  report: &mut CollectionRestore,
+) -> anyhow::Result<()> {
+  let collection = report.collection.clone();
+  let mut buffer = Vec::<(ObjectId, Document)>::new();
+  // The update collection is bigger than others,
+  // can hit the max bson limit on the bulk upsert call without this.
+  let max_buffer = if collection == "Update" {
+    1_000
+  } else {
+    10_000
+  };
+
+  let mut reader = open_restore_file(restore_file).await?;
+  let mut errors = Vec::new();
+
+  while let Some(line) =
+    reader.try_next().await.context("Failed to get next line")?
+  {
+    let Some((id, document)) = parse_line(&line) else {
+      continue;
+    };
+    if let Some(ids) = ids
+      && !matches_ids(&id, &document, ids)
+    {
+      continue;
+    }
+    buffer.push((id, document));
+    if buffer.len() >= max_buffer {
+      // Keep restoring the other batches,
+      // the errors are still reported after.
+      if let Err(e) =
+        flush(db, &collection, &buffer, dry_run, report).await
+      {
+        error!(
+          "Failed to flush document batch in {collection} collection | {e:#}"
+        );
+        errors.push(format!("{e:#}"));
+      };
+      buffer.clear();
+    }
+  }
+  if !buffer.is_empty()
+    && let Err(e) =
+      flush(db, &collection, &buffer, dry_run, report).await
+  {
+    errors.push(format!("{e:#}"));
+  }
+  if !errors.is_empty() {
+    return Err(anyhow!(
+      "Failed to flush {} document batch/es | {}",
+      errors.len(),
+      errors.join(" | ")
+    ));
+  }
+  Ok(())
+}
+
+/// Counts the inserted / overwritten documents,
+/// and writes them unless `dry_run`.
+async fn flush(
+  db: &Database,
+  collection: &str,
+  buffer: &[(ObjectId, Document)],
+  dry_run: bool,
+  report: &mut CollectionRestore,
+) -> anyhow::Result<()> {
+  let ids = buffer.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+  let existing = db
+    .collection::<Document>(collection)
+    .distinct("_id", doc! { "_id": { "$in": ids } })
+    .await
+    .context("Failed to query existing documents")?
+    .into_iter()
+    .filter_map(|id| id.as_object_id())
+    .collect::<HashSet<_>>();
+
+  if !dry_run {
+    let updates = buffer
+      .iter()
+      .map(|(id, document)| BulkUpdate {
+        query: doc! { "_id": id },
+        update: doc! { "$set": document },
+      })
+      .collect::<Vec<_>>();
+    bulk_update_retry_too_big(db, collection, &updates, true)
+      .await
+      .context("Failed to flush documents")?;
+  }
+
+  for (id, document) in buffer {
+    let (count, names) = if existing.contains(id) {
+      (&mut report.overwritten, &mut report.overwritten_names)
+    } else {
+      (&mut report.inserted, &mut report.inserted_names)
+    };
+    *count += 1;
+    if names.len() < MAX_LISTED {
+      names.push(document_name(id, document));
+    }
+  }
 
   Ok(())
 }
 
+/// Collects the ids of the documents matching `resources` by id or name.
+/// Names are only matched in the selected resource collections,
+/// or all the resource collections if none are selected,
+/// so resources can be selected by name when only restoring
+/// other collections, eg only the Updates of a Stack.
+async fn matching_ids(
+  restore_files: &[(String, PathBuf)],
+  resources: &[String],
+  collections: &[String],
+) -> anyhow::Result<HashSet<String>> {
+  let selected = RESOURCE_COLLECTIONS
+    .iter()
+    .filter(|collection| {
+      collections.iter().any(|selected| selected == *collection)
+    })
+    .copied()
+    .collect::<Vec<_>>();
+  let name_collections = if selected.is_empty() {
+    RESOURCE_COLLECTIONS
+  } else {
+    selected.as_slice()
+  };
+  // Also match ids of documents which no longer exist in the backup.
+  let mut ids = resources.iter().cloned().collect::<HashSet<_>>();
+  for (collection, restore_file) in restore_files {
+    if !name_collections.contains(&collection.as_str()) {
+      continue;
+    }
+    let mut reader = match open_restore_file(restore_file).await {
+      Ok(reader) => reader,
+      Err(e) => {
+        warn!("[{collection}]: {e:#}");
+        continue;
+      }
+    };
+    while let Some(line) =
+      reader.try_next().await.context("Failed to get next line")?
+    {
+      let Some((id, document)) = parse_line(&line) else {
+        continue;
+      };
+      if let Ok(name) = document.get_str("name")
+        && resources.iter().any(|resource| resource == name)
+      {
+        ids.insert(id.to_hex());
+      }
+    }
+  }
+  Ok(ids)
+}
+
+/// Matches the document id, or the resource it targets,
+/// like the `resource_target` of a Permission.
+fn matches_ids(
+  id: &ObjectId,
+  document: &Document,
+  ids: &HashSet<String>,
+) -> bool {
+  if ids.contains(&id.to_hex()) {
+    return true;
+  }
+  ["resource_target", "target"].into_iter().any(|field| {
+    document
+      .get_document(field)
+      .and_then(|target| target.get_str("id"))
+      .is_ok_and(|id| ids.contains(id))
+  })
+}
+
+fn document_name(id: &ObjectId, document: &Document) -> String {
+  document
+    .get_str("name")
+    .or_else(|_| document.get_str("username"))
+    .map(str::to_string)
+    .unwrap_or_else(|_| id.to_hex())
+}
+
+async fn open_restore_file(
+  restore_file: &Path,
+) -> anyhow::Result<
+  FramedRead<GzipDecoder<BufReader<tokio::fs::File>>, LinesCodec>,
+> {
+  let file =
+    tokio::fs::File::open(restore_file).await.with_context(|| {
+      format!("Failed to open file {restore_file:?}")
+    })?;
+  Ok(FramedRead::new(
+    GzipDecoder::new(BufReader::new(file)),
+    LinesCodec::new(),
+  ))
+}
+
+fn parse_line(line: &str) -> Option<(ObjectId, Document)> {
+  if line.is_empty() {
+    return None;
+  }
+  let document = match serde_json::from_str::<Document>(line)
+    .context("Failed to deserialize line")
+  {
+    Ok(doc) => doc,
+    Err(e) => {
+      warn!("{e:#}");
+      return None;
+    }
+  };
+  let id = document.get("_id").and_then(|id| id.as_object_id())?;
+  Some((id, document))
+}
+
 async fn latest_restore_folder(
   backups_folder: &Path,
 ) -> anyhow::Result<PathBuf> {
@@ -8,7 +8,17 @@ use komodo_client::entities::{
   update::Log,
 };
 
-use crate::get_commit_hash_log;
+use crate::{
+  get_commit_hash_log,
+  options::{
+    clone_flags, fetch_commit, sparse_checkout,
+    update_submodules_and_lfs,
+  },
+  ssh::{
+    clone_ssh_key_path, configure_repo_ssh, git_ssh_env, ssh_command,
+    write_ssh_key,
+  },
+};
 
 /// Will delete the existing repo folder,
 /// clone the repo, get the latest hash / message,
@@ -64,19 +74,53 @@ where
     _ => {}
   }
 
+  // Over ssh, the access token is the private key.
+  let ssh_key = access_token.as_deref().filter(|_| args.ssh);
+  let ssh_key_file = match ssh_key {
+    Some(ssh_key) => {
+      let path = clone_ssh_key_path(&res.path);
+      if let Err(e) = write_ssh_key(&path, ssh_key).await {
+        res.logs.push(Log::error(
+          "Write SSH Key",
+          format_serror(&e.into()),
+        ));
+        return Ok(res);
+      }
+      Some(path)
+    }
+    None => None,
+  };
+
   let command = format!(
-    "git clone {repo_url} {} -b {}",
+    "git clone {repo_url} {} -b {}{}",
     res.path.display(),
-    args.branch
+    args.branch,
+    clone_flags(&args),
   );
+  let command = if args.ssh {
+    git_ssh_env(
+      &ssh_command(
+        ssh_key_file.as_deref(),
+        args.ssh_known_hosts.as_deref(),
+        args.ssh_accept_new_host_keys,
+      ),
+      &command,
+    )
+  } else {
+    command
+  };
 
   let mut log =
     run_komodo_standard_command("Clone Repo", None, command).await;
 
-  if let Some(token) = access_token {
-    log.command = log.command.replace(&token, "<TOKEN>");
-    log.stdout = log.stdout.replace(&token, "<TOKEN>");
-    log.stderr = log.stderr.replace(&token, "<TOKEN>");
+  if let Some(ssh_key_file) = &ssh_key_file {
+    let _ = tokio::fs::remove_file(ssh_key_file).await;
+  }
+
+  if let Some(token) = &access_token {
+    log.command = log.command.replace(token, "<TOKEN>");
+    log.stdout = log.stdout.replace(token, "<TOKEN>");
+    log.stderr = log.stderr.replace(token, "<TOKEN>");
   }
 
   res.logs.push(log);
@@ -85,7 +129,31 @@ where
     return Ok(res);
   }
 
-  if let Some(commit) = args.commit {
+  if args.ssh {
+    // Keep the key with the repo for later pulls / pushes.
+    res
+      .logs
+      .push(configure_repo_ssh(&res.path, &args, ssh_key).await);
+    if !all_logs_success(&res.logs) {
+      return Ok(res);
+    }
+  }
+
+  if let Some(log) = sparse_checkout(&res.path, &args).await {
+    res.logs.push(log);
+    if !all_logs_success(&res.logs) {
+      return Ok(res);
+    }
+  }
+
+  if let Some(log) = fetch_commit(&res.path, &args).await {
+    res.logs.push(log);
+    if !all_logs_success(&res.logs) {
+      return Ok(res);
+    }
+  }
+
+  if let Some(commit) = &args.commit {
     let reset_log = run_komodo_standard_command(
       "set commit",
       res.path.as_path(),
@@ -99,6 +167,12 @@ where
     return Ok(res);
   }
 
+  update_submodules_and_lfs(&res.path, &args, &mut res.logs).await;
+
+  if !all_logs_success(&res.logs) {
+    return Ok(res);
+  }
+
   match get_commit_hash_log(&res.path)
     .await
     .context("Failed to get latest commit")
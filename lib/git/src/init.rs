@@ -48,6 +48,17 @@ pub async fn init_folder_as_repo(
     return;
   }
 
+  if args.ssh {
+    // Over ssh, the access token is the private key.
+    let configure_ssh =
+      crate::ssh::configure_repo_ssh(folder_path, args, access_token)
+        .await;
+    if !configure_ssh.success {
+      logs.push(configure_ssh);
+      return;
+    }
+  }
+
   // Set branch.
   let init_repo = run_komodo_standard_command(
     "Set Branch",
@@ -10,8 +10,10 @@ use komodo_client::entities::{
 mod clone;
 mod commit;
 mod init;
+mod options;
 mod pull;
 mod pull_or_clone;
+mod ssh;
 
 pub use crate::{
   clone::clone,
@@ -0,0 +1,115 @@
+use std::{borrow::Cow, path::Path};
+
+use command::run_komodo_standard_command;
+use komodo_client::entities::{
+  RepoExecutionArgs, all_logs_success, update::Log,
+};
+use shell_escape::unix::escape;
+
+use crate::ssh::with_git_ssh_env;
+
+/// Extra flags for `git clone` from the clone options.
+pub fn clone_flags(args: &RepoExecutionArgs) -> String {
+  let mut flags = String::new();
+  if args.depth > 0 {
+    flags.push_str(&format!(" --depth {}", args.depth));
+  }
+  if args.sparse_paths.is_some() {
+    // Only download the blobs which are actually checked out.
+    flags.push_str(" --filter=blob:none --sparse");
+  }
+  flags
+}
+
+/// Extra flags for `git fetch` / `git pull` from the clone options.
+pub fn fetch_flags(args: &RepoExecutionArgs) -> String {
+  if args.depth > 0 {
+    format!(" --depth {}", args.depth)
+  } else {
+    String::new()
+  }
+}
+
+/// Applies the sparse checkout paths, or disables sparse checkout
+/// if the repo was sparse but no longer should be.
+pub async fn sparse_checkout(
+  repo_dir: &Path,
+  args: &RepoExecutionArgs,
+) -> Option<Log> {
+  let command = match &args.sparse_paths {
+    Some(paths) => {
+      let paths = paths
+        .iter()
+        .map(|path| format!(" {}", escape(Cow::Borrowed(path))))
+        .collect::<String>();
+      format!("git sparse-checkout set --cone{paths}")
+    }
+    None
+      if repo_dir
+        .join(".git")
+        .join("info")
+        .join("sparse-checkout")
+        .exists() =>
+    {
+      String::from("git sparse-checkout disable")
+    }
+    None => return None,
+  };
+  Some(
+    run_komodo_standard_command("Sparse Checkout", repo_dir, command)
+      .await,
+  )
+}
+
+/// A shallow clone may not include the configured commit,
+/// so it has to be fetched before it can be checked out.
+pub async fn fetch_commit(
+  repo_dir: &Path,
+  args: &RepoExecutionArgs,
+) -> Option<Log> {
+  let commit = args.commit.as_ref().filter(|_| args.depth > 0)?;
+  Some(
+    run_komodo_standard_command(
+      "Fetch Commit",
+      repo_dir,
+      format!("git fetch --depth {} origin {commit}", args.depth),
+    )
+    .await,
+  )
+}
+
+/// Updates submodules and pulls LFS files after the
+/// working tree is at the target commit.
+pub async fn update_submodules_and_lfs(
+  repo_dir: &Path,
+  args: &RepoExecutionArgs,
+  logs: &mut Vec<Log>,
+) {
+  if args.submodules {
+    logs.push(
+      run_komodo_standard_command(
+        "Update Submodules",
+        repo_dir,
+        with_git_ssh_env(
+          repo_dir,
+          args,
+          "git submodule update --init --recursive",
+        ),
+      )
+      .await,
+    );
+    if !all_logs_success(logs) {
+      return;
+    }
+  }
+  if args.lfs {
+    logs.push(
+      run_komodo_standard_command(
+        "Git LFS Pull",
+        repo_dir,
+        with_git_ssh_env(repo_dir, args, "git lfs pull"),
+      )
+      .await,
+    );
+  }
+}
@@ -11,7 +11,13 @@ use komodo_client::entities::{
 };
 use mogh_cache::TimeoutCache;
 
-use crate::get_commit_hash_log;
+use crate::{
+  get_commit_hash_log,
+  options::{
+    fetch_commit, fetch_flags, sparse_checkout,
+    update_submodules_and_lfs,
+  },
+};
 
 /// Wait this long after a pull to allow another pull through
 const PULL_TIMEOUT: i64 = 5_000;
@@ -83,24 +89,37 @@ where
     )
     .await;
     // Sanitize the output
-    if let Some(token) = access_token {
+    if let Some(token) = &access_token {
       set_remote.command =
-        set_remote.command.replace(&token, "<TOKEN>");
-      set_remote.stdout =
-        set_remote.stdout.replace(&token, "<TOKEN>");
-      set_remote.stderr =
-        set_remote.stderr.replace(&token, "<TOKEN>");
+        set_remote.command.replace(token, "<TOKEN>");
+      set_remote.stdout = set_remote.stdout.replace(token, "<TOKEN>");
+      set_remote.stderr = set_remote.stderr.replace(token, "<TOKEN>");
     }
     res.logs.push(set_remote);
     if !all_logs_success(&res.logs) {
       return Ok(res);
     }
 
+    if args.ssh {
+      // Over ssh, the access token is the private key.
+      res.logs.push(
+        crate::ssh::configure_repo_ssh(
+          &res.path,
+          &args,
+          access_token.as_deref(),
+        )
+        .await,
+      );
+      if !all_logs_success(&res.logs) {
+        return Ok(res);
+      }
+    }
+
     // First fetch remote branches before checkout
     let fetch = run_komodo_standard_command(
       "Git Fetch",
       res.path.as_ref(),
-      "git fetch --all --prune",
+      format!("git fetch --all --prune{}", fetch_flags(&args)),
     )
     .await;
     if !fetch.success {
@@ -122,7 +141,11 @@ where
     let pull_log = run_komodo_standard_command(
       "Git pull",
       res.path.as_ref(),
-      format!("git pull --rebase --force origin {}", args.branch),
+      format!(
+        "git pull --rebase --force origin {}{}",
+        args.branch,
+        fetch_flags(&args)
+      ),
     )
     .await;
     res.logs.push(pull_log);
@@ -130,7 +153,21 @@ where
       return Ok(res);
     }
 
-    if let Some(commit) = args.commit {
+    if let Some(log) = sparse_checkout(&res.path, &args).await {
+      res.logs.push(log);
+      if !all_logs_success(&res.logs) {
+        return Ok(res);
+      }
+    }
+
+    if let Some(log) = fetch_commit(&res.path, &args).await {
+      res.logs.push(log);
+      if !all_logs_success(&res.logs) {
+        return Ok(res);
+      }
+    }
+
+    if let Some(commit) = &args.commit {
       let reset_log = run_komodo_standard_command(
         "Set commit",
         res.path.as_ref(),
@@ -143,6 +180,11 @@ where
       }
     }
 
+    update_submodules_and_lfs(&res.path, &args, &mut res.logs).await;
+    if !all_logs_success(&res.logs) {
+      return Ok(res);
+    }
+
     match get_commit_hash_log(&res.path).await {
       Ok((log, hash, message)) => {
         res.logs.push(log);
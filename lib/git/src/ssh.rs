@@ -0,0 +1,165 @@
+use std::{
+  borrow::Cow,
+  path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use command::run_komodo_standard_command;
+use formatting::format_serror;
+use komodo_client::entities::{RepoExecutionArgs, update::Log};
+use shell_escape::unix::escape;
+use tokio::io::AsyncWriteExt;
+
+/// The private key is kept in the `.git` folder, so later
+/// fetches / pushes in the repo can keep using it,
+/// the same way the http(s) token is kept in the remote url.
+const SSH_KEY_FILE: &str = "komodo_ssh_key";
+
+/// Builds the `core.sshCommand` / `GIT_SSH_COMMAND` used for git over ssh.
+/// Git runs it with the shell, so the paths are shell escaped.
+///
+/// - With a key, only that key is offered to the server.
+///   Otherwise the host's default ssh identity is used.
+/// - With a known_hosts file, host keys are checked against it.
+///   Otherwise the default known_hosts is used.
+/// - Unknown host keys are rejected, unless `accept_new_host_keys`
+///   opts in to accepting them on first use.
+pub fn ssh_command(
+  key_file: Option<&Path>,
+  known_hosts: Option<&Path>,
+  accept_new_host_keys: bool,
+) -> String {
+  let mut command = String::from("ssh");
+  if let Some(key_file) = key_file {
+    command.push_str(&format!(
+      " -i {} -o IdentitiesOnly=yes",
+      escape(key_file.to_string_lossy())
+    ));
+  }
+  if let Some(known_hosts) = known_hosts {
+    command.push_str(&format!(
+      " -o UserKnownHostsFile={}",
+      escape(known_hosts.to_string_lossy())
+    ));
+  }
+  if accept_new_host_keys {
+    command.push_str(" -o StrictHostKeyChecking=accept-new");
+  } else {
+    command.push_str(" -o StrictHostKeyChecking=yes");
+  }
+  command
+}
+
+/// Writes the private key with the permissions ssh requires.
+/// The file is created with them, so the key is never readable
+/// by other users, even briefly.
+pub async fn write_ssh_key(
+  path: &Path,
+  private_key: &str,
+) -> anyhow::Result<()> {
+  // ssh fails to load keys without the trailing newline
+  let mut contents = private_key.trim().to_string();
+  contents.push('\n');
+  // Replace any existing key file, as the permissions
+  // only apply when the file is created.
+  match tokio::fs::remove_file(path).await {
+    Ok(_) => {}
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+    Err(e) => {
+      return Err(e).with_context(|| {
+        format!("Failed to remove existing ssh key at {path:?}")
+      });
+    }
+  }
+  let mut options = tokio::fs::OpenOptions::new();
+  options.write(true).create_new(true);
+  #[cfg(unix)]
+  options.mode(0o600);
+  let mut file = options.open(path).await.with_context(|| {
+    format!("Failed to create ssh key file at {path:?}")
+  })?;
+  file.write_all(contents.as_bytes()).await.with_context(|| {
+    format!("Failed to write ssh key to {path:?}")
+  })?;
+  file
+    .flush()
+    .await
+    .with_context(|| format!("Failed to write ssh key to {path:?}"))
+}
+
+/// Stores the private key (if any) in the repo's `.git` folder,
+/// and sets `core.sshCommand` so all git commands in the repo use it.
+/// Submodules don't read the repo's config, see [with_git_ssh_env].
+pub async fn configure_repo_ssh(
+  repo_dir: &Path,
+  args: &RepoExecutionArgs,
+  private_key: Option<&str>,
+) -> Log {
+  let key_file = repo_dir.join(".git").join(SSH_KEY_FILE);
+  let res = match private_key {
+    Some(private_key) => write_ssh_key(&key_file, private_key).await,
+    // Make sure a previous key isn't picked up by `repo_ssh_command`.
+    None => match tokio::fs::remove_file(&key_file).await {
+      Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e)
+        .with_context(|| {
+          format!("Failed to remove previous ssh key at {key_file:?}")
+        }),
+      _ => Ok(()),
+    },
+  };
+  if let Err(e) = res {
+    return Log::error("Configure SSH", format_serror(&e.into()));
+  }
+  run_komodo_standard_command(
+    "Configure SSH",
+    repo_dir,
+    format!(
+      "git config core.sshCommand {}",
+      escape(repo_ssh_command(repo_dir, args).into())
+    ),
+  )
+  .await
+}
+
+/// The ssh command for the repo, using the key
+/// stored by [configure_repo_ssh] if there is one.
+fn repo_ssh_command(
+  repo_dir: &Path,
+  args: &RepoExecutionArgs,
+) -> String {
+  let key_file = repo_dir.join(".git").join(SSH_KEY_FILE);
+  ssh_command(
+    key_file.exists().then_some(key_file.as_path()),
+    args.ssh_known_hosts.as_deref(),
+    args.ssh_accept_new_host_keys,
+  )
+}
+
+/// Runs the git command with `GIT_SSH_COMMAND` set for ssh repos.
+/// Unlike `core.sshCommand`, the environment is inherited
+/// by the git commands run for submodules.
+pub fn with_git_ssh_env(
+  repo_dir: &Path,
+  args: &RepoExecutionArgs,
+  command: &str,
+) -> String {
+  if args.ssh {
+    git_ssh_env(&repo_ssh_command(repo_dir, args), command)
+  } else {
+    command.to_string()
+  }
+}
+
+/// `env GIT_SSH_COMMAND=<ssh command> <command>`
+pub fn git_ssh_env(ssh_command: &str, command: &str) -> String {
+  format!(
+    "env GIT_SSH_COMMAND={} {command}",
+    escape(Cow::Borrowed(ssh_command))
+  )
+}
+
+/// The key used for the clone itself, before the `.git` folder exists.
+/// Placed next to the clone destination and removed after.
+pub fn clone_ssh_key_path(repo_dir: &Path) -> PathBuf {
+  PathBuf::from(format!("{}.{SSH_KEY_FILE}", repo_dir.display()))
+}
@@ -6,6 +6,32 @@ use komodo_client::entities::{
   stack::Stack, update::Log,
 };
 
+/// Finds the external secret references in `target`,
+/// which look like `[[provider:reference]]`,
+/// eg `[[vault:kv/data/app#password]]`.
+/// Returns the inner `provider:reference`, which is the name
+/// the resolved secret should be given for interpolation.
+pub fn external_secret_references(target: &str) -> HashSet<String> {
+  let mut references = HashSet::new();
+  let mut rest = target;
+  while let Some(start) = rest.find("[[") {
+    rest = &rest[start + 2..];
+    let Some(end) = rest.find("]]") else {
+      break;
+    };
+    let inner = &rest[..end];
+    if let Some((provider, reference)) = inner.split_once(':')
+      && !provider.is_empty()
+      && provider.chars().all(|c| c.is_ascii_lowercase())
+      && !reference.is_empty()
+    {
+      references.insert(inner.to_string());
+    }
+    rest = &rest[end + 2..];
+  }
+  references
+}
+
 pub struct Interpolator<'a> {
   variables: Option<&'a HashMap<String, String>>,
   secrets: &'a HashMap<String, String>,
@@ -1,6 +1,6 @@
 import { useRead } from "@/lib/hooks";
 import { ConfigItem } from "@/ui/config/item";
-import { Button, Group, Select, SelectProps, TextInput } from "@mantine/core";
+import { Group, Select, SelectProps, TextInput } from "@mantine/core";
 import { useState } from "react";
 
 export type ProviderSelectorAccountType = "git" | "docker";
@@ -89,16 +89,20 @@ export default function ProviderSelector({
   );
 }
 
+export type GitProtocol = "https" | "http" | "ssh";
+
 export function ProviderSelectorConfig({
   description,
   https,
-  onHttpsSwitch,
+  ssh,
+  onProtocolSelect,
   accountType,
   ...props
 }: {
   description?: string;
   https?: boolean;
-  onHttpsSwitch?: () => void;
+  ssh?: boolean;
+  onProtocolSelect?: (protocol: GitProtocol) => void;
 } & ProviderSelectorProps) {
   const select = accountType === "git" ? "git provider" : "docker registry";
   const label = accountType === "git" ? "Git Provider" : "Image Registry";
@@ -112,14 +116,20 @@ export function ProviderSelectorConfig({
     >
       {accountType === "git" ? (
         <Group>
-          <Button
-            onClick={onHttpsSwitch}
+          <Select
+            value={ssh ? "ssh" : https ? "https" : "http"}
+            onChange={(protocol) =>
+              protocol && onProtocolSelect?.(protocol as GitProtocol)
+            }
+            data={[
+              { label: "https://", value: "https" },
+              { label: "http://", value: "http" },
+              { label: "ssh://", value: "ssh" },
+            ]}
             disabled={props.disabled}
-            px="sm"
-            py="xs"
-          >
-            {`http${https ? "s" : ""}://`}
-          </Button>
+            allowDeselect={false}
+            w={110}
+          />
           {selector}
         </Group>
       ) : (
@@ -44,6 +44,56 @@ export function useLoginOptions() {
   });
 }
 
+export function useLdapLoginOptions() {
+  return useQuery({
+    queryKey: ["LdapLoginOptions"],
+    queryFn: () => komodo_client().ldap_options(),
+  });
+}
+
+export function useOidcLoginOptions() {
+  return useQuery({
+    queryKey: ["OidcLoginOptions"],
+    queryFn: () => komodo_client().oidc_options(),
+  });
+}
+
+export function useLdapLogin(
+  config?: Omit<
+    UseMutationOptions<
+      Types.LdapLoginResponse,
+      unknown,
+      Types.LdapLogin,
+      unknown
+    >,
+    "mutationKey" | "mutationFn"
+  >,
+) {
+  return useMutation({
+    mutationKey: ["LdapLogin"],
+    mutationFn: (params: Types.LdapLogin) =>
+      komodo_client().ldap_login(params),
+    onError: (e: { result: { error?: string; trace?: string[] } }, ...args) => {
+      console.log("LDAP login error:", e);
+      const msg = e.result.error ?? "Unknown error. See console.";
+      const detail = e.result?.trace
+        ?.map((msg) => msg[0].toUpperCase() + msg.slice(1))
+        .join(" | ");
+      let msg_log = msg ? msg[0].toUpperCase() + msg.slice(1) + " | " : "";
+      if (detail) {
+        msg_log += detail + " | ";
+      }
+      notifications.show({
+        title: "LDAP login failed",
+        message: `${msg_log}See console for details`,
+        color: "red",
+      });
+      config?.onError && config.onError(e, ...args);
+    },
+    ...config,
+  });
+}
+
 export function useLogin<
   T extends MoghAuth.Types.LoginRequest["type"],
   R extends Extract<MoghAuth.Types.LoginRequest, { type: T }>,
@@ -111,6 +161,7 @@ export function useManageAuth<
 }
 
 let jwt_redeem_sent = false;
+let oidc_redeem_sent = false;
 let passkey_sent = false;
 
 /// returns whether to show login / loading screen depending on state of exchange token loop
@@ -158,8 +209,26 @@ export function useAuthState() {
     jwt_redeem_sent = true;
   }
 
+  const oidc_redeem_ready = search.get("oidc_redeem_ready") === "true";
+
+  if (oidc_redeem_ready && !oidc_redeem_sent) {
+    komodo_client()
+      .oidc_redeem()
+      .then(onSuccess)
+      .catch((e) => {
+        console.error(e);
+        notifications.show({
+          title: "OIDC login failed",
+          message: "See console for details",
+          color: "red",
+        });
+        sanitizeQueryInner(search);
+      });
+    oidc_redeem_sent = true;
+  }
+
   return {
-    jwt_redeem_ready,
+    jwt_redeem_ready: jwt_redeem_ready || oidc_redeem_ready,
     passkey_pending: !!passkey,
     totp: search.get("totp") === "true",
   };
@@ -490,7 +559,12 @@ export function useCtrlKeyListener(listenKey: string, onPress: () => void) {
   useKeyListener(listenKey, onPress, "ctrl");
 }
 
-export type WebhookIntegration = "Github" | "Gitlab";
+export type WebhookIntegration =
+  | "Github"
+  | "Gitlab"
+  | "Gitea"
+  | "Bitbucket"
+  | "Generic";
 export type WebhookIntegrations = {
   [key: string]: WebhookIntegration;
 };
@@ -516,7 +590,13 @@ export function useWebhookIntegrations() {
         ? integrations[provider]
         : provider.includes("gitlab")
           ? "Gitlab"
-          : "Github";
+          : provider.includes("bitbucket")
+            ? "Bitbucket"
+            : provider.includes("gitea") ||
+                provider.includes("forgejo") ||
+                provider.includes("codeberg")
+              ? "Gitea"
+              : "Github";
     },
   };
 }
@@ -10,6 +10,7 @@ export function sanitizeQuery() {
 
 export function sanitizeQueryInner(search: URLSearchParams) {
   search.delete("redeem_ready");
+  search.delete("oidc_redeem_ready");
   search.delete("totp");
   search.delete("passkey");
   const query = search.toString();
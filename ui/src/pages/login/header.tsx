@@ -1,4 +1,8 @@
-import { komodo_client, useLoginOptions } from "@/lib/hooks";
+import {
+  komodo_client,
+  useLoginOptions,
+  useOidcLoginOptions,
+} from "@/lib/hooks";
 import {
   Button,
   Group,
@@ -15,6 +19,7 @@ export default function LoginHeader({
   secondFactorPending: boolean;
 }) {
   const options = useLoginOptions().data;
+  const oidcGroupSync = useOidcLoginOptions().data?.enabled;
   const theme = useComputedColorScheme();
   return (
     <Group justify="space-between">
@@ -43,7 +48,11 @@ export default function LoginHeader({
             enabled && (
               <Button
                 key={provider}
-                onClick={() => komodo_client().auth.externalLogin(provider)}
+                onClick={() =>
+                  provider === "Oidc" && oidcGroupSync
+                    ? komodo_client().oidc_login()
+                    : komodo_client().auth.externalLogin(provider)
+                }
                 leftSection={
                   provider === "Oidc" ? (
                     <KeyRound size="1rem" />
@@ -1,4 +1,10 @@
-import { useLogin, useLoginOptions, useUserInvalidate } from "@/lib/hooks";
+import {
+  useLdapLogin,
+  useLdapLoginOptions,
+  useLogin,
+  useLoginOptions,
+  useUserInvalidate,
+} from "@/lib/hooks";
 import { sanitizeQuery } from "@/lib/utils";
 import {
   Button,
@@ -7,6 +13,7 @@ import {
   Group,
   Loader,
   PasswordInput,
+  SegmentedControl,
   Text,
   TextInput,
 } from "@mantine/core";
@@ -25,6 +32,11 @@ export default function Login({
   totpIsPending?: boolean;
 }) {
   const options = useLoginOptions().data;
+  const ldapEnabled = useLdapLoginOptions().data?.enabled ?? false;
+  const [ldapSelected, setLdapSelected] = useState(false);
+  // Use LDAP if it is the only username / password login,
+  // or it is selected over local login.
+  const useLdap = ldapEnabled && (!options?.local || ldapSelected);
   const userInvalidate = useUserInvalidate();
   const [passkeyIsPending, setPasskeyPending] = useState(
     _passkeyIsPending ?? false,
@@ -69,6 +81,10 @@ export default function Login({
     },
   );
 
+  const { mutate: ldapLogin, isPending: ldapLoginPending } = useLdapLogin({
+    onSuccess,
+  });
+
   const { mutate: login, isPending: loginPending } = useLogin(
     "LoginLocalUser",
     {
@@ -98,9 +114,11 @@ export default function Login({
 
   const noAuthConfigured =
     options !== undefined &&
+    !ldapEnabled &&
     Object.values(options).every((value) => value === false);
 
-  const showSignUp = options !== undefined && !options.registration_disabled;
+  const showSignUp =
+    options !== undefined && !options.registration_disabled && !useLdap;
 
   const localForm = useForm({
     mode: "uncontrolled",
@@ -134,14 +152,23 @@ export default function Login({
         onSubmit={
           totpIsPending
             ? totpForm.onSubmit((form) => completeTotpLogin(form))
-            : (localForm.onSubmit((form) => login(form)) as any)
+            : useLdap
+              ? (localForm.onSubmit((form) => ldapLogin(form)) as any)
+              : (localForm.onSubmit((form) => login(form)) as any)
         }
         style={{ display: "flex", flexDirection: "column", gap: "1rem" }}
         miw={{ base: "95vw", xs: "530px" }}
         maw="95vw"
       >
-        {options?.local && !secondFactorPending && (
+        {(options?.local || ldapEnabled) && !secondFactorPending && (
           <>
+            {options?.local && ldapEnabled && (
+              <SegmentedControl
+                data={["Local", "LDAP"]}
+                value={ldapSelected ? "LDAP" : "Local"}
+                onChange={(value) => setLdapSelected(value === "LDAP")}
+              />
+            )}
             <TextInput
               {...localForm.getInputProps("username")}
               autoFocus
@@ -172,7 +199,11 @@ export default function Login({
                   Sign Up
                 </Button>
               )}
-              <Button w={110} type="submit" loading={loginPending}>
+              <Button
+                w={110}
+                type="submit"
+                loading={loginPending || ldapLoginPending}
+              >
                 Log In
               </Button>
             </Group>
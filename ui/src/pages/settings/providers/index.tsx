@@ -13,6 +13,7 @@ import { useState } from "react";
 import NewProviderAccount from "./new";
 import { DataTable, SortableHeader } from "@/ui/data-table";
 import DeleteProviderAccount from "./delete";
+import GitProviderSshKey from "./ssh-key";
 import ProvidersFromConfig from "./from-config";
 import CopyButton from "@/ui/copy-button";
 import { Types } from "komodo_client";
@@ -220,6 +221,23 @@ function Providers({ type }: { type: "GitProvider" | "DockerRegistry" }) {
                 );
               },
             },
+            ...(type === "GitProvider"
+              ? [
+                  {
+                    header: "SSH Key",
+                    cell: ({ row }) => (
+                      <GitProviderSshKey
+                        id={row.original._id?.$oid!}
+                        publicKey={
+                          (row.original as Types.GitProviderAccount)
+                            .ssh_public_key
+                        }
+                        disabled={disabled}
+                      />
+                    ),
+                  },
+                ]
+              : []),
             {
               header: "Delete",
               maxSize: 200,
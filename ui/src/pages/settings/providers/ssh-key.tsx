@@ -0,0 +1,39 @@
+import { useInvalidate, useWrite } from "@/lib/hooks";
+import { ICONS } from "@/theme/icons";
+import ConfirmButton from "@/ui/confirm-button";
+import CopyButton from "@/ui/copy-button";
+import { Group } from "@mantine/core";
+import { notifications } from "@mantine/notifications";
+
+export default function GitProviderSshKey({
+  id,
+  publicKey,
+  disabled,
+}: {
+  id: string;
+  publicKey: string | undefined;
+  disabled: boolean;
+}) {
+  const invalidate = useInvalidate();
+  const { mutate, isPending } = useWrite("GenerateGitProviderAccountSshKey", {
+    onSuccess: () => {
+      invalidate(["ListGitProviderAccounts"], ["GetGitProviderAccount"]);
+      notifications.show({
+        message: "Generated SSH key. Add the public key as a deploy key.",
+      });
+    },
+  });
+  return (
+    <Group gap="sm" wrap="nowrap">
+      <ConfirmButton
+        icon={<ICONS.Key size="1rem" />}
+        onClick={() => mutate({ id })}
+        loading={isPending}
+        disabled={disabled}
+      >
+        {publicKey ? "Regenerate" : "Generate"}
+      </ConfirmButton>
+      {publicKey && <CopyButton content={publicKey} />}
+    </Group>
+  );
+}
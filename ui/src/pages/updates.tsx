@@ -284,6 +284,9 @@ const OPERATIONS_BY_RESOURCE: { [key: string]: Types.Operation[] } = {
     Types.Operation.UnpauseStack,
     Types.Operation.StopStack,
     Types.Operation.DestroyStack,
+    Types.Operation.RollbackStack,
+    Types.Operation.BackupStackVolumes,
+    Types.Operation.RestoreStackVolumes,
     Types.Operation.StartStackService,
     Types.Operation.RestartStackService,
     Types.Operation.PauseStackService,
@@ -301,6 +304,9 @@ const OPERATIONS_BY_RESOURCE: { [key: string]: Types.Operation[] } = {
     Types.Operation.UnpauseDeployment,
     Types.Operation.StopDeployment,
     Types.Operation.DestroyDeployment,
+    Types.Operation.RollbackDeployment,
+    Types.Operation.BackupDeploymentVolumes,
+    Types.Operation.RestoreDeploymentVolumes,
     Types.Operation.RenameDeployment,
   ],
   Build: [
@@ -11,6 +11,8 @@ const ALERT_TYPES: Types.AlertData["type"][] = [
   "ServerCpu",
   "ServerMem",
   "ServerDisk",
+  "ServerLoadAverage",
+  "ServerNetwork",
   // Swarm
   "SwarmUnhealthy",
   // Stack
@@ -21,6 +23,9 @@ const ALERT_TYPES: Types.AlertData["type"][] = [
   "ContainerStateChange",
   "DeploymentImageUpdateAvailable",
   "DeploymentAutoUpdated",
+  // Container
+  "ContainerCpu",
+  "ContainerMem",
   // Misc
   "ScheduleRun",
   "BuildFailed",
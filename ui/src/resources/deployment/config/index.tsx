@@ -13,6 +13,7 @@ import { MonacoEditor } from "@/components/monaco";
 import DeploymentNetworkSelector from "./network";
 import SecretsSearch from "@/components/config/secrets-search";
 import DeploymentRestartSelector from "./restart";
+import DeploymentStrategySelector from "./strategy";
 import { Link } from "react-router-dom";
 import AddExtraArg from "@/components/config/add-extra-arg";
 import InputList from "@/ui/input-list";
@@ -297,6 +298,43 @@ export default function DeploymentConfig({
               },
             },
           },
+          {
+            label: "Deploy Strategy",
+            hidden: !!currSwarmId,
+            labelHidden: true,
+            fields: {
+              deploy_strategy: (value, set) => (
+                <DeploymentStrategySelector
+                  selected={value}
+                  set={set}
+                  disabled={disabled}
+                />
+              ),
+            },
+          },
+          {
+            label: "Health Check",
+            hidden: !!currSwarmId,
+            fields: {
+              health_check: {
+                description:
+                  "Mark the deploy failed if the container is not running and healthy after deploy.",
+              },
+              health_check_timeout: {
+                description:
+                  "The max time in seconds to wait for the container to be healthy.",
+              },
+              health_check_url: {
+                placeholder: "http://localhost:8080/health",
+                description:
+                  "Optional url which must return 2xx. Requested from the Periphery host.",
+              },
+              rollback_on_health_failure: {
+                description:
+                  "Automatically roll back to the previous deploy if the health check fails.",
+              },
+            },
+          },
         ],
         advanced: [
           {
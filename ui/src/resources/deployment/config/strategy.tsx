@@ -0,0 +1,33 @@
+import { ConfigItem } from "@/ui/config/item";
+import { Select } from "@mantine/core";
+import { Types } from "komodo_client";
+
+export interface DeploymentStrategySelectorProps {
+  selected: Types.DeployStrategy | undefined;
+  set: (input: Partial<Types.DeploymentConfig>) => void;
+  disabled: boolean;
+}
+
+export default function DeploymentStrategySelector({
+  selected,
+  set,
+  disabled,
+}: DeploymentStrategySelectorProps) {
+  return (
+    <ConfigItem
+      label="Deploy Strategy"
+      description="BlueGreen starts the new container next to the old one, and only replaces it once healthy. The container must not bind fixed host ports."
+    >
+      <Select
+        value={selected || Types.DeployStrategy.Recreate}
+        onChange={(deploy_strategy) =>
+          deploy_strategy &&
+          set({ deploy_strategy: deploy_strategy as Types.DeployStrategy })
+        }
+        disabled={disabled}
+        data={Object.values(Types.DeployStrategy)}
+        w={{ base: "100%", xs: "fit-content" }}
+      />
+    </ConfigItem>
+  );
+}
@@ -137,6 +137,40 @@ export function DestroyDeployment({ id }: DeploymentId) {
   );
 }
 
+export function RollbackDeployment({ id }: DeploymentId) {
+  const deployment = useDeployment(id);
+  const state = deployment?.info.state;
+  const { mutateAsync: rollback, isPending } =
+    useExecute("RollbackDeployment");
+  const deploying = useRead(
+    "GetDeploymentActionState",
+    { deployment: id },
+    { refetchInterval: 5000 },
+  ).data?.deploying;
+
+  if (
+    !deployment ||
+    state === undefined ||
+    [Types.DeploymentState.NotDeployed, Types.DeploymentState.Unknown].includes(
+      state,
+    )
+  ) {
+    return null;
+  }
+
+  return (
+    <ConfirmModalWithDisable
+      confirmText={deployment.name}
+      icon={<ICONS.Rollback size="1rem" />}
+      onConfirm={() => rollback({ deployment: id })}
+      disabled={isPending}
+      loading={isPending || deploying}
+    >
+      Rollback
+    </ConfirmModalWithDisable>
+  );
+}
+
 export function PullDeployment({ id }: DeploymentId) {
   const deployment = useDeployment(id);
   const { mutate: pull, isPending: pullPending } = useExecute("PullDeployment");
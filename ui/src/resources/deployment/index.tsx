@@ -12,6 +12,7 @@ import {
   PauseUnpauseDeployment,
   PullDeployment,
   RestartDeployment,
+  RollbackDeployment,
   StartStopDeployment,
 } from "./executions";
 import { useSwarm } from "@/resources/swarm";
@@ -274,6 +275,7 @@ export const DeploymentComponents: RequiredResourceComponents<
     RestartDeployment,
     PauseUnpauseDeployment,
     StartStopDeployment,
+    RollbackDeployment,
     DestroyDeployment,
   },
 
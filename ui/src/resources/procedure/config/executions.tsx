@@ -12,9 +12,11 @@ import {
   Group,
   Modal,
   MultiSelect,
+  Select,
   SimpleGrid,
   Stack,
   Switch,
+  TagsInput,
   Text,
   TextInput,
 } from "@mantine/core";
@@ -283,6 +285,73 @@ export const PROCEDURE_EXECUTIONS: ProcedureExecutions = {
       />
     ),
   },
+  RollbackDeployment: {
+    params: { deployment: "" },
+    Component: ({ params, setParams, disabled }) => (
+      <ResourceSelector
+        type="Deployment"
+        selected={params.deployment}
+        onSelect={(deployment) => setParams({ deployment })}
+        disabled={disabled}
+      />
+    ),
+  },
+  BackupDeploymentVolumes: {
+    params: { deployment: "" },
+    Component: ({ params, setParams, disabled }) => (
+      <Group>
+        <ResourceSelector
+          type="Deployment"
+          selected={params.deployment}
+          onSelect={(deployment) => setParams({ ...params, deployment })}
+          disabled={disabled}
+        />
+        <Select
+          value={params.mode ?? Types.VolumeBackupMode.Stop}
+          data={Object.values(Types.VolumeBackupMode)}
+          onChange={(mode) =>
+            mode &&
+            setParams({ ...params, mode: mode as Types.VolumeBackupMode })
+          }
+          styles={{ input: { width: 100 } }}
+          disabled={disabled}
+        />
+        <Group
+          style={{ cursor: !disabled ? "pointer" : undefined }}
+          onClick={() => {
+            if (!disabled) {
+              setParams({
+                ...params,
+                skip_bind_mounts: !params.skip_bind_mounts,
+              });
+            }
+          }}
+        >
+          Skip bind mounts:
+          <Switch checked={params.skip_bind_mounts} disabled={disabled} />
+        </Group>
+      </Group>
+    ),
+  },
+  RestoreDeploymentVolumes: {
+    params: { deployment: "", backup: "" },
+    Component: ({ params, setParams, disabled }) => (
+      <Group>
+        <ResourceSelector
+          type="Deployment"
+          selected={params.deployment}
+          onSelect={(deployment) => setParams({ ...params, deployment })}
+          disabled={disabled}
+        />
+        <TextInput
+          placeholder="Latest backup"
+          value={params.backup}
+          onChange={(e) => setParams({ ...params, backup: e.target.value })}
+          disabled={disabled}
+        />
+      </Group>
+    ),
+  },
   BatchDestroyDeployment: {
     params: { pattern: "" },
     Component: ({ params, setParams, disabled }) => (
@@ -631,6 +700,17 @@ export const PROCEDURE_EXECUTIONS: ProcedureExecutions = {
       />
     ),
   },
+  RollbackStack: {
+    params: { stack: "" },
+    Component: ({ params, setParams, disabled }) => (
+      <ResourceSelector
+        type="Stack"
+        selected={params.stack}
+        onSelect={(stack) => setParams({ stack })}
+        disabled={disabled}
+      />
+    ),
+  },
   RunStackService: {
     params: {
       stack: "",
@@ -894,6 +974,100 @@ export const PROCEDURE_EXECUTIONS: ProcedureExecutions = {
       );
     },
   },
+  BackupStackVolumes: {
+    params: { stack: "" },
+    Component: ({ params, setParams, disabled }) => {
+      const allServices = useRead("ListStackServices", {
+        stack: params.stack,
+      }).data?.map((s) => s.service);
+      return (
+        <Group>
+          <ResourceSelector
+            type="Stack"
+            selected={params.stack}
+            onSelect={(id) =>
+              setParams(
+                id ? { ...params, stack: id } : { stack: id, services: [] },
+              )
+            }
+            disabled={disabled}
+          />
+          <MultiSelect
+            leftSection={<ICONS.Service size="1rem" />}
+            placeholder={params.services?.length ? undefined : "All services"}
+            value={params.services}
+            data={allServices}
+            onChange={(services) => setParams({ ...params, services })}
+            styles={{ inputField: { width: 130 } }}
+            searchable
+            clearable
+          />
+          <Select
+            value={params.mode ?? Types.VolumeBackupMode.Stop}
+            data={Object.values(Types.VolumeBackupMode)}
+            onChange={(mode) =>
+              mode &&
+              setParams({ ...params, mode: mode as Types.VolumeBackupMode })
+            }
+            styles={{ input: { width: 100 } }}
+            disabled={disabled}
+          />
+          <Group
+            style={{ cursor: !disabled ? "pointer" : undefined }}
+            onClick={() => {
+              if (!disabled) {
+                setParams({
+                  ...params,
+                  skip_bind_mounts: !params.skip_bind_mounts,
+                });
+              }
+            }}
+          >
+            Skip bind mounts:
+            <Switch checked={params.skip_bind_mounts} disabled={disabled} />
+          </Group>
+        </Group>
+      );
+    },
+  },
+  RestoreStackVolumes: {
+    params: { stack: "", backup: "" },
+    Component: ({ params, setParams, disabled }) => {
+      const allServices = useRead("ListStackServices", {
+        stack: params.stack,
+      }).data?.map((s) => s.service);
+      return (
+        <Group>
+          <ResourceSelector
+            type="Stack"
+            selected={params.stack}
+            onSelect={(id) =>
+              setParams(
+                id ? { ...params, stack: id } : { stack: id, services: [] },
+              )
+            }
+            disabled={disabled}
+          />
+          <TextInput
+            placeholder="Latest backup"
+            value={params.backup}
+            onChange={(e) => setParams({ ...params, backup: e.target.value })}
+            disabled={disabled}
+          />
+          <MultiSelect
+            leftSection={<ICONS.Service size="1rem" />}
+            placeholder={params.services?.length ? undefined : "All services"}
+            value={params.services}
+            data={allServices}
+            onChange={(services) => setParams({ ...params, services })}
+            styles={{ inputField: { width: 130 } }}
+            searchable
+            clearable
+          />
+        </Group>
+      );
+    },
+  },
   // Repo
   CloneRepo: {
     params: { repo: "" },
@@ -1289,6 +1463,53 @@ export const PROCEDURE_EXECUTIONS: ProcedureExecutions = {
     params: {},
     Component: () => <></>,
   },
+  RestoreCoreDatabase: {
+    params: {
+      restore_folder: "",
+      collections: [],
+      resources: [],
+      dry_run: true,
+    },
+    Component: ({ params, setParams, disabled }) => (
+      <Group>
+        <TextInput
+          placeholder="Latest backup"
+          value={params.restore_folder}
+          onChange={(e) =>
+            setParams({ ...params, restore_folder: e.target.value })
+          }
+          disabled={disabled}
+        />
+        <TagsInput
+          placeholder={
+            params.collections?.length ? undefined : "All collections"
+          }
+          value={params.collections}
+          onChange={(collections) => setParams({ ...params, collections })}
+          disabled={disabled}
+          clearable
+        />
+        <TagsInput
+          placeholder={params.resources?.length ? undefined : "All resources"}
+          value={params.resources}
+          onChange={(resources) => setParams({ ...params, resources })}
+          disabled={disabled}
+          clearable
+        />
+        <Group
+          style={{ cursor: !disabled ? "pointer" : undefined }}
+          onClick={() => {
+            if (!disabled) {
+              setParams({ ...params, dry_run: !params.dry_run });
+            }
+          }}
+        >
+          Dry run:
+          <Switch checked={params.dry_run} disabled={disabled} />
+        </Group>
+      </Group>
+    ),
+  },
   GlobalAutoUpdate: {
     params: { skip_auto_update: false },
     Component: ({ params, setParams, disabled }) => (
@@ -1323,6 +1544,10 @@ export const PROCEDURE_EXECUTIONS: ProcedureExecutions = {
       </Group>
     ),
   },
+  RotateEncryptionKey: {
+    params: {},
+    Component: () => <></>,
+  },
 
   SendAlert: {
     params: { message: "" },
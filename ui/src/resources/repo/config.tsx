@@ -137,6 +137,7 @@ export default function RepoConfig({
             fields: {
               git_provider: (provider, set) => {
                 const https = update.git_https ?? config.git_https;
+                const ssh = update.git_ssh ?? config.git_ssh;
                 return (
                   <ProviderSelectorConfig
                     accountType="git"
@@ -144,7 +145,13 @@ export default function RepoConfig({
                     disabled={disabled}
                     onSelect={(git_provider) => set({ git_provider })}
                     https={https}
-                    onHttpsSwitch={() => set({ git_https: !https })}
+                    ssh={ssh}
+                    onProtocolSelect={(protocol) =>
+                      set({
+                        git_https: protocol !== "http",
+                        git_ssh: protocol === "ssh",
+                      })
+                    }
                   />
                 );
               },
@@ -199,6 +206,38 @@ export default function RepoConfig({
               },
             },
           },
+          {
+            label: "Clone Options",
+            fields: {
+              git_depth: {
+                label: "Clone Depth",
+                description:
+                  "Clone with '--depth', skipping older history. Use 0 to clone the full history.",
+              },
+              git_sparse_paths: (values, set) => (
+                <ConfigList
+                  label="Sparse Checkout Paths"
+                  addLabel="Add Path"
+                  description="Only check out these folders of the repo. Files at the repo root are always checked out. Leave empty to check out the whole repo."
+                  field="git_sparse_paths"
+                  values={values ?? []}
+                  set={set}
+                  disabled={disabled}
+                  placeholder="path/to/folder"
+                />
+              ),
+              git_submodules: {
+                label: "Submodules",
+                description:
+                  "Initialize and update the repo's submodules (recursively) after clone / pull.",
+              },
+              git_lfs: {
+                label: "Git LFS",
+                description:
+                  "Run 'git lfs pull' after clone / pull to download Git LFS files.",
+              },
+            },
+          },
           {
             label: "Environment",
             description:
@@ -299,6 +338,23 @@ export default function RepoConfig({
                   "Provide a custom webhook secret for this resource, or use the global default.",
                 placeholder: "Input custom secret",
               },
+              webhook_path_filters: (values, set) => (
+                <ConfigList
+                  label="Path Filters"
+                  addLabel="Add Filter"
+                  description="Only trigger when the push changes files matching these paths, relative to the repo root. Supports wildcards. Prefix with '!' to exclude paths. Leave empty to trigger on any push."
+                  field="webhook_path_filters"
+                  values={values ?? []}
+                  set={set}
+                  disabled={disabled}
+                  placeholder="services/api/*"
+                />
+              ),
+              commit_status: {
+                label: "Commit Status",
+                description:
+                  "Report the run status of the commit to the git provider, linking to the Komodo Update. Requires a git account. Supports Github, Gitlab and Gitea / Forgejo.",
+              },
             },
           },
           {
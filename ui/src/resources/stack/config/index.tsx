@@ -407,6 +407,29 @@ export default function StackConfig({
         },
       },
     },
+    {
+      label: "Health Check",
+      hidden: !!currSwarmId,
+      fields: {
+        health_check: {
+          description:
+            "Mark the deploy failed if the service containers are not running and healthy after deploy.",
+        },
+        health_check_timeout: {
+          description:
+            "The max time in seconds to wait for the containers to be healthy.",
+        },
+        health_check_url: {
+          placeholder: "http://localhost:8080/health",
+          description:
+            "Optional url which must return 2xx. Requested from the Periphery host.",
+        },
+        rollback_on_health_failure: {
+          description:
+            "Automatically roll back to the previous deploy if the health check fails.",
+        },
+      },
+    },
     {
       label: "Links",
       labelHidden: true,
@@ -756,6 +779,7 @@ export default function StackConfig({
               ? {
                   git_provider: (provider, set) => {
                     const https = update.git_https ?? config.git_https;
+                    const ssh = update.git_ssh ?? config.git_ssh;
                     return (
                       <ProviderSelectorConfig
                         accountType="git"
@@ -763,7 +787,13 @@ export default function StackConfig({
                         disabled={disabled}
                         onSelect={(git_provider) => set({ git_provider })}
                         https={https}
-                        onHttpsSwitch={() => set({ git_https: !https })}
+                        ssh={ssh}
+                        onProtocolSelect={(protocol) =>
+                          set({
+                            git_https: protocol !== "http",
+                            git_ssh: protocol === "ssh",
+                          })
+                        }
                       />
                     );
                   },
@@ -843,6 +873,44 @@ export default function StackConfig({
             ),
           },
         },
+        {
+          label: "Clone Options",
+          hidden: repoLinked,
+          fields: {
+            git_depth: {
+              label: "Clone Depth",
+              description:
+                "Clone with '--depth', skipping older history. Use 0 to clone the full history.",
+            },
+            git_sparse_checkout: {
+              label: "Sparse Checkout",
+              description:
+                "Only check out the folders the Stack uses. If no Sparse Checkout Paths are given, uses the folders of the Run Directory and the files.",
+            },
+            git_sparse_paths: (values, set) => (
+              <ConfigList
+                label="Sparse Checkout Paths"
+                addLabel="Add Path"
+                description="Only check out these folders of the repo. Files at the repo root are always checked out."
+                field="git_sparse_paths"
+                values={values ?? []}
+                set={set}
+                disabled={disabled}
+                placeholder="path/to/folder"
+              />
+            ),
+            git_submodules: {
+              label: "Submodules",
+              description:
+                "Initialize and update the repo's submodules (recursively) after clone / pull.",
+            },
+            git_lfs: {
+              label: "Git LFS",
+              description:
+                "Run 'git lfs pull' after clone / pull to download Git LFS files.",
+            },
+          },
+        },
         environment,
         configFiles,
         ...generalCommon,
@@ -878,6 +946,16 @@ export default function StackConfig({
                   path={`/stack/${idOrName === "Id" ? id : encodeURIComponent(name ?? "...")}/deploy`}
                 />
               ),
+            ["Preview" as any]: () =>
+              stack?.template &&
+              (update.branch ?? config.branch) && (
+                <CopyWebhookUrl
+                  label="Webhook URL - Preview"
+                  description="Send pull request events here to deploy a preview copy of this template Stack for each pull request. '[[PREVIEW_ID]]' in the compose file, environment, deploy commands and extra args is replaced with eg 'pr-12'."
+                  integration={webhookIntegration}
+                  path={`/stack/${idOrName === "Id" ? id : encodeURIComponent(name ?? "...")}/preview`}
+                />
+              ),
             webhook_force_deploy: {
               description:
                 "Usually the Stack won't deploy unless there are changes to the files. Use this to force deploy.",
@@ -888,6 +966,28 @@ export default function StackConfig({
                 "Provide a custom webhook secret for this resource, or use the global default.",
               placeholder: "Input custom secret",
             },
+            webhook_path_filters: (values, set) => (
+              <ConfigList
+                label="Path Filters"
+                addLabel="Add Filter"
+                description="Only trigger when the push changes files matching these paths, relative to the repo root. Supports wildcards. Prefix with '!' to exclude paths. Leave empty to trigger on any push."
+                field="webhook_path_filters"
+                values={values ?? []}
+                set={set}
+                disabled={disabled}
+                placeholder="services/api/*"
+              />
+            ),
+            commit_status: {
+              label: "Commit Status",
+              description:
+                "Report the run status of the commit to the git provider, linking to the Komodo Update. Requires a git account. Supports Github, Gitlab and Gitea / Forgejo.",
+            },
+            deployment_status: {
+              label: "Deployment Status",
+              description:
+                "Report deploys to the git provider deployments, using the Stack name as the environment. Requires a git account. Supports Github and Gitlab.",
+            },
           },
         },
       ],
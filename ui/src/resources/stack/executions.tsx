@@ -114,6 +114,44 @@ export const DestroyStack = ({
   );
 };
 
+export const RollbackStack = ({
+  id,
+  service,
+}: {
+  id: string;
+  service?: string;
+}) => {
+  const stack = useStack(id);
+  const state = stack?.info.state;
+  const { mutateAsync: rollback, isPending } = useExecute("RollbackStack");
+  const deploying = useRead(
+    "GetStackActionState",
+    { stack: id },
+    { refetchInterval: 5000 },
+  ).data?.deploying;
+
+  if (
+    !stack ||
+    service !== undefined ||
+    state === undefined ||
+    [Types.StackState.Unknown, Types.StackState.Down].includes(state)
+  ) {
+    return null;
+  }
+
+  return (
+    <ConfirmModalWithDisable
+      confirmText={stack.name}
+      icon={<ICONS.Rollback size="1rem" />}
+      onConfirm={() => rollback({ stack: id })}
+      disabled={isPending}
+      loading={isPending || deploying}
+    >
+      Rollback
+    </ConfirmModalWithDisable>
+  );
+};
+
 export const PullStack = ({
   id,
   service,
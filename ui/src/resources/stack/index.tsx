@@ -12,6 +12,7 @@ import {
   PauseUnpauseStack,
   PullStack,
   RestartStack,
+  RollbackStack,
   StartStopStack,
 } from "./executions";
 import { useSwarm } from "@/resources/swarm";
@@ -367,6 +368,7 @@ export const StackComponents: RequiredResourceComponents<
     RestartStack,
     PauseUnpauseStack,
     StartStopStack,
+    RollbackStack,
     DestroyStack,
   },
 
@@ -143,6 +143,7 @@ export const ICONS = {
   CloneRepo: ArrowDownToLine,
   PullRepo: ArrowDownToDot,
   Commit: NotebookPen,
+  Rollback: History,
   // MISC
   User,
   Users,